@@ -94,6 +94,50 @@ fn decode_test_interlaced(c: &mut Criterion)
     });
 }
 
+/// Create a large interlaced image, big enough for passes
+/// to be reconstructed in parallel
+fn create_large_interlaced_png() -> Vec<u8>
+{
+    use zune_png::zune_core::bit_depth::BitDepth;
+    use zune_png::zune_core::colorspace::ColorSpace;
+    use zune_png::zune_core::options::EncoderOptions;
+
+    let (width, height) = (4096, 4096);
+    let pixels: Vec<u8> = (0..width * height * 3).map(|x| (x % 251) as u8).collect();
+
+    let options = EncoderOptions::default()
+        .set_width(width)
+        .set_height(height)
+        .set_colorspace(ColorSpace::RGB)
+        .set_depth(BitDepth::Eight)
+        .png_set_encode_interlaced(true);
+
+    zune_png::PngEncoder::new(&pixels, options).encode().unwrap()
+}
+
+fn decode_test_interlaced_large(c: &mut Criterion)
+{
+    let data = create_large_interlaced_png();
+
+    let mut group = c.benchmark_group("png: PNG decoding interlaced 4096x4096");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    // passes are only decoded in parallel with the `threads` feature
+    let name = if cfg!(feature = "png-threads") { "zune-png threads" } else { "zune-png" };
+
+    group.bench_function(name, |b| {
+        b.iter(|| black_box(decode_zune(data.as_slice())))
+    });
+
+    group.bench_function("image-rs/png", |b| {
+        b.iter(|| black_box(decode_ref(data.as_slice())))
+    });
+
+    group.bench_function("spng", |b| {
+        b.iter(|| black_box(decode_spng(data.as_slice())))
+    });
+}
+
 fn decode_test_16_bit(c: &mut Criterion)
 {
     let path = sample_path().join("test-images/png/benchmarks/speed_bench_16.png");
@@ -143,12 +187,64 @@ fn decode_test_trns_chunk(c: &mut Criterion)
         b.iter(|| black_box(decode_lodepng(data.as_slice())))
     });
 }
+
+/// Create an image carrying a lot of compressed text metadata
+fn create_metadata_heavy_png() -> Vec<u8>
+{
+    let (width, height) = (256, 256);
+    let pixels: Vec<u8> = (0..width * height * 3).map(|x| (x % 251) as u8).collect();
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        for i in 0..64
+        {
+            let text = format!("metadata entry {i} ").repeat(1000);
+
+            encoder.add_ztxt_chunk(format!("Comment{i}"), text.clone()).unwrap();
+            encoder.add_itxt_chunk(format!("Note{i}"), text).unwrap();
+        }
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels).unwrap();
+    }
+    out
+}
+
+fn decode_test_skip_ancillary(c: &mut Criterion)
+{
+    use zune_png::zune_core::options::DecoderOptions;
+
+    let data = create_metadata_heavy_png();
+
+    let mut group = c.benchmark_group("png: PNG decoding metadata heavy image");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("zune-png", |b| {
+        b.iter(|| black_box(decode_zune(data.as_slice())))
+    });
+
+    group.bench_function("zune-png skip ancillary", |b| {
+        let options = DecoderOptions::default().png_set_skip_ancillary(true);
+
+        b.iter(|| {
+            black_box(
+                zune_png::PngDecoder::new_with_options(data.as_slice(), options)
+                    .decode_raw()
+                    .unwrap()
+            )
+        })
+    });
+}
+
 criterion_group!(name=benches;
   config={
   let c = Criterion::default();
     c.measurement_time(Duration::from_secs(20))
   };
-targets=decode_test_trns_chunk,decode_test_16_bit,decode_test,decode_test_interlaced
+targets=decode_test_trns_chunk,decode_test_16_bit,decode_test,decode_test_interlaced,decode_test_interlaced_large,decode_test_skip_ancillary
 );
 
 criterion_main!(benches);
@@ -145,6 +145,12 @@ fn add_settings() -> Vec<Arg>
             .help("Maximum height of images allowed")
             .default_value("37268")
             .value_parser(value_parser!(usize)),
+        Arg::new("max-pixels")
+            .long("max-pixels")
+            .help_heading(HELP_HEADING)
+            .help("Maximum number of pixels (width times height) of images allowed")
+            .default_value("1073741824")
+            .value_parser(value_parser!(usize)),
         Arg::new("strict")
             .long("strict")
             .help_heading(HELP_HEADING)
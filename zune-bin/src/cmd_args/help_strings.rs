@@ -29,6 +29,8 @@ Threshold methods supported supported are
 
 See https://en.wikipedia.org/wiki/Thresholding_(image_processing)
 
+Color images are converted to grayscale first
+
 Example: zune -i [img] -o [img] --threshold='32:binary'";
 
 pub static CROP_HELP: &str = "Crop an image 
@@ -39,12 +39,14 @@ pub fn get_decoder_options(options: &ArgMatches) -> DecoderOptions
 {
     let max_width = *options.get_one::<usize>("max-width").unwrap();
     let max_height = *options.get_one::<usize>("max-height").unwrap();
+    let max_pixels = *options.get_one::<usize>("max-pixels").unwrap();
     let use_unsafe = !*options.get_one::<bool>("safe").unwrap();
     let strict_mode = *options.get_one::<bool>("strict").unwrap();
 
     DecoderOptions::new_cmd()
         .set_max_height(max_height)
         .set_max_width(max_width)
+        .set_max_pixels(max_pixels)
         .set_use_unsafe(use_unsafe)
         .set_strict_mode(strict_mode)
 }
@@ -52,7 +52,7 @@ pub fn parse_options<T: IntoImage>(
                 sigma, threshold
             );
 
-            let unsharpen = Unsharpen::new(sigma_f32, threshold_u16, 0);
+            let unsharpen = Unsharpen::new(sigma_f32, threshold_u16, 100);
             workflow.add_operation(Box::new(unsharpen))
         }
         else if argument == "mean-blur"
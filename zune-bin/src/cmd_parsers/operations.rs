@@ -139,7 +139,7 @@ pub fn parse_options<T: IntoImage>(
             let thresh_int = str::parse::<u16>(thresh_string).map_err(|x| x.to_string())?;
             let thresh_mode = ThresholdMethod::from_string_result(split_args[1])?;
 
-            let threshold = Threshold::new(thresh_int, thresh_mode);
+            let threshold = Threshold::new(thresh_int, thresh_mode).auto_grayscale(true);
             workflow.add_operation(Box::new(threshold));
 
             debug!(
@@ -35,7 +35,8 @@ pub fn probe_input_files(args: &ArgMatches)
                         // We'll just be reading headers so it doesn't matter
                         let options = DecoderOptions::new_cmd()
                             .set_max_height(usize::MAX)
-                            .set_max_width(usize::MAX);
+                            .set_max_width(usize::MAX)
+                            .set_max_pixels(usize::MAX);
 
                         let mut decoder = format
                             .get_decoder_with_options(file_contents, options)
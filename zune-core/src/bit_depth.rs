@@ -11,6 +11,20 @@
 #[non_exhaustive]
 pub enum BitDepth
 {
+    /// 1 bit depth, samples are packed eight to a byte
+    ///
+    /// Returned only by decoders configured to keep sub-8-bit images
+    /// at their native depth, e.g the png decoder via
+    /// [`png_set_expand_depth`](crate::options::DecoderOptions::png_set_expand_depth)
+    One,
+    /// 2 bit depth, samples are packed four to a byte
+    ///
+    /// See [`BitDepth::One`] for when this is returned
+    Two,
+    /// 4 bit depth, samples are packed two to a byte
+    ///
+    /// See [`BitDepth::One`] for when this is returned
+    Four,
     /// U8 bit depth.
     ///
     /// Images with such bit depth use [`u8`] to store
@@ -20,7 +34,8 @@ pub enum BitDepth
     /// by the library.
     ///
     /// For images with bit depths lower than this, they will be scaled
-    /// to this bit depth
+    /// to this bit depth unless the decoder was asked to keep the
+    /// native depth
     Eight,
     /// U16 bit depth
     ///
@@ -78,6 +93,9 @@ impl BitDepth
     {
         match self
         {
+            Self::One => (1 << 01) - 1,
+            Self::Two => (1 << 02) - 1,
+            Self::Four => (1 << 04) - 1,
             Self::Eight => (1 << 08) - 1,
             Self::Sixteen => u16::MAX,
             Self::Float32 => 1,
@@ -109,7 +127,7 @@ impl BitDepth
     {
         match self
         {
-            Self::Eight => BitType::U8,
+            Self::One | Self::Two | Self::Four | Self::Eight => BitType::U8,
             Self::Sixteen => BitType::U16,
             Self::Float32 => BitType::F32,
             Self::Unknown => panic!("Unknown bit type")
@@ -134,7 +152,7 @@ impl BitDepth
     {
         match self
         {
-            Self::Eight => core::mem::size_of::<u8>(),
+            Self::One | Self::Two | Self::Four | Self::Eight => core::mem::size_of::<u8>(),
             Self::Sixteen => core::mem::size_of::<u16>(),
             Self::Float32 => core::mem::size_of::<f32>(),
             Self::Unknown => panic!("Unknown bit type")
@@ -24,6 +24,16 @@ pub enum ColorSpace
     BGR,
     /// Blue, Green, Red, Alpha
     BGRA,
+    /// Hue, Saturation, Value
+    ///
+    /// All components are scaled to the sample range, hue
+    /// covers 0 to 360 degrees
+    HSV,
+    /// Hue, Saturation, Lightness
+    ///
+    /// All components are scaled to the sample range, hue
+    /// covers 0 to 360 degrees
+    HSL,
     /// The colorspace is unknown
     Unknown
 }
@@ -33,7 +43,7 @@ impl ColorSpace
     {
         match self
         {
-            Self::RGB | Self::YCbCr | Self::BGR => 3,
+            Self::RGB | Self::YCbCr | Self::BGR | Self::HSV | Self::HSL => 3,
             Self::RGBA | Self::YCCK | Self::CMYK | Self::BGRA => 4,
             Self::Luma => 1,
             Self::LumaA => 2,
@@ -54,7 +64,7 @@ impl ColorSpace
 
 /// Encapsulates all colorspaces supported by
 /// the library
-pub static ALL_COLORSPACES: [ColorSpace; 8] = [
+pub static ALL_COLORSPACES: [ColorSpace; 10] = [
     ColorSpace::RGB,
     ColorSpace::RGBA,
     ColorSpace::LumaA,
@@ -62,7 +72,9 @@ pub static ALL_COLORSPACES: [ColorSpace; 8] = [
     ColorSpace::CMYK,
     ColorSpace::BGRA,
     ColorSpace::BGR,
-    ColorSpace::YCbCr
+    ColorSpace::YCbCr,
+    ColorSpace::HSV,
+    ColorSpace::HSL
 ];
 
 /// Color characteristics
@@ -11,6 +11,18 @@ fn decoder_strict_mode() -> DecoderFlags
     flags.set(DecoderFlags::INFLATE_CONFIRM_ADLER, true);
     flags.set(DecoderFlags::PNG_CONFIRM_CRC, true);
     flags.set(DecoderFlags::JPG_ERROR_ON_NON_CONFORMANCE, true);
+    flags.set(DecoderFlags::PNG_EXPAND_DEPTH, true);
+    flags.set(DecoderFlags::PNG_KEEP_RAW_ICCP, false);
+    flags.set(DecoderFlags::PNG_RECORD_CHUNKS, false);
+    flags.set(DecoderFlags::PNG_ALLOW_TRUNCATED, false);
+    flags.set(DecoderFlags::PNG_STRIP_16, false);
+    flags.set(DecoderFlags::PNG_LENIENT_CRC, false);
+    flags.set(DecoderFlags::PNG_APPLY_GAMMA, false);
+    flags.set(DecoderFlags::PNG_PREMULTIPLY_ALPHA, false);
+    flags.set(DecoderFlags::PNG_KEEP_PALETTE_INDICES, false);
+    flags.set(DecoderFlags::PNG_FORCE_RGBA, false);
+    flags.set(DecoderFlags::PNG_KEEP_UNKNOWN_CHUNKS, false);
+    flags.set(DecoderFlags::PNG_SKIP_ANCILLARY, false);
 
     flags.set(DecoderFlags::ZUNE_USE_UNSAFE, true);
     flags.set(DecoderFlags::ZUNE_USE_AVX, true);
@@ -34,6 +46,18 @@ fn fast_options() -> DecoderFlags
     flags.set(DecoderFlags::INFLATE_CONFIRM_ADLER, false);
     flags.set(DecoderFlags::PNG_CONFIRM_CRC, false);
     flags.set(DecoderFlags::JPG_ERROR_ON_NON_CONFORMANCE, false);
+    flags.set(DecoderFlags::PNG_EXPAND_DEPTH, true);
+    flags.set(DecoderFlags::PNG_KEEP_RAW_ICCP, false);
+    flags.set(DecoderFlags::PNG_RECORD_CHUNKS, false);
+    flags.set(DecoderFlags::PNG_ALLOW_TRUNCATED, false);
+    flags.set(DecoderFlags::PNG_STRIP_16, false);
+    flags.set(DecoderFlags::PNG_LENIENT_CRC, false);
+    flags.set(DecoderFlags::PNG_APPLY_GAMMA, false);
+    flags.set(DecoderFlags::PNG_PREMULTIPLY_ALPHA, false);
+    flags.set(DecoderFlags::PNG_KEEP_PALETTE_INDICES, false);
+    flags.set(DecoderFlags::PNG_FORCE_RGBA, false);
+    flags.set(DecoderFlags::PNG_KEEP_UNKNOWN_CHUNKS, false);
+    flags.set(DecoderFlags::PNG_SKIP_ANCILLARY, false);
 
     flags.set(DecoderFlags::ZUNE_USE_UNSAFE, true);
     flags.set(DecoderFlags::ZUNE_USE_AVX, true);
@@ -58,6 +82,18 @@ fn cmd_options() -> DecoderFlags
     flags.set(DecoderFlags::INFLATE_CONFIRM_ADLER, false);
     flags.set(DecoderFlags::PNG_CONFIRM_CRC, false);
     flags.set(DecoderFlags::JPG_ERROR_ON_NON_CONFORMANCE, false);
+    flags.set(DecoderFlags::PNG_EXPAND_DEPTH, true);
+    flags.set(DecoderFlags::PNG_KEEP_RAW_ICCP, false);
+    flags.set(DecoderFlags::PNG_RECORD_CHUNKS, false);
+    flags.set(DecoderFlags::PNG_ALLOW_TRUNCATED, false);
+    flags.set(DecoderFlags::PNG_STRIP_16, false);
+    flags.set(DecoderFlags::PNG_LENIENT_CRC, false);
+    flags.set(DecoderFlags::PNG_APPLY_GAMMA, false);
+    flags.set(DecoderFlags::PNG_PREMULTIPLY_ALPHA, false);
+    flags.set(DecoderFlags::PNG_KEEP_PALETTE_INDICES, false);
+    flags.set(DecoderFlags::PNG_FORCE_RGBA, false);
+    flags.set(DecoderFlags::PNG_KEEP_UNKNOWN_CHUNKS, false);
+    flags.set(DecoderFlags::PNG_SKIP_ANCILLARY, false);
 
     flags.set(DecoderFlags::ZUNE_USE_UNSAFE, true);
     flags.set(DecoderFlags::ZUNE_USE_AVX, true);
@@ -99,6 +135,73 @@ bitflags! {
         const ZUNE_USE_AVX                  =  0b0000_0000_0000_0000_0000_0000_1000_0000;
         /// Whether we should use avx2 instructions where possible.
         const ZUNE_USE_AVX2                 =  0b0000_0000_0000_0000_0000_0001_0000_0000;
+        /// Whether the png decoder should expand images with bit depths less than 8 to 8 bits
+        const PNG_EXPAND_DEPTH              =  0b0000_0000_0000_0000_0000_0010_0000_0000;
+        /// Whether the png decoder should keep the compressed bytes of the ICC profile
+        const PNG_KEEP_RAW_ICCP             =  0b0000_0000_0000_0000_0000_0100_0000_0000;
+        /// Whether the png decoder should record a summary of each chunk it reads
+        const PNG_RECORD_CHUNKS             =  0b0000_0000_0000_0000_0000_1000_0000_0000;
+        /// Whether the png decoder should return partial images for truncated image data
+        const PNG_ALLOW_TRUNCATED           =  0b0000_0000_0000_0000_0001_0000_0000_0000;
+        /// Whether the png decoder should reduce 16 bit samples to 8 bits
+        const PNG_STRIP_16                  =  0b0000_0000_0000_0000_0010_0000_0000_0000;
+        /// Whether the png decoder should continue decoding after a CRC mismatch in an ancillary chunk
+        const PNG_LENIENT_CRC               =  0b0000_0000_0000_0000_0100_0000_0000_0000;
+        /// Whether the png decoder should gamma correct decoded samples for display
+        const PNG_APPLY_GAMMA               =  0b0000_0000_0000_0000_1000_0000_0000_0000;
+        /// Whether the png decoder should multiply colour samples by alpha
+        const PNG_PREMULTIPLY_ALPHA         =  0b0000_0000_0000_0001_0000_0000_0000_0000;
+        /// Whether the png decoder should return palette indices instead of expanding them
+        const PNG_KEEP_PALETTE_INDICES      =  0b0000_0000_0000_0010_0000_0000_0000_0000;
+        /// Whether the png decoder should convert all images to RGBA
+        const PNG_FORCE_RGBA                =  0b0000_0000_0000_0100_0000_0000_0000_0000;
+        /// Whether the png decoder should retain unknown ancillary chunks
+        const PNG_KEEP_UNKNOWN_CHUNKS       =  0b0000_0000_0000_1000_0000_0000_0000_0000;
+        /// Whether the png decoder should skip metadata chunks without parsing them
+        const PNG_SKIP_ANCILLARY            =  0b0000_0000_0001_0000_0000_0000_0000_0000;
+    }
+}
+
+/// How 16 bit samples are reduced to 8 bits
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Rounding
+{
+    /// Keep the most significant byte, i.e `value >> 8`
+    ///
+    /// This is the fastest but is biased low, e.g `0x01FF` becomes 1
+    /// even though it is closer to `0x0200`
+    Truncate,
+    /// Round to the nearest 8 bit value, i.e `(value + 128) >> 8`
+    /// clamped to 255
+    Round
+}
+
+/// Factor the png decoder scales images down by while decoding
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScaleDown
+{
+    /// Keep the full resolution
+    One,
+    /// Average each block of 2x2 pixels into one pixel
+    Half,
+    /// Average each block of 4x4 pixels into one pixel
+    Quarter,
+    /// Average each block of 8x8 pixels into one pixel
+    Eighth
+}
+
+impl ScaleDown
+{
+    /// Return the number each image dimension is divided by
+    pub const fn factor(self) -> usize
+    {
+        match self
+        {
+            ScaleDown::One => 1,
+            ScaleDown::Half => 2,
+            ScaleDown::Quarter => 4,
+            ScaleDown::Eighth => 8
+        }
     }
 }
 
@@ -114,14 +217,24 @@ pub struct DecoderOptions
     ///
     /// - Default value: 16384
     /// - Respected by: `all decoders`
-    max_width:      usize,
+    max_width:            usize,
     /// Maximum height for which decoders will not
     /// try to decode images larger than the
     /// specified height
     ///
     /// - Default value: 16384
     /// - Respected by: `all decoders`
-    max_height:     usize,
+    max_height:           usize,
+    /// Maximum number of pixels, i.e width times height
+    /// for which decoders will not try to decode images
+    /// with more pixels than this.
+    ///
+    /// This protects against small files that declare huge
+    /// dimensions and would use a lot of memory to decode
+    ///
+    /// - Default value: 67108864 (`1 << 26`, e.g 8192x8192)
+    /// - Respected by: `png`
+    max_pixels:           usize,
     /// Output colorspace
     ///
     /// The jpeg decoder allows conversion to a separate colorspace
@@ -132,7 +245,7 @@ pub struct DecoderOptions
     ///
     /// - Default value: `ColorSpace::RGB`
     /// - Respected by: `jpeg`
-    out_colorspace: ColorSpace,
+    out_colorspace:       ColorSpace,
 
     /// Maximum number of scans allowed
     /// for progressive jpeg images
@@ -141,15 +254,38 @@ pub struct DecoderOptions
     ///
     /// - Default value:100
     /// - Respected by: `jpeg`
-    max_scans:     usize,
+    max_scans:            usize,
     /// Maximum size for deflate.
     /// Respected by all decoders that use inflate/deflate
-    deflate_limit: usize,
+    deflate_limit:        usize,
     /// Boolean flags that influence decoding
-    flags:         DecoderFlags,
+    flags:                DecoderFlags,
     /// The byte endian of the returned bytes will be stored in
     /// in case a single pixel spans more than a byte
-    endianness:    ByteEndian
+    endianness:           ByteEndian,
+    /// Gamma of the display images will be shown on,
+    /// used when gamma correction is enabled
+    ///
+    /// - Default value: 2.2
+    /// - Respected by: `png`
+    display_gamma:        f32,
+    /// Factor to scale images down by while decoding
+    ///
+    /// - Default value: `ScaleDown::One`
+    /// - Respected by: `png`
+    scale_down:           ScaleDown,
+    /// Maximum number of bytes of unknown chunk data
+    /// retained when keeping unknown chunks
+    ///
+    /// - Default value: 1048576 (`1 << 20`)
+    /// - Respected by: `png`
+    unknown_chunks_limit: usize,
+    /// How 16 bit samples are reduced to 8 bits
+    /// when stripping 16 bit images
+    ///
+    /// - Default value: `Rounding::Truncate`
+    /// - Respected by: `png`
+    downsample_rounding:  Rounding
 }
 
 /// Initializers
@@ -207,6 +343,13 @@ impl DecoderOptions
         self.max_height
     }
 
+    /// Get maximum number of pixels configured for which the decoder
+    /// should not try to decode images with more pixels than this
+    pub const fn get_max_pixels(&self) -> usize
+    {
+        self.max_pixels
+    }
+
     /// Return true whether the decoder should be in strict mode
     /// And reject most errors
     pub fn get_strict_mode(&self) -> bool
@@ -252,6 +395,21 @@ impl DecoderOptions
         self
     }
 
+    /// Set maximum number of pixels for which the decoder should not try
+    /// decoding images with more pixels, the number of pixels is width times height
+    /// # Arguments
+    ///
+    /// * `pixels`: The maximum number of pixels allowed
+    ///
+    /// returns: DecoderOptions
+    ///
+    #[must_use]
+    pub fn set_max_pixels(mut self, pixels: usize) -> Self
+    {
+        self.max_pixels = pixels;
+        self
+    }
+
     /// Whether the routines can use unsafe platform specific
     /// intrinsics when necessary
     ///
@@ -296,6 +454,12 @@ impl DecoderOptions
             | DecoderFlags::INFLATE_CONFIRM_ADLER;
 
         self.flags.set(flags, yes);
+
+        if yes
+        {
+            // CRC mismatches are always errors in strict mode
+            self.flags.set(DecoderFlags::PNG_LENIENT_CRC, false);
+        }
         self
     }
 
@@ -370,6 +534,340 @@ impl DecoderOptions
         self.flags.set(DecoderFlags::PNG_CONFIRM_CRC, yes);
         self
     }
+    /// Whether the png decoder should expand images with
+    /// bit depths less than 8 to 8 bits
+    pub const fn png_get_expand_depth(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_EXPAND_DEPTH)
+    }
+    /// Set whether the png decoder should expand images with bit depths
+    /// less than 8 (1, 2 and 4 bits) to 8 bits
+    ///
+    /// If set to false, such images are returned with their samples packed
+    /// as they are stored in the file, i.e each row starts on a byte boundary and
+    /// the leftmost pixel occupies the high order bits of a byte.
+    /// Palette and transparency expansion is also skipped since they require
+    /// 8 bit samples, so paletted images return packed palette indices.
+    ///
+    /// Default value is true
+    #[must_use]
+    pub fn png_set_expand_depth(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_EXPAND_DEPTH, yes);
+        self
+    }
+    /// Whether the png decoder should keep the compressed
+    /// ICC profile as stored in the file
+    pub const fn png_get_keep_raw_iccp(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_KEEP_RAW_ICCP)
+    }
+    /// Set whether the png decoder should keep the compressed ICC profile
+    /// bytes exactly as they are stored in the `iCCP` chunk, in addition to
+    /// the decompressed profile.
+    ///
+    /// This is useful for re-embedding the profile without recompressing it
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_keep_raw_iccp(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_KEEP_RAW_ICCP, yes);
+        self
+    }
+    /// Whether the png decoder should record a summary of
+    /// each chunk read while decoding headers
+    pub const fn png_get_record_chunks(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_RECORD_CHUNKS)
+    }
+    /// Set whether the png decoder should record the type, CRC and length
+    /// of each chunk read while decoding headers.
+    ///
+    /// CRCs are recorded as stored in the file, irrespective of whether
+    /// CRC confirmation is enabled
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_record_chunks(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_RECORD_CHUNKS, yes);
+        self
+    }
+    /// Whether the png decoder should return the rows it managed to
+    /// decode from truncated or corrupt image data instead of an error
+    pub const fn png_get_allow_truncated(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_ALLOW_TRUNCATED)
+    }
+    /// Set whether the png decoder should decode as much as possible
+    /// of images whose data is cut short or fails to decompress.
+    ///
+    /// When enabled, complete rows decoded before the error are returned and
+    /// the remaining rows are filled with zeroes, the number of complete rows
+    /// can be retrieved from the decoder.
+    ///
+    /// This is useful for recovering partial images from interrupted downloads
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_allow_truncated(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_ALLOW_TRUNCATED, yes);
+        self
+    }
+    /// Whether the png decoder should reduce 16 bit samples to 8 bits
+    pub const fn png_get_strip_16(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_STRIP_16)
+    }
+    /// Set whether the png decoder should reduce 16 bit samples to 8 bits
+    ///
+    /// Each sample is replaced by its most significant byte, i.e `value >> 8`, or rounded
+    /// as set by [`png_set_downsample_rounding`](Self::png_set_downsample_rounding), after
+    /// all other processing such as tRNS expansion and gamma correction, so 16 bit images
+    /// are returned with a depth of 8 bits and half the memory.
+    ///
+    /// This has no effect on images that are not 16 bit.
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_strip_16(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_STRIP_16, yes);
+        self
+    }
+    /// How the png decoder reduces 16 bit samples to 8 bits
+    pub const fn png_get_downsample_rounding(&self) -> Rounding
+    {
+        self.downsample_rounding
+    }
+    /// Set how the png decoder reduces 16 bit samples to 8 bits
+    /// when [`png_set_strip_16`](Self::png_set_strip_16) is enabled
+    ///
+    /// [`Rounding::Truncate`] is faster but biased low, [`Rounding::Round`]
+    /// picks the nearest 8 bit value.
+    ///
+    /// Default value is `Rounding::Truncate`
+    #[must_use]
+    pub fn png_set_downsample_rounding(mut self, rounding: Rounding) -> Self
+    {
+        self.downsample_rounding = rounding;
+        self
+    }
+    /// Whether the png decoder should continue decoding after
+    /// a CRC mismatch in an ancillary chunk
+    pub const fn png_get_lenient_crc(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_LENIENT_CRC)
+    }
+    /// Set whether the png decoder should treat CRC mismatches in
+    /// ancillary chunks as warnings instead of errors.
+    ///
+    /// Mismatches are reported to the CRC handler of the decoder and decoding
+    /// continues, mismatches in critical chunks such as `IHDR` and `IDAT` are still errors.
+    ///
+    /// This only has an effect if CRC confirmation is enabled, and it's
+    /// disabled again by [`set_strict_mode(true)`](Self::set_strict_mode)
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_lenient_crc(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_LENIENT_CRC, yes);
+        self
+    }
+    /// Whether the png decoder should gamma correct decoded
+    /// samples using the image gamma and display gamma
+    pub const fn png_get_apply_gamma(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_APPLY_GAMMA)
+    }
+    /// Set whether the png decoder should gamma correct decoded samples
+    /// for display, using the gamma stored in the image's `gAMA` chunk and the
+    /// display gamma set by [`png_set_display_gamma`](Self::png_set_display_gamma).
+    ///
+    /// Images without a `gAMA` chunk and alpha samples are not changed,
+    /// neither are images kept at a native depth less than 8 bits.
+    /// The colorspace of the output is unchanged.
+    ///
+    /// This gives images that look right on a typical display without a color
+    /// management engine.
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_apply_gamma(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_APPLY_GAMMA, yes);
+        self
+    }
+    /// Whether the png decoder should output premultiplied alpha
+    pub const fn png_get_premultiply_alpha(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_PREMULTIPLY_ALPHA)
+    }
+    /// Set whether the png decoder should output premultiplied alpha
+    ///
+    /// When the output has an alpha channel, i.e it's `RGBA` or `LumaA`, colour
+    /// samples are multiplied by the alpha of their pixel. This includes alpha
+    /// created from `tRNS` chunks and palette expansion.
+    ///
+    /// Opaque images are unchanged.
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_premultiply_alpha(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_PREMULTIPLY_ALPHA, yes);
+        self
+    }
+    /// Whether the png decoder should return palette indices
+    /// for paletted images
+    pub const fn png_get_keep_palette_indices(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_KEEP_PALETTE_INDICES)
+    }
+    /// Set whether the png decoder should return the palette indices of
+    /// paletted images instead of expanding them to RGB(A)
+    ///
+    /// The image is then returned with one byte per pixel holding its
+    /// palette index, and the colour table, including transparency from a `tRNS`
+    /// chunk, can be retrieved from the decoder's `get_palette`.
+    /// Indices of images with bit depths less than 8 are unpacked to one byte
+    /// each, unless [`png_set_expand_depth`](Self::png_set_expand_depth) is false.
+    ///
+    /// This has no effect on other images.
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_keep_palette_indices(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_KEEP_PALETTE_INDICES, yes);
+        self
+    }
+    /// Whether the png decoder should convert all images to RGBA
+    pub const fn png_get_force_rgba(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_FORCE_RGBA)
+    }
+    /// Set whether the png decoder should convert all images to RGBA
+    ///
+    /// Grayscale samples are replicated to red, green and blue, and images
+    /// without an alpha channel get a fully opaque one, so every image is returned
+    /// as `RGBA` with the depth it would otherwise have, i.e 8 or 16 bits.
+    ///
+    /// This has no effect if palette indices or native bit depths less than 8
+    /// are kept, see [`png_set_keep_palette_indices`](Self::png_set_keep_palette_indices)
+    /// and [`png_set_expand_depth`](Self::png_set_expand_depth)
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_force_rgba(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_FORCE_RGBA, yes);
+        self
+    }
+    /// Get the gamma of the display used by the png
+    /// decoder for gamma correction
+    pub const fn png_get_display_gamma(&self) -> f32
+    {
+        self.display_gamma
+    }
+    /// Set the gamma of the display decoded images will be shown on,
+    /// used when gamma correction is enabled via [`png_set_apply_gamma`](Self::png_set_apply_gamma)
+    ///
+    /// Default value is 2.2
+    #[must_use]
+    pub fn png_set_display_gamma(mut self, gamma: f32) -> Self
+    {
+        self.display_gamma = gamma;
+        self
+    }
+    /// Get the factor the png decoder scales images down by
+    pub const fn png_get_scale_down(&self) -> ScaleDown
+    {
+        self.scale_down
+    }
+    /// Set the factor the png decoder scales images down by while decoding
+    ///
+    /// Each block of pixels, e.g 2x2 pixels for [`ScaleDown::Half`], is averaged into a
+    /// single output pixel as rows are decoded, so previews of huge images can be produced
+    /// without holding the full resolution image in memory. Blocks at the right and bottom
+    /// edges may be smaller, i.e the output dimensions are the image dimensions divided by the
+    /// [`factor`](ScaleDown::factor) and rounded up.
+    ///
+    /// Scaling is not applied when palette indices or sub 8 bit samples are kept,
+    /// see [`png_set_keep_palette_indices`](Self::png_set_keep_palette_indices) and
+    /// [`png_set_expand_depth`](Self::png_set_expand_depth), nor to animation frames.
+    ///
+    /// Default value is `ScaleDown::One`, i.e no scaling
+    #[must_use]
+    pub fn png_set_scale_down(mut self, scale: ScaleDown) -> Self
+    {
+        self.scale_down = scale;
+        self
+    }
+    /// Whether the png decoder should retain unknown ancillary chunks
+    pub const fn png_get_keep_unknown_chunks(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_KEEP_UNKNOWN_CHUNKS)
+    }
+    /// Set whether the png decoder should retain unknown ancillary chunks
+    ///
+    /// Ancillary chunks the decoder doesn't understand (e.g vendor chunks) are kept in
+    /// file order and can be retrieved via `PngDecoder::get_unknown_chunks`, allowing tools
+    /// to write them back out. Unknown critical chunks are never retained.
+    ///
+    /// The total size of the retained data is bounded by
+    /// [`png_set_unknown_chunks_limit`](Self::png_set_unknown_chunks_limit), chunks that would
+    /// exceed it are skipped.
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_keep_unknown_chunks(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_KEEP_UNKNOWN_CHUNKS, yes);
+        self
+    }
+    /// Get the maximum number of bytes of unknown chunk data the png decoder retains
+    pub const fn png_get_unknown_chunks_limit(&self) -> usize
+    {
+        self.unknown_chunks_limit
+    }
+    /// Set the maximum number of bytes of unknown chunk data the png decoder retains
+    /// when [`png_set_keep_unknown_chunks`](Self::png_set_keep_unknown_chunks) is enabled
+    ///
+    /// Default value is 1 MiB
+    #[must_use]
+    pub fn png_set_unknown_chunks_limit(mut self, limit: usize) -> Self
+    {
+        self.unknown_chunks_limit = limit;
+        self
+    }
+    /// Whether the png decoder should skip metadata chunks
+    /// without parsing them
+    pub const fn png_get_skip_ancillary(&self) -> bool
+    {
+        self.flags.contains(DecoderFlags::PNG_SKIP_ANCILLARY)
+    }
+    /// Set whether the png decoder should skip ancillary chunks that only carry
+    /// metadata without parsing their contents
+    ///
+    /// This covers text chunks (`tEXt`, `zTXt`, `iTXt`), ICC profiles (`iCCP`), exif data (`eXIf`)
+    /// and other metadata such as `tIME`, `pHYs`, `bKGD`, `cHRM`, `sRGB`, `sBIT`, `sPLT` and `hIST`,
+    /// avoiding the cost of parsing and decompressing them when only pixels and dimensions are needed.
+    /// The corresponding fields of the image information are left empty.
+    ///
+    /// Chunks needed to decode pixels, i.e `tRNS`, `gAMA` and animation chunks, are still parsed,
+    /// and unknown chunks are handled as usual.
+    ///
+    /// Default value is false
+    #[must_use]
+    pub fn png_set_skip_ancillary(mut self, yes: bool) -> Self
+    {
+        self.flags.set(DecoderFlags::PNG_SKIP_ANCILLARY, yes);
+        self
+    }
 }
 
 /// JPEG specific options
@@ -607,13 +1105,18 @@ impl Default for DecoderOptions
     fn default() -> Self
     {
         Self {
-            out_colorspace: ColorSpace::RGB,
-            max_width:      1 << 14,
-            max_height:     1 << 14,
-            max_scans:      100,
-            deflate_limit:  1 << 30,
-            flags:          decoder_strict_mode(),
-            endianness:     ByteEndian::BE
+            out_colorspace:       ColorSpace::RGB,
+            max_width:            1 << 14,
+            max_height:           1 << 14,
+            max_pixels:           1 << 26,
+            max_scans:            100,
+            deflate_limit:        1 << 30,
+            flags:                decoder_strict_mode(),
+            endianness:           ByteEndian::BE,
+            display_gamma:        2.2,
+            scale_down:           ScaleDown::One,
+            unknown_chunks_limit: 1 << 20,
+            downsample_rounding:  Rounding::Truncate
         }
     }
 }
@@ -13,6 +13,8 @@ bitflags! {
         const JPEG_OPTIMIZED_HUFFMAN  = 0b0000_0000_0000_0000_0000_0000_0000_0010;
         /// Whether to not preserve metadata across image transformations
         const IMAGE_STRIP_METADATA    = 0b0000_0000_0000_0000_0000_0000_0000_0100;
+        /// Whether PNG images should be encoded with Adam7 interlacing
+        const PNG_ENCODE_INTERLACED   = 0b0000_0000_0000_0000_0000_0000_0000_1000;
 
     }
 }
@@ -24,6 +26,7 @@ impl Default for EncoderFlags
         options.set(EncoderFlags::JPEG_ENCODE_PROGRESSIVE, false);
         options.set(EncoderFlags::JPEG_OPTIMIZED_HUFFMAN, false);
         options.set(EncoderFlags::IMAGE_STRIP_METADATA, false);
+        options.set(EncoderFlags::PNG_ENCODE_INTERLACED, false);
 
         options
     }
@@ -107,10 +110,22 @@ impl EncoderOptions
     {
         self.colorspace
     }
+    /// Get the effort the encoder should spend on making the output smaller
+    ///
+    /// Higher values mean more time is spent encoding, for
+    /// lossless formats this produces smaller files
     pub const fn get_effort(&self) -> u8
     {
         self.effort
     }
+    /// Set the effort the encoder should spend on making the output smaller
+    ///
+    /// The interpretation of this value depends on the encoder
+    pub fn set_effort(mut self, effort: u8) -> Self
+    {
+        self.effort = effort;
+        self
+    }
 
     /// Set width for the image to be encoded
     pub fn set_width(mut self, width: usize) -> Self
@@ -197,3 +212,26 @@ impl EncoderOptions
         self.flags.contains(EncoderFlags::JPEG_OPTIMIZED_HUFFMAN)
     }
 }
+
+/// PNG options
+impl EncoderOptions
+{
+    /// Whether the png encoder should interlace the image
+    /// using Adam7 interlacing
+    ///
+    /// Default is `false`.
+    ///
+    /// Interlaced images can be displayed progressively as they are loaded at the cost
+    /// of slightly larger files
+    pub const fn png_encode_interlaced(&self) -> bool
+    {
+        self.flags.contains(EncoderFlags::PNG_ENCODE_INTERLACED)
+    }
+    /// Set whether the png encoder should interlace the image
+    /// using Adam7 interlacing
+    pub fn png_set_encode_interlaced(mut self, yes: bool) -> Self
+    {
+        self.flags.set(EncoderFlags::PNG_ENCODE_INTERLACED, yes);
+        self
+    }
+}
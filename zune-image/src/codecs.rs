@@ -115,8 +115,11 @@ impl ImageFormat
             {
                 #[cfg(feature = "png")]
                 {
+                    // images store at least 8 bits per sample, so packed
+                    // samples are always expanded
                     Ok(Box::new(zune_png::PngDecoder::new_with_options(
-                        data, options
+                        data,
+                        options.png_set_expand_depth(true)
                     )))
                 }
                 #[cfg(not(feature = "png"))]
@@ -25,7 +25,8 @@ impl<'a> DecoderTrait<'a> for PngDecoder<'a>
             .map_err(<PngDecodeErrors as Into<ImageErrors>>::into)?;
 
         let depth = self.get_depth().unwrap();
-        let (width, height) = self.get_dimensions().unwrap();
+        // the image may have been scaled down while decoding
+        let (width, height) = self.get_output_dimensions().unwrap();
         let colorspace = self.get_colorspace().unwrap();
 
         let mut image = match pixels
@@ -59,7 +60,7 @@ impl<'a> DecoderTrait<'a> for PngDecoder<'a>
         self.decode_headers()
             .map_err(<PngDecodeErrors as Into<ImageErrors>>::into)?;
 
-        let (width, height) = self.get_dimensions().unwrap();
+        let (width, height) = self.get_output_dimensions().unwrap();
         let depth = self.get_depth().unwrap();
 
         let mut metadata = ImageMetadata {
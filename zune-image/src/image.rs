@@ -15,14 +15,21 @@ use std::fmt::Debug;
 use std::mem::size_of;
 
 use bytemuck::{Pod, Zeroable};
-use zune_core::bit_depth::BitDepth;
+use zune_core::bit_depth::{BitDepth, BitType};
 use zune_core::colorspace::{ColorCharacteristics, ColorSpace};
+use zune_imageprocs::ascii_art::ascii_art;
+pub use zune_imageprocs::ascii_art::AsciiCharset;
+use zune_imageprocs::integral_image::integral_image;
+use zune_imageprocs::laplacian_blend::laplacian_blend;
+use zune_imageprocs::mipmap::{box_downsample, mipmap_dimensions};
+use zune_imageprocs::perceptual_hash::perceptual_hash;
 use zune_imageprocs::traits::NumOps;
 
 use crate::channel::{Channel, ChannelErrors};
 use crate::deinterleave::{deinterleave_f32, deinterleave_u16, deinterleave_u8};
-use crate::errors::ImageErrors;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::frame::Frame;
+use crate::impls::colorspace::ColorspaceConv;
 use crate::impls::depth::Depth;
 use crate::metadata::ImageMetadata;
 use crate::traits::{OperationsTrait, ZuneInts};
@@ -228,7 +235,7 @@ impl Image
         pixel: T, colorspace: ColorSpace, width: usize, height: usize
     ) -> Result<Image, ImageErrors>
     {
-        let dims = width * height * T::depth().size_of();
+        let dims = width * height;
 
         let channels = vec![Channel::from_elm::<T>(dims, pixel); colorspace.num_components()];
 
@@ -525,6 +532,560 @@ impl Image
     }
 }
 
+/// Channel manipulation
+impl Image
+{
+    /// Split the image into one grayscale image per channel
+    ///
+    /// The images are returned in the order channels are stored for
+    /// the colorspace, e.g for RGBA that's red, green, blue and alpha.
+    /// Each image keeps the dimensions, depth, frames and metadata
+    /// of this image.
+    ///
+    /// Use [`merge_channels`](Self::merge_channels) to recombine them
+    ///
+    /// ```
+    /// use zune_core::colorspace::ColorSpace;
+    /// use zune_image::image::Image;
+    ///
+    /// let image = Image::from_u8(&[1, 2, 3, 4, 5, 6], 2, 1, ColorSpace::RGB);
+    ///
+    /// let channels = image.split_channels();
+    ///
+    /// assert_eq!(channels.len(), 3);
+    /// assert_eq!(channels[2].flatten_frames::<u8>()[0], [3, 6]);
+    ///
+    /// let merged = Image::merge_channels(channels, ColorSpace::RGB).unwrap();
+    ///
+    /// assert_eq!(merged.flatten_frames::<u8>()[0], [1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn split_channels(self) -> Vec<Image>
+    {
+        let components = self.get_colorspace().num_components();
+
+        let mut metadata = self.metadata;
+        metadata.set_colorspace(ColorSpace::Luma);
+
+        (0..components)
+            .map(|component| {
+                let frames = self
+                    .frames
+                    .iter()
+                    .map(|frame| Frame {
+                        channels: vec![frame.channels[component].clone()],
+                        duration: frame.duration
+                    })
+                    .collect();
+
+                Image {
+                    frames,
+                    metadata: metadata.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Combine single channel images into one image of `colorspace`
+    ///
+    /// The images should be in the order channels are stored for
+    /// the colorspace, e.g red, green, blue for RGB.
+    ///
+    /// The new image takes its metadata from the first image
+    ///
+    /// # Errors
+    /// - The number of images does not match the colorspace components
+    /// - An image has more than one channel
+    /// - The images differ in dimensions, depth or number of frames
+    pub fn merge_channels(images: Vec<Image>, colorspace: ColorSpace) -> Result<Image, ImageErrors>
+    {
+        let components = colorspace.num_components();
+
+        if images.len() != components
+        {
+            return Err(ImageErrors::OperationsError(
+                ImageOperationsErrors::WrongComponents(components, images.len())
+            ));
+        }
+        let first = &images[0];
+        let (width, height) = first.get_dimensions();
+
+        for image in &images
+        {
+            let (image_width, image_height) = image.get_dimensions();
+
+            if (width, height) != (image_width, image_height)
+            {
+                return Err(ImageErrors::DimensionsMisMatch(
+                    width * height,
+                    image_width * image_height
+                ));
+            }
+            let image_components = image.get_colorspace().num_components();
+
+            if image_components != 1
+            {
+                return Err(ImageErrors::OperationsError(
+                    ImageOperationsErrors::WrongComponents(1, image_components)
+                ));
+            }
+            if image.get_depth() != first.get_depth() || image.frames.len() != first.frames.len()
+            {
+                return Err(ImageErrors::GenericStr(
+                    "Images to merge must have the same depth and number of frames"
+                ));
+            }
+        }
+        let mut metadata = first.metadata.clone();
+        metadata.set_colorspace(colorspace);
+
+        let frames = (0..first.frames.len())
+            .map(|i| Frame {
+                channels: images
+                    .iter()
+                    .map(|image| image.frames[i].channels[0].clone())
+                    .collect(),
+                duration: first.frames[i].duration
+            })
+            .collect();
+
+        Ok(Image { frames, metadata })
+    }
+}
+
+/// Texture utilities
+impl Image
+{
+    /// Generate the full mipmap chain of this image and pack all levels into
+    /// a single atlas
+    ///
+    /// Each level is half the size of the previous one, created by averaging 2x2 blocks
+    /// of the previous level, until a 1x1 level is reached.
+    ///
+    /// Level 0 (the full image) is placed on the left of the atlas, and successive
+    /// levels are stacked top to bottom to its right, areas not covered by any level
+    /// are filled with zeroes.
+    ///
+    /// The atlas keeps the colorspace, depth and metadata of the image and each
+    /// frame of an animated image gets its own atlas
+    ///
+    /// # Errors
+    /// If the image depth is not 8 or 16 bits
+    pub fn generate_mipmap_atlas(&self) -> Result<Image, ImageErrors>
+    {
+        let (width, height) = self.get_dimensions();
+        let levels = mipmap_dimensions(width, height);
+
+        // position of each level in the atlas
+        let mut positions = vec![(0, 0)];
+        let mut y_offset = 0;
+
+        for (_, level_height) in levels.iter().skip(1)
+        {
+            positions.push((width, y_offset));
+            y_offset += level_height;
+        }
+        let atlas_width = width + levels.get(1).map_or(0, |x| x.0);
+        let atlas_height = height.max(y_offset);
+
+        let colorspace = self.get_colorspace();
+        let depth = self.get_depth();
+
+        let frames = self
+            .frames
+            .iter()
+            .map(|frame| {
+                let channels = frame
+                    .get_channels_ref(colorspace, false)
+                    .iter()
+                    .map(|channel| match depth.bit_type()
+                    {
+                        BitType::U8 => Ok(build_atlas::<u8>(
+                            channel.reinterpret_as().unwrap(),
+                            &levels,
+                            &positions,
+                            atlas_width,
+                            atlas_height
+                        )),
+                        BitType::U16 => Ok(build_atlas::<u16>(
+                            channel.reinterpret_as().unwrap(),
+                            &levels,
+                            &positions,
+                            atlas_width,
+                            atlas_height
+                        )),
+                        bit_type => Err(ImageErrors::OperationsError(
+                            ImageOperationsErrors::UnsupportedType("Mipmap atlas", bit_type)
+                        ))
+                    })
+                    .collect::<Result<Vec<Channel>, ImageErrors>>()?;
+
+                Ok(Frame::new_with_duration(channels, frame.duration))
+            })
+            .collect::<Result<Vec<Frame>, ImageErrors>>()?;
+
+        let mut metadata = self.metadata.clone();
+        metadata.set_dimensions(atlas_width, atlas_height);
+
+        Ok(Image { frames, metadata })
+    }
+}
+
+/// Image analysis
+impl Image
+{
+    /// Compute the integral image (summed-area table) of each channel
+    ///
+    /// Entry `(x,y)` of each table contains the sum of all pixels above
+    /// and to the left of `(x,y)` inclusive, allowing the sum of any rectangle to be
+    /// computed in constant time via [`rectangle_sum`](zune_imageprocs::integral_image::rectangle_sum).
+    ///
+    /// Tables are returned in channel order, for animated images the tables of each
+    /// frame follow those of the previous frame.
+    ///
+    /// # Errors
+    /// If the image depth is not 8 or 16 bits
+    pub fn integral_image(&self) -> Result<Vec<Vec<u64>>, ImageErrors>
+    {
+        let (width, height) = self.get_dimensions();
+        let depth = self.get_depth();
+
+        self.get_channels_ref(false)
+            .iter()
+            .map(|channel| match depth.bit_type()
+            {
+                BitType::U8 => Ok(integral_image::<u8>(
+                    channel.reinterpret_as().unwrap(),
+                    width,
+                    height
+                )),
+                BitType::U16 => Ok(integral_image::<u16>(
+                    channel.reinterpret_as().unwrap(),
+                    width,
+                    height
+                )),
+                bit_type => Err(ImageErrors::OperationsError(
+                    ImageOperationsErrors::UnsupportedType("Integral image", bit_type)
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Compositing
+impl Image
+{
+    /// Blend this image with `other` using multi-band (Laplacian pyramid) blending
+    ///
+    /// Where the mask is black the result is this image, where it's white the result
+    /// is `other`. Unlike compositing with the mask directly, each frequency band is blended
+    /// over a region proportional to its scale, so seams between the images are
+    /// hidden without blurring fine detail, which is useful for panorama stitching
+    /// and seamless inserts.
+    ///
+    /// Frames of animated images are blended pairwise, the first frame of the mask
+    /// is used for every frame.
+    ///
+    /// # Arguments
+    /// - other: Image to blend with, must have the same dimensions, colorspace, depth
+    ///   and number of frames as this image
+    /// - mask: Grayscale image with the same dimensions as this image
+    /// - levels: Number of pyramid levels, more levels give a wider transition for
+    ///   low frequencies, `1` is equivalent to compositing with the mask
+    pub fn laplacian_blend(
+        &self, other: &Image, mask: &Image, levels: usize
+    ) -> Result<Image, ImageErrors>
+    {
+        let (width, height) = self.get_dimensions();
+
+        for image in [other, mask]
+        {
+            let (image_width, image_height) = image.get_dimensions();
+
+            if (width, height) != (image_width, image_height)
+            {
+                return Err(ImageErrors::DimensionsMisMatch(
+                    width * height,
+                    image_width * image_height
+                ));
+            }
+        }
+        let colorspace = self.get_colorspace();
+        let depth = self.get_depth();
+
+        if other.get_colorspace() != colorspace
+            || other.get_depth() != depth
+            || other.frames.len() != self.frames.len()
+        {
+            return Err(ImageErrors::GenericStr(
+                "Images to blend must have the same colorspace, depth and number of frames"
+            ));
+        }
+        let mask_colorspace = mask.get_colorspace();
+
+        if !matches!(mask_colorspace, ColorSpace::Luma | ColorSpace::LumaA)
+        {
+            return Err(ImageErrors::UnsupportedColorspace(
+                mask_colorspace,
+                "Laplacian Blend",
+                &[ColorSpace::Luma, ColorSpace::LumaA]
+            ));
+        }
+        let mask_depth = mask.get_depth();
+        let mask_max = f32::from(mask_depth.max_value());
+        let mask_channel = &mask.frames[0].get_channels_ref(mask_colorspace, true)[0];
+
+        let weights: Vec<f32> = match mask_depth.bit_type()
+        {
+            BitType::U8 => mask_channel
+                .reinterpret_as::<u8>()
+                .unwrap()
+                .iter()
+                .map(|x| f32::from(*x) / mask_max)
+                .collect(),
+            BitType::U16 => mask_channel
+                .reinterpret_as::<u16>()
+                .unwrap()
+                .iter()
+                .map(|x| f32::from(*x) / mask_max)
+                .collect(),
+            _ => return Err(ImageErrors::GenericStr("Unsupported bit depth for mask"))
+        };
+
+        let mut output = self.clone();
+
+        for (out_frame, other_frame) in output.frames.iter_mut().zip(&other.frames)
+        {
+            let other_channels = other_frame.get_channels_ref(colorspace, false);
+            let out_channels = out_frame.get_channels_mut(colorspace, false);
+
+            for (out_channel, other_channel) in out_channels.iter_mut().zip(other_channels)
+            {
+                let first = out_channel.clone();
+
+                match depth.bit_type()
+                {
+                    BitType::U8 => laplacian_blend::<u8>(
+                        first.reinterpret_as().unwrap(),
+                        other_channel.reinterpret_as().unwrap(),
+                        &weights,
+                        width,
+                        height,
+                        levels,
+                        out_channel.reinterpret_as_mut().unwrap()
+                    ),
+                    BitType::U16 => laplacian_blend::<u16>(
+                        first.reinterpret_as().unwrap(),
+                        other_channel.reinterpret_as().unwrap(),
+                        &weights,
+                        width,
+                        height,
+                        levels,
+                        out_channel.reinterpret_as_mut().unwrap()
+                    ),
+                    bit_type =>
+                    {
+                        return Err(ImageErrors::OperationsError(
+                            ImageOperationsErrors::UnsupportedType("Laplacian Blend", bit_type)
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+/// Image hashing
+impl Image
+{
+    /// Compute the DCT based perceptual hash (pHash) of the image
+    ///
+    /// Images that look alike, e.g resized, recompressed or slightly recoloured
+    /// copies of each other, have hashes differing in few bits, so the Hamming
+    /// distance `(a ^ b).count_ones()` between two hashes measures how similar
+    /// two images are, with distances below about 10 indicating near duplicates.
+    ///
+    /// Only the first frame of animated images is hashed and alpha is ignored.
+    ///
+    /// # Errors
+    /// If the image depth is not 8 or 16 bits, or the image can't be converted to RGB
+    pub fn perceptual_hash(&self) -> Result<u64, ImageErrors>
+    {
+        let colorspace = self.get_colorspace();
+
+        if !matches!(
+            colorspace,
+            ColorSpace::Luma | ColorSpace::LumaA | ColorSpace::RGB | ColorSpace::RGBA
+        )
+        {
+            let mut image = self.clone();
+
+            ColorspaceConv::new(ColorSpace::RGB).execute(&mut image)?;
+
+            return image.perceptual_hash();
+        }
+        let (width, height) = self.get_dimensions();
+        let channels = self.frames[0].get_channels_ref(colorspace, true);
+
+        match self.get_depth().bit_type()
+        {
+            BitType::U8 =>
+            {
+                let channels: Vec<&[u8]> = channels
+                    .iter()
+                    .map(|channel| channel.reinterpret_as().unwrap())
+                    .collect();
+
+                Ok(perceptual_hash(&channels, width, height))
+            }
+            BitType::U16 =>
+            {
+                let channels: Vec<&[u16]> = channels
+                    .iter()
+                    .map(|channel| channel.reinterpret_as().unwrap())
+                    .collect();
+
+                Ok(perceptual_hash(&channels, width, height))
+            }
+            bit_type => Err(ImageErrors::OperationsError(
+                ImageOperationsErrors::UnsupportedType("Perceptual hash", bit_type)
+            ))
+        }
+    }
+}
+
+/// Terminal previews
+impl Image
+{
+    /// Render the image as text, e.g. for previews in a terminal
+    ///
+    /// The image is downsampled to `width` columns, with as many rows as needed to
+    /// preserve its aspect ratio given that terminal cells are about twice as
+    /// tall as they are wide. The luminance of each cell is mapped to a character of
+    /// `charset`, black to its darkest and white to its lightest character.
+    ///
+    /// Rows are separated by newlines, only the first frame of animated images is rendered
+    /// and alpha is ignored.
+    ///
+    /// # Errors
+    /// If the image depth is not 8 or 16 bits, or the image can't be converted to RGB
+    pub fn to_ascii(&self, width: usize, charset: AsciiCharset) -> Result<String, ImageErrors>
+    {
+        self.render_ascii(width, charset, false)
+    }
+
+    /// Render the image as text colored with ANSI 24 bit color escapes
+    ///
+    /// This is the same as [`to_ascii`](Image::to_ascii), with each character
+    /// drawn in the average color of the pixels it covers,
+    /// for terminals supporting true color.
+    ///
+    /// # Errors
+    /// If the image depth is not 8 or 16 bits, or the image can't be converted to RGB
+    pub fn to_ascii_colored(
+        &self, width: usize, charset: AsciiCharset
+    ) -> Result<String, ImageErrors>
+    {
+        self.render_ascii(width, charset, true)
+    }
+
+    fn render_ascii(
+        &self, columns: usize, charset: AsciiCharset, color: bool
+    ) -> Result<String, ImageErrors>
+    {
+        let colorspace = self.get_colorspace();
+
+        if !matches!(
+            colorspace,
+            ColorSpace::Luma | ColorSpace::LumaA | ColorSpace::RGB | ColorSpace::RGBA
+        )
+        {
+            let mut image = self.clone();
+
+            ColorspaceConv::new(ColorSpace::RGB).execute(&mut image)?;
+
+            return image.render_ascii(columns, charset, color);
+        }
+        let (width, height) = self.get_dimensions();
+        let depth = self.get_depth();
+        let channels = self.frames[0].get_channels_ref(colorspace, true);
+
+        match depth.bit_type()
+        {
+            BitType::U8 =>
+            {
+                let channels: Vec<&[u8]> = channels
+                    .iter()
+                    .map(|channel| channel.reinterpret_as().unwrap())
+                    .collect();
+
+                Ok(ascii_art(
+                    &channels,
+                    width,
+                    height,
+                    columns,
+                    depth.max_value(),
+                    charset,
+                    color
+                ))
+            }
+            BitType::U16 =>
+            {
+                let channels: Vec<&[u16]> = channels
+                    .iter()
+                    .map(|channel| channel.reinterpret_as().unwrap())
+                    .collect();
+
+                Ok(ascii_art(
+                    &channels,
+                    width,
+                    height,
+                    columns,
+                    depth.max_value(),
+                    charset,
+                    color
+                ))
+            }
+            bit_type => Err(ImageErrors::OperationsError(
+                ImageOperationsErrors::UnsupportedType("ASCII art", bit_type)
+            ))
+        }
+    }
+}
+
+/// Build a mipmap atlas for a single channel
+fn build_atlas<T>(
+    channel: &[T], levels: &[(usize, usize)], positions: &[(usize, usize)], atlas_width: usize,
+    atlas_height: usize
+) -> Channel
+where
+    T: Copy + Default + NumOps<T> + 'static + Pod
+{
+    let mut atlas = Channel::new_with_length::<T>(atlas_width * atlas_height * size_of::<T>());
+    let atlas_pixels = atlas.reinterpret_as_mut::<T>().unwrap();
+
+    let mut level = channel.to_vec();
+
+    for (i, (&(level_width, level_height), &(x, y))) in levels.iter().zip(positions).enumerate()
+    {
+        if i > 0
+        {
+            let (prev_width, prev_height) = levels[i - 1];
+            let mut next = vec![T::default(); level_width * level_height];
+
+            box_downsample(&level, prev_width, prev_height, &mut next);
+            level = next;
+        }
+        for (row, atlas_row) in level
+            .chunks_exact(level_width)
+            .zip(atlas_pixels.chunks_exact_mut(atlas_width).skip(y))
+        {
+            atlas_row[x..x + level_width].copy_from_slice(row);
+        }
+    }
+    atlas
+}
+
 fn checked_mul(width: usize, height: usize, depth: usize, colorspace_components: usize) -> usize
 {
     width
@@ -2,27 +2,46 @@
 //!
 //! This contains structs that implement `OperationsTrait`
 //! meaning they can manipulate images
+pub mod alpha;
+pub mod anisotropic_diffusion;
 pub mod box_blur;
 pub mod brighten;
 pub mod colorspace;
+pub mod composite;
 pub mod contrast;
 pub mod convolve;
 pub mod crop;
 pub mod depth;
+pub mod displacement;
+pub mod draw_text;
 pub mod flip;
 pub mod flop;
 pub mod gamma;
 pub mod gaussian_blur;
 pub mod grayscale;
+pub mod guided_filter;
+pub mod halftone;
+pub mod histogram_match;
+pub mod hsv;
 pub mod invert;
+pub mod kmeans;
 pub mod median;
 pub mod mirror;
 pub mod orientation;
+pub mod perspective;
+pub mod posterize;
+pub mod reduce_depth;
 pub mod resize;
+pub mod rotate;
+pub mod retinex;
 pub mod scharr;
+pub mod signed_distance_field;
+pub mod smart_crop;
 pub mod sobel;
 pub mod statistics;
 pub mod stretch_contrast;
+pub mod swap_channels;
 pub mod threshold;
 pub mod transpose;
 pub mod unsharpen;
+pub mod ycbcr;
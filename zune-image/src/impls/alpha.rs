@@ -0,0 +1,170 @@
+//! Add or remove the alpha channel of an image
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Add a constant alpha channel to an image
+///
+/// RGB becomes RGBA, BGR becomes BGRA and Luma becomes LumaA.
+/// Images that already have an alpha channel are left untouched.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::alpha::AddAlpha;
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::from_u8(&[10, 20, 30], 1, 1, ColorSpace::RGB);
+///
+/// AddAlpha::new(1.0).execute(&mut image).unwrap();
+///
+/// assert_eq!(image.get_colorspace(), ColorSpace::RGBA);
+/// assert_eq!(image.flatten_frames::<u8>()[0], [10, 20, 30, 255]);
+/// ```
+pub struct AddAlpha
+{
+    value: f32
+}
+
+impl AddAlpha
+{
+    /// Create a new alpha channel adder
+    ///
+    /// # Arguments
+    /// - value: Opacity of the new channel, between 0.0 (transparent)
+    ///   and 1.0 (opaque), it's scaled to the image depth
+    pub fn new(value: f32) -> AddAlpha
+    {
+        AddAlpha { value }
+    }
+}
+
+impl OperationsTrait for AddAlpha
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Add alpha"
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let new_colorspace = match image.get_colorspace()
+        {
+            ColorSpace::RGB => ColorSpace::RGBA,
+            ColorSpace::BGR => ColorSpace::BGRA,
+            ColorSpace::Luma => ColorSpace::LumaA,
+            // already has alpha
+            _ => return Ok(())
+        };
+        if !(0.0..=1.0).contains(&self.value)
+        {
+            let msg = format!("Alpha value {} is not between 0.0 and 1.0", self.value);
+            return Err(ImageErrors::GenericString(msg));
+        }
+        let (width, height) = image.get_dimensions();
+        let length = width * height;
+        let depth = image.get_depth();
+
+        let scaled = (self.value * f32::from(depth.max_value())).round();
+
+        let alpha = match depth.bit_type()
+        {
+            BitType::U8 => Channel::from_elm(length, scaled as u8),
+            BitType::U16 => Channel::from_elm(length, scaled as u16),
+            BitType::F32 => Channel::from_elm(length, self.value),
+            _ => todo!()
+        };
+
+        for frame in image.get_frames_mut()
+        {
+            frame.add(alpha.clone());
+        }
+        image.set_colorspace(new_colorspace);
+
+        Ok(())
+    }
+
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[
+            ColorSpace::RGB,
+            ColorSpace::RGBA,
+            ColorSpace::BGR,
+            ColorSpace::BGRA,
+            ColorSpace::Luma,
+            ColorSpace::LumaA
+        ]
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16, BitType::F32]
+    }
+}
+
+/// Remove the alpha channel of an image
+///
+/// RGBA becomes RGB, BGRA becomes BGR and LumaA becomes Luma.
+/// Images without an alpha channel are left untouched.
+///
+/// The color channels are kept as is, to blend transparent pixels
+/// with a background, composite the image before dropping alpha.
+#[derive(Default)]
+pub struct DropAlpha;
+
+impl DropAlpha
+{
+    pub fn new() -> DropAlpha
+    {
+        DropAlpha
+    }
+}
+
+impl OperationsTrait for DropAlpha
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Drop alpha"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let new_colorspace = match image.get_colorspace()
+        {
+            ColorSpace::RGBA => ColorSpace::RGB,
+            ColorSpace::BGRA => ColorSpace::BGR,
+            ColorSpace::LumaA => ColorSpace::Luma,
+            _ => return Ok(())
+        };
+        // alpha is always the last channel
+        for frame in image.get_frames_mut()
+        {
+            frame.channels_vec().pop();
+        }
+        image.set_colorspace(new_colorspace);
+
+        Ok(())
+    }
+
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[
+            ColorSpace::RGB,
+            ColorSpace::RGBA,
+            ColorSpace::BGR,
+            ColorSpace::BGRA,
+            ColorSpace::Luma,
+            ColorSpace::LumaA
+        ]
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16, BitType::F32]
+    }
+}
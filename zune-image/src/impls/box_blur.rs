@@ -8,10 +8,14 @@ use crate::traits::OperationsTrait;
 
 /// Perform a box blur
 ///
-/// Radius is a measure of how many
-/// pixels to include in the box blur.
+/// Each pixel becomes the average of the `(2*radius+1)` by `(2*radius+1)`
+/// box centered on it, pixels outside the image are treated as copies
+/// of the nearest edge pixel.
 ///
-/// The greater the radius, the more pronounced the box blur
+/// The greater the radius, the more pronounced the box blur,
+/// it uses running sums so the cost does not depend on the radius.
+///
+/// Applying it three times is a cheap approximation of a gaussian blur
 #[derive(Default)]
 pub struct BoxBlur
 {
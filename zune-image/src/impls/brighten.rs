@@ -6,6 +6,13 @@ use crate::errors::ImageErrors;
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
+/// Brighten or darken an image
+///
+/// Adds a signed value to every sample of the color channels,
+/// positive values brighten the image and negative values darken it.
+///
+/// Results saturate at zero and the maximum value for the image depth,
+/// the alpha channel is left untouched
 #[derive(Default)]
 pub struct Brighten
 {
@@ -37,12 +44,12 @@ impl OperationsTrait for Brighten
             {
                 BitType::U8 => brighten(
                     channel.reinterpret_as_mut::<u8>().unwrap(),
-                    self.value as u8,
+                    self.value,
                     max_val as u8
                 ),
                 BitType::U16 => brighten(
                     channel.reinterpret_as_mut::<u16>().unwrap(),
-                    self.value as u16,
+                    self.value,
                     max_val
                 ),
                 _ => todo!()
@@ -1,10 +1,12 @@
-use zune_core::bit_depth::BitType;
-use zune_core::colorspace::ColorSpace;
+use zune_core::bit_depth::{BitDepth, BitType};
+use zune_core::colorspace::{ColorSpace, ALL_COLORSPACES};
 
 use crate::channel::Channel;
 use crate::errors::ImageErrors;
 use crate::image::Image;
 use crate::impls::grayscale::RgbToGrayScale;
+use crate::impls::hsv::{HslToRgb, HsvToRgb, RgbToHsl, RgbToHsv};
+use crate::impls::ycbcr::{RgbToYCbCr, YCbCrStandard, YCbCrToRgb};
 use crate::traits::OperationsTrait;
 
 pub struct ColorspaceConv
@@ -129,6 +131,30 @@ impl OperationsTrait for ColorspaceConv
             {
                 convert_luma_to_rgb(image, self.to)?;
             }
+            (ColorSpace::RGB, ColorSpace::YCbCr) =>
+            {
+                RgbToYCbCr::new(YCbCrStandard::Jpeg).execute(image)?;
+            }
+            (ColorSpace::YCbCr, ColorSpace::RGB) =>
+            {
+                YCbCrToRgb::new(YCbCrStandard::Jpeg).execute(image)?;
+            }
+            (ColorSpace::RGB, ColorSpace::HSV) =>
+            {
+                RgbToHsv::new().execute(image)?;
+            }
+            (ColorSpace::HSV, ColorSpace::RGB) =>
+            {
+                HsvToRgb::new().execute(image)?;
+            }
+            (ColorSpace::RGB, ColorSpace::HSL) =>
+            {
+                RgbToHsl::new().execute(image)?;
+            }
+            (ColorSpace::HSL, ColorSpace::RGB) =>
+            {
+                HslToRgb::new().execute(image)?;
+            }
             (ColorSpace::LumaA, ColorSpace::Luma) =>
             {
                 // pop last item in the vec which should
@@ -160,6 +186,10 @@ impl OperationsTrait for ColorspaceConv
         Ok(())
     }
 
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &ALL_COLORSPACES
+    }
     fn supported_types(&self) -> &'static [BitType]
     {
         &[BitType::U16, BitType::U8]
@@ -206,3 +236,20 @@ fn convert_luma_to_rgb(image: &mut Image, out_colorspace: ColorSpace) -> Result<
     }
     Ok(())
 }
+
+/// Run `function` on the three channels of every frame
+pub(crate) fn convert_color_channels<F>(image: &mut Image, function: F)
+where
+    F: Fn(&mut Channel, &mut Channel, &mut Channel, BitDepth)
+{
+    let colorspace = image.get_colorspace();
+    let depth = image.get_depth();
+
+    for frame in image.get_frames_mut()
+    {
+        if let [c1, c2, c3, ..] = frame.get_channels_mut(colorspace, true)
+        {
+            function(c1, c2, c3, depth);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Blend an image onto another
+use bytemuck::Pod;
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+pub use zune_imageprocs::composite::CompositeMode;
+use zune_imageprocs::composite::{composite, composite_alpha};
+use zune_imageprocs::traits::NumOps;
+
+use crate::errors::ImageErrors;
+use crate::frame::Frame;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Blend an image onto the image this operation is executed on
+///
+/// The top image is placed with its top left corner at `(x, y)` and
+/// parts of it falling outside the base image are clipped.
+///
+/// Both images must have the same depth and the same colorspace apart
+/// from alpha, e.g an RGBA image can be composited onto an RGB image.
+/// Images without an alpha channel are treated as fully opaque, and
+/// the alpha channel of the base, if present, is updated to cover the
+/// top image.
+///
+/// For animated base images the first frame of the top image is
+/// composited onto every frame.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::composite::{Composite, CompositeMode};
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::fill(0_u8, ColorSpace::RGB, 4, 4).unwrap();
+/// // a half transparent white watermark
+/// let watermark = Image::from_u8(&[255, 255, 255, 128], 1, 1, ColorSpace::RGBA);
+///
+/// Composite::new(watermark, 3, 3, CompositeMode::SourceOver)
+///     .execute(&mut image)
+///     .unwrap();
+///
+/// assert_eq!(image.flatten_frames::<u8>()[0][45..], [128, 128, 128]);
+/// ```
+pub struct Composite
+{
+    top:  Image,
+    x:    usize,
+    y:    usize,
+    mode: CompositeMode
+}
+
+impl Composite
+{
+    /// Create a new composite operation
+    ///
+    /// # Arguments
+    /// - top: The image to place over the base image
+    /// - x,y: Position of the top left corner of `top` in the base image
+    /// - mode: How colors of the two images are combined
+    pub fn new(top: Image, x: usize, y: usize, mode: CompositeMode) -> Composite
+    {
+        Composite { top, x, y, mode }
+    }
+
+    fn composite_frame<T>(&self, base: &mut Frame, top: &Frame, has_alpha: bool, width: usize)
+    where
+        T: Copy + Default + Pod + NumOps<T>
+    {
+        let (top_width, top_height) = self.top.get_dimensions();
+        let max_value = self.top.get_depth().max_value();
+
+        // alpha is always the last channel
+        let (base_colors, base_alpha) = if has_alpha
+        {
+            let (alpha, colors) = base.channels.split_last_mut().unwrap();
+            (colors, Some(alpha))
+        }
+        else
+        {
+            (&mut base.channels[..], None)
+        };
+        let top_alpha = self
+            .top
+            .get_colorspace()
+            .has_alpha()
+            .then(|| top.channels.last().unwrap().reinterpret_as::<T>().unwrap());
+
+        for (base_channel, top_channel) in base_colors.iter_mut().zip(&top.channels)
+        {
+            composite(
+                base_channel.reinterpret_as_mut::<T>().unwrap(),
+                base_alpha.as_deref().map(|a| a.reinterpret_as::<T>().unwrap()),
+                width,
+                top_channel.reinterpret_as::<T>().unwrap(),
+                top_alpha,
+                top_width,
+                self.x,
+                self.y,
+                self.mode,
+                max_value
+            );
+        }
+        if let Some(alpha) = base_alpha
+        {
+            composite_alpha(
+                alpha.reinterpret_as_mut::<T>().unwrap(),
+                width,
+                top_alpha,
+                top_width,
+                top_height,
+                self.x,
+                self.y,
+                max_value
+            );
+        }
+    }
+}
+
+/// The colorspace with the alpha channel removed
+fn without_alpha(colorspace: ColorSpace) -> ColorSpace
+{
+    match colorspace
+    {
+        ColorSpace::RGBA => ColorSpace::RGB,
+        ColorSpace::BGRA => ColorSpace::BGR,
+        ColorSpace::LumaA => ColorSpace::Luma,
+        colorspace => colorspace
+    }
+}
+
+impl OperationsTrait for Composite
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Composite"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let colorspace = image.get_colorspace();
+        let top_colorspace = self.top.get_colorspace();
+
+        if without_alpha(colorspace) != without_alpha(top_colorspace)
+        {
+            let msg =
+                format!("Cannot composite a {top_colorspace:?} image onto a {colorspace:?} image");
+            return Err(ImageErrors::GenericString(msg));
+        }
+        let depth = image.get_depth();
+
+        if self.top.get_depth() != depth
+        {
+            return Err(ImageErrors::GenericStr(
+                "Images to composite must have the same bit depth"
+            ));
+        }
+        let (width, _) = image.get_dimensions();
+        let has_alpha = colorspace.has_alpha();
+        let top = &self.top.get_frames_ref()[0];
+
+        for frame in image.get_frames_mut()
+        {
+            match depth.bit_type()
+            {
+                BitType::U8 => self.composite_frame::<u8>(frame, top, has_alpha, width),
+                BitType::U16 => self.composite_frame::<u16>(frame, top, has_alpha, width),
+                _ => todo!()
+            }
+        }
+
+        Ok(())
+    }
+
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[
+            ColorSpace::RGB,
+            ColorSpace::RGBA,
+            ColorSpace::BGR,
+            ColorSpace::BGRA,
+            ColorSpace::Luma,
+            ColorSpace::LumaA
+        ]
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -1,22 +1,41 @@
 use zune_core::bit_depth::BitType;
 use zune_core::colorspace::ColorSpace;
-use zune_imageprocs::contrast::contrast_u8;
+use zune_imageprocs::contrast::{contrast_factor, contrast_u16, contrast_u8};
 
 use crate::errors::ImageErrors;
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
+/// Adjust the contrast of an image
+///
+/// Samples are scaled around the middle of the sample range,
+/// `(v - mid) * factor + mid`, saturating at zero and the maximum
+/// value for the image depth.
+///
+/// The alpha channel is left untouched
 #[derive(Default)]
 pub struct Contrast
 {
-    contrast: f32
+    factor: f32
 }
 
 impl Contrast
 {
+    /// Create a contrast operation from a contrast level
+    ///
+    /// `contrast` should be in the range `-255..=255`, positive values
+    /// increase contrast, negative values reduce it and `0` does nothing
     pub fn new(contrast: f32) -> Contrast
     {
-        Contrast { contrast }
+        Contrast::with_factor(contrast_factor(contrast))
+    }
+    /// Create a contrast operation which scales samples by `factor`
+    ///
+    /// Factors greater than `1` increase contrast, factors between `0`
+    /// and `1` reduce it
+    pub fn with_factor(factor: f32) -> Contrast
+    {
+        Contrast { factor }
     }
 }
 
@@ -37,14 +56,13 @@ impl OperationsTrait for Contrast
             {
                 BitType::U8 =>
                 {
-                    contrast_u8(channel.reinterpret_as_mut::<u8>().unwrap(), self.contrast)
-                }
-                BitType::U16 =>
-                {
-                    return Err(ImageErrors::GenericStr(
-                        "Contrast for 16 bit depth is not yet implemented"
-                    ));
+                    contrast_u8(channel.reinterpret_as_mut::<u8>().unwrap(), self.factor)
                 }
+                BitType::U16 => contrast_u16(
+                    channel.reinterpret_as_mut::<u16>().unwrap(),
+                    self.factor,
+                    depth.max_value()
+                ),
                 _ => todo!()
             }
         }
@@ -61,6 +79,6 @@ impl OperationsTrait for Contrast
     }
     fn supported_types(&self) -> &'static [BitType]
     {
-        &[BitType::U8]
+        &[BitType::U8, BitType::U16]
     }
 }
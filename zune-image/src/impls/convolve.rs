@@ -1,24 +1,75 @@
-#![allow(dead_code)]
-
 use zune_core::bit_depth::BitType;
+use zune_imageprocs::convolve::convolve;
 
-//use zune_imageprocs::convolve::convolve_1d;
-use crate::errors::ImageErrors;
+use crate::channel::Channel;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
-/// Convolve an image
+/// Convolve an image with an arbitrary kernel
+///
+/// Each color sample becomes the weighted sum of its neighbourhood,
+/// divided by `divisor` with `bias` added afterwards.
+///
+/// Pixels outside the image are treated as copies of the nearest edge pixel,
+/// results saturate at zero and the maximum value for the image depth.
+/// The alpha channel is left untouched.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::convolve::Convolve;
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::fill(128_u8, ColorSpace::RGB, 10, 10).unwrap();
+///
+/// // emboss
+/// let kernel = vec![-2.0, -1.0, 0.0, -1.0, 1.0, 1.0, 0.0, 1.0, 2.0];
+///
+/// Convolve::new(kernel, 3, 3).execute(&mut image).unwrap();
+/// ```
 #[derive(Default)]
 pub struct Convolve
 {
-    weights: Vec<f64>
+    kernel:        Vec<f32>,
+    kernel_width:  usize,
+    kernel_height: usize,
+    divisor:       f32,
+    bias:          f32
 }
 
 impl Convolve
 {
-    pub fn new(weights: Vec<f64>) -> Convolve
+    /// Create a new convolution
+    ///
+    /// # Arguments
+    /// - kernel: Kernel weights in row major order
+    /// - width, height: Kernel dimensions, should be odd and match the kernel length
+    ///
+    /// The divisor defaults to `1.0` and the bias to `0.0`
+    pub fn new(kernel: Vec<f32>, width: usize, height: usize) -> Convolve
+    {
+        Convolve {
+            kernel,
+            kernel_width: width,
+            kernel_height: height,
+            divisor: 1.0,
+            bias: 0.0
+        }
+    }
+    /// Divide the weighted sum by `divisor`
+    ///
+    /// Use the sum of the kernel weights to keep the brightness of the image the same
+    pub fn divisor(mut self, divisor: f32) -> Convolve
+    {
+        self.divisor = divisor;
+        self
+    }
+    /// Add `bias` to every result after dividing
+    pub fn bias(mut self, bias: f32) -> Convolve
     {
-        Convolve { weights }
+        self.bias = bias;
+        self
     }
 }
 
@@ -26,101 +77,75 @@ impl OperationsTrait for Convolve
 {
     fn get_name(&self) -> &'static str
     {
-        "1D convolution"
+        "2D convolution"
     }
 
-    fn execute_impl(&self, _image: &mut Image) -> Result<(), ImageErrors>
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
     {
-        // let (width, height) = image.get_dimensions();
-        // let max_val = image.get_depth().max_value();
-        // let depth = image.get_depth();
-        //
-        // #[cfg(feature = "threads")]
-        // {
-        //     trace!("Running convolve in multithreaded mode");
-        //
-        //     std::thread::scope(|s| {
-        //         for channel in image.get_channels_mut(true)
-        //         {
-        //             s.spawn(|| {
-        //                 // Hello
-        //                 let mut out_channel =
-        //                     Channel::new_with_length(width * height * depth.size_of());
-        //
-        //                 match depth.bit_type()
-        //                 {
-        //                     BitType::U8 =>
-        //                     {
-        //                         convolve_1d(
-        //                             channel.reinterpret_as::<u8>().unwrap(),
-        //                             out_channel.reinterpret_as_mut::<u8>().unwrap(),
-        //                             width,
-        //                             height,
-        //                             &self.weights,
-        //                             self.weights.len() as f64,
-        //                             max_val
-        //                         );
-        //                         *channel = out_channel;
-        //                     }
-        //                     BitType::U16 =>
-        //                     {
-        //                         convolve_1d(
-        //                             channel.reinterpret_as::<u16>().unwrap(),
-        //                             out_channel.reinterpret_as_mut::<u16>().unwrap(),
-        //                             width,
-        //                             height,
-        //                             &self.weights,
-        //                             self.weights.len() as f64,
-        //                             max_val
-        //                         );
-        //                         *channel = out_channel;
-        //                     }
-        //                     _ => todo!()
-        //                 }
-        //             });
-        //         }
-        //     });
-        // }
-        // #[cfg(not(feature = "threads"))]
-        // {
-        //     trace!("Running convolve in single threaded mode");
-        //
-        //     for channel in image.get_channels_mut(false)
-        //     {
-        //         let mut out_channel = Channel::new_with_length(width * height * depth.size_of());
-        //
-        //         match depth.bit_type()
-        //         {
-        //             BitType::U8 =>
-        //             {
-        //                 convolve_1d(
-        //                     channel.reinterpret_as::<u8>().unwrap(),
-        //                     out_channel.reinterpret_as_mut::<u8>().unwrap(),
-        //                     width,
-        //                     height,
-        //                     &self.weights,
-        //                     self.weights.len() as f64,
-        //                     max_val
-        //                 );
-        //                 *channel = out_channel;
-        //             }
-        //             BitType::U16 =>
-        //             {
-        //                 convolve_1d(
-        //                     channel.reinterpret_as::<u16>().unwrap(),
-        //                     out_channel.reinterpret_as_mut::<u16>().unwrap(),
-        //                     width,
-        //                     height,
-        //                     &self.weights,
-        //                     self.weights.len() as f64,
-        //                     max_val
-        //                 );
-        //                 *channel = out_channel;
-        //             }
-        //             _ => todo!()
-        //         }
-        //     }
-        // }
+        if self.kernel_width & 1 == 0 || self.kernel_height & 1 == 0
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Convolution kernel dimensions should be odd"
+            )));
+        }
+        if self.kernel.len() != self.kernel_width * self.kernel_height
+        {
+            let msg = format!(
+                "Convolution kernel has {} weights, expected {} for a {}x{} kernel",
+                self.kernel.len(),
+                self.kernel_width * self.kernel_height,
+                self.kernel_width,
+                self.kernel_height
+            );
+            return Err(ImageErrors::OperationsError(
+                ImageOperationsErrors::GenericString(msg)
+            ));
+        }
+        if !self.divisor.is_normal() || !self.bias.is_finite()
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Convolution divisor should be a finite non-zero number and bias should be finite"
+            )));
+        }
+        let (width, height) = image.get_dimensions();
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+
+        for channel in image.get_channels_mut(true)
+        {
+            let mut out_channel = Channel::new_with_bit_type(channel.len(), depth.bit_type());
+
+            match depth.bit_type()
+            {
+                BitType::U8 => convolve(
+                    channel.reinterpret_as::<u8>().unwrap(),
+                    out_channel.reinterpret_as_mut::<u8>().unwrap(),
+                    width,
+                    height,
+                    &self.kernel,
+                    self.kernel_width,
+                    self.kernel_height,
+                    self.divisor,
+                    self.bias,
+                    max_value
+                ),
+                BitType::U16 => convolve(
+                    channel.reinterpret_as::<u16>().unwrap(),
+                    out_channel.reinterpret_as_mut::<u16>().unwrap(),
+                    width,
+                    height,
+                    &self.kernel,
+                    self.kernel_width,
+                    self.kernel_height,
+                    self.divisor,
+                    self.bias,
+                    max_value
+                ),
+                _ => todo!()
+            }
+            *channel = out_channel;
+        }
+
         Ok(())
     }
     fn supported_types(&self) -> &'static [BitType]
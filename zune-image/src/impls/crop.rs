@@ -2,10 +2,15 @@ use zune_core::bit_depth::BitType;
 use zune_imageprocs::crop::crop;
 
 use crate::channel::Channel;
-use crate::errors::ImageErrors;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
+/// Extract a rectangular region of an image
+///
+/// The region starts at `(x, y)` and is `width` by `height` pixels,
+/// it must lie completely within the image otherwise executing
+/// the operation returns an error
 pub struct Crop
 {
     x:      usize,
@@ -36,8 +41,29 @@ impl OperationsTrait for Crop
 
     fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
     {
+        let (old_width, old_height) = image.get_dimensions();
+
+        let fits = |start: usize, length: usize, size: usize| {
+            start.checked_add(length).is_some_and(|end| end <= size)
+        };
+
+        if self.width == 0 || self.height == 0
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Crop dimensions should be non-zero"
+            )));
+        }
+        if !fits(self.x, self.width, old_width) || !fits(self.y, self.height, old_height)
+        {
+            let msg = format!(
+                "Crop region {}x{} at ({},{}) does not fit in image of dimensions {}x{}",
+                self.width, self.height, self.x, self.y, old_width, old_height
+            );
+            return Err(ImageErrors::OperationsError(
+                ImageOperationsErrors::GenericString(msg)
+            ));
+        }
         let new_dims = self.width * self.height * image.get_depth().size_of();
-        let (old_width, _) = image.get_dimensions();
         let depth = image.get_depth().bit_type();
 
         for channel in image.get_channels_mut(false)
@@ -0,0 +1,133 @@
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::displacement::displace;
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Warp an image using a displacement map
+///
+/// The red and green channels of the map are used as the horizontal
+/// and vertical offsets for each pixel, a map value `v` moves the pixel by
+/// `scale * (v / max_value - 0.5)` pixels, hence mid-gray leaves pixels in place.
+///
+/// Pixels are sampled using bilinear interpolation and coordinates
+/// outside the image are clamped to the edges.
+///
+/// The map must have the same dimensions as the image and be in the RGB
+/// or RGBA colorspace.
+pub struct DisplacementMap
+{
+    map:   Image,
+    scale: f32
+}
+
+impl DisplacementMap
+{
+    pub fn new(map: Image, scale: f32) -> DisplacementMap
+    {
+        DisplacementMap { map, scale }
+    }
+
+    /// Convert the red and green channels of the map to pixel offsets
+    fn offsets(&self) -> Result<(Vec<f32>, Vec<f32>), ImageErrors>
+    {
+        let colorspace = self.map.get_colorspace();
+
+        if !matches!(colorspace, ColorSpace::RGB | ColorSpace::RGBA)
+        {
+            return Err(ImageErrors::UnsupportedColorspace(
+                colorspace,
+                "Displacement Map",
+                &[ColorSpace::RGB, ColorSpace::RGBA]
+            ));
+        }
+        let depth = self.map.get_depth();
+        let max_value = f32::from(depth.max_value());
+        let channels = self.map.get_frames_ref()[0].get_channels_ref(colorspace, false);
+
+        let to_offsets = |channel: &Channel| -> Result<Vec<f32>, ImageErrors> {
+            let offset = |value: f32| self.scale * (value / max_value - 0.5);
+
+            match depth.bit_type()
+            {
+                BitType::U8 => Ok(channel
+                    .reinterpret_as::<u8>()
+                    .unwrap()
+                    .iter()
+                    .map(|x| offset(f32::from(*x)))
+                    .collect()),
+                BitType::U16 => Ok(channel
+                    .reinterpret_as::<u16>()
+                    .unwrap()
+                    .iter()
+                    .map(|x| offset(f32::from(*x)))
+                    .collect()),
+                _ => Err(ImageErrors::GenericStr(
+                    "Unsupported bit depth for displacement map"
+                ))
+            }
+        };
+
+        Ok((to_offsets(&channels[0])?, to_offsets(&channels[1])?))
+    }
+}
+
+impl OperationsTrait for DisplacementMap
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Displacement Map"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let (width, height) = image.get_dimensions();
+        let (map_width, map_height) = self.map.get_dimensions();
+
+        if (width, height) != (map_width, map_height)
+        {
+            return Err(ImageErrors::DimensionsMisMatch(
+                width * height,
+                map_width * map_height
+            ));
+        }
+        let (x_offsets, y_offsets) = self.offsets()?;
+
+        let depth = image.get_depth();
+
+        for channel in image.get_channels_mut(false)
+        {
+            let source = channel.clone();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => displace(
+                    source.reinterpret_as::<u8>().unwrap(),
+                    channel.reinterpret_as_mut::<u8>().unwrap(),
+                    &x_offsets,
+                    &y_offsets,
+                    width,
+                    height
+                ),
+                BitType::U16 => displace(
+                    source.reinterpret_as::<u16>().unwrap(),
+                    channel.reinterpret_as_mut::<u16>().unwrap(),
+                    &x_offsets,
+                    &y_offsets,
+                    width,
+                    height
+                ),
+                _ => todo!()
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,122 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::draw_text::draw_text;
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Stamp text onto an image using a built in 5x7 bitmap font
+///
+/// This is meant for simple labels such as timestamps, watermarks and debug overlays
+/// without needing a font renderer. Printable ASCII is supported, other characters
+/// are drawn as `?`, and `\n` starts a new line. Text outside the image is clipped.
+///
+/// - text: Text to draw
+/// - x, y: Position of the top left corner of the text
+/// - color: Color of the text, one value per color channel in the image's channel order
+///   followed by the opacity in the last entry, e.g `[r, g, b, a]` for RGB images,
+///   and `[luma, _, _, a]` for grayscale images. Values are in the range of the image depth.
+/// - scale: Size of each font pixel, a scale of 1 gives 5x7 pixel glyphs
+///
+/// Text is alpha blended with the image, for images with an alpha channel the text is
+/// composited over the image, increasing its alpha where text is drawn.
+pub struct DrawText
+{
+    text:  String,
+    x:     usize,
+    y:     usize,
+    color: [u16; 4],
+    scale: usize
+}
+
+impl DrawText
+{
+    pub fn new(text: &str, x: usize, y: usize, color: [u16; 4], scale: usize) -> DrawText
+    {
+        DrawText {
+            text: text.to_string(),
+            x,
+            y,
+            color,
+            scale
+        }
+    }
+}
+
+impl OperationsTrait for DrawText
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Draw Text"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if self.scale == 0
+        {
+            return Err(ImageErrors::GenericStr(
+                "Text scale must be greater than zero"
+            ));
+        }
+        let (width, height) = image.get_dimensions();
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+        let colorspace = image.get_colorspace();
+
+        let color_channels = colorspace.num_components() - usize::from(colorspace.has_alpha());
+
+        if color_channels > 3
+        {
+            return Err(ImageErrors::GenericStr(
+                "Text can only be drawn on images with at most three color channels"
+            ));
+        }
+        let opacity = f32::from(self.color[3].min(max_value)) / f32::from(max_value);
+
+        for frame in image.get_frames_mut()
+        {
+            for (i, channel) in frame
+                .get_channels_mut(colorspace, false)
+                .iter_mut()
+                .enumerate()
+            {
+                // drawing on the alpha channel composites the text over the image
+                let value =
+                    if i < color_channels { self.color[i].min(max_value) } else { max_value };
+
+                match depth.bit_type()
+                {
+                    BitType::U8 => draw_text(
+                        channel.reinterpret_as_mut::<u8>().unwrap(),
+                        width,
+                        height,
+                        &self.text,
+                        self.x,
+                        self.y,
+                        self.scale,
+                        value,
+                        opacity
+                    ),
+                    BitType::U16 => draw_text(
+                        channel.reinterpret_as_mut::<u16>().unwrap(),
+                        width,
+                        height,
+                        &self.text,
+                        self.x,
+                        self.y,
+                        self.scale,
+                        value,
+                        opacity
+                    ),
+                    _ => todo!()
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -1,12 +1,19 @@
 use log::trace;
 use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
 use zune_imageprocs::gamma::gamma;
 
-use crate::errors::ImageErrors;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
 /// Gamma adjust an image
+///
+/// Applies `out = max * (in / max) ^ (1 / gamma)` to every color sample,
+/// where `max` is the maximum value for the image depth.
+///
+/// A gamma of `1.0` leaves the image unchanged, the alpha channel
+/// is never modified
 #[derive(Default)]
 pub struct Gamma
 {
@@ -15,6 +22,10 @@ pub struct Gamma
 
 impl Gamma
 {
+    /// Create a new gamma operation
+    ///
+    /// `value` should be greater than zero, otherwise
+    /// executing the operation returns an error
     pub fn new(value: f32) -> Gamma
     {
         Gamma { value }
@@ -29,6 +40,12 @@ impl OperationsTrait for Gamma
 
     fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
     {
+        if !(self.value.is_finite() && self.value > 0.0)
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Gamma value should be a finite number greater than zero"
+            )));
+        }
         let max_value = image.get_depth().max_value();
 
         let depth = image.get_depth();
@@ -36,7 +53,7 @@ impl OperationsTrait for Gamma
         {
             trace!("Running gamma correction in single threaded mode");
 
-            for channel in image.get_channels_mut(false)
+            for channel in image.get_channels_mut(true)
             {
                 match depth.bit_type()
                 {
@@ -59,7 +76,7 @@ impl OperationsTrait for Gamma
             trace!("Running gamma correction in multithreaded mode");
 
             std::thread::scope(|s| {
-                for channel in image.get_channels_mut(false)
+                for channel in image.get_channels_mut(true)
                 {
                     s.spawn(|| match depth.bit_type()
                     {
@@ -80,6 +97,15 @@ impl OperationsTrait for Gamma
         }
         Ok(())
     }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[
+            ColorSpace::RGBA,
+            ColorSpace::RGB,
+            ColorSpace::LumaA,
+            ColorSpace::Luma
+        ]
+    }
     fn supported_types(&self) -> &'static [BitType]
     {
         &[BitType::U8, BitType::U16]
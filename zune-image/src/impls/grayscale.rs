@@ -4,7 +4,7 @@ use zune_core::colorspace::ColorSpace;
 use zune_imageprocs::grayscale::{rgb_to_grayscale_u16, rgb_to_grayscale_u8};
 
 use crate::channel::Channel;
-use crate::errors::ImageErrors;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
@@ -20,18 +20,37 @@ use crate::traits::OperationsTrait;
 /// ```
 /// but it's implemented using fixed point integer mathematics and simd kernels
 /// where applicable (see zune-imageprocs/grayscale)
+///
+/// These are the Rec. 601 weights, other weights can be used via
+/// [`with_coefficients`](Self::with_coefficients) or the [`REC_709`](Self::REC_709)
+/// preset, which is better suited to HD video
 pub struct RgbToGrayScale
 {
-    preserve_alpha: bool
+    preserve_alpha: bool,
+    coefficients:   [f32; 3]
 }
 
 impl RgbToGrayScale
 {
+    /// Convert using the Rec. 601 weights, `0.299R + 0.587G + 0.114B`
+    pub const REC_601: RgbToGrayScale = RgbToGrayScale::with_coefficients(0.299, 0.587, 0.114);
+    /// Convert using the Rec. 709 weights, `0.2126R + 0.7152G + 0.0722B`
+    pub const REC_709: RgbToGrayScale = RgbToGrayScale::with_coefficients(0.2126, 0.7152, 0.0722);
+
     #[allow(clippy::new_without_default)]
     pub fn new() -> RgbToGrayScale
+    {
+        RgbToGrayScale::REC_601
+    }
+    /// Convert using custom weights for the red, green and blue channels
+    ///
+    /// Weights are normalized to sum to 1.0 when the operation runs,
+    /// it returns an error if any of them is negative or they sum to zero
+    pub const fn with_coefficients(r: f32, g: f32, b: f32) -> RgbToGrayScale
     {
         RgbToGrayScale {
-            preserve_alpha: false
+            preserve_alpha: false,
+            coefficients:   [r, g, b]
         }
     }
     pub fn preserve_alpha(mut self, yes: bool) -> RgbToGrayScale
@@ -49,6 +68,17 @@ impl OperationsTrait for RgbToGrayScale
 
     fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
     {
+        let sum: f32 = self.coefficients.iter().sum();
+
+        // also catches NaNs, which make the sum NaN
+        if self.coefficients.iter().any(|x| *x < 0.0) || !sum.is_finite() || sum <= 0.0
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Grayscale coefficients should be non-negative and have a positive sum"
+            )));
+        }
+        let coefficients = self.coefficients.map(|x| x / sum);
+
         let im_colorspace = image.get_colorspace();
 
         if im_colorspace == ColorSpace::Luma || im_colorspace == ColorSpace::LumaA
@@ -68,7 +98,7 @@ impl OperationsTrait for RgbToGrayScale
 
         for frame in image.get_frames_mut()
         {
-            let channel = frame.get_channels_ref(colorspace, self.preserve_alpha);
+            let channel = frame.get_channels_ref(colorspace, !self.preserve_alpha);
 
             match depth.bit_type()
             {
@@ -84,7 +114,8 @@ impl OperationsTrait for RgbToGrayScale
                         g,
                         b,
                         out.reinterpret_as_mut::<u8>().unwrap(),
-                        max_value as u8
+                        max_value as u8,
+                        coefficients
                     );
 
                     if self.preserve_alpha && colorspace.has_alpha()
@@ -110,7 +141,8 @@ impl OperationsTrait for RgbToGrayScale
                         g,
                         b,
                         out.reinterpret_as_mut::<u16>().unwrap(),
-                        max_value
+                        max_value,
+                        coefficients
                     );
 
                     if self.preserve_alpha && colorspace.has_alpha()
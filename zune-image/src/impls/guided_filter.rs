@@ -0,0 +1,157 @@
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::guided_filter::guided_filter;
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Edge preserving smoothing using a second image as a guide
+///
+/// The image is smoothed while edges present in the guide are kept,
+/// which is useful for flash/no-flash denoising and detail transfer where the guide is
+/// a sharper reference of the same scene.
+///
+/// If the guide has the same colorspace as the image, each channel is filtered using
+/// the corresponding channel of the guide, otherwise the guide must be a grayscale image
+/// and its luma channel guides all channels.
+///
+/// The alpha channel of the image is left untouched.
+///
+/// - radius: Radius of the window used to compute local statistics
+/// - epsilon: Regularization, larger values give smoother output, samples are treated
+///   as being in the range `0.0..=1.0` so a value of `0.01` preserves edges with a
+///   contrast of about `0.1` or more
+pub struct GuidedFilter
+{
+    guide:   Image,
+    radius:  usize,
+    epsilon: f32
+}
+
+impl GuidedFilter
+{
+    pub fn new(guide: Image, radius: usize, epsilon: f32) -> GuidedFilter
+    {
+        GuidedFilter {
+            guide,
+            radius,
+            epsilon
+        }
+    }
+}
+
+/// Convert a guide channel to floats in the range `0.0..=1.0`
+fn normalize_channel(channel: &Channel, bit_type: BitType, max_value: u16) -> Vec<f32>
+{
+    let max = f32::from(max_value);
+
+    match bit_type
+    {
+        BitType::U8 => channel
+            .reinterpret_as::<u8>()
+            .unwrap()
+            .iter()
+            .map(|x| f32::from(*x) / max)
+            .collect(),
+        BitType::U16 => channel
+            .reinterpret_as::<u16>()
+            .unwrap()
+            .iter()
+            .map(|x| f32::from(*x) / max)
+            .collect(),
+        _ => todo!()
+    }
+}
+
+impl OperationsTrait for GuidedFilter
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Guided Filter"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let (width, height) = image.get_dimensions();
+        let (guide_width, guide_height) = self.guide.get_dimensions();
+
+        if (width, height) != (guide_width, guide_height)
+        {
+            return Err(ImageErrors::DimensionsMisMatch(
+                width * height,
+                guide_width * guide_height
+            ));
+        }
+        let colorspace = image.get_colorspace();
+        let guide_colorspace = self.guide.get_colorspace();
+
+        if guide_colorspace != colorspace
+            && !matches!(guide_colorspace, ColorSpace::Luma | ColorSpace::LumaA)
+        {
+            return Err(ImageErrors::UnsupportedColorspace(
+                guide_colorspace,
+                "Guided Filter",
+                &[ColorSpace::Luma, ColorSpace::LumaA]
+            ));
+        }
+        let guide_depth = self.guide.get_depth();
+
+        if !self.supported_types().contains(&guide_depth.bit_type())
+        {
+            return Err(ImageErrors::GenericStr(
+                "Unsupported bit depth for guide image"
+            ));
+        }
+        let guides: Vec<Vec<f32>> = self.guide.get_frames_ref()[0]
+            .get_channels_ref(guide_colorspace, true)
+            .iter()
+            .map(|channel| {
+                normalize_channel(channel, guide_depth.bit_type(), guide_depth.max_value())
+            })
+            .collect();
+
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+
+        for frame in image.get_frames_mut()
+        {
+            let channels = frame.get_channels_mut(colorspace, true);
+
+            for (i, channel) in channels.iter_mut().enumerate()
+            {
+                let guide = &guides[i.min(guides.len() - 1)];
+
+                match depth.bit_type()
+                {
+                    BitType::U8 => guided_filter(
+                        channel.reinterpret_as_mut::<u8>().unwrap(),
+                        guide,
+                        width,
+                        height,
+                        self.radius,
+                        self.epsilon,
+                        max_value
+                    ),
+                    BitType::U16 => guided_filter(
+                        channel.reinterpret_as_mut::<u16>().unwrap(),
+                        guide,
+                        width,
+                        height,
+                        self.radius,
+                        self.epsilon,
+                        max_value
+                    ),
+                    _ => todo!()
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,103 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::halftone::halftone;
+pub use zune_imageprocs::halftone::DotShape;
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Render an image as a halftone screen, like a print or comic
+///
+/// Each channel is divided into cells along a grid rotated by the screen angle,
+/// and each cell is replaced by a dot whose size grows with the darkness of the cell,
+/// so dark regions get large dots and light regions small ones.
+///
+/// Grayscale images give a classic black and white halftone. For color images
+/// each channel is screened separately, with the screen of each successive channel
+/// rotated a further 30 degrees to avoid moiré patterns, giving a color print look.
+///
+/// The alpha channel is left untouched.
+///
+/// - dot_size: Size of each cell of the screen in pixels, i.e the largest dot size
+/// - angle: Angle of the screen in degrees, 45 is traditional for black ink
+/// - shape: Shape of the dots
+pub struct Halftone
+{
+    dot_size: usize,
+    angle:    f32,
+    shape:    DotShape
+}
+
+impl Halftone
+{
+    pub fn new(dot_size: usize, angle: f32, shape: DotShape) -> Halftone
+    {
+        Halftone {
+            dot_size,
+            angle,
+            shape
+        }
+    }
+}
+
+impl OperationsTrait for Halftone
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Halftone"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if self.dot_size == 0
+        {
+            return Err(ImageErrors::GenericStr(
+                "Halftone dot size must be greater than zero"
+            ));
+        }
+        let (width, height) = image.get_dimensions();
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+        let colorspace = image.get_colorspace();
+
+        for frame in image.get_frames_mut()
+        {
+            for (i, channel) in frame
+                .get_channels_mut(colorspace, true)
+                .iter_mut()
+                .enumerate()
+            {
+                let angle = self.angle + 30.0 * i as f32;
+
+                match depth.bit_type()
+                {
+                    BitType::U8 => halftone(
+                        channel.reinterpret_as_mut::<u8>().unwrap(),
+                        width,
+                        height,
+                        self.dot_size,
+                        angle,
+                        self.shape,
+                        max_value
+                    ),
+                    BitType::U16 => halftone(
+                        channel.reinterpret_as_mut::<u16>().unwrap(),
+                        width,
+                        height,
+                        self.dot_size,
+                        angle,
+                        self.shape,
+                        max_value
+                    ),
+                    _ => todo!()
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,159 @@
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::histogram_match::{match_histogram, match_histogram_luminance};
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Colorspaces whose luminance can be matched
+const LUMINANCE_COLORSPACES: &[ColorSpace] = &[
+    ColorSpace::RGB,
+    ColorSpace::RGBA,
+    ColorSpace::Luma,
+    ColorSpace::LumaA
+];
+
+/// Remap the tones of an image so that its histogram matches
+/// the histogram of a reference image
+///
+/// This is also known as histogram specification, applying it to a set of photos with
+/// the same reference gives them consistent exposure and tonal characteristics.
+///
+/// By default each channel is matched against the same channel of the reference,
+/// which also matches the color balance. When matching luminance only, RGB images are
+/// remapped based on their luminance, which keeps their colors, and grayscale images
+/// are unaffected by the flag.
+///
+/// The reference must have the same colorspace and depth as the image, but may
+/// have different dimensions. Only the first frame of the reference is used.
+pub struct MatchHistogram
+{
+    reference: Image,
+    luminance: bool
+}
+
+impl MatchHistogram
+{
+    pub fn new(reference: Image) -> MatchHistogram
+    {
+        MatchHistogram {
+            reference,
+            luminance: false
+        }
+    }
+    /// Match the luminance histogram instead of each channel's histogram
+    pub fn luminance(mut self, yes: bool) -> MatchHistogram
+    {
+        self.luminance = yes;
+        self
+    }
+}
+
+fn match_channels(
+    channels: &mut [Channel], reference: &[Channel], luminance: bool, bit_type: BitType,
+    max_value: u16
+)
+{
+    match (luminance, channels, reference)
+    {
+        (true, [r, g, b, ..], [ref_r, ref_g, ref_b, ..]) => match bit_type
+        {
+            BitType::U8 => match_histogram_luminance(
+                [
+                    r.reinterpret_as_mut::<u8>().unwrap(),
+                    g.reinterpret_as_mut::<u8>().unwrap(),
+                    b.reinterpret_as_mut::<u8>().unwrap()
+                ],
+                [
+                    ref_r.reinterpret_as::<u8>().unwrap(),
+                    ref_g.reinterpret_as::<u8>().unwrap(),
+                    ref_b.reinterpret_as::<u8>().unwrap()
+                ],
+                max_value
+            ),
+            BitType::U16 => match_histogram_luminance(
+                [
+                    r.reinterpret_as_mut::<u16>().unwrap(),
+                    g.reinterpret_as_mut::<u16>().unwrap(),
+                    b.reinterpret_as_mut::<u16>().unwrap()
+                ],
+                [
+                    ref_r.reinterpret_as::<u16>().unwrap(),
+                    ref_g.reinterpret_as::<u16>().unwrap(),
+                    ref_b.reinterpret_as::<u16>().unwrap()
+                ],
+                max_value
+            ),
+            _ => todo!()
+        },
+        (_, channels, reference) =>
+        {
+            for (channel, reference) in channels.iter_mut().zip(reference)
+            {
+                match bit_type
+                {
+                    BitType::U8 => match_histogram(
+                        channel.reinterpret_as_mut::<u8>().unwrap(),
+                        reference.reinterpret_as::<u8>().unwrap(),
+                        max_value
+                    ),
+                    BitType::U16 => match_histogram(
+                        channel.reinterpret_as_mut::<u16>().unwrap(),
+                        reference.reinterpret_as::<u16>().unwrap(),
+                        max_value
+                    ),
+                    _ => todo!()
+                }
+            }
+        }
+    }
+}
+
+impl OperationsTrait for MatchHistogram
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Match Histogram"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let colorspace = image.get_colorspace();
+        let depth = image.get_depth();
+
+        if self.reference.get_colorspace() != colorspace || self.reference.get_depth() != depth
+        {
+            return Err(ImageErrors::GenericStr(
+                "Reference image must have the same colorspace and depth as the image"
+            ));
+        }
+        if self.luminance && !LUMINANCE_COLORSPACES.contains(&colorspace)
+        {
+            return Err(ImageErrors::UnsupportedColorspace(
+                colorspace,
+                "Match Histogram (luminance)",
+                LUMINANCE_COLORSPACES
+            ));
+        }
+        let reference = self.reference.get_frames_ref()[0].get_channels_ref(colorspace, true);
+
+        for frame in image.get_frames_mut()
+        {
+            match_channels(
+                frame.get_channels_mut(colorspace, true),
+                reference,
+                self.luminance,
+                depth.bit_type(),
+                depth.max_value()
+            );
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,259 @@
+//! RGB to HSV and HSL conversions
+//!
+//! Hue adjustments can be made by converting to one of these colorspaces,
+//! changing the hue channel and converting back
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::colorspace::hsv::{hsl_to_rgb, hsv_to_rgb, rgb_to_hsl, rgb_to_hsv};
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::impls::colorspace::convert_color_channels;
+use crate::traits::OperationsTrait;
+
+/// Convert an RGB image to HSV
+///
+/// The image colorspace becomes [`ColorSpace::HSV`], with the
+/// channels stored in hue, saturation, value order and every
+/// component scaled to the sample range.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::hsv::RgbToHsv;
+/// use zune_image::traits::OperationsTrait;
+///
+/// // pure blue, a hue of 240 degrees
+/// let mut image = Image::from_u8(&[0, 0, 255], 1, 1, ColorSpace::RGB);
+///
+/// RgbToHsv::new().execute(&mut image).unwrap();
+///
+/// assert_eq!(image.get_colorspace(), ColorSpace::HSV);
+/// assert_eq!(image.flatten_frames::<u8>()[0], [170, 255, 255]);
+/// ```
+#[derive(Default)]
+pub struct RgbToHsv;
+
+impl RgbToHsv
+{
+    pub fn new() -> RgbToHsv
+    {
+        RgbToHsv
+    }
+}
+
+impl OperationsTrait for RgbToHsv
+{
+    fn get_name(&self) -> &'static str
+    {
+        "RGB to HSV"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        convert_color_channels(image, |c1, c2, c3, depth| {
+            let max_value = depth.max_value();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => rgb_to_hsv(
+                    c1.reinterpret_as_mut::<u8>().unwrap(),
+                    c2.reinterpret_as_mut::<u8>().unwrap(),
+                    c3.reinterpret_as_mut::<u8>().unwrap(),
+                    max_value
+                ),
+                BitType::U16 => rgb_to_hsv(
+                    c1.reinterpret_as_mut::<u16>().unwrap(),
+                    c2.reinterpret_as_mut::<u16>().unwrap(),
+                    c3.reinterpret_as_mut::<u16>().unwrap(),
+                    max_value
+                ),
+                _ => todo!()
+            }
+        });
+        image.set_colorspace(ColorSpace::HSV);
+
+        Ok(())
+    }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::RGB]
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
+
+/// Convert an HSV image to RGB
+///
+/// The image colorspace becomes [`ColorSpace::RGB`]
+#[derive(Default)]
+pub struct HsvToRgb;
+
+impl HsvToRgb
+{
+    pub fn new() -> HsvToRgb
+    {
+        HsvToRgb
+    }
+}
+
+impl OperationsTrait for HsvToRgb
+{
+    fn get_name(&self) -> &'static str
+    {
+        "HSV to RGB"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        convert_color_channels(image, |c1, c2, c3, depth| {
+            let max_value = depth.max_value();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => hsv_to_rgb(
+                    c1.reinterpret_as_mut::<u8>().unwrap(),
+                    c2.reinterpret_as_mut::<u8>().unwrap(),
+                    c3.reinterpret_as_mut::<u8>().unwrap(),
+                    max_value
+                ),
+                BitType::U16 => hsv_to_rgb(
+                    c1.reinterpret_as_mut::<u16>().unwrap(),
+                    c2.reinterpret_as_mut::<u16>().unwrap(),
+                    c3.reinterpret_as_mut::<u16>().unwrap(),
+                    max_value
+                ),
+                _ => todo!()
+            }
+        });
+        image.set_colorspace(ColorSpace::RGB);
+
+        Ok(())
+    }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::HSV]
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
+
+/// Convert an RGB image to HSL
+///
+/// The image colorspace becomes [`ColorSpace::HSL`], with the
+/// channels stored in hue, saturation, lightness order and every
+/// component scaled to the sample range.
+#[derive(Default)]
+pub struct RgbToHsl;
+
+impl RgbToHsl
+{
+    pub fn new() -> RgbToHsl
+    {
+        RgbToHsl
+    }
+}
+
+impl OperationsTrait for RgbToHsl
+{
+    fn get_name(&self) -> &'static str
+    {
+        "RGB to HSL"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        convert_color_channels(image, |c1, c2, c3, depth| {
+            let max_value = depth.max_value();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => rgb_to_hsl(
+                    c1.reinterpret_as_mut::<u8>().unwrap(),
+                    c2.reinterpret_as_mut::<u8>().unwrap(),
+                    c3.reinterpret_as_mut::<u8>().unwrap(),
+                    max_value
+                ),
+                BitType::U16 => rgb_to_hsl(
+                    c1.reinterpret_as_mut::<u16>().unwrap(),
+                    c2.reinterpret_as_mut::<u16>().unwrap(),
+                    c3.reinterpret_as_mut::<u16>().unwrap(),
+                    max_value
+                ),
+                _ => todo!()
+            }
+        });
+        image.set_colorspace(ColorSpace::HSL);
+
+        Ok(())
+    }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::RGB]
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
+
+/// Convert an HSL image to RGB
+///
+/// The image colorspace becomes [`ColorSpace::RGB`]
+#[derive(Default)]
+pub struct HslToRgb;
+
+impl HslToRgb
+{
+    pub fn new() -> HslToRgb
+    {
+        HslToRgb
+    }
+}
+
+impl OperationsTrait for HslToRgb
+{
+    fn get_name(&self) -> &'static str
+    {
+        "HSL to RGB"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        convert_color_channels(image, |c1, c2, c3, depth| {
+            let max_value = depth.max_value();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => hsl_to_rgb(
+                    c1.reinterpret_as_mut::<u8>().unwrap(),
+                    c2.reinterpret_as_mut::<u8>().unwrap(),
+                    c3.reinterpret_as_mut::<u8>().unwrap(),
+                    max_value
+                ),
+                BitType::U16 => hsl_to_rgb(
+                    c1.reinterpret_as_mut::<u16>().unwrap(),
+                    c2.reinterpret_as_mut::<u16>().unwrap(),
+                    c3.reinterpret_as_mut::<u16>().unwrap(),
+                    max_value
+                ),
+                _ => todo!()
+            }
+        });
+        image.set_colorspace(ColorSpace::RGB);
+
+        Ok(())
+    }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::HSL]
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
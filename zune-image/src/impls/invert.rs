@@ -6,7 +6,12 @@ use crate::errors::ImageErrors;
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
-/// Invert
+/// Invert the color samples of an image
+///
+/// Each sample `v` becomes `max - v` where `max` is the maximum value
+/// for the image depth, the alpha channel is left untouched.
+///
+/// Inverting twice gives back the original image
 #[derive(Default)]
 pub struct Invert;
 
@@ -25,14 +30,19 @@ impl OperationsTrait for Invert
     }
     fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
     {
-        let depth = image.get_depth().bit_type();
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
 
         for channel in image.get_channels_mut(true)
         {
-            match depth
+            match depth.bit_type()
             {
-                BitType::U8 => invert(channel.reinterpret_as_mut::<u8>().unwrap()),
-                BitType::U16 => invert(channel.reinterpret_as_mut::<u16>().unwrap()),
+                BitType::U8 => invert(
+                    channel.reinterpret_as_mut::<u8>().unwrap(),
+                    max_value as u8
+                ),
+                BitType::U16 => invert(channel.reinterpret_as_mut::<u16>().unwrap(), max_value),
+                BitType::F32 => invert(channel.reinterpret_as_mut::<f32>().unwrap(), 1.0),
                 _ => todo!()
             }
         }
@@ -51,6 +61,6 @@ impl OperationsTrait for Invert
     }
     fn supported_types(&self) -> &'static [BitType]
     {
-        &[BitType::U8, BitType::U16]
+        &[BitType::U8, BitType::U16, BitType::F32]
     }
 }
@@ -0,0 +1,94 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::kmeans::kmeans_segment;
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Segment an image into regions of similar color using k-means clustering
+///
+/// Pixels are clustered by their color, ignoring alpha, into `k` groups and each
+/// pixel is recolored to the mean color of its group, giving a poster like image
+/// with at most `k` distinct colors.
+///
+/// The result is deterministic for the same image and seed, for animated images each
+/// frame is segmented independently.
+///
+/// To get the cluster index of each pixel instead of the recolored image, use
+/// [`kmeans_segment`] from `zune-imageprocs` directly.
+///
+/// - k: Number of clusters
+/// - iterations: Maximum number of iterations used to refine the clusters
+/// - seed: Seed used to choose the initial clusters
+pub struct KMeansSegment
+{
+    k:          usize,
+    iterations: usize,
+    seed:       u64
+}
+
+impl KMeansSegment
+{
+    pub fn new(k: usize, iterations: usize, seed: u64) -> KMeansSegment
+    {
+        KMeansSegment {
+            k,
+            iterations,
+            seed
+        }
+    }
+}
+
+impl OperationsTrait for KMeansSegment
+{
+    fn get_name(&self) -> &'static str
+    {
+        "K-Means Segment"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if self.k == 0
+        {
+            return Err(ImageErrors::GenericStr(
+                "K-means segmentation needs at least one cluster"
+            ));
+        }
+        let depth = image.get_depth();
+        let colorspace = image.get_colorspace();
+
+        for frame in image.get_frames_mut()
+        {
+            let channels = frame.get_channels_mut(colorspace, true);
+
+            match depth.bit_type()
+            {
+                BitType::U8 =>
+                {
+                    let mut planes: Vec<&mut [u8]> = channels
+                        .iter_mut()
+                        .map(|channel| channel.reinterpret_as_mut::<u8>().unwrap())
+                        .collect();
+
+                    kmeans_segment(&mut planes, self.k, self.iterations, self.seed);
+                }
+                BitType::U16 =>
+                {
+                    let mut planes: Vec<&mut [u16]> = channels
+                        .iter_mut()
+                        .map(|channel| channel.reinterpret_as_mut::<u16>().unwrap())
+                        .collect();
+
+                    kmeans_segment(&mut planes, self.k, self.iterations, self.seed);
+                }
+                _ => todo!()
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
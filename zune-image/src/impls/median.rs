@@ -1,6 +1,6 @@
 use log::trace;
 use zune_core::bit_depth::BitType;
-use zune_imageprocs::median::median;
+use zune_imageprocs::median::{median, median_u8};
 
 use crate::channel::Channel;
 use crate::errors::ImageErrors;
@@ -12,6 +12,10 @@ use crate::traits::OperationsTrait;
 /// The parameter radius corresponds to the radius of the neighbor area to be searched,
 ///
 /// for example a radius of R will result in a search window length of 2R+1 for each dimension.
+///
+/// Pixels outside the image are treated as copies of the nearest edge pixel.
+///
+/// This is good at removing salt and pepper noise while keeping edges sharp
 #[derive(Default)]
 pub struct Median
 {
@@ -55,7 +59,7 @@ impl OperationsTrait for Median
                         width,
                         height
                     ),
-                    BitType::U8 => median(
+                    BitType::U8 => median_u8(
                         channel.reinterpret_as::<u8>().unwrap(),
                         new_channel.reinterpret_as_mut::<u8>().unwrap(),
                         self.radius,
@@ -72,7 +76,7 @@ impl OperationsTrait for Median
             trace!("Running median filter multithreaded mode");
 
             std::thread::scope(|s| {
-                for channel in image.get_channels_mut(true)
+                for channel in image.get_channels_mut(false)
                 {
                     s.spawn(|| {
                         let mut new_channel =
@@ -87,7 +91,7 @@ impl OperationsTrait for Median
                                 width,
                                 height
                             ),
-                            BitType::U8 => median(
+                            BitType::U8 => median_u8(
                                 channel.reinterpret_as::<u8>().unwrap(),
                                 new_channel.reinterpret_as_mut::<u8>().unwrap(),
                                 self.radius,
@@ -4,6 +4,7 @@ use crate::errors::ImageErrors;
 use crate::image::Image;
 use crate::impls::flip::Flip;
 use crate::impls::flop::Flop;
+use crate::impls::rotate::{Rotate, RotationAngle};
 use crate::traits::OperationsTrait;
 
 pub enum OrientationType
@@ -37,7 +38,9 @@ impl OperationsTrait for AutoOrient
                 Flop::new().execute(image)?;
             }
             OrientationType::Rotate180 =>
-            {}
+            {
+                Rotate::new(RotationAngle::Rotate180).execute(image)?;
+            }
             OrientationType::FlipVertically =>
             {
                 Flip::new().execute(image)?;
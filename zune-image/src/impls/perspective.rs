@@ -0,0 +1,114 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::perspective::{homography, perspective_warp};
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Warp an image so that a quadrilateral maps to another quadrilateral
+///
+/// This computes the perspective transform (homography) taking the source quad
+/// to the destination quad and resamples the image with bilinear interpolation,
+/// e.g. mapping the corners of a photographed document to the corners of the output
+/// gives a flat, rectified view of the document.
+///
+/// Output pixels whose source lies outside the image are set to the fill color.
+///
+/// - src_quad: Four points in the input image, in pixels
+/// - dst_quad: Where each of the source points ends up in the output, in pixels
+/// - output_size: Width and height of the output image
+/// - fill: Value of each channel for pixels sampled outside the image, values are
+///   clamped to the maximum value of the image depth
+#[derive(Copy, Clone)]
+pub struct PerspectiveWarp
+{
+    src_quad:    [(f32, f32); 4],
+    dst_quad:    [(f32, f32); 4],
+    output_size: (usize, usize),
+    fill:        [u16; 4]
+}
+
+impl PerspectiveWarp
+{
+    pub fn new(
+        src_quad: [(f32, f32); 4], dst_quad: [(f32, f32); 4], output_size: (usize, usize),
+        fill: [u16; 4]
+    ) -> PerspectiveWarp
+    {
+        PerspectiveWarp {
+            src_quad,
+            dst_quad,
+            output_size,
+            fill
+        }
+    }
+}
+
+impl OperationsTrait for PerspectiveWarp
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Perspective Warp"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        // map output coordinates back to the input
+        let matrix = homography(self.dst_quad, self.src_quad).ok_or(ImageErrors::GenericStr(
+            "Perspective warp quads must not have three collinear points"
+        ))?;
+
+        let (in_width, in_height) = image.get_dimensions();
+        let (out_width, out_height) = self.output_size;
+        let depth = image.get_depth();
+        let colorspace = image.get_colorspace();
+
+        let new_length = out_width * out_height * depth.size_of();
+
+        for frame in image.get_frames_mut()
+        {
+            for (old_channel, fill) in frame
+                .get_channels_mut(colorspace, false)
+                .iter_mut()
+                .zip(self.fill)
+            {
+                let mut new_channel = Channel::new_with_bit_type(new_length, depth.bit_type());
+
+                match depth.bit_type()
+                {
+                    BitType::U8 => perspective_warp::<u8>(
+                        old_channel.reinterpret_as().unwrap(),
+                        in_width,
+                        in_height,
+                        new_channel.reinterpret_as_mut().unwrap(),
+                        out_width,
+                        out_height,
+                        &matrix,
+                        fill.min(255) as u8
+                    ),
+                    BitType::U16 => perspective_warp::<u16>(
+                        old_channel.reinterpret_as().unwrap(),
+                        in_width,
+                        in_height,
+                        new_channel.reinterpret_as_mut().unwrap(),
+                        out_width,
+                        out_height,
+                        &matrix,
+                        fill.min(depth.max_value())
+                    ),
+                    _ => todo!()
+                }
+                *old_channel = new_channel;
+            }
+        }
+        image.set_dimensions(out_width, out_height);
+
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,97 @@
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::posterize::{posterize, posterize_u8};
+
+use crate::errors::{ImageErrors, ImageOperationsErrors};
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Posterize an image
+///
+/// Every color sample is snapped to the nearest of `levels` evenly
+/// spaced values, so each channel ends up with at most `levels`
+/// distinct values, the alpha channel is left untouched.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::posterize::Posterize;
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::from_u8(&[0, 60, 100, 200], 4, 1, ColorSpace::Luma);
+///
+/// Posterize::new(3).execute(&mut image).unwrap();
+///
+/// assert_eq!(image.flatten_frames::<u8>()[0], [0, 0, 128, 255]);
+/// ```
+pub struct Posterize
+{
+    levels: u16
+}
+
+impl Posterize
+{
+    /// Create a new posterize operation
+    ///
+    /// `levels` is the number of values each channel is reduced to,
+    /// it should be at least 2, otherwise executing the operation
+    /// returns an error
+    pub fn new(levels: u16) -> Posterize
+    {
+        Posterize { levels }
+    }
+}
+
+impl OperationsTrait for Posterize
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Posterize"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if self.levels < 2
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Posterize levels should be at least 2"
+            )));
+        }
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+
+        for channel in image.get_channels_mut(true)
+        {
+            match depth.bit_type()
+            {
+                BitType::U8 =>
+                {
+                    posterize_u8(channel.reinterpret_as_mut::<u8>().unwrap(), self.levels);
+                }
+                BitType::U16 => posterize(
+                    channel.reinterpret_as_mut::<u16>().unwrap(),
+                    self.levels,
+                    max_value
+                ),
+                _ => todo!()
+            }
+        }
+
+        Ok(())
+    }
+
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[
+            ColorSpace::RGB,
+            ColorSpace::RGBA,
+            ColorSpace::LumaA,
+            ColorSpace::Luma
+        ]
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,92 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::reduce_depth::reduce_bit_depth;
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Reduce the effective bit depth of each channel
+///
+/// This quantizes each channel to the specified number of bits,
+/// which is useful when targeting hardware with limited precision, e.g
+/// `[5, 6, 5, 8]` produces RGB565 equivalent output and
+/// `[3, 3, 2, 8]` produces RGB332 equivalent output.
+///
+/// `bits[i]` applies to the i'th channel of the image colorspace, e.g for RGBA
+/// `bits[3]` is the alpha channel, entries beyond the number of channels are ignored.
+///
+/// The image storage depth is not changed, i.e an 8 bit image stays 8 bit
+/// but each channel only contains `2^bits` distinct values spread across the whole
+/// range.
+///
+/// If `dither` is true, Floyd-Steinberg error diffusion is applied to reduce banding
+pub struct ReduceBitDepth
+{
+    bits:   [u8; 4],
+    dither: bool
+}
+
+impl ReduceBitDepth
+{
+    pub fn new(bits: [u8; 4], dither: bool) -> ReduceBitDepth
+    {
+        ReduceBitDepth { bits, dither }
+    }
+}
+
+impl OperationsTrait for ReduceBitDepth
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Reduce Bit Depth"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if self.bits.contains(&0)
+        {
+            return Err(ImageErrors::GenericStr(
+                "Bits for bit depth reduction must be greater than zero"
+            ));
+        }
+        let (width, height) = image.get_dimensions();
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+        let colorspace = image.get_colorspace();
+
+        for frame in image.get_frames_mut()
+        {
+            let channels = frame.get_channels_mut(colorspace, false);
+
+            for (channel, bits) in channels.iter_mut().zip(self.bits)
+            {
+                match depth.bit_type()
+                {
+                    BitType::U8 => reduce_bit_depth(
+                        channel.reinterpret_as_mut::<u8>().unwrap(),
+                        width,
+                        height,
+                        bits,
+                        max_value,
+                        self.dither
+                    ),
+                    BitType::U16 => reduce_bit_depth(
+                        channel.reinterpret_as_mut::<u16>().unwrap(),
+                        width,
+                        height,
+                        bits,
+                        max_value,
+                        self.dither
+                    ),
+                    _ => todo!()
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -3,10 +3,29 @@ use zune_imageprocs::resize::resize;
 pub use zune_imageprocs::resize::ResizeMethod;
 
 use crate::channel::Channel;
-use crate::errors::ImageErrors;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::image::Image;
 use crate::traits::OperationsTrait;
 
+/// Resize an image to new dimensions
+///
+/// Every channel, including alpha, is resized with the chosen
+/// [`ResizeMethod`], pixels past the image edges are treated as
+/// copies of the edge pixels.
+///
+/// # Example
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::resize::{Resize, ResizeMethod};
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::fill(128_u8, ColorSpace::RGB, 100, 50).unwrap();
+///
+/// Resize::new(40, 20, ResizeMethod::Lanczos3).execute(&mut image).unwrap();
+///
+/// assert_eq!(image.get_dimensions(), (40, 20));
+/// ```
 #[derive(Copy, Clone)]
 pub struct Resize
 {
@@ -36,6 +55,12 @@ impl OperationsTrait for Resize
 
     fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
     {
+        if self.new_width == 0 || self.new_height == 0
+        {
+            return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                "Resize dimensions should be non-zero"
+            )));
+        }
         let (old_w, old_h) = image.get_dimensions();
         let depth = image.get_depth().bit_type();
 
@@ -63,7 +88,7 @@ impl OperationsTrait for Resize
             }
             BitType::U16 =>
             {
-                for old_channel in image.get_channels_mut(true)
+                for old_channel in image.get_channels_mut(false)
                 {
                     let mut new_channel = Channel::new_with_bit_type(new_length, depth);
 
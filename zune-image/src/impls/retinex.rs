@@ -0,0 +1,135 @@
+use bytemuck::Pod;
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::retinex::retinex;
+use zune_imageprocs::traits::NumOps;
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Colorspaces whose luminance can be enhanced
+const SUPPORTED_COLORSPACES: &[ColorSpace] = &[
+    ColorSpace::RGB,
+    ColorSpace::RGBA,
+    ColorSpace::Luma,
+    ColorSpace::LumaA
+];
+
+/// Multi-scale retinex, compressing the dynamic range of an image
+///
+/// This estimates the illumination of the image by blurring it at several scales
+/// and removes most of it, which brings out detail in the shadows of underexposed
+/// or backlit photos while bright regions are affected less.
+///
+/// Only the luminance is modified, RGB pixels are scaled to their new luminance
+/// which preserves their colors. The alpha channel is left untouched.
+///
+/// - scales: Standard deviations of the gaussian blurs estimating illumination,
+///   `[15.0, 80.0, 250.0]` is a common choice for photos
+/// - gain, offset: Contrast and brightness applied to the normalized result as
+///   `gain * x + offset`, use `1.0` and `0.0` to leave it unchanged
+pub struct Retinex
+{
+    scales: Vec<f32>,
+    gain:   f32,
+    offset: f32
+}
+
+impl Retinex
+{
+    pub fn new(scales: Vec<f32>, gain: f32, offset: f32) -> Retinex
+    {
+        Retinex {
+            scales,
+            gain,
+            offset
+        }
+    }
+}
+
+fn retinex_channels<T>(
+    channels: &mut [Channel], width: usize, height: usize, scales: &[f32], gain: f32, offset: f32,
+    max_value: u16
+) where
+    T: Copy + NumOps<T> + Default + 'static + Pod
+{
+    let mut channels: Vec<&mut [T]> = channels
+        .iter_mut()
+        .map(|channel| channel.reinterpret_as_mut::<T>().unwrap())
+        .collect();
+
+    retinex(
+        &mut channels,
+        width,
+        height,
+        scales,
+        gain,
+        offset,
+        max_value
+    );
+}
+
+impl OperationsTrait for Retinex
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Retinex"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if self.scales.is_empty() || self.scales.iter().any(|x| !x.is_finite() || *x <= 0.0)
+        {
+            return Err(ImageErrors::GenericStr(
+                "Retinex scales must be positive numbers, and at least one scale is needed"
+            ));
+        }
+        let (width, height) = image.get_dimensions();
+        let depth = image.get_depth();
+        let colorspace = image.get_colorspace();
+
+        if !SUPPORTED_COLORSPACES.contains(&colorspace)
+        {
+            return Err(ImageErrors::UnsupportedColorspace(
+                colorspace,
+                "Retinex",
+                SUPPORTED_COLORSPACES
+            ));
+        }
+        for frame in image.get_frames_mut()
+        {
+            let channels = frame.get_channels_mut(colorspace, true);
+
+            match depth.bit_type()
+            {
+                BitType::U8 => retinex_channels::<u8>(
+                    channels,
+                    width,
+                    height,
+                    &self.scales,
+                    self.gain,
+                    self.offset,
+                    depth.max_value()
+                ),
+                BitType::U16 => retinex_channels::<u16>(
+                    channels,
+                    width,
+                    height,
+                    &self.scales,
+                    self.gain,
+                    self.offset,
+                    depth.max_value()
+                ),
+                _ => todo!()
+            }
+        }
+        Ok(())
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,120 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::rotate::rotate;
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Clockwise rotation angles supported by [`Rotate`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RotationAngle
+{
+    /// Rotate by 90 degrees clockwise
+    Rotate90,
+    /// Rotate by 180 degrees
+    Rotate180,
+    /// Rotate by 270 degrees clockwise, i.e 90 degrees anti-clockwise
+    Rotate270
+}
+
+impl RotationAngle
+{
+    const fn degrees(self) -> u16
+    {
+        match self
+        {
+            RotationAngle::Rotate90 => 90,
+            RotationAngle::Rotate180 => 180,
+            RotationAngle::Rotate270 => 270
+        }
+    }
+}
+
+/// Rotate an image clockwise by a multiple of 90 degrees
+///
+/// Rotating by 90 or 270 degrees swaps the image width and height
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::rotate::{Rotate, RotationAngle};
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::fill(0_u8, ColorSpace::RGB, 30, 20).unwrap();
+///
+/// Rotate::new(RotationAngle::Rotate90).execute(&mut image).unwrap();
+///
+/// assert_eq!(image.get_dimensions(), (20, 30));
+/// ```
+pub struct Rotate
+{
+    angle: RotationAngle
+}
+
+impl Rotate
+{
+    pub fn new(angle: RotationAngle) -> Rotate
+    {
+        Rotate { angle }
+    }
+}
+
+impl OperationsTrait for Rotate
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Rotate"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let (width, height) = image.get_dimensions();
+        let out_dim = width * height * image.get_depth().size_of();
+
+        let depth = image.get_depth();
+        let angle = self.angle.degrees();
+
+        for channel in image.get_channels_mut(false)
+        {
+            let mut out_channel = Channel::new_with_bit_type(out_dim, depth.bit_type());
+
+            match depth.bit_type()
+            {
+                BitType::U8 =>
+                {
+                    rotate(
+                        angle,
+                        channel.reinterpret_as::<u8>().unwrap(),
+                        out_channel.reinterpret_as_mut::<u8>().unwrap(),
+                        width,
+                        height
+                    );
+                }
+                BitType::U16 =>
+                {
+                    rotate(
+                        angle,
+                        channel.reinterpret_as::<u16>().unwrap(),
+                        out_channel.reinterpret_as_mut::<u16>().unwrap(),
+                        width,
+                        height
+                    );
+                }
+                _ => todo!()
+            };
+            *channel = out_channel;
+        }
+
+        if self.angle != RotationAngle::Rotate180
+        {
+            image.set_dimensions(height, width);
+        }
+
+        Ok(())
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
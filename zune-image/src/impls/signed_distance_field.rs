@@ -0,0 +1,103 @@
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+use zune_imageprocs::signed_distance_field::signed_distance_field;
+
+use crate::channel::Channel;
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Create a signed distance field from a grayscale mask
+///
+/// Pixels at or above half of the maximum value are treated as inside the mask.
+/// The result is a single channel image where each pixel encodes the distance to the
+/// nearest edge, above the mid value inside the mask and below it outside.
+///
+/// This is commonly used for scalable glyph and UI rendering, where thresholding a
+/// bilinearly upscaled field at the mid value recovers a sharp edge.
+///
+/// Any alpha channel is dropped, the output is always [`ColorSpace::Luma`].
+///
+/// - spread: Distance in pixels mapped to each half of the output range,
+///   pixels further than `spread` from an edge are clamped to zero or the maximum value
+pub struct SignedDistanceField
+{
+    spread: f32
+}
+
+impl SignedDistanceField
+{
+    pub fn new(spread: f32) -> SignedDistanceField
+    {
+        SignedDistanceField { spread }
+    }
+}
+
+impl OperationsTrait for SignedDistanceField
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Signed Distance Field"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let (width, height) = image.get_dimensions();
+        let depth = image.get_depth();
+        let max_value = depth.max_value();
+        let colorspace = image.get_colorspace();
+        let size = width * height * depth.size_of();
+
+        for frame in image.get_frames_mut()
+        {
+            let mask = &frame.get_channels_ref(colorspace, true)[0];
+
+            let out = match depth.bit_type()
+            {
+                BitType::U8 =>
+                {
+                    let mut out = Channel::new_with_length::<u8>(size);
+
+                    signed_distance_field(
+                        mask.reinterpret_as::<u8>().unwrap(),
+                        out.reinterpret_as_mut::<u8>().unwrap(),
+                        width,
+                        height,
+                        self.spread,
+                        max_value
+                    );
+                    out
+                }
+                BitType::U16 =>
+                {
+                    let mut out = Channel::new_with_length::<u16>(size);
+
+                    signed_distance_field(
+                        mask.reinterpret_as::<u16>().unwrap(),
+                        out.reinterpret_as_mut::<u16>().unwrap(),
+                        width,
+                        height,
+                        self.spread,
+                        max_value
+                    );
+                    out
+                }
+                _ => todo!()
+            };
+            frame.set_channels(vec![out]);
+        }
+        image.set_colorspace(ColorSpace::Luma);
+
+        Ok(())
+    }
+
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::Luma, ColorSpace::LumaA]
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -0,0 +1,152 @@
+use zune_core::bit_depth::BitType;
+use zune_imageprocs::smart_crop::{best_crop, edge_energy};
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::impls::crop::Crop;
+use crate::traits::OperationsTrait;
+
+/// A function returning the saliency (importance) map of an image
+///
+/// The map must contain one non-negative weight per pixel, in row major
+/// order, i.e `width*height` weights. Higher weights mark more important pixels.
+pub type SaliencyFn = Box<dyn Fn(&Image) -> Vec<f32>>;
+
+/// The default saliency map, the edge energy of an image
+///
+/// This treats detailed and textured regions as important and
+/// flat regions such as sky or backgrounds as unimportant.
+/// The energies of all color channels of the first frame are summed,
+/// alpha is ignored.
+pub fn edge_energy_saliency(image: &Image) -> Vec<f32>
+{
+    let (width, height) = image.get_dimensions();
+    let depth = image.get_depth();
+    let colorspace = image.get_colorspace();
+
+    let mut saliency = vec![0.0; width * height];
+
+    for channel in image.get_frames_ref()[0].get_channels_ref(colorspace, true)
+    {
+        match depth.bit_type()
+        {
+            BitType::U8 => edge_energy(
+                channel.reinterpret_as::<u8>().unwrap(),
+                width,
+                height,
+                depth.max_value(),
+                &mut saliency
+            ),
+            BitType::U16 => edge_energy(
+                channel.reinterpret_as::<u16>().unwrap(),
+                width,
+                height,
+                depth.max_value(),
+                &mut saliency
+            ),
+            _ => todo!()
+        }
+    }
+    saliency
+}
+
+/// Content aware crop
+///
+/// Crops the image to the largest region with the target aspect ratio,
+/// placed where it holds the most saliency (importance) according to a saliency
+/// function. This is useful for generating thumbnails that keep the subject
+/// of an image in view.
+///
+/// The default saliency function is [`edge_energy_saliency`], a face or object
+/// detector can be plugged in via [`saliency`](SmartCrop::saliency) by returning
+/// a map with high weights over detected regions.
+///
+/// - target_aspect: Aspect ratio of the crop, i.e width divided by height
+pub struct SmartCrop
+{
+    target_aspect: f32,
+    saliency:      SaliencyFn
+}
+
+impl SmartCrop
+{
+    pub fn new(target_aspect: f32) -> SmartCrop
+    {
+        SmartCrop {
+            target_aspect,
+            saliency: Box::new(edge_energy_saliency)
+        }
+    }
+    /// Set the function used to compute the saliency map of the image
+    pub fn saliency<F>(mut self, saliency: F) -> SmartCrop
+    where
+        F: Fn(&Image) -> Vec<f32> + 'static
+    {
+        self.saliency = Box::new(saliency);
+        self
+    }
+    /// Return the dimensions of the largest crop with the target
+    /// aspect ratio fitting in an image of the given dimensions
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn crop_dimensions(&self, width: usize, height: usize) -> (usize, usize)
+    {
+        if width as f32 / height as f32 > self.target_aspect
+        {
+            let crop_width = (height as f32 * self.target_aspect).round() as usize;
+
+            (crop_width.clamp(1, width), height)
+        }
+        else
+        {
+            let crop_height = (width as f32 / self.target_aspect).round() as usize;
+
+            (width, crop_height.clamp(1, height))
+        }
+    }
+}
+
+impl OperationsTrait for SmartCrop
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Smart Crop"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        if !self.target_aspect.is_finite() || self.target_aspect <= 0.0
+        {
+            return Err(ImageErrors::GenericStr(
+                "Smart crop aspect ratio must be a positive number"
+            ));
+        }
+        let (width, height) = image.get_dimensions();
+
+        if width == 0 || height == 0
+        {
+            return Ok(());
+        }
+        let saliency = (self.saliency)(image);
+
+        if saliency.len() != width * height
+        {
+            return Err(ImageErrors::DimensionsMisMatch(
+                width * height,
+                saliency.len()
+            ));
+        }
+        let (crop_width, crop_height) = self.crop_dimensions(width, height);
+        let (x, y) = best_crop(&saliency, width, height, crop_width, crop_height);
+
+        Crop::new(crop_width, crop_height, x, y).execute_impl(image)
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
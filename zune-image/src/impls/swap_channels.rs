@@ -0,0 +1,124 @@
+//! Reorder the channels of an image
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::{ColorSpace, ALL_COLORSPACES};
+
+use crate::errors::{ImageErrors, ImageOperationsErrors};
+use crate::image::Image;
+use crate::traits::OperationsTrait;
+
+/// Permute the channels of an image
+///
+/// Channel `i` of the result is channel `order[i]` of the input,
+/// so `[2, 1, 0]` swaps the first and third channels.
+///
+/// The permutation must cover every channel of the image, including
+/// alpha. Swapping the red and blue channels of RGB(A) and BGR(A)
+/// images also changes the colorspace, e.g `[2, 1, 0, 3]` turns an RGBA
+/// image into BGRA, other permutations leave the colorspace untouched.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::swap_channels::SwapChannels;
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::from_u8(&[10, 20, 30, 255], 1, 1, ColorSpace::RGBA);
+///
+/// SwapChannels::new([2, 1, 0, 3]).execute(&mut image).unwrap();
+///
+/// assert_eq!(image.get_colorspace(), ColorSpace::BGRA);
+/// assert_eq!(image.flatten_frames::<u8>()[0], [30, 20, 10, 255]);
+/// ```
+pub struct SwapChannels<const N: usize>
+{
+    order: [usize; N]
+}
+
+impl<const N: usize> SwapChannels<N>
+{
+    /// Create a new channel permutation
+    ///
+    /// # Arguments
+    /// - order: The input channel to use for each output channel
+    pub fn new(order: [usize; N]) -> SwapChannels<N>
+    {
+        SwapChannels { order }
+    }
+
+    /// Colorspace of the image after the permutation
+    fn new_colorspace(&self, colorspace: ColorSpace) -> ColorSpace
+    {
+        let swaps_red_blue = N >= 3
+            && self.order[..3] == [2, 1, 0]
+            && self.order.iter().enumerate().skip(3).all(|(i, v)| i == *v);
+
+        if !swaps_red_blue
+        {
+            return colorspace;
+        }
+        match colorspace
+        {
+            ColorSpace::RGB => ColorSpace::BGR,
+            ColorSpace::RGBA => ColorSpace::BGRA,
+            ColorSpace::BGR => ColorSpace::RGB,
+            ColorSpace::BGRA => ColorSpace::RGBA,
+            colorspace => colorspace
+        }
+    }
+}
+
+impl<const N: usize> OperationsTrait for SwapChannels<N>
+{
+    fn get_name(&self) -> &'static str
+    {
+        "Swap channels"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        let colorspace = image.get_colorspace();
+        let components = colorspace.num_components();
+
+        if N != components
+        {
+            return Err(ImageErrors::OperationsError(
+                ImageOperationsErrors::WrongComponents(components, N)
+            ));
+        }
+        let mut seen = [false; N];
+
+        for &index in &self.order
+        {
+            if index >= N || seen[index]
+            {
+                let msg = format!("Channel order {:?} is not a permutation", self.order);
+                return Err(ImageErrors::GenericString(msg));
+            }
+            seen[index] = true;
+        }
+
+        for frame in image.get_frames_mut()
+        {
+            let mut channels: Vec<_> = frame.channels.drain(..).map(Some).collect();
+
+            frame.channels = self
+                .order
+                .iter()
+                .map(|&index| channels[index].take().unwrap())
+                .collect();
+        }
+        image.set_colorspace(self.new_colorspace(colorspace));
+
+        Ok(())
+    }
+
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &ALL_COLORSPACES
+    }
+
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16, BitType::F32]
+    }
+}
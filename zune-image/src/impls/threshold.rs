@@ -1,23 +1,57 @@
-use log::warn;
 use zune_core::bit_depth::BitType;
-use zune_imageprocs::threshold::threshold;
+use zune_imageprocs::threshold::{otsu_threshold, threshold};
 pub use zune_imageprocs::threshold::ThresholdMethod;
 
-use crate::errors::ImageErrors;
+use crate::errors::{ImageErrors, ImageOperationsErrors};
 use crate::image::Image;
+use crate::impls::grayscale::RgbToGrayScale;
 use crate::traits::OperationsTrait;
 
+/// Threshold a grayscale image
+///
+/// Samples are compared against a threshold and replaced according to the
+/// [`ThresholdMethod`], e.g [`ThresholdMethod::Binary`] turns the image into
+/// a black and white image.
+///
+/// The image should be grayscale, otherwise executing the operation returns an error,
+/// use [`auto_grayscale`](Self::auto_grayscale) to convert color images to grayscale first.
+///
+/// The alpha channel is left untouched
 pub struct Threshold
 {
-    method:    ThresholdMethod,
-    threshold: u16
+    method:         ThresholdMethod,
+    threshold:      u16,
+    otsu:           bool,
+    auto_grayscale: bool
 }
 
 impl Threshold
 {
+    /// Threshold using a fixed value
     pub fn new(threshold: u16, method: ThresholdMethod) -> Threshold
     {
-        Threshold { method, threshold }
+        Threshold {
+            method,
+            threshold,
+            otsu: false,
+            auto_grayscale: false
+        }
+    }
+    /// Threshold using a value computed from the image histogram
+    /// via [Otsu's method](https://en.wikipedia.org/wiki/Otsu%27s_method)
+    pub fn otsu(method: ThresholdMethod) -> Threshold
+    {
+        Threshold {
+            otsu: true,
+            ..Threshold::new(0, method)
+        }
+    }
+    /// Convert RGB images to grayscale before thresholding instead of
+    /// returning an error
+    pub fn auto_grayscale(mut self, yes: bool) -> Threshold
+    {
+        self.auto_grayscale = yes;
+        self
     }
 }
 impl OperationsTrait for Threshold
@@ -31,24 +65,48 @@ impl OperationsTrait for Threshold
     {
         if !image.get_colorspace().is_grayscale()
         {
-            warn!("Threshold works well with grayscale images, results may be something you don't expect")
+            if !self.auto_grayscale
+            {
+                return Err(ImageErrors::OperationsError(ImageOperationsErrors::Generic(
+                    "Threshold expects a grayscale image, convert it first or enable auto_grayscale"
+                )));
+            }
+            RgbToGrayScale::new().preserve_alpha(true).execute(image)?;
         }
 
         let depth = image.get_depth();
+        let max_value = depth.max_value();
+
         for channel in image.get_channels_mut(true)
         {
             match depth.bit_type()
             {
-                BitType::U16 => threshold(
-                    channel.reinterpret_as_mut::<u16>().unwrap(),
-                    self.threshold,
-                    self.method
-                ),
-                BitType::U8 => threshold(
-                    channel.reinterpret_as_mut::<u8>().unwrap(),
-                    self.threshold as u8,
-                    self.method
-                ),
+                BitType::U16 =>
+                {
+                    let channel = channel.reinterpret_as_mut::<u16>().unwrap();
+                    let value = if self.otsu
+                    {
+                        otsu_threshold(channel, max_value)
+                    }
+                    else
+                    {
+                        self.threshold
+                    };
+                    threshold(channel, value, self.method);
+                }
+                BitType::U8 =>
+                {
+                    let channel = channel.reinterpret_as_mut::<u8>().unwrap();
+                    let value = if self.otsu
+                    {
+                        otsu_threshold(channel, max_value)
+                    }
+                    else
+                    {
+                        self.threshold
+                    };
+                    threshold(channel, value.min(max_value) as u8, self.method);
+                }
                 _ => todo!()
             }
         }
@@ -7,22 +7,43 @@ use crate::image::Image;
 use crate::traits::OperationsTrait;
 
 /// Perform an unsharpen mask
+///
+/// A gaussian blurred copy of the image is subtracted from the image and
+/// the difference is added back scaled by an amount,
+/// `out = in + amount * (in - blurred)`, for pixels where the difference
+/// is greater than a threshold.
+///
+/// Results saturate at zero and the maximum value for the image depth,
+/// the alpha channel is left untouched
 #[derive(Default)]
 pub struct Unsharpen
 {
-    sigma:      f32,
-    threshold:  u16,
-    percentage: u8
+    sigma:     f32,
+    threshold: u16,
+    amount:    f32
 }
 
 impl Unsharpen
 {
+    /// Create an unsharpen mask which adds `percentage` percent of the
+    /// difference between the image and its blurred copy
+    ///
+    /// # Arguments
+    /// - sigma: Sigma of the gaussian blur
+    /// - threshold: Minimum absolute difference needed to sharpen a pixel
+    /// - percentage: Amount of the difference to add, `100` adds all of it
     pub fn new(sigma: f32, threshold: u16, percentage: u8) -> Unsharpen
+    {
+        Unsharpen::with_amount(sigma, f32::from(percentage) / 100.0, threshold)
+    }
+    /// Create an unsharpen mask which adds `amount` times the difference
+    /// between the image and its blurred copy, `0.0` does nothing
+    pub fn with_amount(sigma: f32, amount: f32, threshold: u16) -> Unsharpen
     {
         Unsharpen {
             sigma,
             threshold,
-            percentage
+            amount
         }
     }
 }
@@ -59,7 +80,7 @@ impl OperationsTrait for Unsharpen
                             &mut blur_scratch,
                             self.sigma,
                             self.threshold,
-                            self.percentage as u16,
+                            self.amount,
                             width,
                             height
                         );
@@ -78,8 +99,8 @@ impl OperationsTrait for Unsharpen
                             &mut blur_buffer,
                             &mut blur_scratch,
                             self.sigma,
-                            self.threshold as u8,
-                            self.percentage,
+                            self.threshold.min(255) as u8,
+                            self.amount,
                             width,
                             height
                         );
@@ -108,7 +129,7 @@ impl OperationsTrait for Unsharpen
                                 &mut blur_scratch,
                                 self.sigma,
                                 self.threshold,
-                                self.percentage as u16,
+                                self.amount,
                                 width,
                                 height
                             );
@@ -124,8 +145,8 @@ impl OperationsTrait for Unsharpen
                                 &mut blur_buffer,
                                 &mut blur_scratch,
                                 self.sigma,
-                                self.threshold as u8,
-                                self.percentage,
+                                self.threshold.min(255) as u8,
+                                self.amount,
                                 width,
                                 height
                             );
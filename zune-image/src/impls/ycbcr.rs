@@ -0,0 +1,148 @@
+//! RGB to YCbCr conversions
+use zune_core::bit_depth::BitType;
+use zune_core::colorspace::ColorSpace;
+pub use zune_imageprocs::colorspace::ycbcr::YCbCrStandard;
+use zune_imageprocs::colorspace::ycbcr::{rgb_to_ycbcr, ycbcr_to_rgb};
+
+use crate::errors::ImageErrors;
+use crate::image::Image;
+use crate::impls::colorspace::convert_color_channels;
+use crate::traits::OperationsTrait;
+
+/// Convert an RGB image to YCbCr
+///
+/// The image colorspace becomes [`ColorSpace::YCbCr`], with the
+/// channels stored in Y, Cb, Cr order.
+///
+/// ```
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_image::image::Image;
+/// use zune_image::impls::ycbcr::{RgbToYCbCr, YCbCrStandard};
+/// use zune_image::traits::OperationsTrait;
+///
+/// let mut image = Image::fill(255_u8, ColorSpace::RGB, 10, 10).unwrap();
+///
+/// RgbToYCbCr::new(YCbCrStandard::Jpeg).execute(&mut image).unwrap();
+///
+/// assert_eq!(image.get_colorspace(), ColorSpace::YCbCr);
+/// assert_eq!(image.flatten_frames::<u8>()[0][..3], [255, 128, 128]);
+/// ```
+pub struct RgbToYCbCr
+{
+    standard: YCbCrStandard
+}
+
+impl RgbToYCbCr
+{
+    pub fn new(standard: YCbCrStandard) -> RgbToYCbCr
+    {
+        RgbToYCbCr { standard }
+    }
+}
+
+impl OperationsTrait for RgbToYCbCr
+{
+    fn get_name(&self) -> &'static str
+    {
+        "RGB to YCbCr"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        convert_color_channels(image, |r, g, b, depth| {
+            let max_value = depth.max_value();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => rgb_to_ycbcr(
+                    r.reinterpret_as_mut::<u8>().unwrap(),
+                    g.reinterpret_as_mut::<u8>().unwrap(),
+                    b.reinterpret_as_mut::<u8>().unwrap(),
+                    self.standard,
+                    max_value
+                ),
+                BitType::U16 => rgb_to_ycbcr(
+                    r.reinterpret_as_mut::<u16>().unwrap(),
+                    g.reinterpret_as_mut::<u16>().unwrap(),
+                    b.reinterpret_as_mut::<u16>().unwrap(),
+                    self.standard,
+                    max_value
+                ),
+                _ => todo!()
+            }
+        });
+        image.set_colorspace(ColorSpace::YCbCr);
+
+        Ok(())
+    }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::RGB]
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
+
+/// Convert a YCbCr image to RGB
+///
+/// The image colorspace becomes [`ColorSpace::RGB`], the standard should
+/// match the one the image was converted with
+pub struct YCbCrToRgb
+{
+    standard: YCbCrStandard
+}
+
+impl YCbCrToRgb
+{
+    pub fn new(standard: YCbCrStandard) -> YCbCrToRgb
+    {
+        YCbCrToRgb { standard }
+    }
+}
+
+impl OperationsTrait for YCbCrToRgb
+{
+    fn get_name(&self) -> &'static str
+    {
+        "YCbCr to RGB"
+    }
+
+    fn execute_impl(&self, image: &mut Image) -> Result<(), ImageErrors>
+    {
+        convert_color_channels(image, |y, cb, cr, depth| {
+            let max_value = depth.max_value();
+
+            match depth.bit_type()
+            {
+                BitType::U8 => ycbcr_to_rgb(
+                    y.reinterpret_as_mut::<u8>().unwrap(),
+                    cb.reinterpret_as_mut::<u8>().unwrap(),
+                    cr.reinterpret_as_mut::<u8>().unwrap(),
+                    self.standard,
+                    max_value
+                ),
+                BitType::U16 => ycbcr_to_rgb(
+                    y.reinterpret_as_mut::<u16>().unwrap(),
+                    cb.reinterpret_as_mut::<u16>().unwrap(),
+                    cr.reinterpret_as_mut::<u16>().unwrap(),
+                    self.standard,
+                    max_value
+                ),
+                _ => todo!()
+            }
+        });
+        image.set_colorspace(ColorSpace::RGB);
+
+        Ok(())
+    }
+    fn supported_colorspaces(&self) -> &'static [ColorSpace]
+    {
+        &[ColorSpace::YCbCr]
+    }
+    fn supported_types(&self) -> &'static [BitType]
+    {
+        &[BitType::U8, BitType::U16]
+    }
+}
@@ -40,3 +40,2556 @@ fn test_fractal()
         .unwrap();
     image.save_to("a.ppm", ImageFormat::PPM).unwrap()
 }
+
+#[test]
+fn test_reduce_bit_depth_rgb565()
+{
+    use std::collections::BTreeSet;
+
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::impls::reduce_depth::ReduceBitDepth;
+    use crate::traits::OperationsTrait;
+
+    // horizontal gradients covering every 8 bit value
+    let pixels: Vec<u8> = (0..4)
+        .flat_map(|_| (0..=255).flat_map(|x: u8| [x, x, 255 - x]))
+        .collect();
+
+    let gradient = |dither: bool| {
+        let mut image = crate::image::Image::from_u8(&pixels, 256, 4, ColorSpace::RGB);
+        ReduceBitDepth::new([5, 6, 5, 8], dither)
+            .execute(&mut image)
+            .unwrap();
+        image
+    };
+
+    for dither in [false, true]
+    {
+        let image = gradient(dither);
+
+        for (channel, bits) in image.get_channels_ref(false).iter().zip([5, 6, 5])
+        {
+            let values: BTreeSet<u8> = channel
+                .reinterpret_as::<u8>()
+                .unwrap()
+                .iter()
+                .copied()
+                .collect();
+
+            if dither
+            {
+                assert!(values.len() <= 1 << bits);
+            }
+            else
+            {
+                assert_eq!(values.len(), 1 << bits);
+            }
+            // range is preserved
+            assert!(values.contains(&0));
+            assert!(values.contains(&255));
+        }
+    }
+}
+
+#[test]
+fn test_mipmap_atlas()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    // independent reference, average 2x2 blocks of an interleaved image
+    fn downsample(pixels: &[u8], width: usize, height: usize, components: usize) -> Vec<u8>
+    {
+        let (out_w, out_h) = ((width / 2).max(1), (height / 2).max(1));
+        let mut out = vec![0; out_w * out_h * components];
+
+        for y in 0..out_h
+        {
+            for x in 0..out_w
+            {
+                for c in 0..components
+                {
+                    let mut sum = 0_usize;
+
+                    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)]
+                    {
+                        let sx = (2 * x + dx).min(width - 1);
+                        let sy = (2 * y + dy).min(height - 1);
+
+                        sum += usize::from(pixels[(sy * width + sx) * components + c]);
+                    }
+                    out[(y * out_w + x) * components + c] = ((sum + 2) / 4) as u8;
+                }
+            }
+        }
+        out
+    }
+
+    let (width, height) = (16, 8);
+    let pixels: Vec<u8> = (0..width * height * 3)
+        .map(|x| (x * 37 % 256) as u8)
+        .collect();
+
+    let image = crate::image::Image::from_u8(&pixels, width, height, ColorSpace::RGB);
+    let atlas = image.generate_mipmap_atlas().unwrap();
+
+    // levels are 16x8, 8x4, 4x2, 2x1 and 1x1, stacked to the right of level 0
+    assert_eq!(atlas.get_dimensions(), (24, 8));
+    assert_eq!(atlas.get_colorspace(), ColorSpace::RGB);
+    assert_eq!(atlas.get_depth(), image.get_depth());
+
+    let atlas_pixels = &atlas.flatten_frames::<u8>()[0];
+
+    let mut level = pixels.clone();
+    let (mut level_w, mut level_h) = (width, height);
+    let (mut x_offset, mut y_offset) = (0, 0);
+
+    loop
+    {
+        for y in 0..level_h
+        {
+            let start = ((y_offset + y) * 24 + x_offset) * 3;
+
+            assert_eq!(
+                &atlas_pixels[start..start + level_w * 3],
+                &level[y * level_w * 3..(y + 1) * level_w * 3]
+            );
+        }
+        if level_w == 1 && level_h == 1
+        {
+            break;
+        }
+        if x_offset == 0
+        {
+            x_offset = width;
+        }
+        else
+        {
+            y_offset += level_h;
+        }
+        level = downsample(&level, level_w, level_h, 3);
+        level_w = (level_w / 2).max(1);
+        level_h = (level_h / 2).max(1);
+    }
+
+    // float images are not supported
+    let float = crate::image::Image::from_f32(&[0.5; 16 * 8], 16, 8, ColorSpace::Luma);
+
+    assert!(float.generate_mipmap_atlas().is_err());
+}
+
+#[test]
+fn test_displacement_map_gradient()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::impls::displacement::DisplacementMap;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (8, 8);
+
+    let source: Vec<u8> = (0..height)
+        .flat_map(|_| (0..width).map(|x| (x * 20) as u8))
+        .collect();
+
+    // red moves pixels horizontally, top half samples to the left(-2),
+    // bottom half to the right(+2), green is mid gray for no vertical movement
+    let map_pixels: Vec<u8> = (0..height)
+        .flat_map(|y| {
+            let red = if y < 4 { 0 } else { 255 };
+            (0..width).flat_map(move |_| [red, 128, 0])
+        })
+        .collect();
+
+    let map = crate::image::Image::from_u8(&map_pixels, width, height, ColorSpace::RGB);
+    let mut image = crate::image::Image::from_u8(&source, width, height, ColorSpace::Luma);
+
+    DisplacementMap::new(map, 4.0).execute(&mut image).unwrap();
+
+    let output = image.get_channels_ref(false)[0]
+        .reinterpret_as::<u8>()
+        .unwrap()
+        .to_vec();
+
+    for y in 0..height
+    {
+        for x in 0..width
+        {
+            let expected_x = if y < 4 { x.saturating_sub(2) } else { (x + 2).min(width - 1) };
+            // rows are identical, so the tiny vertical shift from 128 not being
+            // the exact midpoint does not affect the result
+            assert_eq!(output[y * width + x], source[y * width + expected_x]);
+        }
+    }
+
+    // mismatched dimensions are an error
+    let map = crate::image::Image::from_u8(&map_pixels[..3 * 4 * 8], 4, 8, ColorSpace::RGB);
+    assert!(DisplacementMap::new(map, 4.0).execute(&mut image).is_err());
+}
+
+#[test]
+fn test_integral_image_rectangle_sum()
+{
+    use zune_core::colorspace::ColorSpace;
+    use zune_imageprocs::integral_image::rectangle_sum;
+
+    let (width, height) = (13, 9);
+
+    let pixels: Vec<u16> = (0..width * height * 2)
+        .map(|x| ((x * 7919) % 65536) as u16)
+        .collect();
+    let image = crate::image::Image::from_u16(&pixels, width, height, ColorSpace::LumaA);
+
+    let tables = image.integral_image().unwrap();
+    assert_eq!(tables.len(), 2);
+
+    for (channel, table) in tables.iter().enumerate()
+    {
+        for (x, y, rect_width, rect_height) in
+            [(0, 0, 13, 9), (3, 2, 5, 4), (12, 8, 1, 1), (0, 4, 7, 5)]
+        {
+            let mut expected = 0_u64;
+
+            for j in y..y + rect_height
+            {
+                for i in x..x + rect_width
+                {
+                    expected += u64::from(pixels[(j * width + i) * 2 + channel]);
+                }
+            }
+            assert_eq!(
+                rectangle_sum(table, width, x, y, rect_width, rect_height),
+                expected
+            );
+        }
+    }
+
+    // float images are not supported
+    let float = crate::image::Image::from_f32(&[0.5; 13 * 9], width, height, ColorSpace::Luma);
+
+    assert!(float.integral_image().is_err());
+}
+
+#[test]
+fn test_guided_filter_preserves_guide_edges()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::impls::guided_filter::GuidedFilter;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (16, 8);
+
+    // a sharp vertical edge in the middle of the guide
+    let guide: Vec<u8> = (0..width * height)
+        .map(|i| {
+            if i % width < 8
+            {
+                0
+            }
+            else
+            {
+                255
+            }
+        })
+        .collect();
+    // the same edge with checkerboard noise
+    let target: Vec<u8> = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let base: i32 = if x < 8 { 60 } else { 195 };
+            let noise = if (x + y) % 2 == 0 { 30 } else { -30 };
+
+            (base + noise) as u8
+        })
+        .collect();
+
+    let guide = crate::image::Image::from_u8(&guide, width, height, ColorSpace::Luma);
+    let mut image = crate::image::Image::from_u8(&target, width, height, ColorSpace::Luma);
+
+    GuidedFilter::new(guide, 2, 0.01)
+        .execute(&mut image)
+        .unwrap();
+
+    let output = image.get_channels_ref(false)[0]
+        .reinterpret_as::<u8>()
+        .unwrap()
+        .to_vec();
+
+    for row in output.chunks_exact(width)
+    {
+        // the edge stays sharp
+        assert!(row[8] - row[7] > 100, "{row:?}");
+
+        // noise on either side is smoothed out
+        let left = &row[..7];
+        let right = &row[9..];
+
+        assert!(left.iter().max().unwrap() - left.iter().min().unwrap() < 30);
+        assert!(right.iter().max().unwrap() - right.iter().min().unwrap() < 30);
+    }
+}
+
+#[test]
+fn test_kmeans_segment_two_regions()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::impls::kmeans::KMeansSegment;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (16, 8);
+
+    // a reddish left half and a bluish right half, with some noise
+    let pixels: Vec<u8> = (0..width * height)
+        .flat_map(|i| {
+            let (x, y) = (i % width, i / width);
+            let noise = ((x * 7 + y * 3) % 11) as u8;
+
+            if x < 8
+            {
+                [200 + noise, 20, 30 - noise]
+            }
+            else
+            {
+                [20 + noise, 40, 210 - noise]
+            }
+        })
+        .collect();
+
+    let mut image = crate::image::Image::from_u8(&pixels, width, height, ColorSpace::RGB);
+
+    KMeansSegment::new(2, 10, 42).execute(&mut image).unwrap();
+
+    let output = image.flatten_frames::<u8>().remove(0);
+
+    let left = &output[0..3];
+    let right = &output[(width - 1) * 3..width * 3];
+
+    assert_ne!(left, right);
+
+    for (i, pixel) in output.chunks_exact(3).enumerate()
+    {
+        let expected = if i % width < 8 { left } else { right };
+        assert_eq!(pixel, expected);
+    }
+    // the cluster color is the mean of its region
+    assert!(left[0] > 190 && left[2] < 30);
+    assert!(right[0] < 40 && right[2] > 190);
+}
+
+#[test]
+fn test_laplacian_blend_soft_mask()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+
+    let (width, height) = (64, 16);
+
+    let first = Image::from_u8(
+        &vec![20; width * height * 3],
+        width,
+        height,
+        ColorSpace::RGB
+    );
+    let second = Image::from_u8(
+        &vec![220; width * height * 3],
+        width,
+        height,
+        ColorSpace::RGB
+    );
+
+    // black on the left, white on the right, with a linear ramp in the middle
+    let mask: Vec<u8> = (0..width * height)
+        .map(|i| (((i % width).clamp(24, 40) - 24) * 255 / 16) as u8)
+        .collect();
+    let mask = Image::from_u8(&mask, width, height, ColorSpace::Luma);
+
+    let blended = first.laplacian_blend(&second, &mask, 4).unwrap();
+
+    for channel in blended.get_channels_ref(false)
+    {
+        let pixels = channel.reinterpret_as::<u8>().unwrap();
+
+        for row in pixels.chunks_exact(width)
+        {
+            // the ends keep the original colors
+            assert_eq!(row[0], 20, "{row:?}");
+            assert_eq!(row[width - 1], 220, "{row:?}");
+
+            for pair in row.windows(2)
+            {
+                // no overshoot or ringing, values only increase
+                assert!(pair[0] <= pair[1], "{row:?}");
+                // and they increase gradually
+                assert!(pair[1] - pair[0] <= 40, "{row:?}");
+            }
+        }
+    }
+
+    // float images are not supported
+    let float = Image::from_f32(&vec![0.5; width * height * 3], width, height, ColorSpace::RGB);
+
+    assert!(float.laplacian_blend(&float, &mask, 4).is_err());
+}
+
+#[test]
+fn test_perspective_warp_corners()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::perspective::PerspectiveWarp;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (40, 40);
+
+    // every pixel has a unique value
+    let pixels: Vec<u16> = (0..width * height).map(|i| i as u16).collect();
+    let mut image = Image::from_u16(&pixels, width, height, ColorSpace::Luma);
+
+    let src_quad = [(5.0, 5.0), (30.0, 8.0), (34.0, 35.0), (4.0, 30.0)];
+    let dst_quad = [(0.0, 0.0), (19.0, 0.0), (19.0, 14.0), (0.0, 14.0)];
+
+    PerspectiveWarp::new(src_quad, dst_quad, (20, 15), [0; 4])
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.get_dimensions(), (20, 15));
+
+    let output = image.get_channels_ref(false)[0]
+        .reinterpret_as::<u16>()
+        .unwrap()
+        .to_vec();
+
+    for ((src_x, src_y), (dst_x, dst_y)) in src_quad.into_iter().zip(dst_quad)
+    {
+        let expected = pixels[src_y as usize * width + src_x as usize];
+
+        assert_eq!(output[dst_y as usize * 20 + dst_x as usize], expected);
+    }
+}
+
+#[test]
+fn test_perspective_warp_fill()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::perspective::PerspectiveWarp;
+    use crate::traits::OperationsTrait;
+
+    let mut image = Image::from_u8(&[100; 10 * 10 * 3], 10, 10, ColorSpace::RGB);
+
+    // the left half of the output comes from outside the image
+    let src_quad = [(-9.0, 0.0), (9.0, 0.0), (9.0, 9.0), (-9.0, 9.0)];
+    let dst_quad = [(0.0, 0.0), (18.0, 0.0), (18.0, 9.0), (0.0, 9.0)];
+
+    PerspectiveWarp::new(src_quad, dst_quad, (19, 10), [1, 2, 300, 0])
+        .execute(&mut image)
+        .unwrap();
+
+    let channels = image.get_channels_ref(false);
+
+    for (channel, fill) in channels.iter().zip([1, 2, 255])
+    {
+        let pixels = channel.reinterpret_as::<u8>().unwrap();
+
+        for row in pixels.chunks_exact(19)
+        {
+            assert!(row[..8].iter().all(|x| *x == fill), "{row:?}");
+            assert!(row[9..].iter().all(|x| *x == 100), "{row:?}");
+        }
+    }
+}
+
+#[test]
+fn test_halftone_dark_regions_have_larger_dots()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::halftone::{DotShape, Halftone};
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (64, 32);
+    // dark left half, light right half
+    let pixels: Vec<u8> = (0..width * height)
+        .map(|i| {
+            if i % width < width / 2
+            {
+                40
+            }
+            else
+            {
+                220
+            }
+        })
+        .collect();
+
+    for shape in [DotShape::Circle, DotShape::Square, DotShape::Line]
+    {
+        for angle in [0.0, 45.0]
+        {
+            let mut image = Image::from_u8(&pixels, width, height, ColorSpace::Luma);
+
+            Halftone::new(8, angle, shape).execute(&mut image).unwrap();
+
+            let channel = &image.get_channels_ref(false)[0];
+            let out = channel.reinterpret_as::<u8>().unwrap();
+
+            assert!(out.iter().all(|x| *x == 0 || *x == 255));
+
+            let (mut dark_ink, mut light_ink) = (0, 0);
+
+            for row in out.chunks_exact(width)
+            {
+                // stay away from the cells straddling the edge
+                dark_ink += row[..24].iter().filter(|x| **x == 0).count();
+                light_ink += row[40..].iter().filter(|x| **x == 0).count();
+            }
+            assert!(
+                dark_ink > light_ink * 2,
+                "{shape:?} {angle}: {dark_ink} vs {light_ink}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_match_histogram_approaches_reference()
+{
+    use zune_core::colorspace::ColorSpace;
+    use zune_imageprocs::histogram_match::histogram;
+
+    use crate::image::Image;
+    use crate::impls::histogram_match::MatchHistogram;
+    use crate::traits::OperationsTrait;
+
+    /// Sum of absolute differences between the cumulative histograms of two channels
+    fn cdf_distance(a: &[u8], b: &[u8]) -> f64
+    {
+        let (hist_a, hist_b) = (histogram(a, 255), histogram(b, 255));
+        let (mut cdf_a, mut cdf_b, mut distance) = (0.0, 0.0, 0.0);
+
+        for (x, y) in hist_a.iter().zip(&hist_b)
+        {
+            cdf_a += *x as f64 / a.len() as f64;
+            cdf_b += *y as f64 / b.len() as f64;
+            distance += (cdf_a - cdf_b).abs();
+        }
+        distance
+    }
+
+    // an underexposed image and a well exposed reference of a different size
+    let dark: Vec<u8> = (0..64 * 64 * 3).map(|i| ((i * 7) % 97) as u8).collect();
+    let bright: Vec<u8> = (0..48 * 40 * 3)
+        .map(|i| (60 + (i * 13) % 196) as u8)
+        .collect();
+
+    let reference = Image::from_u8(&bright, 48, 40, ColorSpace::RGB);
+
+    for luminance in [false, true]
+    {
+        let mut image = Image::from_u8(&dark, 64, 64, ColorSpace::RGB);
+
+        let before: Vec<f64> = image
+            .get_channels_ref(false)
+            .iter()
+            .zip(reference.get_channels_ref(false))
+            .map(|(a, b)| {
+                cdf_distance(
+                    a.reinterpret_as::<u8>().unwrap(),
+                    b.reinterpret_as::<u8>().unwrap()
+                )
+            })
+            .collect();
+
+        MatchHistogram::new(reference.clone())
+            .luminance(luminance)
+            .execute(&mut image)
+            .unwrap();
+
+        let channels = image.get_channels_ref(false);
+
+        for ((a, b), before) in channels
+            .iter()
+            .zip(reference.get_channels_ref(false))
+            .zip(before)
+        {
+            let after = cdf_distance(
+                a.reinterpret_as::<u8>().unwrap(),
+                b.reinterpret_as::<u8>().unwrap()
+            );
+            assert!(after < before / 4.0, "{luminance}: {after} vs {before}");
+        }
+    }
+}
+
+#[test]
+fn test_smart_crop_centers_on_salient_region()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::smart_crop::SmartCrop;
+    use crate::traits::OperationsTrait;
+
+    // each pixel holds its x coordinate so the crop position can be read back
+    let (width, height) = (600, 200);
+    let pixels: Vec<u16> = (0..width * height).map(|i| (i % width) as u16).collect();
+
+    // importance peaked near the bottom right corner
+    let (peak_x, peak_y) = (450.0, 150.0);
+    let saliency = move |_: &Image| -> Vec<f32> {
+        (0..width * height)
+            .map(|i| {
+                let dx = (i % width) as f32 - peak_x;
+                let dy = (i / width) as f32 - peak_y;
+
+                (-(dx * dx + dy * dy) / (2.0 * 15.0 * 15.0)).exp()
+            })
+            .collect()
+    };
+    let mut image = Image::from_u16(&pixels, width, height, ColorSpace::Luma);
+
+    SmartCrop::new(1.0)
+        .saliency(saliency)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.get_dimensions(), (200, 200));
+
+    let channel = &image.get_channels_ref(false)[0];
+    let left = channel.reinterpret_as::<u16>().unwrap()[0];
+
+    // the crop's center is at the peak
+    assert!((349..=351).contains(&left), "{left}");
+}
+
+#[test]
+fn test_smart_crop_edge_energy_keeps_detail()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::smart_crop::SmartCrop;
+    use crate::traits::OperationsTrait;
+
+    // flat image with a checkerboard patch on the left
+    let (width, height) = (120, 40);
+    let pixels: Vec<u8> = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+
+            if (10..30).contains(&x) && (10..30).contains(&y) && (x + y) % 2 == 0
+            {
+                255
+            }
+            else
+            {
+                128
+            }
+        })
+        .collect();
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::Luma);
+
+    SmartCrop::new(1.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_dimensions(), (40, 40));
+
+    // the whole patch is kept
+    let channel = &image.get_channels_ref(false)[0];
+    let kept = channel
+        .reinterpret_as::<u8>()
+        .unwrap()
+        .iter()
+        .filter(|x| **x == 255)
+        .count();
+
+    assert_eq!(kept, 200);
+}
+
+#[test]
+fn test_ascii_black_and_white()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::{AsciiCharset, Image};
+
+    for charset in [
+        AsciiCharset::Standard,
+        AsciiCharset::Detailed,
+        AsciiCharset::Blocks
+    ]
+    {
+        let ramp = charset.ramp();
+        let darkest = ramp[0];
+        let lightest = ramp[ramp.len() - 1];
+
+        let black = Image::from_u8(&[0; 40 * 20 * 3], 40, 20, ColorSpace::RGB);
+        let white = Image::from_u16(&[65535; 40 * 20], 40, 20, ColorSpace::Luma);
+
+        let black_text = black.to_ascii(10, charset).unwrap();
+        let white_text = white.to_ascii(10, charset).unwrap();
+
+        // 40x20 pixels in 10 columns of 4x8 pixel cells
+        assert_eq!(black_text.lines().count(), 3);
+        assert!(black_text
+            .lines()
+            .all(|line| line.chars().eq([darkest; 10].into_iter())));
+        assert!(white_text
+            .lines()
+            .all(|line| line.chars().eq([lightest; 10].into_iter())));
+    }
+    let white = Image::from_u8(&[255; 4 * 2 * 3], 4, 2, ColorSpace::RGB);
+
+    assert_eq!(
+        white.to_ascii_colored(1, AsciiCharset::Standard).unwrap(),
+        "\x1b[38;2;255;255;255m@\x1b[0m\n"
+    );
+
+    // float images are not supported
+    let float = Image::from_f32(&[1.0; 4 * 2], 4, 2, ColorSpace::Luma);
+
+    assert!(float.to_ascii(1, AsciiCharset::Standard).is_err());
+    assert!(float.to_ascii_colored(1, AsciiCharset::Standard).is_err());
+}
+
+#[test]
+fn test_draw_text_sets_glyph_pixels()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::draw_text::DrawText;
+    use crate::traits::OperationsTrait;
+
+    // the 5x7 glyph of 'A'
+    let glyph = [
+        ".###.", "#...#", "#...#", "#...#", "#####", "#...#", "#...#"
+    ];
+
+    for scale in [1, 2]
+    {
+        let (width, height) = (20, 20);
+        let mut image = Image::fill::<u8>(0, ColorSpace::Luma, width, height).unwrap();
+
+        DrawText::new("A", 3, 2, [255, 0, 0, 255], scale)
+            .execute(&mut image)
+            .unwrap();
+
+        let channel = &image.get_channels_ref(false)[0];
+        let pixels = channel.reinterpret_as::<u8>().unwrap();
+
+        for y in 0..height
+        {
+            for x in 0..width
+            {
+                let inside = (3..3 + 5 * scale).contains(&x) && (2..2 + 7 * scale).contains(&y);
+                let expected = inside && glyph[(y - 2) / scale].as_bytes()[(x - 3) / scale] == b'#';
+
+                assert_eq!(
+                    pixels[y * width + x],
+                    if expected { 255 } else { 0 },
+                    "scale {scale} at ({x},{y})"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_draw_text_blends_and_clips()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::draw_text::DrawText;
+    use crate::traits::OperationsTrait;
+
+    let mut image = Image::from_u16(&[1000; 8 * 8 * 4], 8, 8, ColorSpace::RGBA);
+
+    // half transparent red, running past the right and bottom edges
+    DrawText::new("|||\n|||", 2, 2, [3000, 0, 0, 32768], 1)
+        .execute(&mut image)
+        .unwrap();
+
+    let channels = image.get_channels_ref(false);
+    let [r, g, _, a] = [0, 1, 2, 3].map(|i| channels[i].reinterpret_as::<u16>().unwrap());
+
+    // '|' is a vertical bar in the middle column of the glyph
+    let i = 3 * 8 + 4;
+    assert_eq!(r[i], 2000);
+    assert_eq!(g[i], 500);
+    assert_eq!(a[i], 33268);
+
+    // untouched pixel
+    assert_eq!(r[3 * 8 + 3], 1000);
+}
+
+#[test]
+fn test_retinex_brightens_shadows()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::retinex::Retinex;
+    use crate::traits::OperationsTrait;
+
+    // a textured image with a dark left half and a bright right half
+    let (width, height) = (64, 64);
+    let pixels: Vec<u8> = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let texture = ((x * 7 + y * 13) % 11) as u8;
+
+            if x < width / 2
+            {
+                20 + texture
+            }
+            else
+            {
+                210 + texture
+            }
+        })
+        .collect();
+
+    let mean = |image: &Image, columns: std::ops::Range<usize>| -> f64 {
+        let channel = &image.get_channels_ref(false)[0];
+        let pixels = channel.reinterpret_as::<u8>().unwrap();
+        let region: Vec<f64> = (0..height)
+            .flat_map(|y| {
+                columns
+                    .clone()
+                    .map(move |x| f64::from(pixels[y * width + x]))
+            })
+            .collect();
+
+        region.iter().sum::<f64>() / region.len() as f64
+    };
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::Luma);
+
+    // regions away from the boundary between both halves
+    let dark = 4..24;
+    let bright = 40..60;
+
+    let dark_before = mean(&image, dark.clone());
+    let bright_before = mean(&image, bright.clone());
+
+    Retinex::new(vec![2.0, 8.0, 32.0], 1.0, 0.0)
+        .execute(&mut image)
+        .unwrap();
+
+    let dark_after = mean(&image, dark);
+    let bright_after = mean(&image, bright);
+
+    assert!(
+        dark_after > dark_before + 50.0,
+        "dark region went from {dark_before} to {dark_after}"
+    );
+    assert!(
+        (bright_after - bright_before).abs() < dark_after - dark_before,
+        "bright region went from {bright_before} to {bright_after}"
+    );
+}
+
+#[test]
+fn test_anisotropic_diffusion_keeps_edges()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::anisotropic_diffusion::AnisotropicDiffusion;
+    use crate::traits::OperationsTrait;
+
+    // a noisy dark left half and a noisy bright right half
+    let (width, height) = (32, 32);
+    let pixels: Vec<u8> = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let noise = ((x * 7 + y * 13) % 9) as u8;
+
+            if x < width / 2
+            {
+                40 + noise
+            }
+            else
+            {
+                200 + noise
+            }
+        })
+        .collect();
+
+    let spread = |pixels: &[u8], columns: std::ops::Range<usize>| -> u8 {
+        let region: Vec<u8> = (0..height)
+            .flat_map(|y| columns.clone().map(move |x| pixels[y * width + x]))
+            .collect();
+
+        region.iter().max().unwrap() - region.iter().min().unwrap()
+    };
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::Luma);
+
+    AnisotropicDiffusion::new(20, 0.1, 0.25)
+        .execute(&mut image)
+        .unwrap();
+
+    let channel = &image.get_channels_ref(false)[0];
+    let output = channel.reinterpret_as::<u8>().unwrap();
+
+    // noise in the flat regions is smoothed
+    assert!(spread(output, 2..14) < spread(&pixels, 2..14) / 2);
+    assert!(spread(output, 18..30) < spread(&pixels, 18..30) / 2);
+
+    // while the edge stays sharp
+    for y in 0..height
+    {
+        let left = output[y * width + width / 2 - 1];
+        let right = output[y * width + width / 2];
+
+        assert!(left < 60 && right > 190, "{left} {right}");
+    }
+}
+
+#[test]
+fn test_perceptual_hash_similarity()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+
+    let (width, height) = (96, 64);
+
+    // a few soft shapes, the second image is brighter, noisy and
+    // has a different size, the third one has a different structure
+    let scene = |width: usize, height: usize, brightness: f32, noise: u8| -> Vec<u8> {
+        (0..width * height)
+            .map(|i| {
+                let x = (i % width) as f32 / width as f32;
+                let y = (i / width) as f32 / height as f32;
+                let circle = ((x - 0.3).powi(2) + (y - 0.4).powi(2)).sqrt() < 0.2;
+                let value = if circle { 200.0 } else { 60.0 + 100.0 * x };
+                let jitter = ((i * 31 + 7) % usize::from(noise.max(1))) as f32;
+
+                (value * brightness + jitter).min(255.0) as u8
+            })
+            .collect()
+    };
+    let hash = |pixels: Vec<u8>, width: usize, height: usize| -> u64 {
+        Image::from_u8(&pixels, width, height, ColorSpace::Luma)
+            .perceptual_hash()
+            .unwrap()
+    };
+
+    let original = hash(scene(width, height, 1.0, 1), width, height);
+    let similar = hash(scene(150, 100, 1.1, 12), 150, 100);
+
+    let different: Vec<u8> = (0..width * height)
+        .map(|i| {
+            if (i / width / 8 + i % width / 8) % 2 == 0
+            {
+                30
+            }
+            else
+            {
+                220
+            }
+        })
+        .collect();
+    let different = hash(different, width, height);
+
+    assert!(
+        (original ^ similar).count_ones() <= 6,
+        "{original:x} {similar:x}"
+    );
+    assert!(
+        (original ^ different).count_ones() >= 20,
+        "{original:x} {different:x}"
+    );
+
+    // colour images hash their luminance
+    let rgb: Vec<u8> = scene(width, height, 1.0, 1)
+        .iter()
+        .flat_map(|x| [*x, *x, *x])
+        .collect();
+    let rgb = Image::from_u8(&rgb, width, height, ColorSpace::RGB).perceptual_hash();
+
+    assert_eq!(rgb.unwrap(), original);
+
+    // float images are not supported
+    let float = Image::from_f32(&vec![0.5; width * height], width, height, ColorSpace::Luma);
+
+    assert!(float.perceptual_hash().is_err());
+}
+
+#[test]
+fn test_signed_distance_field_circle()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::signed_distance_field::SignedDistanceField;
+    use crate::traits::OperationsTrait;
+
+    // a filled circle of radius 12 centered in a 41x41 image
+    let (size, center, radius) = (41, 20, 12);
+    let pixels: Vec<u8> = (0..size * size)
+        .map(|i| {
+            let (x, y) = (i % size, i / size);
+            let (dx, dy) = (x as i32 - center, y as i32 - center);
+
+            if dx * dx + dy * dy <= radius * radius
+            {
+                255
+            }
+            else
+            {
+                0
+            }
+        })
+        .collect();
+
+    let mut image = Image::from_u8(&pixels, size, size, ColorSpace::Luma);
+
+    SignedDistanceField::new(8.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+
+    let channel = &image.get_channels_ref(false)[0];
+    let output = channel.reinterpret_as::<u8>().unwrap();
+
+    let at = |x: usize, y: usize| output[y * size + x];
+
+    let center = center as usize;
+    let radius = radius as usize;
+
+    // the center is furthest from the edge
+    assert_eq!(at(center, center), 255);
+    assert_eq!(output.iter().max(), Some(&255));
+    // and the corners are far outside
+    assert_eq!(at(0, 0), 0);
+
+    // the edge lies between the last inside and first outside pixel,
+    // so those two straddle the mid value
+    for (inside, outside) in [
+        (at(center + radius, center), at(center + radius + 1, center)),
+        (at(center - radius, center), at(center - radius - 1, center)),
+        (at(center, center + radius), at(center, center + radius + 1)),
+        (at(center, center - radius), at(center, center - radius - 1))
+    ]
+    {
+        assert!((128..=145).contains(&inside), "{inside}");
+        assert!((110..128).contains(&outside), "{outside}");
+    }
+    // values fall off monotonically from the center
+    for x in center..size - 1
+    {
+        assert!(at(x, center) >= at(x + 1, center));
+    }
+}
+
+#[test]
+fn test_grayscale_rgb_and_rgba()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::grayscale::RgbToGrayScale;
+    use crate::traits::OperationsTrait;
+
+    // wide enough to go through the simd kernels and their remainders
+    let (width, height) = (67, 3);
+    let rgb: Vec<[u8; 3]> = (0..width * height)
+        .map(|i| [(i * 7) as u8, (i * 13 + 50) as u8, (i * 3) as u8])
+        .collect();
+
+    let expected: Vec<u8> = rgb
+        .iter()
+        .map(|[r, g, b]| {
+            (0.299 * f64::from(*r) + 0.587 * f64::from(*g) + 0.114 * f64::from(*b)).round() as u8
+        })
+        .collect();
+
+    let check = |image: &Image| {
+        let gray = &image.get_channels_ref(false)[0];
+
+        for (found, expected) in gray.reinterpret_as::<u8>().unwrap().iter().zip(&expected)
+        {
+            assert!(found.abs_diff(*expected) <= 1, "{found} {expected}");
+        }
+    };
+
+    let pixels: Vec<u8> = rgb.iter().flatten().copied().collect();
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::RGB);
+
+    RgbToGrayScale::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+    check(&image);
+
+    let alpha = |i: usize| (i % 256) as u8;
+    let pixels: Vec<u8> = rgb
+        .iter()
+        .enumerate()
+        .flat_map(|(i, [r, g, b])| [*r, *g, *b, alpha(i)])
+        .collect();
+
+    // alpha is dropped by default
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::RGBA);
+
+    RgbToGrayScale::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+    check(&image);
+
+    // or kept
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::RGBA);
+
+    RgbToGrayScale::new()
+        .preserve_alpha(true)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::LumaA);
+    check(&image);
+
+    let channels = image.get_channels_ref(false);
+    let kept_alpha = channels[1].reinterpret_as::<u8>().unwrap();
+
+    assert!(kept_alpha.iter().enumerate().all(|(i, a)| *a == alpha(i)));
+}
+
+#[test]
+fn test_grayscale_other_colorspaces()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::errors::ImageErrors;
+    use crate::image::Image;
+    use crate::impls::grayscale::RgbToGrayScale;
+    use crate::traits::OperationsTrait;
+
+    // grayscale images are left as they are
+    let pixels: Vec<u8> = (0..=255).collect();
+    let mut image = Image::from_u8(&pixels, 16, 16, ColorSpace::Luma);
+
+    RgbToGrayScale::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    // anything that isn't rgb or grayscale is rejected
+    let pixels = vec![0_u8; 16 * 16 * 4];
+    let mut image = Image::from_u8(&pixels, 16, 16, ColorSpace::CMYK);
+
+    let result = RgbToGrayScale::new().execute(&mut image);
+
+    assert!(matches!(
+        result,
+        Err(ImageErrors::UnsupportedColorspace(ColorSpace::CMYK, _, _))
+    ));
+}
+
+#[test]
+fn test_grayscale_coefficients()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::grayscale::RgbToGrayScale;
+    use crate::traits::OperationsTrait;
+
+    // pure red, green and blue, repeated to reach the simd kernels
+    let pixels = [255, 0, 0, 0, 255, 0, 0, 0, 255].repeat(20);
+
+    let convert = |operation: RgbToGrayScale| {
+        let mut image = Image::from_u8(&pixels, 3, 20, ColorSpace::RGB);
+        operation.execute(&mut image).unwrap();
+
+        image.flatten_frames::<u8>()[0][..3].to_vec()
+    };
+    // fixed point math can be off by one
+    let assert_close = |found: Vec<u8>, expected: [u8; 3]| {
+        for (found, expected) in found.iter().zip(expected)
+        {
+            assert!(found.abs_diff(expected) <= 1, "{found} {expected}");
+        }
+    };
+
+    // each primary comes out as its weight
+    assert_close(convert(RgbToGrayScale::new()), [76, 150, 29]);
+    assert_close(convert(RgbToGrayScale::REC_601), [76, 150, 29]);
+    assert_close(convert(RgbToGrayScale::REC_709), [54, 182, 18]);
+
+    // weights are normalized, including ones the simd kernels can't hold
+    assert_close(
+        convert(RgbToGrayScale::with_coefficients(1.0, 1.0, 2.0)),
+        [64, 64, 128]
+    );
+    assert_close(
+        convert(RgbToGrayScale::with_coefficients(0.0, 3.0, 0.0)),
+        [0, 255, 0]
+    );
+
+    for (r, g, b) in [(-0.1, 0.6, 0.5), (0.0, 0.0, 0.0), (f32::NAN, 0.5, 0.5)]
+    {
+        let mut image = Image::from_u8(&pixels, 3, 20, ColorSpace::RGB);
+        let operation = RgbToGrayScale::with_coefficients(r, g, b);
+
+        assert!(operation.execute(&mut image).is_err());
+    }
+}
+
+#[test]
+fn test_grayscale_16_bit()
+{
+    use zune_core::bit_depth::BitDepth;
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::grayscale::RgbToGrayScale;
+    use crate::traits::OperationsTrait;
+
+    let rgb: Vec<[u16; 3]> = (0..64 * 4_u32)
+        .map(|i| [i * 257, 65535 - i * 31, i * 1021 % 65536].map(|x| x as u16))
+        .collect();
+    let pixels: Vec<u16> = rgb.iter().flatten().copied().collect();
+
+    for (operation, [wr, wg, wb]) in [
+        (RgbToGrayScale::REC_601, [0.299, 0.587, 0.114]),
+        (RgbToGrayScale::REC_709, [0.2126, 0.7152, 0.0722])
+    ]
+    {
+        let mut image = Image::from_u16(&pixels, 64, 4, ColorSpace::RGB);
+
+        operation.execute(&mut image).unwrap();
+
+        assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+        assert_eq!(image.get_depth(), BitDepth::Sixteen);
+
+        let gray = image.flatten_frames::<u16>().remove(0);
+
+        for ([r, g, b], found) in rgb.iter().zip(gray)
+        {
+            let expected = wr * f64::from(*r) + wg * f64::from(*g) + wb * f64::from(*b);
+
+            // within a step of the exact value, allowing for fixed point rounding
+            assert!((f64::from(found) - expected).abs() < 1.5, "{found} {expected}");
+        }
+    }
+}
+
+#[test]
+fn test_resize_nearest_neighbor()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::resize::{Resize, ResizeMethod};
+    use crate::traits::OperationsTrait;
+
+    let pixels = [10_u8, 20, 30, 40, 50, 60];
+    let mut image = Image::from_u8(&pixels, 3, 2, ColorSpace::Luma);
+
+    // each pixel becomes a 2x2 block
+    Resize::new(6, 4, ResizeMethod::NearestNeighbor)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.get_dimensions(), (6, 4));
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [
+            10, 10, 20, 20, 30, 30, //
+            10, 10, 20, 20, 30, 30, //
+            40, 40, 50, 50, 60, 60, //
+            40, 40, 50, 50, 60, 60
+        ]
+    );
+
+    // and back again
+    Resize::new(3, 2, ResizeMethod::NearestNeighbor)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+}
+
+#[test]
+fn test_resize_interpolation()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::resize::{Resize, ResizeMethod};
+    use crate::traits::OperationsTrait;
+
+    // a horizontal ramp
+    let (width, height) = (64, 8);
+    let pixels: Vec<u8> = (0..width * height).map(|i| (i % width * 4) as u8).collect();
+
+    for method in [ResizeMethod::Bilinear, ResizeMethod::Lanczos3]
+    {
+        for (new_width, new_height) in [(160, 20), (16, 2), (64, 8)]
+        {
+            let mut image = Image::from_u8(&pixels, width, height, ColorSpace::Luma);
+
+            Resize::new(new_width, new_height, method)
+                .execute(&mut image)
+                .unwrap();
+
+            assert_eq!(image.get_dimensions(), (new_width, new_height));
+
+            let output = image.flatten_frames::<u8>().remove(0);
+
+            for row in output.chunks_exact(new_width)
+            {
+                // rows are unchanged vertically, and still follow the ramp
+                assert_eq!(row, &output[..new_width]);
+
+                for (x, value) in row.iter().enumerate()
+                {
+                    let scale = width as f32 / new_width as f32;
+                    let expected = (((x as f32 + 0.5) * scale - 0.5) * 4.0).clamp(0.0, 252.0);
+
+                    // edges are clamped, so only check away from them
+                    if x > new_width / 10 && x < new_width - new_width / 10
+                    {
+                        let error = (f32::from(*value) - expected).abs();
+
+                        assert!(error <= 2.0, "{method:?} {x} {value} {expected}");
+                    }
+                }
+            }
+            if new_width == width
+            {
+                assert_eq!(output, pixels, "{method:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_resize_16_bit_with_alpha()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::resize::{Resize, ResizeMethod};
+    use crate::traits::OperationsTrait;
+
+    let pixels: Vec<u16> = [1000, 2000, 3000, 65535].repeat(10 * 6);
+    let mut image = Image::from_u16(&pixels, 10, 6, ColorSpace::RGBA);
+
+    Resize::new(25, 3, ResizeMethod::Lanczos3)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.get_dimensions(), (25, 3));
+
+    // flat colors stay flat, alpha included
+    let output = image.flatten_frames::<u16>().remove(0);
+
+    assert_eq!(output, [1000, 2000, 3000, 65535].repeat(25 * 3));
+
+    assert!(Resize::new(0, 3, ResizeMethod::Bilinear)
+        .execute(&mut image)
+        .is_err());
+}
+
+#[test]
+fn test_crop()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::crop::Crop;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (7, 5);
+
+    for colorspace in [ColorSpace::Luma, ColorSpace::RGB, ColorSpace::RGBA]
+    {
+        let components = colorspace.num_components();
+
+        // every sample stores its position, x in the low byte, y and the component above
+        let position = |x: usize, y: usize, c: usize| (x + (y << 4) + (c << 8)) as u16;
+
+        let pixels: Vec<u16> = (0..height)
+            .flat_map(|y| (0..width).flat_map(move |x| (0..components).map(move |c| (x, y, c))))
+            .map(|(x, y, c)| position(x, y, c))
+            .collect();
+
+        let expected: Vec<u16> = (1..4)
+            .flat_map(|y| (2..6).flat_map(move |x| (0..components).map(move |c| (x, y, c))))
+            .map(|(x, y, c)| position(x, y, c))
+            .collect();
+
+        let mut image = Image::from_u16(&pixels, width, height, colorspace);
+
+        Crop::new(4, 3, 2, 1).execute(&mut image).unwrap();
+
+        assert_eq!(image.get_dimensions(), (4, 3));
+        assert_eq!(image.flatten_frames::<u16>()[0], expected);
+
+        // the same crop on 8 bit data
+        let pixels: Vec<u8> = pixels.iter().map(|x| *x as u8).collect();
+        let mut image = Image::from_u8(&pixels, width, height, colorspace);
+
+        Crop::new(4, 3, 2, 1).execute(&mut image).unwrap();
+
+        let expected: Vec<u8> = expected.iter().map(|x| *x as u8).collect();
+
+        assert_eq!(image.flatten_frames::<u8>()[0], expected);
+    }
+
+    // the whole image, and regions reaching past the edges
+    let pixels = vec![0_u8; width * height];
+
+    for (crop_width, crop_height, x, y, fits) in [
+        (7, 5, 0, 0, true),
+        (1, 1, 6, 4, true),
+        (8, 5, 0, 0, false),
+        (7, 5, 1, 0, false),
+        (2, 2, 0, 4, false),
+        (1, 1, usize::MAX, 0, false),
+        (0, 1, 0, 0, false)
+    ]
+    {
+        let mut image = Image::from_u8(&pixels, width, height, ColorSpace::Luma);
+        let result = Crop::new(crop_width, crop_height, x, y).execute(&mut image);
+
+        assert_eq!(result.is_ok(), fits, "{crop_width}x{crop_height} at ({x},{y})");
+    }
+}
+
+#[test]
+fn test_rotate()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::rotate::{Rotate, RotationAngle};
+    use crate::traits::OperationsTrait;
+
+    // 3x2 image
+    //
+    // [1,2,3]
+    // [4,5,6]
+    let pixels = [1_u8, 2, 3, 4, 5, 6];
+
+    let expected: [(RotationAngle, (usize, usize), [u8; 6]); 3] = [
+        // [4,1]
+        // [5,2]
+        // [6,3]
+        (RotationAngle::Rotate90, (2, 3), [4, 1, 5, 2, 6, 3]),
+        // [6,5,4]
+        // [3,2,1]
+        (RotationAngle::Rotate180, (3, 2), [6, 5, 4, 3, 2, 1]),
+        // [3,6]
+        // [2,5]
+        // [1,4]
+        (RotationAngle::Rotate270, (2, 3), [3, 6, 2, 5, 1, 4])
+    ];
+
+    for (angle, dimensions, rotated) in expected
+    {
+        let mut image = Image::from_u8(&pixels, 3, 2, ColorSpace::Luma);
+
+        Rotate::new(angle).execute(&mut image).unwrap();
+
+        assert_eq!(image.get_dimensions(), dimensions, "{angle:?}");
+        assert_eq!(image.flatten_frames::<u8>()[0], rotated, "{angle:?}");
+
+        // each channel of an RGBA 16 bit image is moved the same way
+        let rgba: Vec<u16> = pixels
+            .iter()
+            .flat_map(|x| (0..4).map(move |c| u16::from(*x) + (c << 8)))
+            .collect();
+        let expected: Vec<u16> = rotated
+            .iter()
+            .flat_map(|x| (0..4).map(move |c| u16::from(*x) + (c << 8)))
+            .collect();
+
+        let mut image = Image::from_u16(&rgba, 3, 2, ColorSpace::RGBA);
+
+        Rotate::new(angle).execute(&mut image).unwrap();
+
+        assert_eq!(image.get_dimensions(), dimensions, "{angle:?}");
+        assert_eq!(image.flatten_frames::<u16>()[0], expected, "{angle:?}");
+    }
+
+    // four quarter turns is the original image
+    let mut image = Image::from_u8(&pixels, 3, 2, ColorSpace::Luma);
+
+    for _ in 0..4
+    {
+        Rotate::new(RotationAngle::Rotate90).execute(&mut image).unwrap();
+    }
+    assert_eq!(image.get_dimensions(), (3, 2));
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+}
+
+#[test]
+fn test_brighten()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::brighten::Brighten;
+    use crate::traits::OperationsTrait;
+
+    // two RGBA pixels, alpha should never change
+    let pixels = [10_u8, 100, 250, 128, 0, 200, 30, 7];
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::RGBA);
+    Brighten::new(40).execute(&mut image).unwrap();
+
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [50, 140, 255, 128, 40, 240, 70, 7]
+    );
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::RGBA);
+    Brighten::new(-40).execute(&mut image).unwrap();
+
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [0, 60, 210, 128, 0, 160, 0, 7]
+    );
+
+    // deltas larger than the sample range saturate instead of wrapping
+    let mut image = Image::from_u8(&[20, 200, 100, 9], 2, 1, ColorSpace::LumaA);
+    Brighten::new(300).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], [255, 200, 255, 9]);
+
+    let mut image = Image::from_u8(&[20, 200, 100, 9], 2, 1, ColorSpace::LumaA);
+    Brighten::new(-300).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], [0, 200, 0, 9]);
+
+    // 16 bit
+    let pixels = [1000_u16, 65000, 30, 40000];
+
+    let mut image = Image::from_u16(&pixels, 2, 1, ColorSpace::LumaA);
+    Brighten::new(1000).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [2000, 65000, 1030, 40000]);
+
+    let mut image = Image::from_u16(&pixels, 4, 1, ColorSpace::Luma);
+    Brighten::new(-1000).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [0, 64000, 0, 39000]);
+
+    let mut image = Image::from_u16(&pixels, 4, 1, ColorSpace::Luma);
+    Brighten::new(i16::MAX).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [33767, 65535, 32797, 65535]);
+}
+
+#[test]
+fn test_contrast()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::contrast::Contrast;
+    use crate::traits::OperationsTrait;
+
+    // the midpoint stays put whatever the factor
+    for factor in [0.0, 0.5, 1.0, 2.0, 10.0]
+    {
+        let mut image = Image::from_u8(&[128], 1, 1, ColorSpace::Luma);
+        Contrast::with_factor(factor).execute(&mut image).unwrap();
+
+        assert_eq!(image.flatten_frames::<u8>()[0], [128], "factor {factor}");
+
+        let mut image = Image::from_u16(&[32768], 1, 1, ColorSpace::Luma);
+        Contrast::with_factor(factor).execute(&mut image).unwrap();
+
+        assert_eq!(image.flatten_frames::<u16>()[0], [32768], "factor {factor}");
+    }
+
+    // a factor of one and a level of zero do nothing
+    let pixels: Vec<u8> = (0..=255).collect();
+
+    for contrast in [Contrast::with_factor(1.0), Contrast::new(0.0)]
+    {
+        let mut image = Image::from_u8(&pixels, 16, 16, ColorSpace::Luma);
+        contrast.execute(&mut image).unwrap();
+
+        assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+    }
+
+    // RGBA, alpha is untouched and extremes clamp
+    let pixels = [0_u8, 100, 228, 50, 255, 120, 136, 200];
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::RGBA);
+    Contrast::with_factor(2.0).execute(&mut image).unwrap();
+
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [0, 72, 255, 50, 255, 112, 144, 200]
+    );
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::RGBA);
+    Contrast::with_factor(0.5).execute(&mut image).unwrap();
+
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [64, 114, 178, 50, 192, 124, 132, 200]
+    );
+
+    // 16 bit
+    let pixels = [0_u16, 30000, 40000, 65535];
+
+    let mut image = Image::from_u16(&pixels, 2, 1, ColorSpace::LumaA);
+    Contrast::with_factor(3.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [0, 30000, 54464, 65535]);
+
+    let mut image = Image::from_u16(&pixels, 4, 1, ColorSpace::Luma);
+    Contrast::with_factor(0.5).execute(&mut image).unwrap();
+
+    assert_eq!(
+        image.flatten_frames::<u16>()[0],
+        [16384, 31384, 36384, 49152]
+    );
+}
+
+#[test]
+fn test_gamma()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::gamma::Gamma;
+    use crate::traits::OperationsTrait;
+
+    // gamma 1.0 is a no-op
+    let pixels: Vec<u8> = (0..=255).collect();
+
+    let mut image = Image::from_u8(&pixels, 16, 16, ColorSpace::Luma);
+    Gamma::new(1.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    let pixels: Vec<u16> = (0..=65535).step_by(257).collect();
+
+    let mut image = Image::from_u16(&pixels, 16, 16, ColorSpace::Luma);
+    Gamma::new(1.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], pixels);
+
+    // gamma 2.0 takes the square root, alpha is untouched
+    let pixels = [0_u8, 64, 255, 64];
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::LumaA);
+    Gamma::new(2.0).execute(&mut image).unwrap();
+
+    // 255 * (64/255)^0.5 = 127.75
+    assert_eq!(image.flatten_frames::<u8>()[0], [0, 64, 255, 64]);
+
+    let mut image = Image::from_u8(&pixels, 1, 1, ColorSpace::RGBA);
+    Gamma::new(2.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], [0, 128, 255, 64]);
+
+    let mut image = Image::from_u16(&[16384, 65535], 2, 1, ColorSpace::Luma);
+    Gamma::new(0.5).execute(&mut image).unwrap();
+
+    // 65535 * (16384/65535)^2 = 4096.06
+    assert_eq!(image.flatten_frames::<u16>()[0], [4096, 65535]);
+
+    // invalid values
+    for value in [0.0, -1.0, f32::NAN, f32::INFINITY]
+    {
+        let mut image = Image::from_u8(&[0], 1, 1, ColorSpace::Luma);
+
+        assert!(Gamma::new(value).execute(&mut image).is_err());
+    }
+}
+
+#[test]
+fn test_invert()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::invert::Invert;
+    use crate::traits::OperationsTrait;
+
+    let pixels = [0_u8, 100, 255, 30, 7, 8, 9, 200];
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::RGBA);
+    Invert::new().execute(&mut image).unwrap();
+
+    // alpha is preserved
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [255, 155, 0, 30, 248, 247, 246, 200]
+    );
+
+    Invert::new().execute(&mut image).unwrap();
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    let pixels = [0_u16, 1000, 65535, 40000];
+
+    let mut image = Image::from_u16(&pixels, 2, 1, ColorSpace::LumaA);
+    Invert::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [65535, 1000, 0, 40000]);
+
+    Invert::new().execute(&mut image).unwrap();
+    assert_eq!(image.flatten_frames::<u16>()[0], pixels);
+
+    let pixels = [0.0_f32, 0.25, 1.0];
+
+    let mut image = Image::from_f32(&pixels, 1, 1, ColorSpace::RGB);
+    Invert::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<f32>()[0], [1.0, 0.75, 0.0]);
+
+    Invert::new().execute(&mut image).unwrap();
+    assert_eq!(image.flatten_frames::<f32>()[0], pixels);
+}
+
+#[test]
+fn test_threshold()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::threshold::{Threshold, ThresholdMethod};
+    use crate::traits::OperationsTrait;
+
+    let pixels = [10_u8, 100, 101, 250];
+
+    let expected: [(ThresholdMethod, [u8; 4]); 4] = [
+        (ThresholdMethod::Binary, [0, 0, 255, 255]),
+        (ThresholdMethod::BinaryInv, [255, 255, 0, 0]),
+        (ThresholdMethod::ThreshTrunc, [10, 100, 100, 100]),
+        (ThresholdMethod::ThreshToZero, [0, 0, 101, 250])
+    ];
+
+    for (method, result) in expected
+    {
+        let mut image = Image::from_u8(&pixels, 4, 1, ColorSpace::Luma);
+        Threshold::new(100, method).execute(&mut image).unwrap();
+
+        assert_eq!(image.flatten_frames::<u8>()[0], result, "{method:?}");
+    }
+
+    // 16 bit, alpha is untouched
+    let pixels = [1000_u16, 5, 50000, 7];
+
+    let mut image = Image::from_u16(&pixels, 2, 1, ColorSpace::LumaA);
+    Threshold::new(30000, ThresholdMethod::Binary)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [0, 5, 65535, 7]);
+
+    // color images need to be converted first
+    let pixels = [200_u8, 200, 200, 20, 20, 20];
+
+    let mut image = Image::from_u8(&pixels, 2, 1, ColorSpace::RGB);
+    assert!(Threshold::new(100, ThresholdMethod::Binary)
+        .execute(&mut image)
+        .is_err());
+
+    Threshold::new(100, ThresholdMethod::Binary)
+        .auto_grayscale(true)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+    assert_eq!(image.flatten_frames::<u8>()[0], [255, 0]);
+
+    // otsu picks a value between the two clusters
+    let pixels: Vec<u8> = (0..64)
+        .map(|x| if x % 2 == 0 { 40 + x % 7 } else { 180 + x % 5 })
+        .collect();
+
+    let mut image = Image::from_u8(&pixels, 8, 8, ColorSpace::Luma);
+    Threshold::otsu(ThresholdMethod::Binary)
+        .execute(&mut image)
+        .unwrap();
+
+    let expected: Vec<u8> = (0..64).map(|x| if x % 2 == 0 { 0 } else { 255 }).collect();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], expected);
+}
+
+#[test]
+fn test_box_blur()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::box_blur::BoxBlur;
+    use crate::traits::OperationsTrait;
+
+    // naive box filter, clamping coordinates at the borders
+    fn naive(pixels: &[u32], width: usize, height: usize, radius: usize) -> Vec<u32>
+    {
+        let r = radius as isize;
+        let clamp = |v: isize, size: usize| v.clamp(0, size as isize - 1) as usize;
+
+        let blur_rows: Vec<u32> = (0..width * height)
+            .map(|i| {
+                let (x, y) = ((i % width) as isize, i / width);
+                let sum: u32 = (x - r..=x + r)
+                    .map(|nx| pixels[y * width + clamp(nx, width)])
+                    .sum();
+                (sum + radius as u32) / (2 * radius as u32 + 1)
+            })
+            .collect();
+
+        (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, (i / width) as isize);
+                let sum: u32 = (y - r..=y + r)
+                    .map(|ny| blur_rows[clamp(ny, height) * width + x])
+                    .sum();
+                (sum + radius as u32) / (2 * radius as u32 + 1)
+            })
+            .collect()
+    }
+
+    let (width, height) = (13, 7);
+
+    let pixels: Vec<u32> = (0..width * height)
+        .map(|x| (x * 7919 + (x * x) * 31) as u32 % 256)
+        .collect();
+
+    for radius in [1, 2, 5, 20]
+    {
+        let expected = naive(&pixels, width, height, radius);
+
+        let data: Vec<u8> = pixels.iter().map(|x| *x as u8).collect();
+        let mut image = Image::from_u8(&data, width, height, ColorSpace::Luma);
+
+        BoxBlur::new(radius).execute(&mut image).unwrap();
+
+        let result: Vec<u32> = image.flatten_frames::<u8>()[0]
+            .iter()
+            .map(|x| u32::from(*x))
+            .collect();
+
+        assert_eq!(result, expected, "radius {radius}");
+
+        // 16 bit
+        let wide: Vec<u32> = pixels.iter().map(|x| *x * 257).collect();
+        let data: Vec<u16> = wide.iter().map(|x| *x as u16).collect();
+        let expected = naive(&wide, width, height, radius);
+
+        let mut image = Image::from_u16(&data, width, height, ColorSpace::Luma);
+
+        BoxBlur::new(radius).execute(&mut image).unwrap();
+
+        let result: Vec<u32> = image.flatten_frames::<u16>()[0]
+            .iter()
+            .map(|x| u32::from(*x))
+            .collect();
+
+        assert_eq!(result, expected, "radius {radius}");
+    }
+
+    // a flat image stays flat
+    let mut image = Image::fill(77_u8, ColorSpace::RGB, 9, 5).unwrap();
+    BoxBlur::new(3).execute(&mut image).unwrap();
+
+    assert!(image.flatten_frames::<u8>()[0].iter().all(|x| *x == 77));
+}
+
+#[test]
+fn test_median()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::median::Median;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (17, 11);
+
+    // a smooth gradient with a sprinkling of impulse noise
+    let clean: Vec<u8> = (0..width * height)
+        .map(|i| ((i % width) * 4 + (i / width) * 3) as u8)
+        .collect();
+
+    let mut noisy = clean.clone();
+
+    for (n, i) in (0..noisy.len()).step_by(13).enumerate()
+    {
+        noisy[i] = if n % 2 == 0 { 0 } else { 255 };
+    }
+
+    let mut image = Image::from_u8(&noisy, width, height, ColorSpace::Luma);
+    Median::new(1).execute(&mut image).unwrap();
+
+    let filtered = &image.flatten_frames::<u8>()[0];
+
+    // no impulse survives, and pixels stay close to the clean gradient
+    for (a, b) in filtered.iter().zip(clean.iter())
+    {
+        assert!(a.abs_diff(*b) <= 7, "{a} {b}");
+    }
+
+    // the histogram and sorting paths agree
+    for radius in [0, 1, 2, 6]
+    {
+        let mut image_u8 = Image::from_u8(&noisy, width, height, ColorSpace::Luma);
+        Median::new(radius).execute(&mut image_u8).unwrap();
+
+        let wide: Vec<u16> = noisy.iter().map(|x| u16::from(*x) * 257).collect();
+        let mut image_u16 = Image::from_u16(&wide, width, height, ColorSpace::Luma);
+        Median::new(radius).execute(&mut image_u16).unwrap();
+
+        let expected: Vec<u16> = image_u8.flatten_frames::<u8>()[0]
+            .iter()
+            .map(|x| u16::from(*x) * 257)
+            .collect();
+
+        assert_eq!(image_u16.flatten_frames::<u16>()[0], expected, "radius {radius}");
+    }
+
+    // a radius of zero changes nothing
+    let mut image = Image::from_u8(&noisy, width, height, ColorSpace::Luma);
+    Median::new(0).execute(&mut image).unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], noisy);
+}
+
+#[test]
+fn test_unsharpen()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::unsharpen::Unsharpen;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (32, 8);
+
+    // a vertical step edge, with an alpha channel
+    let pixels: Vec<u8> = (0..width * height)
+        .flat_map(|i| [if i % width < width / 2 { 50 } else { 200 }, 99])
+        .collect();
+
+    // amount 0 is a no-op
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::LumaA);
+    Unsharpen::with_amount(2.0, 0.0, 0)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::LumaA);
+    Unsharpen::with_amount(2.0, 1.5, 0)
+        .execute(&mut image)
+        .unwrap();
+
+    let result = &image.flatten_frames::<u8>()[0];
+
+    for row in result.chunks_exact(width * 2)
+    {
+        let luma: Vec<u8> = row.iter().step_by(2).copied().collect();
+
+        // the dark side gets darker and the bright side brighter next to the edge
+        assert!(luma[width / 2 - 1] < 50, "{luma:?}");
+        assert!(luma[width / 2] > 200, "{luma:?}");
+        // far away from the edge nothing changes
+        assert_eq!(luma[0], 50);
+        assert_eq!(luma[width - 1], 200);
+        // alpha is untouched
+        assert!(row.iter().skip(1).step_by(2).all(|x| *x == 99));
+    }
+
+    // strong sharpening saturates instead of wrapping around
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::LumaA);
+    Unsharpen::with_amount(2.0, 50.0, 0)
+        .execute(&mut image)
+        .unwrap();
+
+    let result = &image.flatten_frames::<u8>()[0];
+
+    assert_eq!(result[(width / 2 - 1) * 2], 0);
+    assert_eq!(result[(width / 2) * 2], 255);
+
+    // a threshold larger than any difference does nothing
+    let wide: Vec<u16> = pixels.iter().map(|x| u16::from(*x) * 257).collect();
+
+    let mut image = Image::from_u16(&wide, width, height, ColorSpace::LumaA);
+    Unsharpen::with_amount(2.0, 1.5, 65535)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], wide);
+}
+
+#[test]
+fn test_convolve()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::convolve::Convolve;
+    use crate::traits::OperationsTrait;
+
+    let (width, height) = (6, 5);
+
+    let pixels: Vec<u8> = (0..width * height * 4)
+        .map(|x| (x * 37 % 256) as u8)
+        .collect();
+
+    // a 3x3 identity kernel is a no-op
+    let identity = vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+
+    let mut image = Image::from_u8(&pixels, width, height, ColorSpace::RGBA);
+    Convolve::new(identity.clone(), 3, 3)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    // a box kernel with clamped borders, 1x3 so only rows matter
+    //
+    // [10, 20, 60] -> [(10+10+20)/3, (10+20+60)/3, (20+60+60)/3]
+    let mut image = Image::from_u8(&[10, 20, 60], 3, 1, ColorSpace::Luma);
+    Convolve::new(vec![1.0; 3], 3, 1)
+        .divisor(3.0)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u8>()[0], [13, 30, 47]);
+
+    // bias and saturation, alpha is untouched
+    let mut image = Image::from_u16(&[100, 7, 65000, 9], 2, 1, ColorSpace::LumaA);
+    Convolve::new(vec![2.0], 1, 1)
+        .bias(-300.0)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [0, 7, 65535, 9]);
+
+    // invalid kernels
+    for convolve in [
+        Convolve::new(identity.clone(), 2, 4),
+        Convolve::new(identity.clone(), 3, 1),
+        Convolve::new(identity, 3, 3).divisor(0.0)
+    ]
+    {
+        let mut image = Image::from_u8(&pixels, width, height, ColorSpace::RGBA);
+
+        assert!(convolve.execute(&mut image).is_err());
+    }
+}
+
+#[test]
+fn test_ycbcr_round_trip()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::colorspace::ColorspaceConv;
+    use crate::impls::ycbcr::{RgbToYCbCr, YCbCrStandard, YCbCrToRgb};
+    use crate::traits::OperationsTrait;
+
+    // known values, black, white and red
+    let pixels = [0_u8, 0, 0, 255, 255, 255, 255, 0, 0];
+
+    let expected: [(YCbCrStandard, [u8; 9]); 3] = [
+        (YCbCrStandard::Jpeg, [0, 128, 128, 255, 128, 128, 76, 85, 255]),
+        (YCbCrStandard::Bt601, [16, 128, 128, 235, 128, 128, 81, 90, 240]),
+        (YCbCrStandard::Bt709, [16, 128, 128, 235, 128, 128, 63, 102, 240])
+    ];
+
+    for (standard, ycbcr) in expected
+    {
+        let mut image = Image::from_u8(&pixels, 3, 1, ColorSpace::RGB);
+        RgbToYCbCr::new(standard).execute(&mut image).unwrap();
+
+        assert_eq!(image.flatten_frames::<u8>()[0], ycbcr, "{standard:?}");
+    }
+
+    // a spread of colors including the extremes
+    let pixels: Vec<u8> = (0..32 * 32 * 3)
+        .map(|x: usize| (x.wrapping_mul(2_654_435_761) >> 7) as u8)
+        .chain([0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 255, 0, 0, 0, 255])
+        .collect();
+    let width = pixels.len() / 3;
+
+    // limited range has fewer levels than 8 bits, so it can't round trip as closely
+    for (standard, tolerance) in [
+        (YCbCrStandard::Jpeg, 1),
+        (YCbCrStandard::Bt601, 2),
+        (YCbCrStandard::Bt709, 2)
+    ]
+    {
+        let mut image = Image::from_u8(&pixels, width, 1, ColorSpace::RGB);
+
+        RgbToYCbCr::new(standard).execute(&mut image).unwrap();
+        assert_eq!(image.get_colorspace(), ColorSpace::YCbCr);
+
+        YCbCrToRgb::new(standard).execute(&mut image).unwrap();
+        assert_eq!(image.get_colorspace(), ColorSpace::RGB);
+
+        for (a, b) in image.flatten_frames::<u8>()[0].iter().zip(pixels.iter())
+        {
+            assert!(a.abs_diff(*b) <= tolerance, "{standard:?} {a} {b}");
+        }
+    }
+
+    // 16 bit, through the generic colorspace conversion
+    let wide: Vec<u16> = pixels.iter().map(|x| u16::from(*x) * 257).collect();
+
+    let mut image = Image::from_u16(&wide, width, 1, ColorSpace::RGB);
+
+    ColorspaceConv::new(ColorSpace::YCbCr)
+        .execute(&mut image)
+        .unwrap();
+    assert_eq!(image.get_colorspace(), ColorSpace::YCbCr);
+
+    ColorspaceConv::new(ColorSpace::RGB)
+        .execute(&mut image)
+        .unwrap();
+
+    for (a, b) in image.flatten_frames::<u16>()[0].iter().zip(wide.iter())
+    {
+        assert!(a.abs_diff(*b) <= 1, "{a} {b}");
+    }
+}
+
+#[test]
+fn test_hsv_hsl_round_trip()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::colorspace::ColorspaceConv;
+    use crate::impls::hsv::{HslToRgb, HsvToRgb, RgbToHsl, RgbToHsv};
+    use crate::traits::OperationsTrait;
+
+    // known values, red, yellow-ish, gray and white
+    let pixels = [255_u8, 0, 0, 255, 255, 0, 128, 128, 128, 255, 255, 255];
+
+    let mut image = Image::from_u8(&pixels, 4, 1, ColorSpace::RGB);
+    RgbToHsv::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::HSV);
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [0, 255, 255, 43, 255, 255, 0, 0, 128, 0, 0, 255]
+    );
+
+    let mut image = Image::from_u8(&pixels, 4, 1, ColorSpace::RGB);
+    RgbToHsl::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::HSL);
+    assert_eq!(
+        image.flatten_frames::<u8>()[0],
+        [0, 255, 128, 43, 255, 128, 0, 0, 128, 0, 0, 255]
+    );
+
+    // a spread of colors
+    let pixels: Vec<u8> = (0..64 * 64 * 3)
+        .map(|x: usize| (x.wrapping_mul(2_654_435_761) >> 7) as u8)
+        .collect();
+
+    // hue is quantized to the sample range, so 8 bit images
+    // can't round trip exactly
+    for colorspace in [ColorSpace::HSV, ColorSpace::HSL]
+    {
+        let mut image = Image::from_u8(&pixels, 64, 64, ColorSpace::RGB);
+
+        ColorspaceConv::new(colorspace)
+            .execute(&mut image)
+            .unwrap();
+        assert_eq!(image.get_colorspace(), colorspace);
+
+        if colorspace == ColorSpace::HSV
+        {
+            HsvToRgb::new().execute(&mut image).unwrap();
+        }
+        else
+        {
+            HslToRgb::new().execute(&mut image).unwrap();
+        }
+        assert_eq!(image.get_colorspace(), ColorSpace::RGB);
+
+        for (a, b) in image.flatten_frames::<u8>()[0].iter().zip(pixels.iter())
+        {
+            assert!(a.abs_diff(*b) <= 2, "{colorspace:?} {a} {b}");
+        }
+
+        // 16 bit
+        let wide: Vec<u16> = pixels.iter().map(|x| u16::from(*x) * 257).collect();
+        let mut image = Image::from_u16(&wide, 64, 64, ColorSpace::RGB);
+
+        ColorspaceConv::new(colorspace)
+            .execute(&mut image)
+            .unwrap();
+        ColorspaceConv::new(ColorSpace::RGB)
+            .execute(&mut image)
+            .unwrap();
+
+        for (a, b) in image.flatten_frames::<u16>()[0].iter().zip(wide.iter())
+        {
+            assert!(a.abs_diff(*b) <= 3, "{colorspace:?} {a} {b}");
+        }
+    }
+}
+
+#[test]
+fn test_split_merge_channels()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::errors::{ImageErrors, ImageOperationsErrors};
+    use crate::image::Image;
+
+    let pixels: Vec<u8> = (0..4 * 3 * 4).map(|x| x as u8).collect();
+    let image = Image::from_u8(&pixels, 4, 3, ColorSpace::RGBA);
+
+    let mut channels = image.split_channels();
+
+    assert_eq!(channels.len(), 4);
+
+    for (i, channel) in channels.iter().enumerate()
+    {
+        assert_eq!(channel.get_colorspace(), ColorSpace::Luma);
+        assert_eq!(channel.get_dimensions(), (4, 3));
+
+        let expected: Vec<u8> = pixels.iter().skip(i).step_by(4).copied().collect();
+        assert_eq!(channel.flatten_frames::<u8>()[0], expected);
+    }
+    // zero the green channel and merge back
+    channels[1] = Image::fill(0_u8, ColorSpace::Luma, 4, 3).unwrap();
+
+    let merged = Image::merge_channels(channels, ColorSpace::RGBA).unwrap();
+
+    assert_eq!(merged.get_colorspace(), ColorSpace::RGBA);
+
+    for (i, (a, b)) in merged.flatten_frames::<u8>()[0]
+        .iter()
+        .zip(pixels.iter())
+        .enumerate()
+    {
+        assert_eq!(*a, if i & 3 == 1 { 0 } else { *b });
+    }
+
+    // 16 bit round trip
+    let wide: Vec<u16> = (0..5 * 2 * 3).map(|x| x * 1000).collect();
+    let image = Image::from_u16(&wide, 5, 2, ColorSpace::RGB);
+    let merged = Image::merge_channels(image.split_channels(), ColorSpace::RGB).unwrap();
+
+    assert_eq!(merged.flatten_frames::<u16>()[0], wide);
+
+    // wrong number of channels
+    let channels = Image::from_u8(&pixels, 4, 3, ColorSpace::RGBA).split_channels();
+    assert!(matches!(
+        Image::merge_channels(channels, ColorSpace::RGB),
+        Err(ImageErrors::OperationsError(
+            ImageOperationsErrors::WrongComponents(3, 4)
+        ))
+    ));
+
+    // mismatched dimensions
+    let mut channels = Image::from_u8(&pixels[..36], 4, 3, ColorSpace::RGB).split_channels();
+    channels[2] = Image::fill(0_u8, ColorSpace::Luma, 2, 3).unwrap();
+    assert!(matches!(
+        Image::merge_channels(channels, ColorSpace::RGB),
+        Err(ImageErrors::DimensionsMisMatch(..))
+    ));
+
+    // inputs must be single channel
+    let channels = vec![
+        Image::fill(0_u8, ColorSpace::Luma, 4, 3).unwrap(),
+        Image::fill(0_u8, ColorSpace::LumaA, 4, 3).unwrap()
+    ];
+    assert!(Image::merge_channels(channels, ColorSpace::LumaA).is_err());
+}
+
+#[test]
+fn test_swap_channels()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::swap_channels::SwapChannels;
+    use crate::traits::OperationsTrait;
+
+    let pixels: Vec<u8> = (0..4 * 4 * 3).map(|x| x as u8).collect();
+    let mut image = Image::from_u8(&pixels, 4, 4, ColorSpace::RGB);
+
+    let plane = |image: &Image, i: usize| {
+        image.get_channels_ref(false)[i]
+            .reinterpret_as::<u8>()
+            .unwrap()
+            .to_vec()
+    };
+    let (red, blue) = (plane(&image, 0), plane(&image, 2));
+
+    SwapChannels::new([2, 1, 0]).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::BGR);
+    assert_eq!(plane(&image, 0), blue);
+    assert_eq!(plane(&image, 2), red);
+
+    for (out, input) in image.flatten_frames::<u8>()[0]
+        .chunks_exact(3)
+        .zip(pixels.chunks_exact(3))
+    {
+        assert_eq!(out, [input[2], input[1], input[0]]);
+    }
+    // swapping back restores the original
+    SwapChannels::new([2, 1, 0]).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::RGB);
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    // other permutations keep the colorspace
+    let wide: Vec<u16> = (0..3 * 2 * 4).map(|x| x * 1000).collect();
+    let mut image = Image::from_u16(&wide, 3, 2, ColorSpace::RGBA);
+
+    SwapChannels::new([3, 0, 1, 2]).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::RGBA);
+
+    for (out, input) in image.flatten_frames::<u16>()[0]
+        .chunks_exact(4)
+        .zip(wide.chunks_exact(4))
+    {
+        assert_eq!(out, [input[3], input[0], input[1], input[2]]);
+    }
+
+    // wrong length and invalid permutations
+    let mut image = Image::from_u8(&pixels, 4, 4, ColorSpace::RGB);
+
+    assert!(SwapChannels::new([1, 0]).execute(&mut image).is_err());
+    assert!(SwapChannels::new([0, 1, 2, 3]).execute(&mut image).is_err());
+    assert!(SwapChannels::new([0, 0, 1]).execute(&mut image).is_err());
+    assert!(SwapChannels::new([0, 1, 3]).execute(&mut image).is_err());
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+}
+
+#[test]
+fn test_add_drop_alpha()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::alpha::{AddAlpha, DropAlpha};
+    use crate::traits::OperationsTrait;
+
+    let pixels: Vec<u8> = (0..5 * 3 * 3).map(|x| x as u8).collect();
+    let mut image = Image::from_u8(&pixels, 5, 3, ColorSpace::RGB);
+
+    AddAlpha::new(1.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::RGBA);
+
+    for (out, input) in image.flatten_frames::<u8>()[0]
+        .chunks_exact(4)
+        .zip(pixels.chunks_exact(3))
+    {
+        assert_eq!(out[..3], *input);
+        assert_eq!(out[3], 255);
+    }
+
+    // already has alpha, nothing changes
+    AddAlpha::new(0.0).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::RGBA);
+    assert!(image.flatten_frames::<u8>()[0]
+        .chunks_exact(4)
+        .all(|x| x[3] == 255));
+
+    DropAlpha::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::RGB);
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    // no alpha, nothing to drop
+    DropAlpha::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::RGB);
+    assert_eq!(image.flatten_frames::<u8>()[0], pixels);
+
+    // luma, 16 bit
+    let wide: Vec<u16> = (0..4 * 4).map(|x| x * 100).collect();
+    let mut image = Image::from_u16(&wide, 4, 4, ColorSpace::Luma);
+
+    AddAlpha::new(0.5).execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::LumaA);
+    assert_eq!(image.flatten_frames::<u16>()[0].len(), wide.len() * 2);
+
+    for (out, input) in image.flatten_frames::<u16>()[0]
+        .chunks_exact(2)
+        .zip(wide.iter())
+    {
+        assert_eq!(out, [*input, 32768]);
+    }
+    DropAlpha::new().execute(&mut image).unwrap();
+
+    assert_eq!(image.get_colorspace(), ColorSpace::Luma);
+    assert_eq!(image.flatten_frames::<u16>()[0], wide);
+
+    // out of range alpha
+    let mut image = Image::from_u8(&pixels, 5, 3, ColorSpace::RGB);
+    assert!(AddAlpha::new(1.5).execute(&mut image).is_err());
+}
+
+#[test]
+fn test_composite()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::composite::{Composite, CompositeMode};
+    use crate::traits::OperationsTrait;
+
+    // half transparent blue over opaque red, partly outside the base
+    let red: Vec<u8> = [255, 0, 0, 255].repeat(16);
+    let mut image = Image::from_u8(&red, 4, 4, ColorSpace::RGBA);
+    let top = Image::from_u8(&[0, 0, 255, 128].repeat(4), 2, 2, ColorSpace::RGBA);
+
+    Composite::new(top, 3, 3, CompositeMode::SourceOver)
+        .execute(&mut image)
+        .unwrap();
+
+    let pixels = &image.flatten_frames::<u8>()[0];
+
+    assert_eq!(pixels[..60], red[..60]);
+    assert_eq!(pixels[60..], [127, 0, 128, 255]);
+
+    // over a transparent base the top color is kept
+    let mut image = Image::fill(0_u8, ColorSpace::LumaA, 3, 3).unwrap();
+    let top = Image::from_u8(&[200, 128], 1, 1, ColorSpace::LumaA);
+
+    Composite::new(top, 1, 1, CompositeMode::SourceOver)
+        .execute(&mut image)
+        .unwrap();
+
+    let pixels = &image.flatten_frames::<u8>()[0];
+
+    assert_eq!(pixels[8..10], [200, 128]);
+    assert!(pixels[..8].iter().chain(&pixels[10..]).all(|x| *x == 0));
+
+    // opaque top without alpha replaces the base
+    let mut image = Image::from_u8(&red, 4, 4, ColorSpace::RGBA);
+    let top = Image::fill(0_u8, ColorSpace::RGB, 4, 4).unwrap();
+
+    Composite::new(top, 0, 0, CompositeMode::SourceOver)
+        .execute(&mut image)
+        .unwrap();
+
+    assert!(image.flatten_frames::<u8>()[0]
+        .chunks_exact(4)
+        .all(|x| x == [0, 0, 0, 255]));
+
+    // multiply
+    let mut image = Image::fill(128_u8, ColorSpace::RGB, 4, 4).unwrap();
+    let top = Image::from_u8(&[255, 128, 0].repeat(4), 2, 2, ColorSpace::RGB);
+
+    Composite::new(top, 1, 1, CompositeMode::Multiply)
+        .execute(&mut image)
+        .unwrap();
+
+    for (i, pixel) in image.flatten_frames::<u8>()[0].chunks_exact(3).enumerate()
+    {
+        let (x, y) = (i % 4, i / 4);
+
+        if (1..3).contains(&x) && (1..3).contains(&y)
+        {
+            assert_eq!(pixel, [128, 64, 0]);
+        }
+        else
+        {
+            assert_eq!(pixel, [128, 128, 128]);
+        }
+    }
+
+    // add saturates, 16 bit
+    let mut image = Image::fill(40000_u16, ColorSpace::Luma, 2, 2).unwrap();
+    let top = Image::fill(30000_u16, ColorSpace::Luma, 1, 2).unwrap();
+
+    Composite::new(top, 0, 0, CompositeMode::Add)
+        .execute(&mut image)
+        .unwrap();
+
+    assert_eq!(image.flatten_frames::<u16>()[0], [65535, 40000, 65535, 40000]);
+
+    // mismatched colorspace and depth
+    let mut image = Image::fill(0_u8, ColorSpace::RGB, 4, 4).unwrap();
+    let luma = Image::fill(0_u8, ColorSpace::Luma, 2, 2).unwrap();
+    let wide = Image::fill(0_u16, ColorSpace::RGB, 2, 2).unwrap();
+
+    assert!(Composite::new(luma, 0, 0, CompositeMode::SourceOver)
+        .execute(&mut image)
+        .is_err());
+    assert!(Composite::new(wide, 0, 0, CompositeMode::SourceOver)
+        .execute(&mut image)
+        .is_err());
+}
+
+#[test]
+fn test_posterize()
+{
+    use std::collections::BTreeSet;
+
+    use zune_core::colorspace::ColorSpace;
+
+    use crate::image::Image;
+    use crate::impls::posterize::Posterize;
+    use crate::traits::OperationsTrait;
+
+    // 8 bit gradient covering every value, with an untouched alpha channel
+    let pixels: Vec<u8> = (0..=255).flat_map(|x| [x, 255 - x, x / 2, x]).collect();
+
+    for levels in [2, 3, 4, 7, 16, 256]
+    {
+        let mut image = Image::from_u8(&pixels, 16, 16, ColorSpace::RGBA);
+
+        Posterize::new(levels).execute(&mut image).unwrap();
+
+        let output = &image.flatten_frames::<u8>()[0];
+
+        for channel in 0..2
+        {
+            let distinct: BTreeSet<u8> = output.iter().skip(channel).step_by(4).copied().collect();
+
+            assert_eq!(distinct.len(), usize::from(levels));
+            assert_eq!(distinct.first(), Some(&0));
+            assert_eq!(distinct.last(), Some(&255));
+        }
+        let alpha: Vec<u8> = output.iter().skip(3).step_by(4).copied().collect();
+        let expected: Vec<u8> = (0..=255).collect();
+
+        assert_eq!(alpha, expected);
+    }
+
+    // 16 bit
+    let wide: Vec<u16> = (0..256 * 256).map(|x| x as u16).collect();
+
+    for levels in [2, 5, 1000]
+    {
+        let mut image = Image::from_u16(&wide, 256, 256, ColorSpace::Luma);
+
+        Posterize::new(levels).execute(&mut image).unwrap();
+
+        let distinct: BTreeSet<u16> = image.flatten_frames::<u16>()[0].iter().copied().collect();
+
+        assert_eq!(distinct.len(), usize::from(levels));
+    }
+
+    let mut image = Image::from_u8(&pixels, 16, 16, ColorSpace::RGBA);
+
+    assert!(Posterize::new(1).execute(&mut image).is_err());
+    assert!(Posterize::new(0).execute(&mut image).is_err());
+}
@@ -0,0 +1,158 @@
+//! Render images as text
+//!
+//! This downsamples an image to a grid of character cells and maps
+//! the luminance of each cell to a character, giving a dependency free
+//! preview that can be printed to a terminal.
+use std::fmt::Write;
+
+use crate::traits::NumOps;
+
+/// Height of a terminal character cell relative to its width
+const CELL_ASPECT: f64 = 2.0;
+
+/// Characters used to render luminance
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum AsciiCharset
+{
+    /// Printable ASCII characters, ` .:-=+*#%@`
+    #[default]
+    Standard,
+    /// A longer ASCII ramp with finer gradations
+    Detailed,
+    /// Unicode shade blocks, ` ░▒▓█`
+    Blocks
+}
+
+impl AsciiCharset
+{
+    /// Return the characters of this charset, ordered from darkest to lightest
+    ///
+    /// Characters are ordered by how much of their cell they cover,
+    /// i.e. as they appear when drawn in a light color on a dark terminal
+    #[must_use]
+    pub const fn ramp(self) -> &'static [char]
+    {
+        match self
+        {
+            AsciiCharset::Standard => &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'],
+            AsciiCharset::Detailed => &[
+                ' ', '.', '\'', '`', '^', '"', ',', ':', ';', 'I', 'l', '!', 'i', '>', '<', '~',
+                '+', '_', '-', '?', ']', '[', '}', '{', '1', ')', '(', '|', '\\', '/', 't', 'f',
+                'j', 'r', 'x', 'n', 'u', 'v', 'c', 'z', 'X', 'Y', 'U', 'J', 'C', 'L', 'Q', '0',
+                'O', 'Z', 'm', 'w', 'q', 'p', 'd', 'b', 'k', 'h', 'a', 'o', '*', '#', 'M', 'W',
+                '&', '8', '%', 'B', '@', '$'
+            ],
+            AsciiCharset::Blocks => &[' ', '░', '▒', '▓', '█']
+        }
+    }
+}
+
+/// Return the number of text rows needed to render an image
+/// `columns` characters wide, preserving its aspect ratio
+///
+/// Terminal cells are about twice as tall as they are wide, so each
+/// row covers twice as many pixels vertically as each column does horizontally
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn ascii_rows(width: usize, height: usize, columns: usize) -> usize
+{
+    if width == 0 || height == 0 || columns == 0
+    {
+        return 0;
+    }
+    let cell_height = width as f64 / columns as f64 * CELL_ASPECT;
+
+    ((height as f64 / cell_height).round() as usize).max(1)
+}
+
+/// Render an image as text
+///
+/// Each character cell covers a block of pixels whose average luminance
+/// selects a character from the charset, black mapping to the first (darkest)
+/// character and white to the last (lightest) character. Rows are separated by newlines.
+///
+/// If `color` is true, each character is preceded by an ANSI 24 bit foreground color
+/// escape with the average color of its cell, and each row ends with a reset escape.
+///
+/// # Arguments
+/// - channels: Either a single luma channel or red, green and blue channels
+/// - width, height: Dimensions of the channels
+/// - columns: Number of characters per row
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+/// - charset: Characters to render luminance with
+/// - color: Whether to emit ANSI color escapes
+///
+/// # Panics
+/// If the number of channels is not 1 or 3
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn ascii_art<T>(
+    channels: &[&[T]], width: usize, height: usize, columns: usize, max_value: u16,
+    charset: AsciiCharset, color: bool
+) -> String
+where
+    T: Copy + NumOps<T>
+{
+    assert!(
+        channels.len() == 1 || channels.len() == 3,
+        "Expected either a luma channel or RGB channels"
+    );
+    let rows = ascii_rows(width, height, columns);
+    let ramp = charset.ramp();
+    let max = f64::from(max_value);
+
+    let mut output = String::new();
+
+    for row in 0..rows
+    {
+        let y_start = (row * height / rows).min(height - 1);
+        let y_end = ((row + 1) * height / rows).max(y_start + 1);
+
+        for column in 0..columns
+        {
+            let x_start = (column * width / columns).min(width - 1);
+            let x_end = ((column + 1) * width / columns).max(x_start + 1);
+
+            let mut sums = [0.0; 3];
+
+            for y in y_start..y_end
+            {
+                let offset = y * width;
+
+                for (sum, channel) in sums.iter_mut().zip(channels)
+                {
+                    *sum += channel[offset + x_start..offset + x_end]
+                        .iter()
+                        .map(|x| x.to_f64())
+                        .sum::<f64>();
+                }
+            }
+            let count = ((y_end - y_start) * (x_end - x_start)) as f64;
+            let [r, g, b] =
+                if channels.len() == 1 { [sums[0] / count; 3] } else { sums.map(|x| x / count) };
+            let luma = ((0.299 * r + 0.587 * g + 0.114 * b) / max).clamp(0.0, 1.0);
+            let character = ramp[(luma * (ramp.len() - 1) as f64).round() as usize];
+
+            if color
+            {
+                let [r, g, b] = [r, g, b].map(|x| (x / max * 255.0).round().clamp(0.0, 255.0));
+
+                let _ = write!(output, "\x1b[38;2;{r};{g};{b}m");
+            }
+            output.push(character);
+        }
+        if color
+        {
+            output.push_str("\x1b[0m");
+        }
+        output.push('\n');
+    }
+    output
+}
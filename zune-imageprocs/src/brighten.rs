@@ -1,11 +1,34 @@
 use crate::traits::NumOps;
 
-#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+/// Add `value` to every sample in `channel`
+///
+/// Positive values brighten, negative values darken, the result
+/// saturates at zero and `max_value`
+///
+/// # Arguments
+/// - channel: Samples to brighten
+/// - value: Signed amount to add to every sample
+/// - max_value: Maximum value of a sample for this bit depth
 pub fn brighten<T: Copy + PartialOrd + NumOps<T> + Ord + Default>(
-    channel: &mut [T], value: T, _max_value: T
+    channel: &mut [T], value: i16, max_value: T
 )
 {
-    channel
-        .iter_mut()
-        .for_each(|x| *x = (*x).saturating_add(value));
+    // clamp the delta to the sample range, so it can be
+    // represented in T without wrapping
+    let delta = T::from_usize(usize::from(value.unsigned_abs()).min(max_value.to_usize()));
+
+    if value >= 0
+    {
+        for x in channel.iter_mut()
+        {
+            *x = (*x).saturating_add(delta).min(max_value);
+        }
+    }
+    else
+    {
+        for x in channel.iter_mut()
+        {
+            *x = (*x).saturating_sub(delta);
+        }
+    }
 }
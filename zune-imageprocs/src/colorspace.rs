@@ -1 +1,3 @@
+pub mod hsv;
 pub mod rgb_to_xyb;
+pub mod ycbcr;
@@ -0,0 +1,166 @@
+//! RGB to HSV and HSL conversions
+//!
+//! Conversions are done in place, math is carried out in `f32`
+//! and every component is scaled to the sample range, i.e for 8 bit
+//! images a hue of 360 degrees, full saturation and full value/lightness
+//! are all stored as 255.
+#![allow(clippy::many_single_char_names)]
+
+use crate::traits::NumOps;
+
+/// Convert RGB samples to HSV in place
+///
+/// After conversion `r` contains H, `g` contains S and `b` contains V
+///
+/// # Arguments
+/// - r,g,b: Channels to convert, should be of equal length
+/// - max_value: Maximum value of a sample for this bit depth
+pub fn rgb_to_hsv<T>(r: &mut [T], g: &mut [T], b: &mut [T], max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    convert(r, g, b, max_value, |red, green, blue| {
+        let (max, min) = (red.max(green).max(blue), red.min(green).min(blue));
+        let delta = max - min;
+
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+        [hue(red, green, blue, max, delta), saturation, max]
+    });
+}
+
+/// Convert HSV samples to RGB in place
+///
+/// After conversion `h` contains R, `s` contains G and `v` contains B
+///
+/// # Arguments
+/// - h,s,v: Channels to convert, should be of equal length
+/// - max_value: Maximum value of a sample for this bit depth
+pub fn hsv_to_rgb<T>(h: &mut [T], s: &mut [T], v: &mut [T], max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    convert(h, s, v, max_value, |hue, saturation, value| {
+        let chroma = value * saturation;
+
+        from_hue(hue, chroma, value - chroma)
+    });
+}
+
+/// Convert RGB samples to HSL in place
+///
+/// After conversion `r` contains H, `g` contains S and `b` contains L
+///
+/// # Arguments
+/// - r,g,b: Channels to convert, should be of equal length
+/// - max_value: Maximum value of a sample for this bit depth
+pub fn rgb_to_hsl<T>(r: &mut [T], g: &mut [T], b: &mut [T], max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    convert(r, g, b, max_value, |red, green, blue| {
+        let (max, min) = (red.max(green).max(blue), red.min(green).min(blue));
+        let delta = max - min;
+
+        let lightness = (max + min) * 0.5;
+        let saturation = if delta == 0.0
+        {
+            0.0
+        }
+        else
+        {
+            delta / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        [hue(red, green, blue, max, delta), saturation, lightness]
+    });
+}
+
+/// Convert HSL samples to RGB in place
+///
+/// After conversion `h` contains R, `s` contains G and `l` contains B
+///
+/// # Arguments
+/// - h,s,l: Channels to convert, should be of equal length
+/// - max_value: Maximum value of a sample for this bit depth
+pub fn hsl_to_rgb<T>(h: &mut [T], s: &mut [T], l: &mut [T], max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    convert(h, s, l, max_value, |hue, saturation, lightness| {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+
+        from_hue(hue, chroma, lightness - chroma * 0.5)
+    });
+}
+
+/// Run `function` on every normalized triple of samples, storing
+/// the result back in the same place
+#[allow(clippy::cast_possible_truncation)]
+fn convert<T, F>(c1: &mut [T], c2: &mut [T], c3: &mut [T], max_value: u16, function: F)
+where
+    T: Copy + NumOps<T>,
+    F: Fn(f32, f32, f32) -> [f32; 3]
+{
+    let max_value = f32::from(max_value);
+
+    let to_sample = |v: f32| T::from_f32((v * max_value).round().clamp(0.0, max_value));
+
+    for ((a, b), c) in c1.iter_mut().zip(c2.iter_mut()).zip(c3.iter_mut())
+    {
+        let [x, y, z] = function(
+            a.to_f64() as f32 / max_value,
+            b.to_f64() as f32 / max_value,
+            c.to_f64() as f32 / max_value
+        );
+
+        *a = to_sample(x);
+        *b = to_sample(y);
+        *c = to_sample(z);
+    }
+}
+
+/// Hue of an RGB color in the range `0..1`
+#[allow(clippy::float_cmp)]
+fn hue(red: f32, green: f32, blue: f32, max: f32, delta: f32) -> f32
+{
+    let sector = if delta == 0.0
+    {
+        0.0
+    }
+    else if max == red
+    {
+        ((green - blue) / delta).rem_euclid(6.0)
+    }
+    else if max == green
+    {
+        (blue - red) / delta + 2.0
+    }
+    else
+    {
+        (red - green) / delta + 4.0
+    };
+
+    sector / 6.0
+}
+
+/// RGB color from a hue in the range `0..1`, chroma and the value
+/// added to every component
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_hue(hue: f32, chroma: f32, offset: f32) -> [f32; 3]
+{
+    let sector = (hue * 6.0).rem_euclid(6.0);
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match sector as u8
+    {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x)
+    };
+
+    [r + offset, g + offset, b + offset]
+}
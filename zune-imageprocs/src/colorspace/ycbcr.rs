@@ -0,0 +1,134 @@
+//! RGB to YCbCr conversions
+//!
+//! Conversions use the [`YCbCrStandard`] coefficients and ranges,
+//! math is carried out in `f32` and results are rounded and clamped
+//! to the sample range.
+//!
+//! For depths other than 8 bits, offsets and ranges are scaled, e.g
+//! limited range 16 bit luma covers `4096..=60160`
+#![allow(clippy::many_single_char_names)]
+
+use crate::traits::NumOps;
+
+/// YCbCr variants supported by the conversions
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum YCbCrStandard
+{
+    /// BT.601 coefficients with full range samples, as used
+    /// by JPEG (JFIF)
+    Jpeg,
+    /// BT.601 coefficients with limited range samples, luma covers
+    /// `16..=235` and chroma `16..=240` for 8 bit images
+    Bt601,
+    /// BT.709 coefficients with limited range samples, luma covers
+    /// `16..=235` and chroma `16..=240` for 8 bit images
+    Bt709
+}
+
+impl YCbCrStandard
+{
+    /// Return the red and blue luma coefficients
+    const fn coefficients(self) -> (f32, f32)
+    {
+        match self
+        {
+            YCbCrStandard::Jpeg | YCbCrStandard::Bt601 => (0.299, 0.114),
+            YCbCrStandard::Bt709 => (0.2126, 0.0722)
+        }
+    }
+    /// Return offset and scale of luma, and offset and scale of chroma
+    /// for samples whose maximum value is `max_value`
+    fn ranges(self, max_value: f32) -> (f32, f32, f32, f32)
+    {
+        // 128 for 8 bit images
+        let midpoint = (max_value + 1.0) * 0.5;
+
+        match self
+        {
+            YCbCrStandard::Jpeg => (0.0, max_value, midpoint, max_value),
+            YCbCrStandard::Bt601 | YCbCrStandard::Bt709 =>
+            {
+                // the 8 bit ranges, scaled to the depth
+                let scale = (max_value + 1.0) / 256.0;
+
+                (16.0 * scale, 219.0 * scale, midpoint, 224.0 * scale)
+            }
+        }
+    }
+}
+
+/// Convert RGB samples to YCbCr in place
+///
+/// After conversion `r` contains Y, `g` contains Cb and `b` contains Cr
+///
+/// # Arguments
+/// - r,g,b: Channels to convert, should be of equal length
+/// - standard: YCbCr variant to convert to
+/// - max_value: Maximum value of a sample for this bit depth
+#[allow(clippy::cast_possible_truncation)]
+pub fn rgb_to_ycbcr<T>(
+    r: &mut [T], g: &mut [T], b: &mut [T], standard: YCbCrStandard, max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    let max_value = f32::from(max_value);
+    let (kr, kb) = standard.coefficients();
+    let kg = 1.0 - kr - kb;
+    let (y_offset, y_scale, c_offset, c_scale) = standard.ranges(max_value);
+
+    let to_sample = |v: f32| T::from_f32(v.round().clamp(0.0, max_value));
+
+    for ((r, g), b) in r.iter_mut().zip(g.iter_mut()).zip(b.iter_mut())
+    {
+        let (red, green, blue) = (
+            r.to_f64() as f32 / max_value,
+            g.to_f64() as f32 / max_value,
+            b.to_f64() as f32 / max_value
+        );
+
+        let luma = kr * red + kg * green + kb * blue;
+        let cb = (blue - luma) / (2.0 * (1.0 - kb));
+        let cr = (red - luma) / (2.0 * (1.0 - kr));
+
+        *r = to_sample(y_offset + y_scale * luma);
+        *g = to_sample(c_offset + c_scale * cb);
+        *b = to_sample(c_offset + c_scale * cr);
+    }
+}
+
+/// Convert YCbCr samples to RGB in place
+///
+/// After conversion `y` contains R, `cb` contains G and `cr` contains B
+///
+/// # Arguments
+/// - y,cb,cr: Channels to convert, should be of equal length
+/// - standard: YCbCr variant to convert from
+/// - max_value: Maximum value of a sample for this bit depth
+#[allow(clippy::cast_possible_truncation)]
+pub fn ycbcr_to_rgb<T>(
+    y: &mut [T], cb: &mut [T], cr: &mut [T], standard: YCbCrStandard, max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    let max_value = f32::from(max_value);
+    let (kr, kb) = standard.coefficients();
+    let kg = 1.0 - kr - kb;
+    let (y_offset, y_scale, c_offset, c_scale) = standard.ranges(max_value);
+
+    let to_sample = |v: f32| T::from_f32((v * max_value).round().clamp(0.0, max_value));
+
+    for ((y, cb), cr) in y.iter_mut().zip(cb.iter_mut()).zip(cr.iter_mut())
+    {
+        let luma = (y.to_f64() as f32 - y_offset) / y_scale;
+        let pb = (cb.to_f64() as f32 - c_offset) / c_scale;
+        let pr = (cr.to_f64() as f32 - c_offset) / c_scale;
+
+        let red = luma + 2.0 * (1.0 - kr) * pr;
+        let blue = luma + 2.0 * (1.0 - kb) * pb;
+        let green = (luma - kr * red - kb * blue) / kg;
+
+        *y = to_sample(red);
+        *cb = to_sample(green);
+        *cr = to_sample(blue);
+    }
+}
@@ -0,0 +1,141 @@
+//! Blend one image onto another
+//!
+//! Colors are blended with non-premultiplied alpha, following the
+//! [W3C compositing model](https://www.w3.org/TR/compositing-1/), i.e
+//! for a blend function `B` the composited color is
+//!
+//! ```text
+//! cs = (1 - ab) * ct + ab * B(cb, ct)
+//! ao = at + ab * (1 - at)
+//! co = (cs * at + cb * ab * (1 - at)) / ao
+//! ```
+//!
+//! where `c` and `a` are the color and alpha of the base (`b`) and
+//! top (`t`) image, normalized to `0..1`.
+//! Channels without alpha are treated as fully opaque.
+use crate::traits::NumOps;
+
+/// How colors of the top image are combined with the base image
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CompositeMode
+{
+    /// Place the top image over the base, `B(cb, ct) = ct`
+    SourceOver,
+    /// Multiply colors, darkening the base, `B(cb, ct) = cb * ct`
+    Multiply,
+    /// Add colors, saturating at white, `B(cb, ct) = min(cb + ct, 1)`
+    Add
+}
+
+impl CompositeMode
+{
+    fn blend(self, base: f32, top: f32) -> f32
+    {
+        match self
+        {
+            Self::SourceOver => top,
+            Self::Multiply => base * top,
+            Self::Add => (base + top).min(1.0)
+        }
+    }
+}
+
+/// Blend a color channel of the top image onto the base image
+///
+/// The top image is placed with its top left corner at `(x, y)`,
+/// parts falling outside the base image are ignored.
+///
+/// This does not modify the base alpha, call [`composite_alpha`]
+/// once every color channel has been blended.
+///
+/// # Arguments
+/// - base: Base channel, modified in place
+/// - base_alpha: Alpha channel of the base, `None` if it is opaque
+/// - base_width: Width of the base image
+/// - top: Channel of the top image
+/// - top_alpha: Alpha channel of the top image, `None` if it is opaque
+/// - top_width: Width of the top image
+/// - x,y: Position of the top image in the base image
+/// - mode: How colors are combined
+/// - max_value: Maximum value of a sample for this bit depth
+#[allow(clippy::too_many_arguments, clippy::cast_possible_truncation)]
+pub fn composite<T>(
+    base: &mut [T], base_alpha: Option<&[T]>, base_width: usize, top: &[T],
+    top_alpha: Option<&[T]>, top_width: usize, x: usize, y: usize, mode: CompositeMode,
+    max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    let max_value = f32::from(max_value);
+    let normalize = |v: T| v.to_f64() as f32 / max_value;
+
+    for (bi, ti) in overlap(base.len(), base_width, top.len(), top_width, x, y)
+    {
+        let at = top_alpha.map_or(1.0, |a| normalize(a[ti]));
+        let ab = base_alpha.map_or(1.0, |a| normalize(a[bi]));
+        let ao = at + ab * (1.0 - at);
+
+        if ao <= 0.0
+        {
+            // both pixels are fully transparent
+            continue;
+        }
+        let (cb, ct) = (normalize(base[bi]), normalize(top[ti]));
+
+        let cs = (1.0 - ab) * ct + ab * mode.blend(cb, ct);
+        let co = (cs * at + cb * ab * (1.0 - at)) / ao;
+
+        base[bi] = T::from_f32((co * max_value).round().clamp(0.0, max_value));
+    }
+}
+
+/// Update the base alpha channel after compositing the top image
+///
+/// # Arguments
+/// - base_alpha: Alpha channel of the base image, modified in place
+/// - base_width: Width of the base image
+/// - top_alpha: Alpha channel of the top image, `None` if it is opaque
+/// - top_width: Width of the top image
+/// - top_height: Height of the top image
+/// - x,y: Position of the top image in the base image
+/// - max_value: Maximum value of a sample for this bit depth
+#[allow(clippy::too_many_arguments, clippy::cast_possible_truncation)]
+pub fn composite_alpha<T>(
+    base_alpha: &mut [T], base_width: usize, top_alpha: Option<&[T]>, top_width: usize,
+    top_height: usize, x: usize, y: usize, max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    let max_value = f32::from(max_value);
+    let normalize = |v: T| v.to_f64() as f32 / max_value;
+
+    let top_len = top_width * top_height;
+
+    for (bi, ti) in overlap(base_alpha.len(), base_width, top_len, top_width, x, y)
+    {
+        let at = top_alpha.map_or(1.0, |a| normalize(a[ti]));
+        let ab = normalize(base_alpha[bi]);
+        let ao = at + ab * (1.0 - at);
+
+        base_alpha[bi] = T::from_f32((ao * max_value).round().clamp(0.0, max_value));
+    }
+}
+
+/// Indices of overlapping pixels in the base and top images
+fn overlap(
+    base_len: usize, base_width: usize, top_len: usize, top_width: usize, x: usize, y: usize
+) -> impl Iterator<Item = (usize, usize)>
+{
+    let base_height = base_len.checked_div(base_width).unwrap_or(0);
+    let top_height = top_len.checked_div(top_width).unwrap_or(0);
+
+    let width = top_width.min(base_width.saturating_sub(x));
+    let height = top_height.min(base_height.saturating_sub(y));
+
+    (0..height).flat_map(move |row| {
+        let base_start = (y + row) * base_width + x;
+        let top_start = row * top_width;
+
+        (0..width).map(move |i| (base_start + i, top_start + i))
+    })
+}
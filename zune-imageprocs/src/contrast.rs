@@ -15,22 +15,63 @@
 //! ```text
 //! R' = F(R-128)+128
 //! ```
+//!
+//! For depths other than 8 bits the midpoint `128` becomes
+//! half of the sample range, i.e `32768` for 16 bit images
+
+/// Convert a contrast level in the range `-255..=255` to the
+/// correlation factor used by [`contrast_u8`] and [`contrast_u16`]
+///
+/// A level of `0` gives a factor of `1`, which leaves the image unchanged
+#[must_use]
+pub fn contrast_factor(contrast: f32) -> f32
+{
+    (259.0 * (contrast + 255.0)) / (255.0 * (259.0 - contrast))
+}
+
+/// Adjust the contrast of an 8 bit image
+///
+/// Each sample is scaled around the midpoint, factors greater than `1`
+/// increase contrast, factors between `0` and `1` reduce it.
+///
+/// See module docs for formula
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+pub fn contrast_u8(channel: &mut [u8], factor: f32)
+{
+    // only 256 possible values, so compute them once
+    let mut lut = [0_u8; 256];
 
-/// Calculate the contrast of an image
+    for (value, entry) in (0..=255_u8).zip(lut.iter_mut())
+    {
+        *entry = scale(f32::from(value), factor, 128.0, 255.0) as u8;
+    }
+    for pix in channel
+    {
+        *pix = lut[usize::from(*pix)];
+    }
+}
+
+/// Adjust the contrast of a 16 bit image
+///
+/// Same as [`contrast_u8`] but for samples in the range `0..=max_value`
 ///
 /// See module docs for formula
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-pub fn contrast_u8(channel: &mut [u8], contrast: f32)
+pub fn contrast_u16(channel: &mut [u16], factor: f32, max_value: u16)
 {
-    // calculate correlation factor
-    // These constants may not work for u16
-    let factor = (259.0 * (contrast + 255.0)) / (255.0 * (259.0 - contrast));
+    let midpoint = f32::from(max_value / 2 + 1);
+    let max_value = f32::from(max_value);
 
     for pix in channel
     {
-        let float_pix = f32::from(*pix);
-        let new_val = ((factor * (float_pix - 128.0)) + 128.0).clamp(0.0, 255.0);
-        // clamp should happen automatically??
-        *pix = new_val as u8;
+        *pix = scale(f32::from(*pix), factor, midpoint, max_value) as u16;
     }
 }
+
+#[inline(always)]
+fn scale(value: f32, factor: f32, midpoint: f32, max_value: f32) -> f32
+{
+    ((factor * (value - midpoint)) + midpoint)
+        .round()
+        .clamp(0.0, max_value)
+}
@@ -1,10 +1,74 @@
-// use crate::traits::NumOps;
-// /// Convolve a matrix
-// pub fn convolve_3x3<T, f32>(in_channel: &[T], out_channel: &mut [T], weights: &[f32])
-// where
-//     T: NumOps<T> + Copy + Default,
-//     f32: std::convert::From<T>
-// {
-//     // storage for an array
-//     let mut temp_array: [T; 9] = [T::default(); 9];
-// }
+//! Two dimensional convolution with an arbitrary kernel
+use crate::traits::NumOps;
+
+/// Convolve a channel with a kernel
+///
+/// Each output pixel is
+///
+/// ```text
+/// out[x,y] = sum(kernel[i,j] * in[x+i-kw/2, y+j-kh/2]) / divisor + bias
+/// ```
+///
+/// where `kw` and `kh` are the kernel dimensions.
+/// Pixels outside the image are treated as copies of the nearest edge pixel,
+/// and results are rounded and saturate at zero and `max_value`.
+///
+/// # Arguments
+/// - in_channel: Input channel
+/// - out_channel: Output channel, same dimensions as the input
+/// - width,height: Channel dimensions
+/// - kernel: Kernel weights in row major order, `kernel_width * kernel_height` long
+/// - kernel_width,kernel_height: Kernel dimensions, should be odd
+/// - divisor: Value to divide the weighted sum by
+/// - bias: Value added after dividing
+/// - max_value: Maximum value of a sample for this bit depth
+///
+/// # Panics
+/// If the kernel length does not match its dimensions
+#[allow(
+    clippy::too_many_arguments,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+pub fn convolve<T>(
+    in_channel: &[T], out_channel: &mut [T], width: usize, height: usize, kernel: &[f32],
+    kernel_width: usize, kernel_height: usize, divisor: f32, bias: f32, max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    assert_eq!(kernel.len(), kernel_width * kernel_height);
+
+    if width == 0 || height == 0
+    {
+        return;
+    }
+    let max_value = f32::from(max_value);
+    let (rx, ry) = ((kernel_width / 2) as isize, (kernel_height / 2) as isize);
+
+    let clamp = |v: isize, size: usize| v.clamp(0, size as isize - 1) as usize;
+
+    for (y, out_row) in out_channel.chunks_exact_mut(width).enumerate().take(height)
+    {
+        for (x, out_px) in out_row.iter_mut().enumerate()
+        {
+            let mut sum = 0.0;
+
+            for (ky, kernel_row) in kernel.chunks_exact(kernel_width).enumerate()
+            {
+                let in_y = clamp(y as isize + ky as isize - ry, height);
+                let in_row = &in_channel[in_y * width..(in_y + 1) * width];
+
+                for (kx, weight) in kernel_row.iter().enumerate()
+                {
+                    let in_x = clamp(x as isize + kx as isize - rx, width);
+
+                    sum += weight * in_row[in_x].to_f64() as f32;
+                }
+            }
+            let value = (sum / divisor + bias).round().clamp(0.0, max_value);
+
+            *out_px = T::from_f32(value);
+        }
+    }
+}
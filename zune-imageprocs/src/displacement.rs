@@ -0,0 +1,61 @@
+//! Displacement mapping
+use crate::traits::NumOps;
+
+/// Move pixels of a channel by per pixel offsets
+///
+/// Each output pixel at `(x,y)` is sampled from the input at
+/// `(x + x_offsets[i], y + y_offsets[i])` using bilinear interpolation,
+/// coordinates outside the image are clamped to the nearest edge.
+///
+/// # Arguments
+/// - in_channel: Input channel
+/// - out_channel: Output channel, must have the same length as the input
+/// - x_offsets, y_offsets: Horizontal and vertical offset for each pixel, in pixels
+/// - width, height: Channel dimensions
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation
+)]
+pub fn displace<T>(
+    in_channel: &[T], out_channel: &mut [T], x_offsets: &[f32], y_offsets: &[f32], width: usize,
+    height: usize
+) where
+    T: Copy + NumOps<T>
+{
+    if width == 0 || height == 0
+    {
+        return;
+    }
+    let max_x = (width - 1) as f32;
+    let max_y = (height - 1) as f32;
+
+    let sample = |x: usize, y: usize| -> f32 { in_channel[y * width + x].to_f64() as f32 };
+
+    for (y, ((out_row, x_row), y_row)) in out_channel
+        .chunks_exact_mut(width)
+        .zip(x_offsets.chunks_exact(width))
+        .zip(y_offsets.chunks_exact(width))
+        .take(height)
+        .enumerate()
+    {
+        for (x, ((out_px, dx), dy)) in out_row.iter_mut().zip(x_row).zip(y_row).enumerate()
+        {
+            let src_x = (x as f32 + dx).clamp(0.0, max_x);
+            let src_y = (y as f32 + dy).clamp(0.0, max_y);
+
+            let x0 = src_x.floor() as usize;
+            let y0 = src_y.floor() as usize;
+            let x1 = (x0 + 1).min(width - 1);
+            let y1 = (y0 + 1).min(height - 1);
+
+            let fx = src_x - x0 as f32;
+            let fy = src_y - y0 as f32;
+
+            let top = sample(x0, y0) * (1.0 - fx) + sample(x1, y0) * fx;
+            let bottom = sample(x0, y1) * (1.0 - fx) + sample(x1, y1) * fx;
+
+            *out_px = T::from_f32((top * (1.0 - fy) + bottom * fy).round());
+        }
+    }
+}
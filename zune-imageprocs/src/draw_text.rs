@@ -0,0 +1,174 @@
+//! Draw text using a built in bitmap font
+//!
+//! The font covers printable ASCII, with each glyph 5 pixels wide and 7 pixels
+//! tall, which is enough for labels, timestamps and debug overlays without
+//! depending on a font renderer.
+use crate::traits::NumOps;
+
+/// Width of a glyph in pixels
+pub const GLYPH_WIDTH: usize = 5;
+/// Height of a glyph in pixels
+pub const GLYPH_HEIGHT: usize = 7;
+/// Horizontal distance between the start of consecutive glyphs
+pub const GLYPH_ADVANCE: usize = GLYPH_WIDTH + 1;
+/// Vertical distance between the start of consecutive lines
+pub const LINE_ADVANCE: usize = GLYPH_HEIGHT + 1;
+
+/// Glyphs for ASCII 0x20 (space) to 0x7E (`~`)
+///
+/// Each glyph is 5 columns, the least significant bit of each column is the top row
+#[rustfmt::skip]
+const FONT: [[u8; GLYPH_WIDTH]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5F, 0x00, 0x00], // ' ' '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], [0x14, 0x7F, 0x14, 0x7F, 0x14], // '"' '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62], // '$' '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], [0x00, 0x05, 0x03, 0x00, 0x00], // '&' '''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], [0x00, 0x41, 0x22, 0x1C, 0x00], // '(' ')'
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], [0x08, 0x08, 0x3E, 0x08, 0x08], // '*' '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], // ',' '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], [0x20, 0x10, 0x08, 0x04, 0x02], // '.' '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], [0x00, 0x42, 0x7F, 0x40, 0x00], // '0' '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], [0x21, 0x41, 0x45, 0x4B, 0x31], // '2' '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], [0x27, 0x45, 0x45, 0x45, 0x39], // '4' '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], [0x01, 0x71, 0x09, 0x05, 0x03], // '6' '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], [0x06, 0x49, 0x49, 0x29, 0x1E], // '8' '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], [0x00, 0x56, 0x36, 0x00, 0x00], // ':' ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], [0x14, 0x14, 0x14, 0x14, 0x14], // '<' '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x51, 0x09, 0x06], // '>' '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], [0x7E, 0x11, 0x11, 0x11, 0x7E], // '@' 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], [0x3E, 0x41, 0x41, 0x41, 0x22], // 'B' 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], [0x7F, 0x49, 0x49, 0x49, 0x41], // 'D' 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'F' 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], [0x00, 0x41, 0x7F, 0x41, 0x00], // 'H' 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], [0x7F, 0x08, 0x14, 0x22, 0x41], // 'J' 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'L' 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'N' 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'P' 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], [0x46, 0x49, 0x49, 0x49, 0x31], // 'R' 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'T' 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'V' 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], [0x07, 0x08, 0x70, 0x08, 0x07], // 'X' 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], [0x00, 0x7F, 0x41, 0x41, 0x00], // 'Z' '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], [0x00, 0x41, 0x41, 0x7F, 0x00], // '\' ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], [0x40, 0x40, 0x40, 0x40, 0x40], // '^' '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], [0x20, 0x54, 0x54, 0x54, 0x78], // '`' 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x20], // 'b' 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], [0x38, 0x54, 0x54, 0x54, 0x18], // 'd' 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'f' 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7D, 0x40, 0x00], // 'h' 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], [0x7F, 0x10, 0x28, 0x44, 0x00], // 'j' 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], [0x7C, 0x04, 0x18, 0x04, 0x78], // 'l' 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38], // 'n' 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], [0x08, 0x14, 0x14, 0x18, 0x7C], // 'p' 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x20], // 'r' 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], [0x3C, 0x40, 0x40, 0x20, 0x7C], // 't' 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'v' 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'x' 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00], // 'z' '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], [0x00, 0x41, 0x36, 0x08, 0x00], // '|' '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08]                                  // '~'
+];
+
+/// Return the glyph of a character
+///
+/// Characters outside printable ASCII are drawn as `?`
+#[must_use]
+pub fn glyph(character: char) -> [u8; GLYPH_WIDTH]
+{
+    let index = match character
+    {
+        ' '..='~' => character as usize - 0x20,
+        _ => usize::from(b'?' - 0x20)
+    };
+    FONT[index]
+}
+
+/// Return true if the pixel at column `x` and row `y` of a glyph is set
+#[must_use]
+pub fn glyph_pixel(character: char, x: usize, y: usize) -> bool
+{
+    x < GLYPH_WIDTH && y < GLYPH_HEIGHT && (glyph(character)[x] >> y) & 1 == 1
+}
+
+/// Return the width and height in pixels of text drawn with [`draw_text`]
+///
+/// Lines are separated by `\n`, the width is that of the longest line
+#[must_use]
+pub fn text_dimensions(text: &str, scale: usize) -> (usize, usize)
+{
+    let lines = text.split('\n');
+    let columns = lines.clone().map(|x| x.chars().count()).max().unwrap_or(0);
+    let rows = lines.count();
+
+    (
+        (columns * GLYPH_ADVANCE).saturating_sub(1) * scale,
+        (rows * LINE_ADVANCE).saturating_sub(1) * scale
+    )
+}
+
+/// Draw text onto a channel
+///
+/// Each set pixel of the font is drawn as a `scale x scale` square, blending
+/// `value` with the channel by `opacity`. Lines are separated by `\n`,
+/// and text falling outside the channel is clipped.
+///
+/// # Arguments
+/// - channel: Channel to draw on
+/// - width, height: Channel dimensions
+/// - text: Text to draw, characters outside printable ASCII are drawn as `?`
+/// - x, y: Position of the top left corner of the text
+/// - scale: Size of each font pixel, a scale of 1 gives 5x7 pixel glyphs
+/// - value: Value to draw the text with
+/// - opacity: Opacity of the text, between 0.0 (invisible) and 1.0 (opaque)
+#[allow(
+    clippy::too_many_arguments,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn draw_text<T>(
+    channel: &mut [T], width: usize, height: usize, text: &str, x: usize, y: usize, scale: usize,
+    value: u16, opacity: f32
+) where
+    T: Copy + NumOps<T>
+{
+    let opacity = f64::from(opacity.clamp(0.0, 1.0));
+    let value = f64::from(value);
+
+    for (line_number, line) in text.split('\n').enumerate()
+    {
+        let line_y = y + line_number * LINE_ADVANCE * scale;
+
+        for (column, character) in line.chars().enumerate()
+        {
+            let glyph_x = x + column * GLYPH_ADVANCE * scale;
+
+            if glyph_x >= width || line_y >= height
+            {
+                break;
+            }
+            for gy in 0..GLYPH_HEIGHT
+            {
+                for gx in 0..GLYPH_WIDTH
+                {
+                    if !glyph_pixel(character, gx, gy)
+                    {
+                        continue;
+                    }
+                    let start_x = glyph_x + gx * scale;
+                    let start_y = line_y + gy * scale;
+
+                    for py in start_y..(start_y + scale).min(height)
+                    {
+                        for px in start_x..(start_x + scale).min(width)
+                        {
+                            let pixel = &mut channel[py * width + px];
+                            let current = pixel.to_f64();
+
+                            *pixel = T::from_f64((current + (value - current) * opacity).round());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
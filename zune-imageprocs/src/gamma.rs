@@ -1,5 +1,15 @@
 use crate::traits::NumOps;
 
+/// Gamma correct an image
+///
+/// Each sample is mapped to `max * (sample / max) ^ (1 / value)`,
+/// so values greater than `1` brighten the image and values
+/// between `0` and `1` darken it
+///
+/// # Arguments
+/// - pixels: Samples to gamma correct
+/// - value: Gamma value, should be greater than zero
+/// - max_value: Maximum value of a sample for this bit depth
 #[allow(
     clippy::cast_sign_loss,
     clippy::cast_possible_truncation,
@@ -20,6 +30,7 @@ where
     let max_usize = usize::from(max_value);
     let max_value = max_value as f32;
     let value_inv = 1.0 / max_value;
+    let exponent = 1.0 / value;
     // optimizer hint to remove bounds check, these values should be
     // powers of two, currently we support 255 and 65535
     assert!(lut.len().is_power_of_two());
@@ -28,12 +39,9 @@ where
     for x in 0..=max_usize
     {
         let pixel_f32 = (x as f32) * value_inv;
-        let mut new_pix_val = max_value * pixel_f32.powf(value);
-
-        if new_pix_val > max_value
-        {
-            new_pix_val = max_value;
-        }
+        let new_pix_val = (max_value * pixel_f32.powf(exponent))
+            .round()
+            .clamp(0.0, max_value);
 
         lut[x & lut_mask] = T::from_f32(new_pix_val);
     }
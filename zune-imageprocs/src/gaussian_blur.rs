@@ -6,7 +6,9 @@
 //!
 //! For the math behind it see <https://blog.ivank.net/fastest-gaussian-blur.html>
 
-/// Create different box radius for each gaussian kernel function.
+/// Create different box widths for each gaussian kernel function.
+///
+/// Widths are always odd, a width of `w` corresponds to a box radius of `w / 2`
 #[allow(
     clippy::cast_possible_truncation,
     clippy::cast_sign_loss,
@@ -75,8 +77,15 @@ pub fn gaussian_blur_u16(
 
     for blur_radius in blur_radii
     {
-        // approximate gaussian blur using multiple box blurs
-        crate::box_blur::box_blur_u16(in_out_image, scratch_space, width, height, blur_radius);
+        // approximate gaussian blur using multiple box blurs,
+        // the box sizes are odd widths so convert them to radii
+        crate::box_blur::box_blur_u16(
+            in_out_image,
+            scratch_space,
+            width,
+            height,
+            blur_radius / 2
+        );
     }
 }
 
@@ -98,7 +107,14 @@ pub fn gaussian_blur_u8(
 
     for blur_radius in blur_radii
     {
-        // approximate gaussian blur using multiple box blurs
-        crate::box_blur::box_blur_u8(in_out_image, scratch_space, width, height, blur_radius);
+        // approximate gaussian blur using multiple box blurs,
+        // the box sizes are odd widths so convert them to radii
+        crate::box_blur::box_blur_u8(
+            in_out_image,
+            scratch_space,
+            width,
+            height,
+            blur_radius / 2
+        );
     }
 }
@@ -1,3 +1,7 @@
+//! RGB to grayscale conversion
+//!
+//! Grayscale values are a weighted sum of the red, green and blue channels,
+//! computed with fixed point integer math.
 use crate::grayscale::scalar::{
     convert_rgb_to_grayscale_scalar, convert_rgb_to_grayscale_scalar_u16
 };
@@ -6,14 +10,29 @@ mod avx2;
 mod scalar;
 mod sse41;
 
-pub fn rgb_to_grayscale_u16(r: &[u16], g: &[u16], b: &[u16], out: &mut [u16], max_value: u16)
+/// Convert 16 bit RGB channels to grayscale
+///
+/// `coefficients` are the weights of the red, green and blue channels,
+/// they should be non-negative and sum to 1.0
+pub fn rgb_to_grayscale_u16(
+    r: &[u16], g: &[u16], b: &[u16], out: &mut [u16], max_value: u16, coefficients: [f32; 3]
+)
 {
-    convert_rgb_to_grayscale_scalar_u16(r, g, b, out, max_value);
+    convert_rgb_to_grayscale_scalar_u16(r, g, b, out, max_value, coefficients);
 }
 
-pub fn rgb_to_grayscale_u8(r: &[u8], g: &[u8], b: &[u8], out: &mut [u8], max_value: u8)
+/// Convert 8 bit RGB channels to grayscale
+///
+/// `coefficients` are the weights of the red, green and blue channels,
+/// they should be non-negative and sum to 1.0
+pub fn rgb_to_grayscale_u8(
+    r: &[u8], g: &[u8], b: &[u8], out: &mut [u8], max_value: u8, coefficients: [f32; 3]
+)
 {
+    // the simd kernels store coefficients as signed 16 bit fixed point numbers,
+    // which can't represent a weight of 1.0
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    if coefficients.iter().all(|x| *x * 32768.0 + 0.5 < 32768.0)
     {
         #[cfg(feature = "avx2")]
         {
@@ -22,7 +41,7 @@ pub fn rgb_to_grayscale_u8(r: &[u8], g: &[u8], b: &[u8], out: &mut [u8], max_val
             if is_x86_feature_detected!("avx2")
             {
                 unsafe {
-                    return convert_rgb_to_grayscale_u8_avx2(r, g, b, out);
+                    return convert_rgb_to_grayscale_u8_avx2(r, g, b, out, coefficients);
                 }
             }
         }
@@ -34,12 +53,12 @@ pub fn rgb_to_grayscale_u8(r: &[u8], g: &[u8], b: &[u8], out: &mut [u8], max_val
             if is_x86_feature_detected!("sse4.1")
             {
                 unsafe {
-                    return convert_rgb_to_grayscale_u8_sse41(r, g, b, out);
+                    return convert_rgb_to_grayscale_u8_sse41(r, g, b, out, coefficients);
                 }
             }
         }
     }
-    convert_rgb_to_grayscale_scalar(r, g, b, out, max_value);
+    convert_rgb_to_grayscale_scalar(r, g, b, out, max_value, coefficients);
 }
 
 #[cfg(all(feature = "benchmarks"))]
@@ -64,7 +83,7 @@ mod benchmarks
         let mut c4 = vec![255; dimensions];
         b.iter(|| {
             unsafe {
-                convert_rgb_to_grayscale_u8_sse41(&c1, &c2, &c3, &mut c4);
+                convert_rgb_to_grayscale_u8_sse41(&c1, &c2, &c3, &mut c4, [0.299, 0.587, 0.114]);
             };
         });
     }
@@ -104,7 +123,7 @@ mod benchmarks
 
         let mut c4 = vec![255; dimensions];
         b.iter(|| {
-            convert_rgb_to_grayscale_scalar(&c1, &c2, &c3, &mut c4, 255);
+            convert_rgb_to_grayscale_scalar(&c1, &c2, &c3, &mut c4, 255, [0.299, 0.587, 0.114]);
         });
     }
 
@@ -127,3 +146,50 @@ mod benchmarks
         });
     }
 }
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn test_grayscale_simd_scalar_identical()
+{
+    use nanorand::Rng;
+
+    let mut rng = nanorand::WyRand::new();
+
+    // not a multiple of any simd width, so remainders are handled too
+    let size = 1000 + 13;
+
+    let mut channels = [vec![0_u8; size], vec![0_u8; size], vec![0_u8; size]];
+
+    for channel in &mut channels
+    {
+        rng.fill(channel);
+    }
+    let [r, g, b] = &channels;
+
+    for coefficients in [[0.299, 0.587, 0.114], [0.2126, 0.7152, 0.0722]]
+    {
+        let mut scalar_out = vec![0; size];
+        scalar::convert_rgb_to_grayscale_scalar(r, g, b, &mut scalar_out, 255, coefficients);
+
+        #[cfg(feature = "sse41")]
+        if is_x86_feature_detected!("sse4.1")
+        {
+            let mut sse_out = vec![0; size];
+
+            unsafe {
+                sse41::convert_rgb_to_grayscale_u8_sse41(r, g, b, &mut sse_out, coefficients);
+            }
+            assert_eq!(scalar_out, sse_out);
+        }
+        #[cfg(feature = "avx2")]
+        if is_x86_feature_detected!("avx2")
+        {
+            let mut avx_out = vec![0; size];
+
+            unsafe {
+                avx2::convert_rgb_to_grayscale_u8_avx2(r, g, b, &mut avx_out, coefficients);
+            }
+            assert_eq!(scalar_out, avx_out);
+        }
+    }
+}
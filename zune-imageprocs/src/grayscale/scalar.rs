@@ -2,16 +2,14 @@ use crate::traits::NumOps;
 
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
 pub(crate) fn convert_rgb_to_grayscale_scalar<T>(
-    r: &[T], g: &[T], b: &[T], gr: &mut [T], max_value: T
+    r: &[T], g: &[T], b: &[T], gr: &mut [T], max_value: T, coefficients: [f32; 3]
 ) where
     T: Copy + NumOps<T>,
     u32: From<T>
 {
     let max_value = u32::from(max_value);
 
-    let r_coef = (0.2989 * 32768.0 + 0.5) as u32;
-    let g_coef = (0.5870 * 32768.0 + 0.5) as u32;
-    let b_coef = (0.1140 * 32768.0 + 0.5) as u32;
+    let [r_coef, g_coef, b_coef] = coefficients.map(|x| (x * 32768.0 + 0.5) as u32);
 
     for (((r_v, g_v), b_v), g_out) in r.iter().zip(g.iter()).zip(b.iter()).zip(gr.iter_mut())
     {
@@ -40,16 +38,15 @@ pub(crate) fn convert_rgb_to_grayscale_scalar<T>(
     clippy::unreadable_literal
 )]
 pub(crate) fn convert_rgb_to_grayscale_scalar_u16<T>(
-    r: &[T], g: &[T], b: &[T], gr: &mut [T], max_value: T
+    r: &[T], g: &[T], b: &[T], gr: &mut [T], max_value: T, coefficients: [f32; 3]
 ) where
     T: Copy + NumOps<T>,
     u64: From<T>
 {
     let max_value = u64::from(max_value);
 
-    let r_coef = (0.2989 * 2147483648.0 + 0.5) as u64;
-    let g_coef = (0.5870 * 2147483648.0 + 0.5) as u64;
-    let b_coef = (0.1140 * 2147483648.0 + 0.5) as u64;
+    let [r_coef, g_coef, b_coef] =
+        coefficients.map(|x| (f64::from(x) * 2147483648.0 + 0.5) as u64);
 
     for (((r_v, g_v), b_v), g_out) in r.iter().zip(g.iter()).zip(b.iter()).zip(gr.iter_mut())
     {
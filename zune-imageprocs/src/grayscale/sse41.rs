@@ -14,19 +14,23 @@ use crate::grayscale::scalar::convert_rgb_to_grayscale_scalar;
     clippy::cast_possible_truncation,
     clippy::cast_possible_wrap
 )]
-pub(crate) unsafe fn convert_rgb_to_grayscale_u8_sse41(r: &[u8], g: &[u8], b: &[u8], gr: &mut [u8])
+pub(crate) unsafe fn convert_rgb_to_grayscale_u8_sse41(
+    r: &[u8], g: &[u8], b: &[u8], gr: &mut [u8], coefficients: [f32; 3]
+)
 {
     // Code is from https://stackoverflow.com/questions/57832444/efficient-c-code-no-libs-for-image-transformation-into-custom-rgb-pixel-grey
     const CHUNK_SIZE: usize = 8;
     // Each coefficient is expanded by 2^15, and rounded to int16 (add 0.5 for rounding).
-    let r_coef = _mm_set1_epi16((0.2989 * 32768.0 + 0.5) as i16); //8 coefficients - R scale factor.
-    let g_coef = _mm_set1_epi16((0.5870 * 32768.0 + 0.5) as i16); //8 coefficients - G scale factor.
-    let b_coef = _mm_set1_epi16((0.1140 * 32768.0 + 0.5) as i16); //8 coefficients - B scale factor.
+    let [r_coef, g_coef, b_coef] = coefficients.map(|x| (x * 32768.0 + 0.5) as i16);
+
+    let r_coef = _mm_set1_epi16(r_coef); //coefficients - R scale factor.
+    let g_coef = _mm_set1_epi16(g_coef); //coefficients - G scale factor.
+    let b_coef = _mm_set1_epi16(b_coef); //coefficients - B scale factor.
 
     for (((r_chunk, g_chunk), b_chunk), out) in r
         .chunks_exact(CHUNK_SIZE)
-        .zip(b.chunks_exact(CHUNK_SIZE))
         .zip(g.chunks_exact(CHUNK_SIZE))
+        .zip(b.chunks_exact(CHUNK_SIZE))
         .zip(gr.chunks_exact_mut(CHUNK_SIZE))
     {
         // PS I'm not sure if this is valid, we chunked 8 values
@@ -67,12 +71,11 @@ pub(crate) unsafe fn convert_rgb_to_grayscale_u8_sse41(r: &[u8], g: &[u8], b: &[
         // assume r ,g and b are equal lengths.
         let rem = r.len() % CHUNK_SIZE;
         let start = r.len() - rem;
-        let c_start = r.len() - (rem / 3);
 
-        let c1 = &r[c_start..];
-        let c2 = &g[c_start..];
-        let c3 = &b[c_start..];
+        let c1 = &r[start..];
+        let c2 = &g[start..];
+        let c3 = &b[start..];
 
-        convert_rgb_to_grayscale_scalar(c1, c2, c3, &mut gr[start..], 255);
+        convert_rgb_to_grayscale_scalar(c1, c2, c3, &mut gr[start..], 255, coefficients);
     }
 }
@@ -0,0 +1,105 @@
+//! Guided image filtering
+//!
+//! An edge preserving filter that smooths an image using the structure
+//! of a second guidance image, see He et al, "Guided Image Filtering"
+use crate::integral_image::{integral_image_f32, rectangle_sum};
+use crate::traits::NumOps;
+
+/// Filter a channel using the edge structure of a guide
+///
+/// The output is locally a linear transform of the guide, so edges present
+/// in the guide are preserved in the output while flat regions of the guide are smoothed.
+///
+/// # Arguments
+/// - channel: Channel to filter, modified in place
+/// - guide: Guidance channel with the same dimensions as `channel`, normalized to `0.0..=1.0`
+/// - width, height: Channel dimensions
+/// - radius: Radius of the square window used for the local statistics
+/// - epsilon: Regularization, larger values give smoother output. Since samples
+///   are normalized to `0.0..=1.0`, edges with a variance much larger than epsilon are preserved
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn guided_filter<T>(
+    channel: &mut [T], guide: &[f32], width: usize, height: usize, radius: usize, epsilon: f32,
+    max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    let size = width * height;
+
+    if size == 0
+    {
+        return;
+    }
+    let max = f32::from(max_value);
+
+    let input: Vec<f32> = channel[..size]
+        .iter()
+        .map(|x| x.to_f64() as f32 / max)
+        .collect();
+
+    let guide = &guide[..size];
+
+    let guide_sq: Vec<f32> = guide.iter().map(|x| x * x).collect();
+    let guide_input: Vec<f32> = guide.iter().zip(&input).map(|(i, p)| i * p).collect();
+
+    let mean_guide = box_mean(guide, width, height, radius);
+    let mean_input = box_mean(&input, width, height, radius);
+    let corr_guide = box_mean(&guide_sq, width, height, radius);
+    let corr_guide_input = box_mean(&guide_input, width, height, radius);
+
+    let mut a = vec![0.0; size];
+    let mut b = vec![0.0; size];
+
+    for i in 0..size
+    {
+        let variance = corr_guide[i] - mean_guide[i] * mean_guide[i];
+        let covariance = corr_guide_input[i] - mean_guide[i] * mean_input[i];
+
+        a[i] = covariance / (variance + epsilon);
+        b[i] = mean_input[i] - a[i] * mean_guide[i];
+    }
+    let mean_a = box_mean(&a, width, height, radius);
+    let mean_b = box_mean(&b, width, height, radius);
+
+    for (i, px) in channel[..size].iter_mut().enumerate()
+    {
+        let value = (mean_a[i] * guide[i] + mean_b[i]).clamp(0.0, 1.0);
+
+        *px = T::from_f32((value * max).round());
+    }
+}
+
+/// Average of each pixel's `(2*radius+1)^2` neighbourhood, computed
+/// in constant time per pixel using an integral image
+///
+/// Windows are clipped at the image edges and averaged over the pixels they cover
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(crate) fn box_mean(data: &[f32], width: usize, height: usize, radius: usize) -> Vec<f32>
+{
+    let table = integral_image_f32(data, width, height);
+
+    let mut out = vec![0.0; width * height];
+
+    for y in 0..height
+    {
+        let y0 = y.saturating_sub(radius);
+        let y1 = (y + radius + 1).min(height);
+
+        for x in 0..width
+        {
+            let x0 = x.saturating_sub(radius);
+            let x1 = (x + radius + 1).min(width);
+
+            let sum = rectangle_sum(&table, width, x0, y0, x1 - x0, y1 - y0);
+            let count = ((y1 - y0) * (x1 - x0)) as f64;
+
+            out[y * width + x] = (sum / count) as f32;
+        }
+    }
+    out
+}
@@ -0,0 +1,135 @@
+//! Halftone (screen print) effect
+use crate::traits::NumOps;
+
+/// Shape of the dots in a halftone screen
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DotShape
+{
+    /// Round dots
+    Circle,
+    /// Square dots
+    Square,
+    /// Lines parallel to the screen, whose thickness varies
+    Line
+}
+
+/// Replace a channel with a halftone pattern
+///
+/// The channel is divided into square cells of `dot_size` pixels along
+/// a grid rotated by `angle` degrees, and each cell is replaced by a dot whose
+/// area is proportional to the darkness of the cell, pixels inside a dot become `0`
+/// and the rest become `max_value`.
+///
+/// # Arguments
+/// - channel: Channel to modify in place
+/// - width, height: Channel dimensions
+/// - dot_size: Size of each cell of the screen, in pixels. A value of 0 leaves the channel untouched
+/// - angle: Angle of the screen, in degrees
+/// - shape: Shape of the dots
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn halftone<T>(
+    channel: &mut [T], width: usize, height: usize, dot_size: usize, angle: f32, shape: DotShape,
+    max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    if dot_size == 0 || width == 0 || height == 0
+    {
+        return;
+    }
+    let size = dot_size as f32;
+    let (sin, cos) = angle.to_radians().sin_cos();
+
+    // position of a pixel on the rotated screen, in cells
+    let to_screen = |x: usize, y: usize| -> (f32, f32) {
+        let (x, y) = (x as f32, y as f32);
+
+        ((x * cos + y * sin) / size, (y * cos - x * sin) / size)
+    };
+    // range of cells covered by the image, found from its corners
+    let corners = [
+        to_screen(0, 0),
+        to_screen(width - 1, 0),
+        to_screen(0, height - 1),
+        to_screen(width - 1, height - 1)
+    ];
+    let min_u = corners.iter().map(|c| c.0.floor()).fold(f32::MAX, f32::min);
+    let min_v = corners.iter().map(|c| c.1.floor()).fold(f32::MAX, f32::min);
+    let max_u = corners.iter().map(|c| c.0.floor()).fold(f32::MIN, f32::max);
+    let max_v = corners.iter().map(|c| c.1.floor()).fold(f32::MIN, f32::max);
+
+    let cells_u = (max_u - min_u) as usize + 1;
+    let cells_v = (max_v - min_v) as usize + 1;
+
+    let cell_index = |u: f32, v: f32| -> usize {
+        let cu = ((u.floor() - min_u) as usize).min(cells_u - 1);
+        let cv = ((v.floor() - min_v) as usize).min(cells_v - 1);
+
+        cv * cells_u + cu
+    };
+
+    // average intensity of each cell
+    let mut sums = vec![0.0_f64; cells_u * cells_v];
+    let mut counts = vec![0_u32; cells_u * cells_v];
+
+    for (y, row) in channel.chunks_exact(width).take(height).enumerate()
+    {
+        for (x, px) in row.iter().enumerate()
+        {
+            let (u, v) = to_screen(x, y);
+            let index = cell_index(u, v);
+
+            sums[index] += px.to_f64();
+            counts[index] += 1;
+        }
+    }
+    let max = f64::from(max_value);
+
+    // fraction of each cell covered by ink
+    let coverage: Vec<f32> = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, count)| {
+            if *count == 0
+            {
+                0.0
+            }
+            else
+            {
+                (1.0 - sum / (f64::from(*count) * max)).clamp(0.0, 1.0) as f32
+            }
+        })
+        .collect();
+
+    let ink = T::from_u32(0);
+    let paper = T::from_u32(u32::from(max_value));
+
+    for (y, row) in channel.chunks_exact_mut(width).take(height).enumerate()
+    {
+        for (x, px) in row.iter_mut().enumerate()
+        {
+            let (u, v) = to_screen(x, y);
+            let coverage = coverage[cell_index(u, v)];
+
+            // offset from the center of the cell, in the range -0.5..0.5
+            let du = u - u.floor() - 0.5;
+            let dv = v - v.floor() - 0.5;
+
+            let inside = match shape
+            {
+                // a circle of radius r covers pi*r^2 of the cell
+                DotShape::Circle => du * du + dv * dv < coverage / core::f32::consts::PI,
+                // a square of side s covers s^2 of the cell
+                DotShape::Square => du.abs().max(dv.abs()) * 2.0 < coverage.sqrt(),
+                // a line of thickness t covers t of the cell
+                DotShape::Line => dv.abs() * 2.0 < coverage
+            };
+            *px = if inside { ink } else { paper };
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Histogram matching (also known as histogram specification)
+//!
+//! This remaps the values of an image so that its histogram approximates
+//! that of a reference image, giving both images similar tonal characteristics.
+use crate::traits::NumOps;
+
+/// Count the occurrences of each value in a channel
+///
+/// The histogram has `max_value + 1` bins, values larger than `max_value`
+/// are counted in the last bin
+pub fn histogram<T>(channel: &[T], max_value: u16) -> Vec<u64>
+where
+    T: Copy + NumOps<T>
+{
+    let max = usize::from(max_value);
+    let mut histogram = vec![0; max + 1];
+
+    for value in channel
+    {
+        histogram[value.to_usize().min(max)] += 1;
+    }
+    histogram
+}
+
+/// Create a lookup table mapping each value of the source histogram to the
+/// value of the reference histogram with the closest cumulative frequency
+///
+/// Both histograms must have the same number of bins, if either is empty the
+/// identity mapping is returned
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn histogram_matching_lut(source: &[u64], reference: &[u64]) -> Vec<u16>
+{
+    assert_eq!(
+        source.len(),
+        reference.len(),
+        "Histograms must have the same number of bins"
+    );
+    let source_total: u64 = source.iter().sum();
+    let reference_total: u64 = reference.iter().sum();
+
+    if source_total == 0 || reference_total == 0
+    {
+        return (0..source.len()).map(|x| x as u16).collect();
+    }
+    let mut lut = Vec::with_capacity(source.len());
+
+    let mut source_cdf = 0;
+    let mut reference_cdf = reference[0];
+    let mut level = 0;
+
+    for count in source
+    {
+        source_cdf += count;
+
+        // smallest reference value whose cumulative frequency
+        // is at least the source's, compared without division
+        while u128::from(reference_cdf) * u128::from(source_total)
+            < u128::from(source_cdf) * u128::from(reference_total)
+        {
+            level += 1;
+            reference_cdf += reference[level];
+        }
+        lut.push(level as u16);
+    }
+    lut
+}
+
+/// Remap a channel so that its histogram matches the histogram of `reference`
+///
+/// The reference doesn't need to have the same dimensions as the channel
+///
+/// # Arguments
+/// - channel: Channel to modify in place
+/// - reference: Channel whose histogram should be matched
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+pub fn match_histogram<T>(channel: &mut [T], reference: &[T], max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    let lut = histogram_matching_lut(
+        &histogram(channel, max_value),
+        &histogram(reference, max_value)
+    );
+    let max = usize::from(max_value);
+
+    for value in channel.iter_mut()
+    {
+        *value = T::from_u32(u32::from(lut[value.to_usize().min(max)]));
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn luminance<T>(r: &[T], g: &[T], b: &[T]) -> Vec<u16>
+where
+    T: Copy + NumOps<T>
+{
+    r.iter()
+        .zip(g)
+        .zip(b)
+        .map(|((r, g), b)| {
+            (0.299 * r.to_f64() + 0.587 * g.to_f64() + 0.114 * b.to_f64()).round() as u16
+        })
+        .collect()
+}
+
+/// Remap RGB channels so that the histogram of their luminance matches the
+/// luminance histogram of the reference channels
+///
+/// Each pixel is shifted by the change its luminance undergoes,
+/// which preserves the differences between channels and hence the colors
+///
+/// # Arguments
+/// - channels: Red, green and blue channels to modify in place
+/// - reference: Red, green and blue channels whose luminance histogram should be matched
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+pub fn match_histogram_luminance<T>(channels: [&mut [T]; 3], reference: [&[T]; 3], max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    let [r, g, b] = channels;
+
+    let source_luma = luminance(r, g, b);
+    let reference_luma = luminance(reference[0], reference[1], reference[2]);
+
+    let lut = histogram_matching_lut(
+        &histogram(&source_luma, max_value),
+        &histogram(&reference_luma, max_value)
+    );
+    let max = i64::from(max_value);
+
+    for (((r, g), b), luma) in r.iter_mut().zip(g).zip(b).zip(source_luma)
+    {
+        let luma = luma.min(max_value);
+        let delta = i64::from(lut[usize::from(luma)]) - i64::from(luma);
+
+        for value in [r, g, b]
+        {
+            *value = T::from_u64((value.to_usize() as i64 + delta).clamp(0, max) as u64);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Integral images (summed-area tables)
+//!
+//! An integral image allows the sum of any rectangle in an image
+//! to be computed in constant time, which is useful for fast box
+//! filters, adaptive thresholding and Haar-like features
+use std::ops::{Add, Sub};
+
+use crate::traits::NumOps;
+
+/// Compute the integral image of a channel
+///
+/// Each entry `(x,y)` of the output contains the sum of all pixels
+/// above and to the left of `(x,y)` inclusive, the output has the same
+/// dimensions as the input.
+///
+/// Sums use `u64` accumulators, so they will not overflow even for large
+/// 16 bit images
+///
+/// # Arguments
+/// - channel: Input channel
+/// - width, height: Channel dimensions
+#[must_use]
+pub fn integral_image<T>(channel: &[T], width: usize, height: usize) -> Vec<u64>
+where
+    T: Copy + NumOps<T>
+{
+    summed_area(channel, width, height, |x| x.to_usize() as u64)
+}
+
+/// Compute the integral image of a floating point channel
+///
+/// Same as [`integral_image`] but for samples that may be fractional
+/// or negative, sums use `f64` accumulators to limit rounding errors
+///
+/// # Arguments
+/// - channel: Input channel
+/// - width, height: Channel dimensions
+#[must_use]
+pub fn integral_image_f32(channel: &[f32], width: usize, height: usize) -> Vec<f64>
+{
+    summed_area(channel, width, height, f64::from)
+}
+
+fn summed_area<T, A, F>(channel: &[T], width: usize, height: usize, to_sum: F) -> Vec<A>
+where
+    T: Copy,
+    A: Copy + Default + Add<Output = A>,
+    F: Fn(T) -> A
+{
+    let mut table = vec![A::default(); width * height];
+
+    if width == 0
+    {
+        return table;
+    }
+    let mut prev_row: Option<&[A]> = None;
+
+    for (in_row, out_row) in channel
+        .chunks_exact(width)
+        .zip(table.chunks_exact_mut(width))
+        .take(height)
+    {
+        let mut row_sum = A::default();
+
+        for (x, (in_px, out_px)) in in_row.iter().zip(out_row.iter_mut()).enumerate()
+        {
+            row_sum = row_sum + to_sum(*in_px);
+            *out_px = row_sum + prev_row.map_or(A::default(), |row| row[x]);
+        }
+        prev_row = Some(out_row);
+    }
+    table
+}
+
+/// Sum the pixels of a rectangle using an integral image
+///
+/// # Arguments
+/// - table: Integral image, as returned by [`integral_image`] or [`integral_image_f32`]
+/// - width: Width of the integral image
+/// - x, y: Top left corner of the rectangle
+/// - rect_width, rect_height: Rectangle dimensions, an empty rectangle sums to zero
+///
+/// # Panics
+/// If the rectangle extends beyond the integral image
+#[must_use]
+pub fn rectangle_sum<A>(
+    table: &[A], width: usize, x: usize, y: usize, rect_width: usize, rect_height: usize
+) -> A
+where
+    A: Copy + Default + Add<Output = A> + Sub<Output = A>
+{
+    if rect_width == 0 || rect_height == 0
+    {
+        return A::default();
+    }
+    let (x1, y1) = (x + rect_width - 1, y + rect_height - 1);
+
+    let at = |x: usize, y: usize| table[y * width + x];
+
+    // add the top left corner before subtracting to avoid underflow
+    let mut sum = at(x1, y1);
+
+    if x > 0 && y > 0
+    {
+        sum = sum + at(x - 1, y - 1);
+    }
+    if x > 0
+    {
+        sum = sum - at(x - 1, y1);
+    }
+    if y > 0
+    {
+        sum = sum - at(x1, y - 1);
+    }
+    sum
+}
@@ -1,18 +1,19 @@
 use std::ops::Sub;
 
-use crate::traits::NumOps;
-
 ///Invert a pixel
 ///
-/// The formula for inverting a 8 bit pixel
-///  is `pixel[x,y] = 255-pixel[x,y]`
-pub fn invert<T>(in_image: &mut [T])
+/// The formula for inverting a pixel
+///  is `pixel[x,y] = max_value-pixel[x,y]`
+///
+/// where `max_value` is the maximum value for the image depth,
+/// e.g `255` for 8 bit images and `1.0` for float images
+pub fn invert<T>(in_image: &mut [T], max_value: T)
 where
-    T: NumOps<T> + Sub<Output = T> + Copy
+    T: Sub<Output = T> + Copy
 {
     for pixel in in_image.iter_mut()
     {
-        *pixel = T::max_val() - *pixel;
+        *pixel = max_value - *pixel;
     }
 }
 
@@ -30,17 +31,17 @@ mod benchmarks
         let mut in_out = vec![0_u8; 800 * 800];
 
         b.iter(|| {
-            invert(&mut in_out);
+            invert(&mut in_out, 255);
         });
     }
 
     #[bench]
     fn invert_u16(b: &mut test::Bencher)
     {
-        let mut in_out = vec![0_u8; 800 * 800];
+        let mut in_out = vec![0_u16; 800 * 800];
 
         b.iter(|| {
-            invert(&mut in_out);
+            invert(&mut in_out, 65535);
         });
     }
 }
@@ -0,0 +1,233 @@
+//! K-means colour segmentation
+use crate::traits::NumOps;
+
+/// A small deterministic pseudo random number generator (splitmix64)
+struct SplitMix64
+{
+    state: u64
+}
+
+impl SplitMix64
+{
+    fn new(seed: u64) -> SplitMix64
+    {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64
+    {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Return a number in the range `0.0..1.0`
+    #[allow(clippy::cast_precision_loss)]
+    fn next_f64(&mut self) -> f64
+    {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+/// Squared euclidean distance between two points
+fn distance(a: &[f64], b: &[f64]) -> f64
+{
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// Return the index of the center closest to `point`
+fn nearest_center(point: &[f64], centers: &[f64], dims: usize) -> usize
+{
+    let mut best = 0;
+    let mut best_distance = f64::INFINITY;
+
+    for (i, center) in centers.chunks_exact(dims).enumerate()
+    {
+        let dist = distance(point, center);
+
+        if dist < best_distance
+        {
+            best_distance = dist;
+            best = i;
+        }
+    }
+    best
+}
+
+/// Choose initial centers using k-means++ seeding
+///
+/// The first center is a random point, each subsequent center is chosen
+/// with a probability proportional to its squared distance from the nearest
+/// already chosen center
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn initial_centers(points: &[f64], dims: usize, k: usize, rng: &mut SplitMix64) -> Vec<f64>
+{
+    let num_points = points.len() / dims;
+
+    let mut centers = Vec::with_capacity(k * dims);
+    let first = (rng.next_f64() * num_points as f64) as usize;
+    centers.extend_from_slice(&points[first * dims..(first + 1) * dims]);
+
+    let mut distances: Vec<f64> = points
+        .chunks_exact(dims)
+        .map(|point| distance(point, &centers[..dims]))
+        .collect();
+
+    for _ in 1..k
+    {
+        let total: f64 = distances.iter().sum();
+
+        let chosen = if total > 0.0
+        {
+            let mut target = rng.next_f64() * total;
+
+            distances
+                .iter()
+                .position(|dist| {
+                    target -= dist;
+                    target < 0.0
+                })
+                .unwrap_or(num_points - 1)
+        }
+        else
+        {
+            // fewer distinct colors than clusters
+            (rng.next_f64() * num_points as f64) as usize
+        };
+        let center = &points[chosen * dims..(chosen + 1) * dims];
+
+        for (dist, point) in distances.iter_mut().zip(points.chunks_exact(dims))
+        {
+            *dist = dist.min(distance(point, center));
+        }
+        centers.extend_from_slice(center);
+    }
+    centers
+}
+
+/// Cluster pixels into `k` groups and recolor each pixel to the
+/// mean color of its cluster
+///
+/// Each pixel is treated as a point whose coordinates are its values
+/// in each channel, clusters are initialized using k-means++ seeding and refined
+/// with Lloyd's algorithm until no pixel changes cluster or `iterations` is reached.
+///
+/// Results are deterministic for the same input and seed.
+///
+/// # Arguments
+/// - channels: Channels of the image, all must have the same length, they are
+///   modified in place
+/// - k: Number of clusters, if larger than the number of pixels it's
+///   reduced to the number of pixels
+/// - iterations: Maximum number of refinement iterations
+/// - seed: Seed for the random number generator used to pick initial clusters
+///
+/// # Returns
+/// The cluster index of each pixel, in the range `0..k`
+#[allow(clippy::cast_precision_loss)]
+pub fn kmeans_segment<T>(
+    channels: &mut [&mut [T]], k: usize, iterations: usize, seed: u64
+) -> Vec<usize>
+where
+    T: Copy + NumOps<T>
+{
+    let dims = channels.len();
+    let num_points = channels.first().map_or(0, |c| c.len());
+
+    if dims == 0 || num_points == 0 || k == 0
+    {
+        return vec![0; num_points];
+    }
+    let k = k.min(num_points);
+
+    // interleave channels into points
+    let mut points = vec![0.0; num_points * dims];
+
+    for (d, channel) in channels.iter().enumerate()
+    {
+        for (point, value) in points.chunks_exact_mut(dims).zip(channel.iter())
+        {
+            point[d] = value.to_f64();
+        }
+    }
+
+    let mut rng = SplitMix64::new(seed);
+    let mut centers = initial_centers(&points, dims, k, &mut rng);
+
+    let mut labels: Vec<usize> = points
+        .chunks_exact(dims)
+        .map(|point| nearest_center(point, &centers, dims))
+        .collect();
+
+    let mut sums = vec![0.0; k * dims];
+    let mut counts = vec![0_usize; k];
+
+    let mut update_centers = |labels: &[usize], centers: &mut [f64]| {
+        sums.fill(0.0);
+        counts.fill(0);
+
+        for (point, label) in points.chunks_exact(dims).zip(labels)
+        {
+            counts[*label] += 1;
+
+            for (sum, value) in sums[label * dims..].iter_mut().zip(point)
+            {
+                *sum += value;
+            }
+        }
+        for ((center, sum), count) in centers
+            .chunks_exact_mut(dims)
+            .zip(sums.chunks_exact(dims))
+            .zip(&counts)
+        {
+            // empty clusters keep their previous center
+            if *count > 0
+            {
+                for (c, s) in center.iter_mut().zip(sum)
+                {
+                    *c = s / *count as f64;
+                }
+            }
+        }
+    };
+
+    for _ in 0..iterations
+    {
+        update_centers(&labels, &mut centers);
+
+        let mut changed = false;
+
+        for (point, label) in points.chunks_exact(dims).zip(labels.iter_mut())
+        {
+            let nearest = nearest_center(point, &centers, dims);
+
+            if nearest != *label
+            {
+                *label = nearest;
+                changed = true;
+            }
+        }
+        if !changed
+        {
+            break;
+        }
+    }
+    // centers are the mean of the final clusters
+    update_centers(&labels, &mut centers);
+
+    for (d, channel) in channels.iter_mut().enumerate()
+    {
+        for (value, label) in channel.iter_mut().zip(&labels)
+        {
+            *value = T::from_f64(centers[label * dims + d].round());
+        }
+    }
+    labels
+}
@@ -0,0 +1,238 @@
+//! Multi-band (Laplacian pyramid) blending
+//!
+//! Blends two images by splitting them into frequency bands and blending each
+//! band with a correspondingly blurred mask, so low frequencies are mixed over a wide
+//! region while fine detail is mixed over a narrow one, see Burt and Adelson,
+//! "A Multiresolution Spline With Application to Image Mosaics"
+use crate::traits::NumOps;
+
+/// A single level of a pyramid
+struct Level
+{
+    pixels: Vec<f32>,
+    width:  usize,
+    height: usize
+}
+
+/// Blur a plane with the `[1, 4, 6, 4, 1] / 16` kernel and keep every
+/// second pixel in each dimension
+///
+/// Edges are handled by repeating the edge pixels
+fn reduce(level: &Level) -> Level
+{
+    const WEIGHTS: [f32; 5] = [1.0 / 16.0, 4.0 / 16.0, 6.0 / 16.0, 4.0 / 16.0, 1.0 / 16.0];
+
+    let (width, height) = (level.width, level.height);
+    let out_width = width.div_ceil(2);
+    let out_height = height.div_ceil(2);
+
+    // horizontal pass, only computing the kept columns
+    let mut horizontal = vec![0.0; out_width * height];
+
+    for (in_row, out_row) in level
+        .pixels
+        .chunks_exact(width)
+        .zip(horizontal.chunks_exact_mut(out_width))
+    {
+        for (x, out_px) in out_row.iter_mut().enumerate()
+        {
+            *out_px = WEIGHTS
+                .iter()
+                .enumerate()
+                .map(|(i, w)| w * in_row[(2 * x + i).saturating_sub(2).min(width - 1)])
+                .sum();
+        }
+    }
+    // vertical pass, only computing the kept rows
+    let mut pixels = vec![0.0; out_width * out_height];
+
+    for (y, out_row) in pixels.chunks_exact_mut(out_width).enumerate()
+    {
+        for (i, w) in WEIGHTS.iter().enumerate()
+        {
+            let in_y = (2 * y + i).saturating_sub(2).min(height - 1);
+            let in_row = &horizontal[in_y * out_width..][..out_width];
+
+            for (out_px, in_px) in out_row.iter_mut().zip(in_row)
+            {
+                *out_px += w * in_px;
+            }
+        }
+    }
+    Level {
+        pixels,
+        width: out_width,
+        height: out_height
+    }
+}
+
+/// Interpolate a line to `out.len()` samples, the inverse of the sampling done by [`reduce`]
+///
+/// Even samples lie on an input sample and are weighted `[1, 6, 1] / 8`,
+/// odd samples lie between two input samples and are their average
+fn expand_line(input: &[f32], out: &mut [f32])
+{
+    let last = input.len() - 1;
+
+    for (x, out_px) in out.iter_mut().enumerate()
+    {
+        let i = x / 2;
+
+        *out_px = if x % 2 == 0
+        {
+            (input[i.saturating_sub(1)] + 6.0 * input[i] + input[(i + 1).min(last)]) / 8.0
+        }
+        else
+        {
+            f32::midpoint(input[i], input[(i + 1).min(last)])
+        };
+    }
+}
+
+/// Upsample a level to `width` x `height`
+fn expand(level: &Level, width: usize, height: usize) -> Vec<f32>
+{
+    // horizontal pass
+    let mut horizontal = vec![0.0; width * level.height];
+
+    for (in_row, out_row) in level
+        .pixels
+        .chunks_exact(level.width)
+        .zip(horizontal.chunks_exact_mut(width))
+    {
+        expand_line(in_row, out_row);
+    }
+    // vertical pass, done column by column
+    let mut output = vec![0.0; width * height];
+    let mut column = vec![0.0; level.height];
+    let mut out_column = vec![0.0; height];
+
+    for x in 0..width
+    {
+        for (y, px) in column.iter_mut().enumerate()
+        {
+            *px = horizontal[y * width + x];
+        }
+        expand_line(&column, &mut out_column);
+
+        for (y, px) in out_column.iter().enumerate()
+        {
+            output[y * width + x] = *px;
+        }
+    }
+    output
+}
+
+/// Build a Gaussian pyramid with at most `levels` levels, stopping early
+/// once a level is 1x1
+fn gaussian_pyramid(pixels: Vec<f32>, width: usize, height: usize, levels: usize) -> Vec<Level>
+{
+    let mut pyramid = vec![Level {
+        pixels,
+        width,
+        height
+    }];
+
+    while pyramid.len() < levels
+    {
+        let last = pyramid.last().unwrap();
+
+        if last.width == 1 && last.height == 1
+        {
+            break;
+        }
+        let next = reduce(last);
+        pyramid.push(next);
+    }
+    pyramid
+}
+
+/// Turn a Gaussian pyramid into a Laplacian pyramid in place
+///
+/// Every level except the last stores the difference between itself
+/// and the expanded next level, the last level is kept as is
+fn to_laplacian(pyramid: &mut [Level])
+{
+    for i in 0..pyramid.len().saturating_sub(1)
+    {
+        let (width, height) = (pyramid[i].width, pyramid[i].height);
+        let expanded = expand(&pyramid[i + 1], width, height);
+
+        for (px, e) in pyramid[i].pixels.iter_mut().zip(expanded)
+        {
+            *px -= e;
+        }
+    }
+}
+
+/// Blend two channels using a Laplacian pyramid guided by a mask
+///
+/// Where the mask is `0.0` the output is `first`, where it's `1.0` the output
+/// is `second`, transitions between the two are smoothed over a region whose size
+/// grows with the number of levels, hiding seams much better than compositing with
+/// the mask directly.
+///
+/// # Arguments
+/// - first: First channel
+/// - second: Second channel, with the same dimensions as `first`
+/// - mask: Blend weights of `second` in the range `0.0..=1.0`, with the same dimensions as `first`
+/// - width, height: Channel dimensions
+/// - levels: Number of pyramid levels, `1` is a plain per pixel blend, the pyramid
+///   stops early if it reaches a 1x1 level
+/// - output: Output channel, with the same dimensions as `first`
+#[allow(clippy::cast_possible_truncation)]
+pub fn laplacian_blend<T>(
+    first: &[T], second: &[T], mask: &[f32], width: usize, height: usize, levels: usize,
+    output: &mut [T]
+) where
+    T: Copy + NumOps<T>
+{
+    let size = width * height;
+
+    if size == 0
+    {
+        return;
+    }
+    let levels = levels.max(1);
+
+    let to_f32 =
+        |channel: &[T]| -> Vec<f32> { channel[..size].iter().map(|x| x.to_f64() as f32).collect() };
+
+    let mut first_pyramid = gaussian_pyramid(to_f32(first), width, height, levels);
+    let mut second_pyramid = gaussian_pyramid(to_f32(second), width, height, levels);
+    let mask_pyramid = gaussian_pyramid(mask[..size].to_vec(), width, height, levels);
+
+    to_laplacian(&mut first_pyramid);
+    to_laplacian(&mut second_pyramid);
+
+    // blend each band, reusing the first pyramid for the result
+    for ((a, b), m) in first_pyramid
+        .iter_mut()
+        .zip(&second_pyramid)
+        .zip(&mask_pyramid)
+    {
+        for ((a_px, b_px), m_px) in a.pixels.iter_mut().zip(&b.pixels).zip(&m.pixels)
+        {
+            *a_px += (b_px - *a_px) * m_px;
+        }
+    }
+    // collapse the pyramid, from the coarsest level to the finest
+    while first_pyramid.len() > 1
+    {
+        let coarse = first_pyramid.pop().unwrap();
+        let fine = first_pyramid.last_mut().unwrap();
+        let expanded = expand(&coarse, fine.width, fine.height);
+
+        for (px, e) in fine.pixels.iter_mut().zip(expanded)
+        {
+            *px += e;
+        }
+    }
+    let min = T::min_val().to_f64() as f32;
+    let max = T::max_val().to_f64() as f32;
+
+    for (out, px) in output[..size].iter_mut().zip(&first_pyramid[0].pixels)
+    {
+        *out = T::from_f32(px.round().clamp(min, max));
+    }
+}
@@ -20,28 +20,47 @@
     clippy::wildcard_imports
 )]
 
+pub mod anisotropic_diffusion;
+pub mod ascii_art;
 pub mod box_blur;
 pub mod brighten;
 pub mod colorspace;
+pub mod composite;
 pub mod contrast;
 pub mod convolve;
 pub mod crop;
 pub mod deinterleave;
 pub mod depth;
+pub mod displacement;
+pub mod draw_text;
 pub mod filter;
 pub mod flip;
 pub mod flop;
 pub mod gamma;
 pub mod gaussian_blur;
 pub mod grayscale;
+pub mod guided_filter;
+pub mod halftone;
+pub mod histogram_match;
+pub mod integral_image;
 pub mod invert;
+pub mod kmeans;
+pub mod laplacian_blend;
 pub mod mathops;
 pub mod median;
+pub mod mipmap;
 pub mod mirror;
 pub mod pad;
+pub mod perceptual_hash;
+pub mod perspective;
+pub mod posterize;
+pub mod reduce_depth;
 pub mod resize;
+pub mod retinex;
 pub mod rotate;
 pub mod scharr;
+pub mod signed_distance_field;
+pub mod smart_crop;
 pub mod sobel;
 pub mod spatial;
 pub mod spatial_ops;
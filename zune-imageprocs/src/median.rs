@@ -1,21 +1,158 @@
-use std::fmt::Debug;
-
-//use crate::spatial::spatial_mut;
+//! Median filter
+//!
+//! Each output pixel is the median of the `(2R+1)` by `(2R+1)` window
+//! centered on it, pixels outside the image are treated as copies of the
+//! nearest edge pixel.
+//!
+//! Two implementations are provided
+//!
+//! - [`median_u8`]: Keeps a histogram of the window and slides it across each row,
+//!   the cost per pixel grows with the radius rather than its square.
+//! - [`median`]: Collects the window and selects the middle element, works for any
+//!   ordered type and is used for 16 bit images where a histogram would be too large.
 
+/// Return the median of `array`, reordering it in the process
 pub fn find_median<T: Copy + Ord>(array: &mut [T]) -> T
 {
-    array.sort_unstable();
     let middle = array.len() / 2;
 
-    array[middle]
+    *array.select_nth_unstable(middle).1
 }
 
 /// Median returns a new image in which each pixel is the median of its neighbors.
 /// The parameter radius corresponds to the radius of the neighbor area to be searched,
 /// for example a radius of R will result in a search window length of 2R+1 for each dimension.
-pub fn median<T: Copy + Ord + Default + Debug>(
-    _in_channel: &[T], _out_channel: &mut [T], _radius: usize, _width: usize, _height: usize
+///
+/// Window coordinates are clamped to the image borders
+///
+/// # Arguments
+/// - in_channel: Input channel
+/// - out_channel: Output channel, same dimensions as the input
+/// - radius: Radius of the window
+/// - width,height: Channel dimensions
+pub fn median<T: Copy + Ord + Default>(
+    in_channel: &[T], out_channel: &mut [T], radius: usize, width: usize, height: usize
 )
 {
-    //spatial_mut(in_channel, out_channel, radius, width, height, find_median);
+    if width == 0 || height == 0
+    {
+        return;
+    }
+    let diameter = 2 * radius + 1;
+    let mut window = vec![T::default(); diameter * diameter];
+
+    for (y, out_row) in out_channel.chunks_exact_mut(width).enumerate().take(height)
+    {
+        for (x, out_px) in out_row.iter_mut().enumerate()
+        {
+            let mut i = 0;
+
+            for wy in window_range(y, radius, height)
+            {
+                let in_row = &in_channel[wy * width..(wy + 1) * width];
+
+                for wx in window_range(x, radius, width)
+                {
+                    window[i] = in_row[wx];
+                    i += 1;
+                }
+            }
+            *out_px = find_median(&mut window);
+        }
+    }
+}
+
+/// Median filter for 8 bit images using a sliding histogram
+///
+/// Produces the same result as [`median`] but is faster for large radii
+///
+/// # Arguments
+/// - in_channel: Input channel
+/// - out_channel: Output channel, same dimensions as the input
+/// - radius: Radius of the window
+/// - width,height: Channel dimensions
+#[allow(
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation
+)]
+pub fn median_u8(
+    in_channel: &[u8], out_channel: &mut [u8], radius: usize, width: usize, height: usize
+)
+{
+    if width == 0 || height == 0
+    {
+        return;
+    }
+    let diameter = 2 * radius + 1;
+    // index of the median in the sorted window
+    let half = (diameter * diameter) / 2;
+
+    let clamp = |v: isize, size: usize| v.clamp(0, size as isize - 1) as usize;
+
+    for (y, out_row) in out_channel.chunks_exact_mut(width).enumerate().take(height)
+    {
+        let rows: Vec<&[u8]> = window_range(y, radius, height)
+            .map(|wy| &in_channel[wy * width..(wy + 1) * width])
+            .collect();
+
+        let mut histogram = [0_usize; 256];
+
+        for row in &rows
+        {
+            for wx in window_range(0, radius, width)
+            {
+                histogram[usize::from(row[wx])] += 1;
+            }
+        }
+
+        // running median and the number of window values below it,
+        // moving the window changes both by a little, so they are
+        // adjusted instead of rescanning the histogram
+        let mut med = 0;
+        let mut below = 0;
+
+        for (x, out_px) in out_row.iter_mut().enumerate()
+        {
+            if x > 0
+            {
+                let old_x = clamp(x as isize - radius as isize - 1, width);
+                let new_x = clamp((x + radius) as isize, width);
+
+                for row in &rows
+                {
+                    let (old, new) = (usize::from(row[old_x]), usize::from(row[new_x]));
+
+                    histogram[old] -= 1;
+                    histogram[new] += 1;
+
+                    below -= usize::from(old < med);
+                    below += usize::from(new < med);
+                }
+            }
+            // the median is the value where the window values below it are at
+            // most `half` and including it they are more than `half`
+            while below > half
+            {
+                med -= 1;
+                below -= histogram[med];
+            }
+            while below + histogram[med] <= half
+            {
+                below += histogram[med];
+                med += 1;
+            }
+            *out_px = med as u8;
+        }
+    }
+}
+
+/// Window coordinates around `center`, clamped to `0..size`
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+fn window_range(center: usize, radius: usize, size: usize) -> impl Iterator<Item = usize>
+{
+    let start = center as isize - radius as isize;
+    let end = (center + radius) as isize;
+
+    (start..=end).map(move |v| v.clamp(0, size as isize - 1) as usize)
 }
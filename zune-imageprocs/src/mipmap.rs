@@ -0,0 +1,62 @@
+//! Mipmap generation routines
+use crate::traits::NumOps;
+
+/// Return the dimensions of every level of a mipmap chain
+///
+/// The first level is the full image, each successive level is half the previous
+/// one (rounded down, but never less than 1) until a 1x1 level is reached
+#[must_use]
+pub fn mipmap_dimensions(width: usize, height: usize) -> Vec<(usize, usize)>
+{
+    let mut levels = vec![(width, height)];
+
+    let (mut w, mut h) = (width, height);
+
+    while w > 1 || h > 1
+    {
+        w = (w / 2).max(1);
+        h = (h / 2).max(1);
+
+        levels.push((w, h));
+    }
+    levels
+}
+
+/// Downsample a single channel by two in each dimension using a box filter
+///
+/// Each output pixel is the rounded average of the corresponding 2x2 input block,
+/// edge pixels are repeated for dimensions that are already 1
+///
+/// # Arguments
+/// - input: Input channel with dimensions `in_width` x `in_height`
+/// - in_width, in_height: Input dimensions
+/// - output: Output channel, its dimensions are given by the next level in [`mipmap_dimensions`]
+pub fn box_downsample<T>(input: &[T], in_width: usize, in_height: usize, output: &mut [T])
+where
+    T: Copy + NumOps<T>
+{
+    let out_width = (in_width / 2).max(1);
+    let out_height = (in_height / 2).max(1);
+
+    for (y, out_row) in output
+        .chunks_exact_mut(out_width)
+        .take(out_height)
+        .enumerate()
+    {
+        let row_a = &input[(2 * y).min(in_height - 1) * in_width..][..in_width];
+        let row_b = &input[(2 * y + 1).min(in_height - 1) * in_width..][..in_width];
+
+        for (x, out_px) in out_row.iter_mut().enumerate()
+        {
+            let x_a = (2 * x).min(in_width - 1);
+            let x_b = (2 * x + 1).min(in_width - 1);
+
+            let sum = row_a[x_a].to_usize()
+                + row_a[x_b].to_usize()
+                + row_b[x_a].to_usize()
+                + row_b[x_b].to_usize();
+
+            *out_px = T::from_usize((sum + 2) / 4);
+        }
+    }
+}
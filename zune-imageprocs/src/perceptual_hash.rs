@@ -0,0 +1,151 @@
+//! Perceptual image hashing
+//!
+//! A perceptual hash summarizes the low frequency structure of an image,
+//! so images that look alike have hashes differing in few bits even if they were
+//! resized, recompressed or slightly recoloured. Similarity is measured
+//! by the Hamming distance between hashes, i.e `(a ^ b).count_ones()`
+use crate::traits::NumOps;
+
+/// Size the image is reduced to before the DCT
+const REDUCED_SIZE: usize = 32;
+
+/// Number of low frequencies kept in each direction
+const HASH_SIZE: usize = 8;
+
+/// Reduce luminance to a `REDUCED_SIZE x REDUCED_SIZE` image by averaging
+/// the area of the source each reduced pixel covers
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn reduce<T>(channels: &[&[T]], width: usize, height: usize) -> Vec<f32>
+where
+    T: Copy + NumOps<T>
+{
+    let luma = |i: usize| -> f32 {
+        if channels.len() >= 3
+        {
+            (0.299 * channels[0][i].to_f64()
+                + 0.587 * channels[1][i].to_f64()
+                + 0.114 * channels[2][i].to_f64()) as f32
+        }
+        else
+        {
+            channels[0][i].to_f64() as f32
+        }
+    };
+    // range of source coordinates covered by a reduced coordinate,
+    // always at least one source pixel for images smaller than the reduced size
+    let span = |out: usize, size: usize| -> (usize, usize) {
+        let start = (out * size / REDUCED_SIZE).min(size - 1);
+        let end = ((out + 1) * size / REDUCED_SIZE).max(start + 1);
+
+        (start, end)
+    };
+
+    let mut reduced = vec![0.0; REDUCED_SIZE * REDUCED_SIZE];
+
+    for (out_y, out_row) in reduced.chunks_exact_mut(REDUCED_SIZE).enumerate()
+    {
+        let (y_start, y_end) = span(out_y, height);
+
+        for (out_x, out) in out_row.iter_mut().enumerate()
+        {
+            let (x_start, x_end) = span(out_x, width);
+            let mut sum = 0.0;
+
+            for y in y_start..y_end
+            {
+                for x in x_start..x_end
+                {
+                    sum += luma(y * width + x);
+                }
+            }
+            *out = sum / ((y_end - y_start) * (x_end - x_start)) as f32;
+        }
+    }
+    reduced
+}
+
+/// Compute the lowest `HASH_SIZE x HASH_SIZE` coefficients of the
+/// type II DCT of the reduced image, in row major order
+#[allow(clippy::cast_precision_loss)]
+fn low_frequencies(reduced: &[f32]) -> Vec<f32>
+{
+    let n = REDUCED_SIZE as f32;
+
+    // cos_table[u][x], basis functions of the frequencies kept
+    let cos_table: Vec<[f32; REDUCED_SIZE]> = (0..HASH_SIZE)
+        .map(|u| {
+            let mut row = [0.0; REDUCED_SIZE];
+
+            for (x, value) in row.iter_mut().enumerate()
+            {
+                *value =
+                    ((2.0 * x as f32 + 1.0) * u as f32 * core::f32::consts::PI / (2.0 * n)).cos();
+            }
+            row
+        })
+        .collect();
+
+    // transform rows, keeping only the low horizontal frequencies
+    let mut rows = vec![0.0; REDUCED_SIZE * HASH_SIZE];
+
+    for (row, out) in reduced
+        .chunks_exact(REDUCED_SIZE)
+        .zip(rows.chunks_exact_mut(HASH_SIZE))
+    {
+        for (value, basis) in out.iter_mut().zip(&cos_table)
+        {
+            *value = row.iter().zip(basis).map(|(a, b)| a * b).sum();
+        }
+    }
+    // then columns, normalization is skipped since only the
+    // relative order of coefficients matters
+    let mut coefficients = vec![0.0; HASH_SIZE * HASH_SIZE];
+
+    for (v, basis) in cos_table.iter().enumerate()
+    {
+        for u in 0..HASH_SIZE
+        {
+            coefficients[v * HASH_SIZE + u] = (0..REDUCED_SIZE)
+                .map(|y| rows[y * HASH_SIZE + u] * basis[y])
+                .sum();
+        }
+    }
+    coefficients
+}
+
+/// Compute the DCT based perceptual hash (pHash) of an image
+///
+/// The image is reduced to a 32x32 grayscale image and its 2D DCT is computed,
+/// bit `i` of the hash is set if the `i`th coefficient of the top left 8x8 block of
+/// low frequencies (in row major order) is larger than the median of that block.
+/// The DC coefficient only reflects the average brightness so it is excluded
+/// when computing the median.
+///
+/// # Arguments
+/// - channels: Either a single luma channel, or red, green and blue channels
+/// - width, height: Image dimensions
+///
+/// # Returns
+/// The hash, or zero for an empty image
+#[must_use = "The hash is returned, not stored"]
+pub fn perceptual_hash<T>(channels: &[&[T]], width: usize, height: usize) -> u64
+where
+    T: Copy + NumOps<T>
+{
+    if channels.is_empty() || width == 0 || height == 0
+    {
+        return 0;
+    }
+    let coefficients = low_frequencies(&reduce(channels, width, height));
+
+    let mut sorted = coefficients[1..].to_vec();
+    sorted.sort_unstable_by(f32::total_cmp);
+
+    let median = sorted[sorted.len() / 2];
+
+    coefficients
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value > median)
+        .fold(0, |hash, (i, _)| hash | (1 << i))
+}
@@ -0,0 +1,144 @@
+//! Perspective (four point) warping
+use crate::traits::NumOps;
+
+/// Compute the homography mapping each of the `from` points to
+/// the corresponding `to` point
+///
+/// The homography is returned as a row major 3x3 matrix whose last entry is 1,
+/// a point `(x,y)` is mapped to `((h0*x + h1*y + h2) / w, (h3*x + h4*y + h5) / w)`
+/// where `w = h6*x + h7*y + 1`.
+///
+/// Returns `None` if three or more points of either quad are collinear
+#[must_use]
+pub fn homography(from: [(f32, f32); 4], to: [(f32, f32); 4]) -> Option<[f64; 9]>
+{
+    // Each point pair gives two rows of the linear system A*h = b
+    // with h being the first 8 entries of the matrix
+    let mut system = [[0.0_f64; 9]; 8];
+
+    for (i, (&(x, y), &(u, v))) in from.iter().zip(&to).enumerate()
+    {
+        let (x, y, u, v) = (f64::from(x), f64::from(y), f64::from(u), f64::from(v));
+
+        system[2 * i] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+        system[2 * i + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+    }
+    // gaussian elimination with partial pivoting
+    for col in 0..8
+    {
+        let pivot = (col..8).max_by(|&a, &b| {
+            system[a][col]
+                .abs()
+                .partial_cmp(&system[b][col].abs())
+                .unwrap()
+        })?;
+
+        if system[pivot][col].abs() < 1e-10
+        {
+            return None;
+        }
+        system.swap(col, pivot);
+
+        let pivot_row = system[col];
+
+        for (row, values) in system.iter_mut().enumerate()
+        {
+            if row != col
+            {
+                let factor = values[col] / pivot_row[col];
+
+                for (value, p) in values[col..].iter_mut().zip(&pivot_row[col..])
+                {
+                    *value -= factor * p;
+                }
+            }
+        }
+    }
+    let mut matrix = [1.0; 9];
+
+    for (i, value) in matrix.iter_mut().take(8).enumerate()
+    {
+        *value = system[i][8] / system[i][i];
+    }
+    Some(matrix)
+}
+
+/// Warp a channel using a homography
+///
+/// Each output pixel `(x,y)` is sampled from the input at the point the
+/// homography maps `(x,y)` to, using bilinear interpolation. Points more
+/// than half a pixel outside the input are set to `fill`.
+///
+/// # Arguments
+/// - in_channel: Input channel
+/// - in_width, in_height: Input dimensions
+/// - out_channel: Output channel
+/// - out_width, out_height: Output dimensions
+/// - matrix: Homography mapping output coordinates to input coordinates,
+///   see [`homography`]
+/// - fill: Value for output pixels whose source lies outside the input
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation,
+    clippy::too_many_arguments
+)]
+pub fn perspective_warp<T>(
+    in_channel: &[T], in_width: usize, in_height: usize, out_channel: &mut [T], out_width: usize,
+    out_height: usize, matrix: &[f64; 9], fill: T
+) where
+    T: Copy + NumOps<T>
+{
+    if out_width == 0 || out_height == 0
+    {
+        return;
+    }
+    if in_width == 0 || in_height == 0
+    {
+        out_channel.fill(fill);
+        return;
+    }
+    let max_x = (in_width - 1) as f64;
+    let max_y = (in_height - 1) as f64;
+
+    let sample = |x: usize, y: usize| -> f64 { in_channel[y * in_width + x].to_f64() };
+
+    for (y, out_row) in out_channel
+        .chunks_exact_mut(out_width)
+        .take(out_height)
+        .enumerate()
+    {
+        let y = y as f64;
+
+        for (x, out_px) in out_row.iter_mut().enumerate()
+        {
+            let x = x as f64;
+
+            let w = matrix[6] * x + matrix[7] * y + matrix[8];
+            let src_x = (matrix[0] * x + matrix[1] * y + matrix[2]) / w;
+            let src_y = (matrix[3] * x + matrix[4] * y + matrix[5]) / w;
+
+            // also rejects NaN coordinates
+            if !(src_x >= -0.5 && src_x <= max_x + 0.5 && src_y >= -0.5 && src_y <= max_y + 0.5)
+            {
+                *out_px = fill;
+                continue;
+            }
+            let src_x = src_x.clamp(0.0, max_x);
+            let src_y = src_y.clamp(0.0, max_y);
+
+            let x0 = src_x.floor() as usize;
+            let y0 = src_y.floor() as usize;
+            let x1 = (x0 + 1).min(in_width - 1);
+            let y1 = (y0 + 1).min(in_height - 1);
+
+            let fx = src_x - x0 as f64;
+            let fy = src_y - y0 as f64;
+
+            let top = sample(x0, y0) * (1.0 - fx) + sample(x1, y0) * fx;
+            let bottom = sample(x0, y1) * (1.0 - fx) + sample(x1, y1) * fx;
+
+            *out_px = T::from_f64((top * (1.0 - fy) + bottom * fy).round());
+        }
+    }
+}
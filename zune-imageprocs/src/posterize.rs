@@ -0,0 +1,55 @@
+//! Reduce the number of distinct values in a channel
+use crate::traits::NumOps;
+
+/// Posterize a channel
+///
+/// Each sample is snapped to the nearest of `levels` evenly spaced
+/// values between zero and `max_value`, giving flat areas of color.
+///
+/// # Arguments
+/// - channel: Samples to posterize
+/// - levels: Number of output values, should be at least 2
+/// - max_value: Maximum value of a sample for this bit depth
+pub fn posterize<T>(channel: &mut [T], levels: u16, max_value: u16)
+where
+    T: Copy + NumOps<T>
+{
+    for px in channel
+    {
+        *px = T::from_u32(quantize(px.to_usize(), levels, max_value));
+    }
+}
+
+/// Posterize an 8 bit channel
+///
+/// Same as [`posterize`] but uses a lookup table
+///
+/// # Arguments
+/// - channel: Samples to posterize
+/// - levels: Number of output values, should be at least 2
+#[allow(clippy::cast_possible_truncation)]
+pub fn posterize_u8(channel: &mut [u8], levels: u16)
+{
+    let mut lut = [0_u8; 256];
+
+    for (i, value) in lut.iter_mut().enumerate()
+    {
+        *value = quantize(i, levels, 255) as u8;
+    }
+    for px in channel
+    {
+        *px = lut[usize::from(*px)];
+    }
+}
+
+/// Snap `value` to the nearest of `levels` evenly spaced values
+#[allow(clippy::cast_possible_truncation)]
+fn quantize(value: usize, levels: u16, max_value: u16) -> u32
+{
+    let steps = u32::from(levels.max(2) - 1);
+    let max_value = u32::from(max_value);
+
+    let level = (value as u32 * steps + max_value / 2) / max_value;
+
+    (level * max_value + steps / 2) / steps
+}
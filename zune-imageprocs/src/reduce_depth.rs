@@ -0,0 +1,77 @@
+use crate::traits::NumOps;
+
+/// Quantize a channel to a fewer number of bits while keeping the
+/// storage type
+///
+/// Each sample is mapped to the nearest of the `2^bits` levels evenly spaced between
+/// `0` and `max_value`, so the channel keeps its range but has reduced effective precision.
+///
+/// If `dither` is true, Floyd-Steinberg error diffusion is used to distribute
+/// the quantization error to neighbouring pixels, which reduces banding.
+///
+/// # Arguments
+/// - channel: Channel samples in row major order
+/// - width, height: Channel dimensions
+/// - bits: Effective number of bits to keep, if this is greater than or equal to
+///   the bits needed to represent `max_value`, the channel is left untouched,
+///   zero is treated as one
+/// - max_value: Maximum value for the storage depth, e.g 255 for 8 bit images
+/// - dither: Whether to use error diffusion
+#[allow(
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss
+)]
+pub fn reduce_bit_depth<T>(
+    channel: &mut [T], width: usize, height: usize, bits: u8, max_value: u16, dither: bool
+) where
+    T: Copy + NumOps<T>
+{
+    let depth_bits = 16 - max_value.leading_zeros();
+
+    if u32::from(bits) >= depth_bits || width == 0
+    {
+        return;
+    }
+    let levels = f32::from((1_u16 << bits.max(1)) - 1);
+    let max = f32::from(max_value);
+
+    let quantize = |value: f32| -> f32 {
+        let level = (value * levels / max).round().clamp(0.0, levels);
+
+        (level * max / levels).round()
+    };
+
+    if !dither
+    {
+        for px in channel.iter_mut()
+        {
+            *px = T::from_f32(quantize(px.to_f64() as f32));
+        }
+        return;
+    }
+    // errors for the current and next row, padded by one on each side
+    // to avoid bounds checking the edges
+    let mut current_err = vec![0.0_f32; width + 2];
+    let mut next_err = vec![0.0_f32; width + 2];
+
+    for row in channel.chunks_exact_mut(width).take(height)
+    {
+        for (x, px) in row.iter_mut().enumerate()
+        {
+            let old = (px.to_f64() as f32 + current_err[x + 1]).clamp(0.0, max);
+            let new = quantize(old);
+
+            *px = T::from_f32(new);
+
+            let error = old - new;
+
+            current_err[x + 2] += error * (7.0 / 16.0);
+            next_err[x] += error * (3.0 / 16.0);
+            next_err[x + 1] += error * (5.0 / 16.0);
+            next_err[x + 2] += error * (1.0 / 16.0);
+        }
+        core::mem::swap(&mut current_err, &mut next_err);
+        next_err.fill(0.0);
+    }
+}
@@ -1,11 +1,21 @@
 use crate::traits::NumOps;
 
 mod bilinear;
+mod lanczos;
+mod nearest;
 
+/// Interpolation used when resizing
 #[derive(Copy, Clone, Debug)]
 pub enum ResizeMethod
 {
-    Bilinear
+    /// Use the input pixel nearest to each output pixel,
+    /// fastest but blocky when upscaling and aliased when downscaling
+    NearestNeighbor,
+    /// Interpolate linearly between the four nearest input pixels
+    Bilinear,
+    /// Use a lanczos filter with three lobes, slowest but sharpest,
+    /// and the filter is widened when downscaling to avoid aliasing
+    Lanczos3
 }
 
 /// Resize an image to new dimensions
@@ -21,6 +31,8 @@ pub enum ResizeMethod
 /// # Panics
 /// - `in_width*in_height` do not match `in_image.len()`.
 /// - `out_width*out_height` do not match `out_image.len()`.
+///
+/// If any of the dimensions is zero, nothing is done
 pub fn resize<T>(
     in_image: &[T], out_image: &mut [T], method: ResizeMethod, in_width: usize, in_height: usize,
     out_width: usize, out_height: usize
@@ -28,13 +40,29 @@ pub fn resize<T>(
     T: Copy + NumOps<T>,
     f64: std::convert::From<T>
 {
+    if in_width == 0 || in_height == 0 || out_width == 0 || out_height == 0
+    {
+        return;
+    }
     match method
     {
+        ResizeMethod::NearestNeighbor =>
+        {
+            nearest::nearest_impl(
+                in_image, out_image, in_width, in_height, out_width, out_height
+            );
+        }
         ResizeMethod::Bilinear =>
         {
             bilinear::bilinear_impl(
                 in_image, out_image, in_width, in_height, out_width, out_height
             );
         }
+        ResizeMethod::Lanczos3 =>
+        {
+            lanczos::lanczos3_impl(
+                in_image, out_image, in_width, in_height, out_width, out_height
+            );
+        }
     }
 }
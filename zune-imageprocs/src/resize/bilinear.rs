@@ -1,18 +1,54 @@
 use crate::traits::NumOps;
 
+/// Position of an output pixel's center in input pixel coordinates, split into
+/// the two input pixels it lies between and the weight of the second one
 ///
-#[allow(
-    clippy::cast_precision_loss,
-    clippy::cast_sign_loss,
-    clippy::cast_possible_truncation
-)]
+/// Positions past the edges are clamped to the edge pixels
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn sample_position(out_pos: usize, in_size: usize, out_size: usize) -> (usize, usize, f32)
+{
+    let scale = in_size as f32 / out_size as f32;
+    let pos = ((out_pos as f32 + 0.5) * scale - 0.5).clamp(0.0, (in_size - 1) as f32);
+
+    let first = pos as usize;
+    let second = (first + 1).min(in_size - 1);
+
+    (first, second, pos - first as f32)
+}
+
+/// Resize a channel interpolating linearly between the
+/// four input pixels around each output pixel
+#[allow(clippy::cast_possible_truncation)]
 pub fn bilinear_impl<T>(
-    _in_image: &[T], _out_image: &mut [T], _in_width: usize, _in_height: usize, _out_width: usize,
-    _out_height: usize
+    in_image: &[T], out_image: &mut [T], in_width: usize, in_height: usize, out_width: usize,
+    out_height: usize
 ) where
     T: Copy + NumOps<T>,
     f64: std::convert::From<T>
 {
-    // stump
-    return;
+    let columns: Vec<(usize, usize, f32)> = (0..out_width)
+        .map(|x| sample_position(x, in_width, out_width))
+        .collect();
+
+    for (y, out_row) in out_image.chunks_exact_mut(out_width).enumerate()
+    {
+        let (y0, y1, wy) = sample_position(y, in_height, out_height);
+
+        let top = &in_image[y0 * in_width..(y0 + 1) * in_width];
+        let bottom = &in_image[y1 * in_width..(y1 + 1) * in_width];
+
+        for (out, (x0, x1, wx)) in out_row.iter_mut().zip(&columns)
+        {
+            let lerp = |a: T, b: T, w: f32| {
+                let a = f64::from(a) as f32;
+                let b = f64::from(b) as f32;
+
+                a + (b - a) * w
+            };
+            let upper = lerp(top[*x0], top[*x1], *wx);
+            let lower = lerp(bottom[*x0], bottom[*x1], *wx);
+
+            *out = T::from_f32((upper + (lower - upper) * wy).round());
+        }
+    }
 }
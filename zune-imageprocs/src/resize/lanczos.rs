@@ -0,0 +1,123 @@
+use core::f32::consts::PI;
+
+use crate::traits::NumOps;
+
+/// Number of lobes of the lanczos kernel
+const LOBES: f32 = 3.0;
+
+fn sinc(x: f32) -> f32
+{
+    if x.abs() < f32::EPSILON
+    {
+        1.0
+    }
+    else
+    {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+fn lanczos3(x: f32) -> f32
+{
+    if x.abs() < LOBES
+    {
+        sinc(x) * sinc(x / LOBES)
+    }
+    else
+    {
+        0.0
+    }
+}
+
+/// Compute the input pixels and their weights contributing to each output pixel
+///
+/// When downscaling the kernel is stretched to cover all input pixels that
+/// map to an output pixel. Input positions past the edges are clamped
+/// to the edge pixels, and weights always sum to one.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap
+)]
+fn compute_weights(in_size: usize, out_size: usize) -> Vec<Vec<(usize, f32)>>
+{
+    let scale = in_size as f32 / out_size as f32;
+    let filter_scale = scale.max(1.0);
+    let support = LOBES * filter_scale;
+
+    (0..out_size)
+        .map(|out_pos| {
+            let center = (out_pos as f32 + 0.5) * scale - 0.5;
+            let start = (center - support).floor() as isize;
+            let end = (center + support).ceil() as isize;
+
+            let mut weights: Vec<(usize, f32)> = (start..=end)
+                .map(|in_pos| {
+                    let weight = lanczos3((in_pos as f32 - center) / filter_scale);
+                    let clamped = in_pos.clamp(0, in_size as isize - 1) as usize;
+
+                    (clamped, weight)
+                })
+                .filter(|(_, weight)| *weight != 0.0)
+                .collect();
+
+            let sum: f32 = weights.iter().map(|(_, weight)| weight).sum();
+
+            for (_, weight) in &mut weights
+            {
+                *weight /= sum;
+            }
+            weights
+        })
+        .collect()
+}
+
+/// Resize a channel using a lanczos filter with three lobes
+///
+/// This is done in two passes, first resizing rows into a temporary buffer,
+/// then columns.
+#[allow(clippy::cast_possible_truncation)]
+pub fn lanczos3_impl<T>(
+    in_image: &[T], out_image: &mut [T], in_width: usize, in_height: usize, out_width: usize,
+    out_height: usize
+) where
+    T: Copy + NumOps<T>,
+    f64: std::convert::From<T>
+{
+    let horizontal = compute_weights(in_width, out_width);
+    let vertical = compute_weights(in_height, out_height);
+
+    // rows resized to the new width, still at the old height
+    let mut temp = vec![0.0_f32; out_width * in_height];
+
+    for (in_row, temp_row) in in_image
+        .chunks_exact(in_width)
+        .zip(temp.chunks_exact_mut(out_width))
+    {
+        for (out, weights) in temp_row.iter_mut().zip(&horizontal)
+        {
+            *out = weights
+                .iter()
+                .map(|(x, weight)| f64::from(in_row[*x]) as f32 * weight)
+                .sum();
+        }
+    }
+
+    // the kernel has negative lobes, so values can over/undershoot the input range
+    let max_value = f64::from(T::max_val()) as f32;
+    let min_value = f64::from(T::min_val()) as f32;
+
+    for (out_row, weights) in out_image.chunks_exact_mut(out_width).zip(&vertical)
+    {
+        for (x, out) in out_row.iter_mut().enumerate()
+        {
+            let value: f32 = weights
+                .iter()
+                .map(|(y, weight)| temp[y * out_width + x] * weight)
+                .sum();
+
+            *out = T::from_f32(value.round().clamp(min_value, max_value));
+        }
+    }
+}
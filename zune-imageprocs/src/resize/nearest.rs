@@ -0,0 +1,24 @@
+/// Resize a channel picking the input pixel nearest to the
+/// center of each output pixel
+pub fn nearest_impl<T>(
+    in_image: &[T], out_image: &mut [T], in_width: usize, in_height: usize, out_width: usize,
+    out_height: usize
+) where
+    T: Copy
+{
+    // input pixel covering the center of each output column
+    let columns: Vec<usize> = (0..out_width)
+        .map(|x| ((2 * x + 1) * in_width / (2 * out_width)).min(in_width - 1))
+        .collect();
+
+    for (y, out_row) in out_image.chunks_exact_mut(out_width).enumerate()
+    {
+        let in_y = ((2 * y + 1) * in_height / (2 * out_height)).min(in_height - 1);
+        let in_row = &in_image[in_y * in_width..(in_y + 1) * in_width];
+
+        for (out, x) in out_row.iter_mut().zip(&columns)
+        {
+            *out = in_row[*x];
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Multi-scale retinex
+//!
+//! Retinex separates an image into illumination, which varies slowly, and
+//! reflectance, which holds the detail. Removing most of the illumination compresses
+//! the dynamic range of an image, bringing out detail in shadows of underexposed
+//! or backlit photos.
+use crate::guided_filter::box_mean;
+use crate::traits::NumOps;
+
+/// Fraction of the darkest and brightest outputs clipped when normalizing
+const CLIP_FRACTION: f32 = 0.005;
+
+/// Approximate a gaussian blur of the given standard deviation with three box blurs
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::suboptimal_flops
+)]
+fn gaussian(data: &[f32], width: usize, height: usize, sigma: f32) -> Vec<f32>
+{
+    // three box blurs of radius r have a variance of r*(r+1)
+    let radius = (((1.0 + 4.0 * sigma * sigma).sqrt() - 1.0) / 2.0).round() as usize;
+
+    let mut blurred = box_mean(data, width, height, radius);
+
+    for _ in 0..2
+    {
+        blurred = box_mean(&blurred, width, height, radius);
+    }
+    blurred
+}
+
+/// Run multi-scale retinex on a luminance channel
+///
+/// For each scale the log of the luminance blurred by a gaussian of that standard deviation
+/// (the estimated illumination) is subtracted from the log of the luminance, and the results
+/// are averaged across scales. The result is normalized to `0.0..=1.0`, clipping
+/// the darkest and brightest 0.5% of pixels, then `gain` and `offset` are applied.
+///
+/// Small scales enhance fine detail, large scales keep more of the overall tonality,
+/// combining several gives a balance of both.
+///
+/// # Arguments
+/// - luminance: Luminance normalized to `0.0..=1.0`
+/// - width, height: Dimensions of the channel
+/// - scales: Standard deviations of the gaussians estimating illumination
+/// - gain, offset: Applied to the normalized output as `gain * x + offset`
+///
+/// # Returns
+/// The new luminance of each pixel, clamped to `0.0..=1.0`
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn multi_scale_retinex(
+    luminance: &[f32], width: usize, height: usize, scales: &[f32], gain: f32, offset: f32
+) -> Vec<f32>
+{
+    let size = width * height;
+
+    if size == 0 || scales.is_empty()
+    {
+        return luminance[..size].to_vec();
+    }
+    // avoid taking the log of zero
+    let luminance: Vec<f32> = luminance[..size]
+        .iter()
+        .map(|x| x.max(0.0) + 1e-3)
+        .collect();
+
+    let mut retinex = vec![0.0; size];
+
+    for scale in scales
+    {
+        let illumination = gaussian(&luminance, width, height, *scale);
+
+        for ((out, value), light) in retinex.iter_mut().zip(&luminance).zip(&illumination)
+        {
+            *out += value.ln() - light.ln();
+        }
+    }
+    let weight = 1.0 / scales.len() as f32;
+
+    for value in &mut retinex
+    {
+        *value *= weight;
+    }
+
+    // normalize, clipping extremes so a few outliers don't compress the rest
+    let mut sorted = retinex.clone();
+    sorted.sort_unstable_by(f32::total_cmp);
+
+    let clip = (size as f32 * CLIP_FRACTION) as usize;
+    let low = sorted[clip];
+    let high = sorted[size - 1 - clip];
+    let range = (high - low).max(f32::EPSILON);
+
+    retinex
+        .iter()
+        .map(|x| (gain * ((x - low) / range).clamp(0.0, 1.0) + offset).clamp(0.0, 1.0))
+        .collect()
+}
+
+/// Run multi-scale retinex on the luminance of an image, preserving its colors
+///
+/// For RGB images each pixel is scaled by the ratio of its new and old luminance,
+/// see [`multi_scale_retinex`] for the details and arguments.
+///
+/// # Arguments
+/// - channels: Either a single luma channel or red, green and blue channels
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+///
+/// # Panics
+/// If the number of channels is not 1 or 3
+#[allow(clippy::cast_possible_truncation, clippy::too_many_arguments)]
+pub fn retinex<T>(
+    channels: &mut [&mut [T]], width: usize, height: usize, scales: &[f32], gain: f32, offset: f32,
+    max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    assert!(
+        channels.len() == 1 || channels.len() == 3,
+        "Expected either a luma channel or RGB channels"
+    );
+    let size = width * height;
+    let max = f64::from(max_value);
+
+    let luminance: Vec<f32> = (0..size)
+        .map(|i| {
+            let luma = if let [r, g, b] = channels
+            {
+                0.299 * r[i].to_f64() + 0.587 * g[i].to_f64() + 0.114 * b[i].to_f64()
+            }
+            else
+            {
+                channels[0][i].to_f64()
+            };
+            (luma / max) as f32
+        })
+        .collect();
+
+    let new_luminance = multi_scale_retinex(&luminance, width, height, scales, gain, offset);
+
+    if let [luma] = channels
+    {
+        for (value, new) in luma.iter_mut().zip(&new_luminance)
+        {
+            *value = T::from_f64((f64::from(*new) * max).round());
+        }
+        return;
+    }
+    for (i, (old, new)) in luminance.iter().zip(&new_luminance).enumerate()
+    {
+        for channel in channels.iter_mut()
+        {
+            let value = if *old > 0.0
+            {
+                channel[i].to_f64() * f64::from(new / old)
+            }
+            else
+            {
+                // black has no color to preserve, use gray
+                f64::from(*new) * max
+            };
+            channel[i] = T::from_f64(value.round().clamp(0.0, max));
+        }
+    }
+}
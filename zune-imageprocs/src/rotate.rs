@@ -1,14 +1,38 @@
 use crate::flip::flip;
 
-pub fn rotate(angle: u16, in_image: &[u8], out_image: &mut [u8])
+/// Rotate an image clockwise by `angle` degrees
+///
+/// Only multiples of 90 degrees are supported, other angles
+/// leave `out_image` untouched.
+///
+/// For 90 and 270 degree rotations the output image has
+/// its width and height swapped, i.e it is `height` pixels wide
+/// and `width` pixels high
+///
+/// # Arguments
+/// - angle: Angle to rotate by, in degrees
+/// - in_image: Image to rotate, `width` by `height` pixels
+/// - out_image: Output for the rotated image, should have the same length as `in_image`
+/// - width: Width of `in_image`
+/// - height: Height of `in_image`
+pub fn rotate<T: Copy>(
+    angle: u16, in_image: &[T], out_image: &mut [T], width: usize, height: usize
+)
 {
-    let angle = angle % 360;
-    if angle == 180
+    assert_eq!(in_image.len(), width * height);
+    assert_eq!(out_image.len(), in_image.len());
+
+    match angle % 360
     {
-        rotate_180(in_image, out_image);
+        0 => out_image.copy_from_slice(in_image),
+        90 => rotate_90(in_image, out_image, width, height),
+        180 => rotate_180(in_image, out_image),
+        270 => rotate_270(in_image, out_image, width, height),
+        _ => ()
     }
 }
-fn rotate_180(in_image: &[u8], out_image: &mut [u8])
+
+fn rotate_180<T: Copy>(in_image: &[T], out_image: &mut [T])
 {
     // rotate 180 is the same as flip, so use that
     // copy to dest
@@ -17,7 +41,7 @@ fn rotate_180(in_image: &[u8], out_image: &mut [u8])
     flip(out_image);
 }
 
-fn _rotate_90(_in_image: &[u8], _out_image: &mut [u8], _width: usize, _height: usize)
+fn rotate_90<T: Copy>(in_image: &[T], out_image: &mut [T], width: usize, height: usize)
 {
     // a 90 degree rotation is a bit cache unfriendly,
     // since widths become heights, but we can still optimize it
@@ -33,4 +57,29 @@ fn _rotate_90(_in_image: &[u8], _out_image: &mut [u8], _width: usize, _height: u
     // [1,2,3]    [7,4,1]
     // [4,5,6] -> [8,5,2]
     // [7,8,9]    [9,6,3]
+    //
+    // so output row y is input column y read from the bottom up
+    for (y, out_row) in out_image.chunks_exact_mut(height).enumerate()
+    {
+        for (x, out_pixel) in out_row.iter_mut().enumerate()
+        {
+            *out_pixel = in_image[(height - 1 - x) * width + y];
+        }
+    }
+}
+
+fn rotate_270<T: Copy>(in_image: &[T], out_image: &mut [T], width: usize, height: usize)
+{
+    // [1,2,3]    [3,6,9]
+    // [4,5,6] -> [2,5,8]
+    // [7,8,9]    [1,4,7]
+    //
+    // output row y is input column (width - 1 - y) read from the top down
+    for (y, out_row) in out_image.chunks_exact_mut(height).enumerate()
+    {
+        for (x, out_pixel) in out_row.iter_mut().enumerate()
+        {
+            *out_pixel = in_image[x * width + (width - 1 - y)];
+        }
+    }
 }
@@ -0,0 +1,170 @@
+//! Signed distance fields
+//!
+//! Turns a mask into a field where each pixel stores its distance to the
+//! nearest edge of the mask, which allows shapes such as glyphs to be
+//! rendered crisply at any scale by thresholding the interpolated field.
+//!
+//! Distances are exact euclidean distances computed with the two pass
+//! (columns then rows) distance transform from Felzenszwalb and Huttenlocher,
+//! "Distance Transforms of Sampled Functions"
+use crate::traits::NumOps;
+
+/// A value larger than any squared distance that can occur in an image
+const INF: f32 = 1e20;
+
+/// Compute the one dimensional squared distance transform of `f` into `d`
+///
+/// This is the lower envelope of parabolas rooted at each sample of `f`.
+///
+/// `v` and `z` are scratch space, they should have a length of at least
+/// `f.len()` and `f.len() + 1` respectively
+#[allow(clippy::cast_precision_loss, clippy::many_single_char_names)]
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32])
+{
+    let n = f.len();
+    let mut k = 0;
+
+    v[0] = 0;
+    z[0] = -INF;
+    z[1] = INF;
+
+    let intersection = |q: usize, p: usize| -> f32 {
+        let (qf, pf) = (q as f32, p as f32);
+        ((f[q] + qf * qf) - (f[p] + pf * pf)) / (2.0 * qf - 2.0 * pf)
+    };
+
+    for q in 1..n
+    {
+        let mut s = intersection(q, v[k]);
+
+        while s <= z[k]
+        {
+            k -= 1;
+            s = intersection(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = INF;
+    }
+
+    k = 0;
+
+    for (q, out) in d.iter_mut().enumerate().take(n)
+    {
+        while z[k + 1] < q as f32
+        {
+            k += 1;
+        }
+        let dist = q as f32 - v[k] as f32;
+
+        *out = dist * dist + f[v[k]];
+    }
+}
+
+/// Compute the squared euclidean distance of every pixel to the nearest
+/// pixel where `is_feature` is true
+fn squared_distance_transform(
+    is_feature: &[bool], width: usize, height: usize, grid: &mut [f32]
+)
+{
+    let longest = width.max(height);
+
+    let mut f = vec![0.0; longest];
+    let mut d = vec![0.0; longest];
+    let mut v = vec![0; longest];
+    let mut z = vec![0.0; longest + 1];
+
+    for (px, feature) in grid.iter_mut().zip(is_feature)
+    {
+        *px = if *feature { 0.0 } else { INF };
+    }
+
+    // first pass, down each column
+    for x in 0..width
+    {
+        for y in 0..height
+        {
+            f[y] = grid[y * width + x];
+        }
+        distance_transform_1d(&f[..height], &mut d[..height], &mut v, &mut z);
+
+        for y in 0..height
+        {
+            grid[y * width + x] = d[y];
+        }
+    }
+    // second pass, along each row
+    for row in grid.chunks_exact_mut(width)
+    {
+        f[..width].copy_from_slice(row);
+
+        distance_transform_1d(&f[..width], &mut d[..width], &mut v, &mut z);
+
+        row.copy_from_slice(&d[..width]);
+    }
+}
+
+/// Create a signed distance field from a mask
+///
+/// Pixels with a value of at least half of `max_value` are considered inside the mask.
+///
+/// Each output pixel holds the distance to the nearest edge of the mask, positive inside
+/// and negative outside, with the edge itself lying midway between pixels.
+/// Distances are normalized by `spread` into `0..=max_value`, so the edge maps to
+/// the mid value, distances of `spread` or more inside map to `max_value` and distances
+/// of `spread` or more outside map to zero.
+///
+/// # Arguments
+/// - mask: The input mask
+/// - out: Output channel, should have the same length as `mask`
+/// - width, height: Channel dimensions
+/// - spread: Distance in pixels covered by each half of the output range
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+#[allow(clippy::cast_possible_truncation)]
+pub fn signed_distance_field<T>(
+    mask: &[T], out: &mut [T], width: usize, height: usize, spread: f32, max_value: u16
+) where
+    T: Copy + NumOps<T>
+{
+    let size = width * height;
+
+    if size == 0
+    {
+        return;
+    }
+    let max = f32::from(max_value);
+    let threshold = f64::from(max_value) / 2.0;
+    let spread = spread.max(f32::EPSILON);
+
+    let inside: Vec<bool> = mask[..size]
+        .iter()
+        .map(|x| x.to_f64() >= threshold)
+        .collect();
+    let outside: Vec<bool> = inside.iter().map(|x| !x).collect();
+
+    // distance from each pixel to the nearest outside pixel, zero for outside pixels
+    let mut to_outside = vec![0.0; size];
+    // distance from each pixel to the nearest inside pixel, zero for inside pixels
+    let mut to_inside = vec![0.0; size];
+
+    squared_distance_transform(&outside, width, height, &mut to_outside);
+    squared_distance_transform(&inside, width, height, &mut to_inside);
+
+    for ((px, out_dist), in_dist) in out[..size].iter_mut().zip(&to_outside).zip(&to_inside)
+    {
+        // one of the two distances is always zero, the edge lies half a pixel
+        // from the centers of the pixels on either side of it
+        let distance = if *out_dist > 0.0
+        {
+            out_dist.sqrt() - 0.5
+        }
+        else
+        {
+            0.5 - in_dist.sqrt()
+        };
+        let normalized = (0.5 + distance / (2.0 * spread)).clamp(0.0, 1.0);
+
+        *px = T::from_f32((normalized * max).round());
+    }
+}
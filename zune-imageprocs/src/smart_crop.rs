@@ -0,0 +1,136 @@
+//! Content aware cropping
+//!
+//! Finds the crop of an image that keeps the most important content,
+//! where importance is given by a saliency map with one weight per pixel.
+use crate::traits::NumOps;
+
+/// Add the edge energy of a channel to a saliency map
+///
+/// The energy of a pixel is the sum of the absolute differences between
+/// its horizontal and vertical neighbours, normalized by `max_value`, so textured
+/// and detailed regions are considered more important than flat ones.
+///
+/// Energies are added to `saliency` so that the energy of several channels
+/// can be accumulated into one map.
+///
+/// # Arguments
+/// - channel: Input channel
+/// - width, height: Channel dimensions
+/// - max_value: Maximum value of the channel depth, e.g 255 for 8 bit images
+/// - saliency: Saliency map of `width*height` weights to add the energy to
+#[allow(clippy::cast_possible_truncation)]
+pub fn edge_energy<T>(
+    channel: &[T], width: usize, height: usize, max_value: u16, saliency: &mut [f32]
+) where
+    T: Copy + NumOps<T>
+{
+    let scale = 1.0 / f64::from(max_value);
+
+    for y in 0..height
+    {
+        let up = y.saturating_sub(1) * width;
+        let down = (y + 1).min(height - 1) * width;
+        let row = &channel[y * width..(y + 1) * width];
+
+        for (x, out) in saliency[y * width..(y + 1) * width].iter_mut().enumerate()
+        {
+            let left = row[x.saturating_sub(1)].to_f64();
+            let right = row[(x + 1).min(width - 1)].to_f64();
+            let top = channel[up + x].to_f64();
+            let bottom = channel[down + x].to_f64();
+
+            *out += (((right - left).abs() + (bottom - top).abs()) * scale) as f32;
+        }
+    }
+}
+
+/// Find the top left corner of the crop of the given size holding the most saliency
+///
+/// If several crops hold (nearly) the same saliency, the one whose center is closest
+/// to the saliency's center of mass is chosen, so salient regions smaller
+/// than the crop end up centered in it.
+///
+/// # Arguments
+/// - saliency: Saliency map, with one non-negative weight per pixel
+/// - width, height: Saliency map dimensions
+/// - crop_width, crop_height: Crop dimensions, must not be larger than the map
+///
+/// # Returns
+/// The `(x,y)` coordinates of the top left corner of the crop
+///
+/// # Panics
+/// If the crop is larger than the saliency map
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn best_crop(
+    saliency: &[f32], width: usize, height: usize, crop_width: usize, crop_height: usize
+) -> (usize, usize)
+{
+    assert!(
+        crop_width <= width && crop_height <= height,
+        "Crop must fit in the saliency map"
+    );
+    // summed area table with an extra zero row and column
+    let stride = width + 1;
+    let mut table = vec![0.0_f64; stride * (height + 1)];
+
+    let mut center_x = 0.0;
+    let mut center_y = 0.0;
+
+    for y in 0..height
+    {
+        let mut row_sum = 0.0;
+
+        for x in 0..width
+        {
+            let weight = f64::from(saliency[y * width + x]);
+
+            row_sum += weight;
+            center_x += weight * x as f64;
+            center_y += weight * y as f64;
+
+            table[(y + 1) * stride + x + 1] = row_sum + table[y * stride + x + 1];
+        }
+    }
+    let total = table[height * stride + width];
+
+    if total > 0.0
+    {
+        center_x /= total;
+        center_y /= total;
+    }
+    else
+    {
+        center_x = (width as f64 - 1.0) / 2.0;
+        center_y = (height as f64 - 1.0) / 2.0;
+    }
+
+    let crop_sum = |x: usize, y: usize| -> f64 {
+        table[(y + crop_height) * stride + x + crop_width]
+            - table[y * stride + x + crop_width]
+            - table[(y + crop_height) * stride + x]
+            + table[y * stride + x]
+    };
+
+    let positions =
+        || (0..=height - crop_height).flat_map(|y| (0..=width - crop_width).map(move |x| (x, y)));
+    let max_sum = positions()
+        .map(|(x, y)| crop_sum(x, y))
+        .fold(f64::MIN, f64::max);
+
+    // sums within this are considered equal, absorbing rounding errors
+    // and negligible amounts of saliency
+    let tolerance = max_sum.abs() * 1e-4;
+
+    let distance = |(x, y): (usize, usize)| -> f64 {
+        let dx = x as f64 + (crop_width as f64 - 1.0) / 2.0 - center_x;
+        let dy = y as f64 + (crop_height as f64 - 1.0) / 2.0 - center_y;
+
+        dx * dx + dy * dy
+    };
+
+    positions()
+        .filter(|(x, y)| crop_sum(*x, *y) >= max_sum - tolerance)
+        .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+        .unwrap_or((0, 0))
+}
@@ -57,12 +57,78 @@ pub fn threshold<T>(in_channel: &mut [T], threshold: T, method: ThresholdMethod)
             {
                 for x in in_channel.iter_mut()
                 {
-                    *x = if *x > threshold { threshold } else { T::min_val() }
+                    *x = if *x > threshold { *x } else { min }
                 }
             }
     }
 }
 
+/// Find a threshold separating the samples into two classes
+/// using [Otsu's method](https://en.wikipedia.org/wiki/Otsu%27s_method)
+///
+/// The returned value is the largest sample of the darker class,
+/// so it can be passed to [`threshold`] directly
+///
+/// # Arguments
+/// - in_channel: Samples to compute the threshold for
+/// - max_value: Maximum value of a sample for this bit depth
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub fn otsu_threshold<T>(in_channel: &[T], max_value: u16) -> u16
+where
+    T: NumOps<T> + Copy
+{
+    let mut histogram = vec![0_u64; usize::from(max_value) + 1];
+
+    for x in in_channel
+    {
+        histogram[(*x).to_usize().min(usize::from(max_value))] += 1;
+    }
+
+    let total = in_channel.len() as f64;
+    let sum_all: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, count)| value as f64 * *count as f64)
+        .sum();
+
+    let mut best_threshold = 0;
+    let mut best_variance = 0.0;
+
+    let mut weight_background = 0.0;
+    let mut sum_background = 0.0;
+
+    for (value, count) in histogram.iter().enumerate()
+    {
+        weight_background += *count as f64;
+        sum_background += value as f64 * *count as f64;
+
+        let weight_foreground = total - weight_background;
+
+        if weight_background == 0.0
+        {
+            continue;
+        }
+        if weight_foreground == 0.0
+        {
+            break;
+        }
+        let mean_background = sum_background / weight_background;
+        let mean_foreground = (sum_all - sum_background) / weight_foreground;
+
+        // between class variance
+        let variance = weight_background
+            * weight_foreground
+            * (mean_background - mean_foreground).powi(2);
+
+        if variance > best_variance
+        {
+            best_variance = variance;
+            best_threshold = value;
+        }
+    }
+    best_threshold as u16
+}
+
 #[cfg(all(feature = "benchmarks"))]
 #[cfg(test)]
 mod benchmarks
@@ -26,3 +26,25 @@ fn test_transpose_sse_scalar_identical()
         assert_eq!(a, b);
     }
 }
+
+#[test]
+fn test_transpose_scalar_small_dimensions()
+{
+    use crate::transpose;
+
+    for (width, height) in [(1, 1), (7, 3), (13, 9), (8, 5), (3, 16)]
+    {
+        let in_matrix: Vec<u16> = (0..).take(width * height).collect();
+        let mut out_matrix = vec![0; width * height];
+
+        transpose::scalar::transpose_scalar(&in_matrix, &mut out_matrix, width, height);
+
+        for y in 0..height
+        {
+            for x in 0..width
+            {
+                assert_eq!(out_matrix[x * height + y], in_matrix[y * width + x]);
+            }
+        }
+    }
+}
@@ -1,29 +1,32 @@
 use crate::gaussian_blur::{gaussian_blur_u16, gaussian_blur_u8};
+use crate::traits::NumOps;
 
 ///  Sharpen an image
 ///
 ///  The underlying algorithm applies a gaussian blur
 /// to a copy of the image and compare it with the image,
-/// if difference is greater than threshold, we add it to the
-/// image
+/// if the difference is greater than threshold, we add a multiple of it
+/// to the image
 ///
 /// The formula is
 ///
-/// sharpened = original + (original − blurred);
+/// sharpened = original + amount * (original − blurred);
 ///
+/// Results saturate at zero and `u16::MAX`
 ///
 /// # Arguments
 /// - channel: Incoming pixels, output will be written to the same location
 /// - blur_buffer: Temporary location we use to store blur coefficients
 /// - blur_scratch_buffer: Temporary location we use during blurring to store blur coefficients
 /// - sigma: Radius of blur
-/// - threshold: If the difference between original and blurred is greater than this, add the diff to
-/// the pixel
+/// - threshold: If the absolute difference between original and blurred is greater than this,
+/// sharpen the pixel
+/// - amount: How much of the difference to add, `0.0` does nothing
 ///- width,height: Image dimensions.
 #[allow(clippy::too_many_arguments)]
 pub fn unsharpen_u16(
     channel: &mut [u16], blur_buffer: &mut [u16], blur_scratch_buffer: &mut [u16], sigma: f32,
-    threshold: u16, _percentage: u16, width: usize, height: usize
+    threshold: u16, amount: f32, width: usize, height: usize
 )
 {
     // copy channel to scratch space
@@ -32,48 +35,35 @@ pub fn unsharpen_u16(
     gaussian_blur_u16(blur_buffer, blur_scratch_buffer, width, height, sigma);
     // blur buffer now contains gaussian blurred pixels
     // so iterate replacing them
-    for (in_pix, blur_pix) in channel.iter_mut().zip(blur_buffer.iter())
-    {
-        let diff = in_pix.saturating_sub(*blur_pix);
-        // pull some branchless tricks to help the optimizer
-        // here
-
-        // We conditionally take the added version or whatever we had based on this mask
-        //  godbolt link: https://godbolt.org/z/YYnEaPedM
-
-        let threshold_mask = u16::from(diff > threshold).wrapping_sub(1);
-
-        // let diff = (diff * percentage) / 100;
-
-        // if diff > threshold { pix = (diff + pix) } else { pix }
-        *in_pix = (in_pix.wrapping_add(diff) & !threshold_mask) | (*in_pix & threshold_mask);
-    }
+    sharpen(channel, blur_buffer, threshold, amount);
 }
 
 ///  Sharpen an image
 ///
 ///  The underlying algorithm applies a gaussian blur
 /// to a copy of the image and compare it with the image,
-/// if difference is greater than threshold, we add it to the
-/// image
+/// if the difference is greater than threshold, we add a multiple of it
+/// to the image
 ///
 /// The formula is
 ///
-/// sharpened = original + (original − blurred);
+/// sharpened = original + amount * (original − blurred);
 ///
+/// Results saturate at zero and `u8::MAX`
 ///
 /// # Arguments
 /// - channel: Incoming pixels, output will be written to the same location
 /// - blur_buffer: Temporary location we use to store blur coefficients
 /// - blur_scratch_buffer: Temporary location we use during blurring to store blur coefficients
 /// - sigma: Radius of blur
-/// - threshold: If the difference between original and blurred is greater than this, add the diff to
-/// the pixel
+/// - threshold: If the absolute difference between original and blurred is greater than this,
+/// sharpen the pixel
+/// - amount: How much of the difference to add, `0.0` does nothing
 ///- width,height: Image dimensions.
 #[allow(clippy::too_many_arguments)]
 pub fn unsharpen_u8(
     channel: &mut [u8], blur_buffer: &mut [u8], blur_scratch_buffer: &mut [u8], sigma: f32,
-    threshold: u8, _percentage: u8, width: usize, height: usize
+    threshold: u8, amount: f32, width: usize, height: usize
 )
 {
     // copy channel to scratch space
@@ -82,17 +72,25 @@ pub fn unsharpen_u8(
     gaussian_blur_u8(blur_buffer, blur_scratch_buffer, width, height, sigma);
     // blur buffer now contains gaussian blurred pixels
     // so iterate replacing them
-    for (in_pix, blur_pix) in channel.iter_mut().zip(blur_buffer.iter())
-    {
-        let diff = in_pix.wrapping_sub(*blur_pix);
-        // pull some branchless tricks to help the optimizer
-        // here
+    sharpen(channel, blur_buffer, threshold, amount);
+}
 
-        // We conditionally take the added version or whatever we had based on this mask
-        //  godbolt link: https://godbolt.org/z/YYnEaPedM
-        let threshold_mask = u8::from(diff > threshold).wrapping_sub(1);
+#[allow(clippy::cast_possible_truncation)]
+fn sharpen<T>(channel: &mut [T], blurred: &[T], threshold: T, amount: f32)
+where
+    T: Copy + NumOps<T>
+{
+    let threshold = threshold.to_f64() as f32;
+    let max_value = T::max_val().to_f64() as f32;
+
+    for (in_pix, blur_pix) in channel.iter_mut().zip(blurred.iter())
+    {
+        let pix = in_pix.to_f64() as f32;
+        let diff = pix - blur_pix.to_f64() as f32;
 
-        // if diff > threshold { pix = (diff + pix) } else { pix }
-        *in_pix = (in_pix.wrapping_add(diff) & !threshold_mask) | (*in_pix & threshold_mask);
+        if diff.abs() > threshold
+        {
+            *in_pix = T::from_f32((pix + amount * diff).round().clamp(0.0, max_value));
+        }
     }
 }
@@ -0,0 +1,61 @@
+//! Checksums used by the deflate based formats
+//!
+//! - CRC-32, as used by gzip (RFC 1952) and PNG chunks
+//! - Adler-32, as used by zlib (RFC 1950)
+//!
+//! The `_update` variants continue a checksum from a previous call, so data can be
+//! hashed in pieces
+//!
+//! ```
+//! use zune_inflate::checksums::{adler32, adler32_update, crc32, crc32_update};
+//!
+//! assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+//! assert_eq!(crc32_update(crc32(b"1234"), b"56789"), crc32(b"123456789"));
+//!
+//! assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+//! assert_eq!(adler32_update(adler32(b"Wiki"), b"pedia"), adler32(b"Wikipedia"));
+//! ```
+
+/// Calculate the CRC-32 of `data`
+///
+/// This is the checksum stored in gzip trailers and PNG chunks
+pub fn crc32(data: &[u8]) -> u32
+{
+    crc32_update(0, data)
+}
+
+/// Continue a CRC-32 checksum with more data
+///
+/// `crc` is a value previously returned by [`crc32`] or this function,
+/// starting from `0` is the same as calling [`crc32`]
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32
+{
+    !crate::crc::crc32(data, !crc)
+}
+
+/// Calculate the Adler-32 checksum of `data`
+///
+/// This is the checksum stored at the end of zlib streams
+///
+/// # Note
+/// This needs the `zlib` feature enabled to be available otherwise it's a
+/// compile time error
+#[cfg(feature = "zlib")]
+pub fn adler32(data: &[u8]) -> u32
+{
+    crate::utils::calc_adler_hash(data)
+}
+
+/// Continue an Adler-32 checksum with more data
+///
+/// `adler` is a value previously returned by [`adler32`] or this function,
+/// starting from `1` is the same as calling [`adler32`]
+///
+/// # Note
+/// This needs the `zlib` feature enabled to be available otherwise it's a
+/// compile time error
+#[cfg(feature = "zlib")]
+pub fn adler32_update(adler: u32, data: &[u8]) -> u32
+{
+    crate::utils::update_adler_hash(adler, data)
+}
@@ -1,5 +1,3 @@
-#![cfg(feature = "gzip")]
-
 use crate::crc::crc_tables::{CRC32_SLICE1_TABLE, CRC32_SLICE8_TABLE};
 
 mod crc_tables;
@@ -48,9 +48,11 @@ impl Default for DeflateHeaderTables
 #[derive(Copy, Clone)]
 pub struct DeflateOptions
 {
-    limit:            usize,
-    confirm_checksum: bool,
-    size_hint:        usize
+    limit:                usize,
+    confirm_checksum:     bool,
+    size_hint:            usize,
+    multi_member:         bool,
+    ignore_trailing_data: bool
 }
 
 impl Default for DeflateOptions
@@ -58,9 +60,11 @@ impl Default for DeflateOptions
     fn default() -> Self
     {
         DeflateOptions {
-            limit:            1 << 30,
-            confirm_checksum: true,
-            size_hint:        37000
+            limit:                1 << 30,
+            confirm_checksum:     true,
+            size_hint:            37000,
+            multi_member:         true,
+            ignore_trailing_data: false
         }
     }
 }
@@ -74,9 +78,6 @@ impl DeflateOptions
     ///
     /// # Returns
     ///  The currently set limit of the instance
-    /// # Note
-    /// This is provided as a best effort, correctly quiting
-    /// is detrimental to speed and hence this should not be relied too much.
     pub const fn get_limit(&self) -> usize
     {
         self.limit
@@ -84,14 +85,22 @@ impl DeflateOptions
     /// Set a limit to the internal vector
     /// used to store decoded zlib/deflate output.
     ///
+    /// When the output would grow past the limit, decoding stops with
+    /// [`DecodeErrorStatus::OutputLimitExceeded`] holding the limit and the size
+    /// the output had reached, and the error's `data` holds the output decoded
+    /// so far, truncated to the limit.
+    ///
+    /// The limit is checked after each match, uncompressed block and huffman block,
+    /// so the decoder may briefly hold slightly more than `limit` bytes, but never
+    /// returns more.
+    ///
+    /// For gzip streams with multiple members, the limit applies to the output
+    /// of all members together.
+    ///
     /// # Arguments
     /// limit: The new decompressor limit
     /// # Returns
     /// A modified version of DeflateDecoder
-    ///
-    /// # Note
-    /// This is provided as a best effort, correctly quiting
-    /// is detrimental to speed and hence this should not be relied too much
     #[must_use]
     pub fn set_limit(mut self, limit: usize) -> Self
     {
@@ -136,12 +145,61 @@ impl DeflateOptions
     /// Set the size hint for the decompressor
     ///
     /// This can be used to prevent multiple re-allocations
+    ///
+    /// The initial allocation is the smaller of the hint and the
+    /// [limit](Self::set_limit), so a large hint doesn't defeat the limit
     #[must_use]
     pub const fn set_size_hint(mut self, hint: usize) -> Self
     {
         self.size_hint = hint;
         self
     }
+
+    /// Get whether the decoder will decode all members
+    /// of a gzip stream
+    pub const fn get_multi_member(&self) -> bool
+    {
+        self.multi_member
+    }
+    /// Set whether the decoder should decode all members of a gzip
+    /// stream, concatenating their output
+    ///
+    /// A gzip file may contain several members back to back, e.g
+    /// from appending to a compressed log, standard tools decompress all of them.
+    /// When false, decoding stops after the first member and anything after it is ignored.
+    ///
+    /// Default value is true
+    ///
+    /// # Notes
+    /// This only has an influence on gzip decoding
+    #[must_use]
+    pub const fn set_multi_member(mut self, yes: bool) -> Self
+    {
+        self.multi_member = yes;
+        self
+    }
+
+    /// Get whether the decoder ignores data after the
+    /// last member of a gzip stream
+    pub const fn get_ignore_trailing_data(&self) -> bool
+    {
+        self.ignore_trailing_data
+    }
+    /// Set whether the decoder should ignore data following the last
+    /// member of a gzip stream that isn't another member
+    ///
+    /// When false, such trailing data is an error.
+    ///
+    /// Default value is false
+    ///
+    /// # Notes
+    /// This only has an influence on gzip decoding
+    #[must_use]
+    pub const fn set_ignore_trailing_data(mut self, yes: bool) -> Self
+    {
+        self.ignore_trailing_data = yes;
+        self
+    }
 }
 
 /// A deflate decoder instance.
@@ -163,7 +221,9 @@ pub struct DeflateDecoder<'a>
     is_last_block:         bool,
     static_codes_loaded:   bool,
     deflate_header_tables: DeflateHeaderTables,
-    options:               DeflateOptions
+    options:               DeflateOptions,
+    dictionary:            &'a [u8],
+    adler32:               Option<u32>
 }
 
 impl<'a> DeflateDecoder<'a>
@@ -230,9 +290,36 @@ impl<'a> DeflateDecoder<'a>
             is_last_block: false,
             static_codes_loaded: false,
             deflate_header_tables: DeflateHeaderTables::default(),
-            options
+            options,
+            dictionary: &[],
+            adler32: None
         }
     }
+    /// Set a preset dictionary used to decode the stream
+    ///
+    /// The dictionary is treated as data decoded before the stream
+    /// so back-references can point into it, but it is not part of the output.
+    ///
+    /// For zlib streams it's only used if the header sets the `FDICT` flag
+    /// and the dictionary's adler32 hash must match the one the stream stores,
+    /// for raw deflate streams it's always used.
+    ///
+    /// A dictionary larger than the 32 KiB deflate window gains nothing, as only its
+    /// last 32 KiB can be referenced.
+    pub fn set_dictionary(&mut self, dictionary: &'a [u8])
+    {
+        self.dictionary = dictionary;
+    }
+    /// Return the adler32 checksum computed over the output
+    /// of the last zlib stream decoded
+    ///
+    /// This is set whenever the checksum is confirmed, even if it didn't
+    /// match the one stored in the stream, and is `None` if checksum confirmation
+    /// is disabled or no zlib stream has been decoded to that point.
+    pub const fn adler32(&self) -> Option<u32>
+    {
+        self.adler32
+    }
     /// Decode zlib-encoded data returning the uncompressed in a `Vec<u8>`
     /// or an error if something went wrong.
     ///
@@ -262,6 +349,8 @@ impl<'a> DeflateDecoder<'a>
     {
         use crate::utils::calc_adler_hash;
 
+        self.adler32 = None;
+
         if self.data.len()
             < 2 /* zlib header */
             + 4
@@ -282,8 +371,8 @@ impl<'a> DeflateDecoder<'a>
         let cinfo = cmf >> 4;
 
         // let fcheck = flg & 0xF;
-        // let fdict = (flg >> 4) & 1;
-        // let flevel = flg >> 5;
+        let fdict = (flg >> 5) & 1;
+        // let flevel = flg >> 6;
 
         // confirm we have the right deflate methods
         if cm != 8
@@ -317,7 +406,40 @@ impl<'a> DeflateDecoder<'a>
 
         self.position = 2;
 
-        let data = self.decode_deflate()?;
+        let mut dictionary: &[u8] = &[];
+
+        if fdict == 1
+        {
+            // a DICTID follows the header, the adler32 hash
+            // of the dictionary the stream was compressed with
+            let Some(dict_id) = self.data.get(2..6)
+            else
+            {
+                return Err(InflateDecodeErrors::new_with_error(
+                    DecodeErrorStatus::InsufficientData
+                ));
+            };
+            let dict_id = u32::from_be_bytes(dict_id.try_into().unwrap());
+
+            if self.dictionary.is_empty()
+            {
+                return Err(InflateDecodeErrors::new_with_error(
+                    DecodeErrorStatus::Generic("Stream needs a preset dictionary but none was set")
+                ));
+            }
+            if calc_adler_hash(self.dictionary) != dict_id
+            {
+                return Err(InflateDecodeErrors::new_with_error(
+                    DecodeErrorStatus::Generic(
+                        "Preset dictionary does not match the one used to compress the stream"
+                    )
+                ));
+            }
+            dictionary = self.dictionary;
+            self.position = 6;
+        }
+
+        let data = self.inflate_with_dictionary(dictionary)?;
 
         if self.options.confirm_checksum
         {
@@ -333,6 +455,8 @@ impl<'a> DeflateDecoder<'a>
 
                 let adler32_found = calc_adler_hash(&data);
 
+                self.adler32 = Some(adler32_found);
+
                 if adler32_expected != adler32_found
                 {
                     let err_msg =
@@ -359,6 +483,15 @@ impl<'a> DeflateDecoder<'a>
     /// Bytes consumed will be from the data passed when the
     /// `new` method was called.
     ///
+    /// Optional header fields (extra field, file name, comment) are skipped,
+    /// and when checksums are confirmed the header crc, the crc32 of the
+    /// output and its size (`ISIZE`) are verified.
+    ///
+    /// If the stream contains several members back to back, all of them are decoded
+    /// and their outputs concatenated, see [`DeflateOptions::set_multi_member`] and
+    /// [`DeflateOptions::set_ignore_trailing_data`] to change how data following
+    /// the first member is treated.
+    ///
     /// # Arguments
     /// - None
     /// # Returns
@@ -379,7 +512,67 @@ impl<'a> DeflateDecoder<'a>
     #[cfg(feature = "gzip")]
     pub fn decode_gzip(&mut self) -> Result<Vec<u8>, InflateDecodeErrors>
     {
-        if self.data.len() < 18
+        let mut out = self.decode_gzip_member()?;
+
+        if !self.options.multi_member
+        {
+            return Ok(out);
+        }
+
+        while let Some(remaining) = self.data.get(self.position..)
+        {
+            if remaining.is_empty()
+            {
+                break;
+            }
+            if !remaining.starts_with(&[GZIP_ID1, GZIP_ID2])
+            {
+                if self.options.ignore_trailing_data
+                {
+                    break;
+                }
+                let err = DecodeErrorStatus::Generic("Trailing data after last gzip member");
+
+                return Err(InflateDecodeErrors::new(err, out));
+            }
+            match self.decode_gzip_member()
+            {
+                Ok(member) => out.extend_from_slice(&member),
+                Err(mut err) =>
+                {
+                    // limits apply to the output of all members
+                    if let DecodeErrorStatus::OutputLimitExceeded(_, produced) = &mut err.error
+                    {
+                        *produced += out.len();
+                    }
+                    out.append(&mut err.data);
+                    out.truncate(self.options.limit);
+                    err.data = out;
+
+                    return Err(err);
+                }
+            }
+            if out.len() > self.options.limit
+            {
+                let produced = out.len();
+
+                out.truncate(self.options.limit);
+
+                return Err(limit_exceeded(out, self.options.limit, produced));
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Decode a single gzip member starting at the current position,
+    /// leaving the position after the member
+    #[cfg(feature = "gzip")]
+    fn decode_gzip_member(&mut self) -> Result<Vec<u8>, InflateDecodeErrors>
+    {
+        let member_start = self.position;
+
+        if self.data.len().saturating_sub(member_start) < 18
         {
             return Err(InflateDecodeErrors::new_with_error(
                 DecodeErrorStatus::InsufficientData
@@ -487,9 +680,31 @@ impl<'a> DeflateDecoder<'a>
                 }
             }
         }
-        // crc16 for gzip header
+        // crc16 for gzip header, the low 16 bits of the crc32
+        // of all header bytes preceding it
         if (flg & GZIP_FHCRC) != 0
         {
+            let Some(crc_bytes) = self.data.get(self.position..self.position + 2)
+            else
+            {
+                return Err(InflateDecodeErrors::new_with_error(
+                    DecodeErrorStatus::InsufficientData
+                ));
+            };
+
+            if self.options.confirm_checksum
+            {
+                let crc16_expected = u32::from(u16::from_le_bytes(crc_bytes.try_into().unwrap()));
+                let header = &self.data[member_start..self.position];
+                let crc16_found = !crate::crc::crc32(header, !0) & 0xFFFF;
+
+                if crc16_expected != crc16_found
+                {
+                    return Err(InflateDecodeErrors::new_with_error(
+                        DecodeErrorStatus::MismatchedCRC(crc16_expected, crc16_found)
+                    ));
+                }
+            }
             self.position += 2;
         }
 
@@ -500,7 +715,7 @@ impl<'a> DeflateDecoder<'a>
             ));
         }
 
-        let data = self.decode_deflate()?;
+        let data = self.inflate_with_dictionary(&[])?;
 
         let mut out_pos = self.stream.get_position() + self.position + self.stream.over_read;
 
@@ -537,9 +752,11 @@ impl<'a> DeflateDecoder<'a>
         if let Some(val) = self.data.get(out_pos..out_pos + 4)
         {
             let actual_bytes: [u8; 4] = val.try_into().unwrap();
-            let ac = u32::from_le_bytes(actual_bytes) as usize;
+            let ac = u32::from_le_bytes(actual_bytes);
 
-            if data.len() != ac
+            // ISIZE stores the size modulo 2^32
+            #[allow(clippy::cast_possible_truncation)]
+            if data.len() as u32 != ac
             {
                 let err = DecodeErrorStatus::Generic("ISIZE does not match actual bytes");
 
@@ -547,6 +764,7 @@ impl<'a> DeflateDecoder<'a>
 
                 return Err(err);
             }
+            self.position = out_pos + 4;
         }
         else
         {
@@ -582,11 +800,32 @@ impl<'a> DeflateDecoder<'a>
     ///  [InflateDecodeErrors]:crate::errors::InflateDecodeErrors
     pub fn decode_deflate(&mut self) -> Result<Vec<u8>, InflateDecodeErrors>
     {
-        self.start_deflate_block()
+        self.inflate_with_dictionary(self.dictionary)
+    }
+    /// Decompress deflate data with `dictionary` preceding it, removing
+    /// the dictionary from the output
+    fn inflate_with_dictionary(&mut self, dictionary: &[u8])
+        -> Result<Vec<u8>, InflateDecodeErrors>
+    {
+        let dict_len = dictionary.len();
+
+        match self.start_deflate_block(dictionary)
+        {
+            Ok(mut data) =>
+            {
+                data.drain(..dict_len);
+                Ok(data)
+            }
+            Err(mut err) =>
+            {
+                err.data.drain(..dict_len.min(err.data.len()));
+                Err(err)
+            }
+        }
     }
     /// Main inner loop for decompressing deflate data
     #[allow(unused_assignments)]
-    fn start_deflate_block(&mut self) -> Result<Vec<u8>, InflateDecodeErrors>
+    fn start_deflate_block(&mut self, dictionary: &[u8]) -> Result<Vec<u8>, InflateDecodeErrors>
     {
         // start deflate decode
         // re-read the stream so that we can remove code read by zlib
@@ -594,12 +833,20 @@ impl<'a> DeflateDecoder<'a>
 
         self.stream.refill();
 
-        // Output space for our decoded bytes.
-        let mut out_block = vec![0; self.options.size_hint];
+        // Output space for our decoded bytes, starting with the dictionary
+        // so back-references can reach into it.
+        // the hint is capped to the limit so that a large hint can't allocate
+        // more than the limit allows
+        let initial_size = self.options.size_hint.min(self.options.limit);
+        let mut out_block = vec![0; dictionary.len() + initial_size];
+        out_block[..dictionary.len()].copy_from_slice(dictionary);
+        // the dictionary doesn't count towards the output limit
+        let dict_len = dictionary.len();
+        let limit = self.options.limit + dict_len;
         // bits used
 
         let mut src_offset = 0;
-        let mut dest_offset = 0;
+        let mut dest_offset = dictionary.len();
 
         loop
         {
@@ -652,6 +899,16 @@ impl<'a> DeflateDecoder<'a>
 
                 let start = self.stream.get_position() + self.position + self.stream.over_read;
 
+                if dest_offset + len > limit
+                {
+                    out_block.truncate(dest_offset);
+
+                    let error =
+                        limit_exceeded(out_block, self.options.limit, dest_offset + len - dict_len);
+
+                    return Err(error);
+                }
+
                 // ensure there is enough space for a fast copy
                 if dest_offset + len + FASTCOPY_BYTES > out_block.len()
                 {
@@ -663,24 +920,19 @@ impl<'a> DeflateDecoder<'a>
 
                 if self.data.get((start + len).saturating_sub(1)).is_none()
                 {
-                    out_block.truncate(dest_offset);
+                    // keep the bytes of the block that are present, so that
+                    // truncated streams can be partially recovered
+                    let available = self.data.get(start..).unwrap_or(&[]);
 
-                    let err_msg = DecodeErrorStatus::CorruptData;
-                    let error = InflateDecodeErrors::new(err_msg, out_block);
-
-                    return Err(error);
-                }
-                if dest_offset > self.options.limit
-                {
-                    out_block.truncate(dest_offset);
+                    out_block[dest_offset..dest_offset + available.len()]
+                        .copy_from_slice(available);
+                    out_block.truncate(dest_offset + available.len());
 
-                    let err_msg =
-                        DecodeErrorStatus::OutputLimitExceeded(self.options.limit, out_block.len());
+                    let err_msg = DecodeErrorStatus::CorruptData;
                     let error = InflateDecodeErrors::new(err_msg, out_block);
 
                     return Err(error);
                 }
-
                 out_block[dest_offset..dest_offset + len]
                     .copy_from_slice(&self.data[start..start + len]);
 
@@ -1044,15 +1296,15 @@ impl<'a> DeflateDecoder<'a>
                             }
                         }
 
-                        if dest_offset > self.options.limit
+                        if dest_offset > limit
                         {
-                            out_block.truncate(dest_offset);
+                            out_block.truncate(limit);
 
-                            let err_msg = DecodeErrorStatus::OutputLimitExceeded(
+                            let error = limit_exceeded(
+                                out_block,
                                 self.options.limit,
-                                dest_offset
+                                dest_offset - dict_len
                             );
-                            let error = InflateDecodeErrors::new(err_msg, out_block);
 
                             return Err(error);
                         }
@@ -1178,13 +1430,12 @@ impl<'a> DeflateDecoder<'a>
 
                     dest_offset += length;
 
-                    if dest_offset > self.options.limit
+                    if dest_offset > limit
                     {
-                        out_block.truncate(dest_offset);
+                        out_block.truncate(limit);
 
-                        let err_msg =
-                            DecodeErrorStatus::OutputLimitExceeded(self.options.limit, dest_offset);
-                        let error = InflateDecodeErrors::new(err_msg, out_block);
+                        let error =
+                            limit_exceeded(out_block, self.options.limit, dest_offset - dict_len);
 
                         return Err(error);
                     }
@@ -1204,6 +1455,16 @@ impl<'a> DeflateDecoder<'a>
                 return Err(error);
             }
 
+            // literals aren't checked as they are decoded, catch them here
+            if dest_offset > limit
+            {
+                out_block.truncate(limit);
+
+                let error = limit_exceeded(out_block, self.options.limit, dest_offset - dict_len);
+
+                return Err(error);
+            }
+
             if self.is_last_block
             {
                 break;
@@ -1777,6 +2038,15 @@ impl<'a> DeflateDecoder<'a>
 
 const RESIZE_BY: usize = 1024 * 4; // 4 kb
 
+/// Create the error returned when the output grows past `limit`
+///
+/// `data` should hold the output decoded so far, up to the limit,
+/// and `produced` the size the output reached before decoding stopped
+fn limit_exceeded(data: Vec<u8>, limit: usize, produced: usize) -> InflateDecodeErrors
+{
+    InflateDecodeErrors::new(DecodeErrorStatus::OutputLimitExceeded(limit, produced), data)
+}
+
 /// Resize vector if its current space wont
 /// be able to store a new byte and then push an element to that new space
 #[inline(always)]
@@ -0,0 +1,771 @@
+//! A simple deflate encoder
+//!
+//! This uses a greedy LZ77 matcher with hash chains. Matches are split into
+//! blocks, and each block is written with whichever of dynamic huffman codes,
+//! the fixed huffman codes defined in RFC 1951 or no compression
+//! produces the smallest output.
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use crate::constants::{
+    DEFLATE_MAX_CODEWORD_LENGTH, DEFLATE_MAX_MATCH_LEN, DEFLATE_MAX_PRE_CODEWORD_LEN,
+    DEFLATE_NUM_LITLEN_SYMS, DEFLATE_NUM_OFFSET_SYMS, DEFLATE_NUM_PRECODE_SYMS,
+    DEFLATE_PRECODE_LENS_PERMUTATION, DIST_BASE, DIST_EXTRA, LENGTH_BASE, LENGTH_EXTRA
+};
+
+/// Minimum match length supported by deflate
+const MIN_MATCH_LEN: usize = 3;
+/// Size of the sliding window, matches can refer at most this far back
+const WINDOW_SIZE: usize = 32768;
+const WINDOW_MASK: usize = WINDOW_SIZE - 1;
+
+const HASH_BITS: usize = 15;
+const HASH_SIZE: usize = 1 << HASH_BITS;
+
+/// Maximum size of an uncompressed block
+const MAX_STORED_BLOCK: usize = 65535;
+
+/// Number of symbols collected before they are written out as a block
+const MAX_BLOCK_SYMBOLS: usize = 1 << 14;
+
+/// Symbol ending a block
+const END_OF_BLOCK: usize = 256;
+
+/// Number of hash chain entries searched for each compression level
+const MAX_CHAIN: [usize; 10] = [0, 4, 8, 16, 32, 64, 128, 256, 1024, 4096];
+
+/// Options that influence compression in Deflate/Zlib
+#[derive(Copy, Clone, Debug)]
+pub struct DeflateEncodeOptions
+{
+    level: u8
+}
+
+impl Default for DeflateEncodeOptions
+{
+    fn default() -> Self
+    {
+        DeflateEncodeOptions { level: 6 }
+    }
+}
+
+impl DeflateEncodeOptions
+{
+    /// Get the compression level
+    pub const fn get_level(&self) -> u8
+    {
+        self.level
+    }
+    /// Set the compression level
+    ///
+    /// Levels range from 0 to 9, where 0 stores data without compression and
+    /// higher levels spend more time looking for matches to produce smaller output.
+    ///
+    /// Values above 9 are treated as 9
+    ///
+    /// Default is 6
+    pub fn set_level(mut self, level: u8) -> Self
+    {
+        self.level = level.min(9);
+        self
+    }
+}
+
+/// A deflate encoder instance
+///
+/// Data is compressed in a single pass and the whole
+/// output is returned in one buffer
+pub struct DeflateEncoder<'a>
+{
+    data:    &'a [u8],
+    options: DeflateEncodeOptions
+}
+
+impl<'a> DeflateEncoder<'a>
+{
+    /// Create a new encoder that will compress `data` with
+    /// the default options
+    pub fn new(data: &'a [u8]) -> DeflateEncoder<'a>
+    {
+        DeflateEncoder::new_with_options(data, DeflateEncodeOptions::default())
+    }
+    /// Create a new encoder that will compress `data` with
+    /// the specified options
+    pub fn new_with_options(data: &'a [u8], options: DeflateEncodeOptions) -> DeflateEncoder<'a>
+    {
+        DeflateEncoder { data, options }
+    }
+
+    /// Compress data into a raw deflate stream
+    pub fn encode_deflate(&mut self) -> Vec<u8>
+    {
+        let mut out = Vec::with_capacity(self.data.len() / 2 + 64);
+
+        self.encode_deflate_into(&mut out);
+
+        out
+    }
+
+    /// Compress data into a zlib stream
+    ///
+    /// This is the deflate stream wrapped with a zlib header and
+    /// an Adler-32 checksum of the uncompressed data
+    #[cfg(feature = "zlib")]
+    pub fn encode_zlib(&mut self) -> Vec<u8>
+    {
+        let mut out = Vec::with_capacity(self.data.len() / 2 + 64);
+
+        // CM = 8 (deflate), CINFO = 7 (32K window)
+        let cmf = 0x78_u8;
+        // FLEVEL, see RFC 1950
+        let flevel = match self.options.level
+        {
+            0 | 1 => 0,
+            2..=5 => 1,
+            6 => 2,
+            _ => 3
+        };
+        let mut flg = flevel << 6;
+        // FCHECK makes the header a multiple of 31
+        flg |= 31 - ((u16::from(cmf) << 8 | u16::from(flg)) % 31) as u8;
+
+        out.push(cmf);
+        out.push(flg);
+
+        self.encode_deflate_into(&mut out);
+
+        out.extend_from_slice(&crate::utils::calc_adler_hash(self.data).to_be_bytes());
+
+        out
+    }
+
+    /// Compress data into a gzip stream
+    ///
+    /// This is the deflate stream wrapped with a minimal gzip header, without a
+    /// file name or modification time, and a trailer with the CRC-32 and size
+    /// of the uncompressed data
+    #[cfg(feature = "gzip")]
+    pub fn encode_gzip(&mut self) -> Vec<u8>
+    {
+        use crate::gzip_constants::{GZIP_CM_DEFLATE, GZIP_ID1, GZIP_ID2};
+
+        let mut out = Vec::with_capacity(self.data.len() / 2 + 64);
+
+        // XFL, see RFC 1952
+        let xfl = match self.options.level
+        {
+            1 => 4,
+            9 => 2,
+            _ => 0
+        };
+        // ID, CM, FLG, MTIME, XFL and OS (unknown)
+        out.extend_from_slice(&[GZIP_ID1, GZIP_ID2, GZIP_CM_DEFLATE, 0]);
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&[xfl, 255]);
+
+        self.encode_deflate_into(&mut out);
+
+        out.extend_from_slice(&crate::checksums::crc32(self.data).to_le_bytes());
+        out.extend_from_slice(&(self.data.len() as u32).to_le_bytes());
+
+        out
+    }
+
+    fn encode_deflate_into(&self, out: &mut Vec<u8>)
+    {
+        let mut writer = BitWriter::new(out);
+
+        if self.options.level == 0
+        {
+            write_stored(self.data, true, &mut writer);
+        }
+        else
+        {
+            compress(
+                self.data,
+                MAX_CHAIN[usize::from(self.options.level)],
+                &mut writer
+            );
+        }
+        writer.flush();
+    }
+}
+
+/// A literal byte or a match found in the input
+#[derive(Copy, Clone)]
+enum Token
+{
+    Literal(u8),
+    Match { length: u16, dist: u16 }
+}
+
+impl Token
+{
+    /// Return the literal/length symbol, plus the length code
+    /// and distance code for matches
+    fn symbols(self) -> (usize, Option<(usize, usize)>)
+    {
+        match self
+        {
+            Token::Literal(byte) => (usize::from(byte), None),
+            Token::Match { length, dist } =>
+            {
+                let length_code = LENGTH_BASE.partition_point(|x| *x <= length) - 1;
+                let dist_code = DIST_BASE.partition_point(|x| *x <= dist) - 1;
+
+                (257 + length_code, Some((length_code, dist_code)))
+            }
+        }
+    }
+}
+
+/// Write data as a series of uncompressed blocks
+fn write_stored(data: &[u8], is_final: bool, writer: &mut BitWriter)
+{
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+
+    loop
+    {
+        // empty data is written as a single empty block
+        let block = blocks.next().unwrap_or(&[]);
+        let is_last = blocks.peek().is_none();
+        let length = block.len() as u16;
+
+        // block header, padded to a byte boundary
+        writer.write_bits(u32::from(is_final && is_last), 1);
+        writer.write_bits(0, 2);
+        writer.flush();
+
+        writer.out.extend_from_slice(&length.to_le_bytes());
+        writer.out.extend_from_slice(&(!length).to_le_bytes());
+        writer.out.extend_from_slice(block);
+
+        if is_last
+        {
+            break;
+        }
+    }
+}
+
+/// Find matches in data and write them out in blocks
+fn compress(data: &[u8], max_chain: usize, writer: &mut BitWriter)
+{
+    // most recent position for each hash, offset by one so zero means empty
+    let mut head = vec![0_u32; HASH_SIZE];
+    // previous position with the same hash, indexed by position in the window
+    let mut prev = vec![0_u32; WINDOW_SIZE];
+
+    let mut tokens = Vec::with_capacity(MAX_BLOCK_SYMBOLS);
+    let mut block_start = 0;
+
+    let hash = |pos: usize| -> usize {
+        let value =
+            u32::from(data[pos]) << 16 | u32::from(data[pos + 1]) << 8 | u32::from(data[pos + 2]);
+
+        (value.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize
+    };
+
+    let insert = |pos: usize, head: &mut [u32], prev: &mut [u32]| {
+        if pos + MIN_MATCH_LEN <= data.len()
+        {
+            let h = hash(pos);
+            prev[pos & WINDOW_MASK] = head[h];
+            head[h] = pos as u32 + 1;
+        }
+    };
+
+    let mut pos = 0;
+
+    while pos < data.len()
+    {
+        let mut best_len = 0;
+        let mut best_dist = 0;
+
+        if pos + MIN_MATCH_LEN <= data.len()
+        {
+            let max_len = (data.len() - pos).min(DEFLATE_MAX_MATCH_LEN);
+            let mut candidate = head[hash(pos)] as usize;
+            let mut chain = max_chain;
+
+            while candidate > 0 && chain > 0
+            {
+                let candidate_pos = candidate - 1;
+                let dist = pos - candidate_pos;
+
+                if dist > WINDOW_SIZE
+                {
+                    break;
+                }
+                let length = data[candidate_pos..]
+                    .iter()
+                    .zip(&data[pos..pos + max_len])
+                    .take_while(|(a, b)| a == b)
+                    .count();
+
+                if length > best_len
+                {
+                    best_len = length;
+                    best_dist = dist;
+
+                    if length == max_len
+                    {
+                        break;
+                    }
+                }
+                let next = prev[candidate_pos & WINDOW_MASK] as usize;
+
+                // chain entries are overwritten as the window slides, stop
+                // when we see an entry that isn't older than the current one
+                if next >= candidate
+                {
+                    break;
+                }
+                candidate = next;
+                chain -= 1;
+            }
+        }
+
+        if best_len >= MIN_MATCH_LEN
+        {
+            tokens.push(Token::Match {
+                length: best_len as u16,
+                dist:   best_dist as u16
+            });
+
+            for i in pos..pos + best_len
+            {
+                insert(i, &mut head, &mut prev);
+            }
+            pos += best_len;
+        }
+        else
+        {
+            tokens.push(Token::Literal(data[pos]));
+            insert(pos, &mut head, &mut prev);
+            pos += 1;
+        }
+
+        if tokens.len() == MAX_BLOCK_SYMBOLS && pos < data.len()
+        {
+            write_block(&tokens, &data[block_start..pos], false, writer);
+            tokens.clear();
+            block_start = pos;
+        }
+    }
+    write_block(&tokens, &data[block_start..], true, writer);
+}
+
+/// Write a block using whichever encoding is smallest
+///
+/// `data` is the input the tokens were created from
+fn write_block(tokens: &[Token], data: &[u8], is_final: bool, writer: &mut BitWriter)
+{
+    let mut litlen_freqs = [0_u32; DEFLATE_NUM_LITLEN_SYMS];
+    let mut dist_freqs = [0_u32; DEFLATE_NUM_OFFSET_SYMS];
+    // extra bits are the same for both huffman encodings
+    let mut extra_bits = 0;
+
+    for token in tokens
+    {
+        let (symbol, codes) = token.symbols();
+
+        litlen_freqs[symbol] += 1;
+
+        if let Some((length_code, dist_code)) = codes
+        {
+            dist_freqs[dist_code] += 1;
+            extra_bits += usize::from(LENGTH_EXTRA[length_code] + DIST_EXTRA[dist_code]);
+        }
+    }
+    litlen_freqs[END_OF_BLOCK] += 1;
+
+    let fixed_litlen = fixed_litlen_lengths();
+    let fixed_dist = [5; DEFLATE_NUM_OFFSET_SYMS];
+
+    let dynamic_litlen = build_lengths(&litlen_freqs, DEFLATE_MAX_CODEWORD_LENGTH as u8);
+    let dynamic_dist = build_lengths(&dist_freqs, DEFLATE_MAX_CODEWORD_LENGTH as u8);
+    let header = DynamicHeader::new(&dynamic_litlen, &dynamic_dist);
+
+    let fixed_size = 3
+        + extra_bits
+        + encoded_size(&litlen_freqs, &fixed_litlen)
+        + encoded_size(&dist_freqs, &fixed_dist);
+    let dynamic_size = 3
+        + header.size()
+        + extra_bits
+        + encoded_size(&litlen_freqs, &dynamic_litlen)
+        + encoded_size(&dist_freqs, &dynamic_dist);
+    // header and worst case padding for each block
+    let stored_size = (data.len() + 5 * data.len().div_ceil(MAX_STORED_BLOCK).max(1)) * 8;
+
+    if stored_size < fixed_size.min(dynamic_size)
+    {
+        write_stored(data, is_final, writer);
+        return;
+    }
+    writer.write_bits(u32::from(is_final), 1);
+
+    if dynamic_size < fixed_size
+    {
+        writer.write_bits(2, 2);
+        header.write(writer);
+        write_tokens(tokens, &dynamic_litlen, &dynamic_dist, writer);
+    }
+    else
+    {
+        writer.write_bits(1, 2);
+        write_tokens(tokens, &fixed_litlen, &fixed_dist, writer);
+    }
+}
+
+/// Write tokens and the end of block symbol with the given code lengths
+fn write_tokens(tokens: &[Token], litlen_lengths: &[u8], dist_lengths: &[u8], writer: &mut BitWriter)
+{
+    let litlen_codes = canonical_codes(litlen_lengths);
+    let dist_codes = canonical_codes(dist_lengths);
+
+    for token in tokens
+    {
+        let (symbol, codes) = token.symbols();
+
+        writer.write_code(litlen_codes[symbol], litlen_lengths[symbol]);
+
+        if let (Token::Match { length, dist }, Some((length_code, dist_code))) = (token, codes)
+        {
+            writer.write_bits(
+                u32::from(length - LENGTH_BASE[length_code]),
+                LENGTH_EXTRA[length_code]
+            );
+            writer.write_code(dist_codes[dist_code], dist_lengths[dist_code]);
+            writer.write_bits(
+                u32::from(dist - DIST_BASE[dist_code]),
+                DIST_EXTRA[dist_code]
+            );
+        }
+    }
+    writer.write_code(litlen_codes[END_OF_BLOCK], litlen_lengths[END_OF_BLOCK]);
+}
+
+/// Size in bits of symbols with the given frequencies and code lengths
+fn encoded_size(freqs: &[u32], lengths: &[u8]) -> usize
+{
+    freqs
+        .iter()
+        .zip(lengths)
+        .map(|(freq, length)| *freq as usize * usize::from(*length))
+        .sum()
+}
+
+/// Code lengths of the fixed literal/length code, see RFC 1951 section 3.2.6
+fn fixed_litlen_lengths() -> [u8; DEFLATE_NUM_LITLEN_SYMS]
+{
+    let mut lengths = [8; DEFLATE_NUM_LITLEN_SYMS];
+
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+
+    lengths
+}
+
+/// The code lengths of a dynamic block, run length encoded with the precode
+struct DynamicHeader
+{
+    num_litlen:      usize,
+    num_dist:        usize,
+    num_precode:     usize,
+    /// Precode symbols and the value of their extra bits
+    symbols:         Vec<(u8, u8)>,
+    precode_lengths: Vec<u8>
+}
+
+impl DynamicHeader
+{
+    fn new(litlen_lengths: &[u8], dist_lengths: &[u8]) -> DynamicHeader
+    {
+        // trailing unused codes don't need to be sent
+        let num_litlen = litlen_lengths.iter().rposition(|x| *x != 0).unwrap() + 1;
+        let num_dist = dist_lengths.iter().rposition(|x| *x != 0).unwrap() + 1;
+
+        // both codes are run length encoded as one sequence
+        let mut lengths = litlen_lengths[..num_litlen].to_vec();
+        lengths.extend_from_slice(&dist_lengths[..num_dist]);
+
+        let symbols = run_length_encode(&lengths);
+
+        let mut precode_freqs = [0_u32; DEFLATE_NUM_PRECODE_SYMS];
+
+        for (symbol, _) in &symbols
+        {
+            precode_freqs[usize::from(*symbol)] += 1;
+        }
+        let precode_lengths = build_lengths(&precode_freqs, DEFLATE_MAX_PRE_CODEWORD_LEN);
+
+        let num_precode = DEFLATE_PRECODE_LENS_PERMUTATION
+            .iter()
+            .rposition(|x| precode_lengths[usize::from(*x)] != 0)
+            .unwrap()
+            .max(3)
+            + 1;
+
+        DynamicHeader {
+            num_litlen,
+            num_dist,
+            num_precode,
+            symbols,
+            precode_lengths
+        }
+    }
+
+    /// Size of the header in bits, excluding the block type
+    fn size(&self) -> usize
+    {
+        let symbols: usize = self
+            .symbols
+            .iter()
+            .map(|(symbol, _)| {
+                usize::from(self.precode_lengths[usize::from(*symbol)] + precode_extra_bits(*symbol))
+            })
+            .sum();
+
+        5 + 5 + 4 + 3 * self.num_precode + symbols
+    }
+
+    fn write(&self, writer: &mut BitWriter)
+    {
+        writer.write_bits((self.num_litlen - 257) as u32, 5);
+        writer.write_bits((self.num_dist - 1) as u32, 5);
+        writer.write_bits((self.num_precode - 4) as u32, 4);
+
+        for position in &DEFLATE_PRECODE_LENS_PERMUTATION[..self.num_precode]
+        {
+            writer.write_bits(u32::from(self.precode_lengths[usize::from(*position)]), 3);
+        }
+        let codes = canonical_codes(&self.precode_lengths);
+
+        for (symbol, extra) in &self.symbols
+        {
+            let symbol = usize::from(*symbol);
+
+            writer.write_code(codes[symbol], self.precode_lengths[symbol]);
+            writer.write_bits(u32::from(*extra), precode_extra_bits(symbol as u8));
+        }
+    }
+}
+
+/// Number of extra bits following a precode symbol
+fn precode_extra_bits(symbol: u8) -> u8
+{
+    match symbol
+    {
+        16 => 2,
+        17 => 3,
+        18 => 7,
+        _ => 0
+    }
+}
+
+/// Encode code lengths with the precode symbols, see RFC 1951 section 3.2.7
+fn run_length_encode(lengths: &[u8]) -> Vec<(u8, u8)>
+{
+    let mut symbols = Vec::new();
+    let mut i = 0;
+
+    while i < lengths.len()
+    {
+        let length = lengths[i];
+        let run = lengths[i..].iter().take_while(|x| **x == length).count();
+        let mut remaining = run;
+
+        if length == 0
+        {
+            while remaining >= 11
+            {
+                let count = remaining.min(138);
+
+                symbols.push((18, (count - 11) as u8));
+                remaining -= count;
+            }
+            if remaining >= 3
+            {
+                symbols.push((17, (remaining - 3) as u8));
+                remaining = 0;
+            }
+        }
+        else
+        {
+            // repeats copy the previous length, so send it once first
+            symbols.push((length, 0));
+            remaining -= 1;
+
+            while remaining >= 3
+            {
+                let count = remaining.min(6);
+
+                symbols.push((16, (count - 3) as u8));
+                remaining -= count;
+            }
+        }
+        symbols.extend((0..remaining).map(|_| (length, 0)));
+
+        i += run;
+    }
+    symbols
+}
+
+/// Build huffman code lengths for symbols with the given frequencies,
+/// no longer than `max_length`
+///
+/// Unused symbols get a length of zero. At least two symbols always get a code,
+/// so the code is complete even when fewer are used.
+fn build_lengths(freqs: &[u32], max_length: u8) -> Vec<u8>
+{
+    let mut freqs = freqs.to_vec();
+
+    if freqs.iter().filter(|x| **x > 0).count() < 2
+    {
+        for freq in &mut freqs[..2]
+        {
+            *freq = (*freq).max(1);
+        }
+    }
+    loop
+    {
+        let lengths = huffman_lengths(&freqs);
+
+        if lengths.iter().all(|x| *x <= max_length)
+        {
+            return lengths;
+        }
+        // flatten the distribution until the code fits, with equal frequencies
+        // every code fits since there are at most 288 symbols
+        for freq in freqs.iter_mut().filter(|x| **x > 0)
+        {
+            *freq = (*freq / 2).max(1);
+        }
+    }
+}
+
+/// Build optimal huffman code lengths, without a length limit
+fn huffman_lengths(freqs: &[u32]) -> Vec<u8>
+{
+    let num_symbols = freqs.len();
+    // leaves are the symbols, internal nodes are numbered after them
+    let mut parents = vec![0; 2 * num_symbols];
+    let mut heap: BinaryHeap<Reverse<(u32, usize)>> = freqs
+        .iter()
+        .enumerate()
+        .filter(|(_, freq)| **freq > 0)
+        .map(|(symbol, freq)| Reverse((*freq, symbol)))
+        .collect();
+
+    let mut next_node = num_symbols;
+
+    while heap.len() > 1
+    {
+        let Reverse((freq_a, a)) = heap.pop().unwrap();
+        let Reverse((freq_b, b)) = heap.pop().unwrap();
+
+        parents[a] = next_node;
+        parents[b] = next_node;
+        heap.push(Reverse((freq_a + freq_b, next_node)));
+
+        next_node += 1;
+    }
+    // parents always come after their children, so depths can be
+    // found walking down from the root
+    let root = next_node - 1;
+    let mut depths = vec![0_u8; 2 * num_symbols];
+
+    for node in (0..root).rev()
+    {
+        depths[node] = depths[parents[node]] + 1;
+    }
+    freqs
+        .iter()
+        .zip(&depths)
+        .map(|(freq, depth)| if *freq > 0 { *depth } else { 0 })
+        .collect()
+}
+
+/// Assign canonical huffman codes to symbols with the given code lengths,
+/// see RFC 1951 section 3.2.2
+fn canonical_codes(lengths: &[u8]) -> Vec<u32>
+{
+    let mut length_counts = [0_u32; DEFLATE_MAX_CODEWORD_LENGTH + 1];
+
+    for length in lengths
+    {
+        length_counts[usize::from(*length)] += 1;
+    }
+    length_counts[0] = 0;
+
+    let mut next_code = [0_u32; DEFLATE_MAX_CODEWORD_LENGTH + 1];
+    let mut code = 0;
+
+    for length in 1..=DEFLATE_MAX_CODEWORD_LENGTH
+    {
+        code = (code + length_counts[length - 1]) << 1;
+        next_code[length] = code;
+    }
+    lengths
+        .iter()
+        .map(|length| {
+            let code = next_code[usize::from(*length)];
+
+            next_code[usize::from(*length)] += 1;
+            code
+        })
+        .collect()
+}
+
+/// Writes bits in the LSB first order used by deflate
+struct BitWriter<'a>
+{
+    out:    &'a mut Vec<u8>,
+    buffer: u64,
+    bits:   u8
+}
+
+impl<'a> BitWriter<'a>
+{
+    fn new(out: &'a mut Vec<u8>) -> BitWriter<'a>
+    {
+        BitWriter {
+            out,
+            buffer: 0,
+            bits: 0
+        }
+    }
+
+    fn write_bits(&mut self, value: u32, bits: u8)
+    {
+        self.buffer |= u64::from(value) << self.bits;
+        self.bits += bits;
+
+        while self.bits >= 8
+        {
+            self.out.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    /// Write a huffman code, which are packed starting from the most significant bit
+    fn write_code(&mut self, code: u32, length: u8)
+    {
+        self.write_bits(code.reverse_bits() >> (32 - length), length);
+    }
+
+    /// Write any remaining bits, padding to a byte boundary
+    fn flush(&mut self)
+    {
+        if self.bits > 0
+        {
+            self.out.push(self.buffer as u8);
+        }
+        self.buffer = 0;
+        self.bits = 0;
+    }
+}
@@ -71,6 +71,9 @@ pub enum DecodeErrorStatus
     CorruptData,
     /// Limit set by the user was exceeded by
     /// decompressed output
+    ///
+    /// Contains the limit and the size the output reached when
+    /// decoding stopped, output up to the limit is kept in the error's `data`
     OutputLimitExceeded(usize, usize),
     /// Output CRC does not match stored CRC.
     ///
@@ -79,7 +82,12 @@ pub enum DecodeErrorStatus
     /// Output Adler does not match stored adler
     ///
     /// Only present for gzip
-    MismatchedAdler(u32, u32)
+    MismatchedAdler(u32, u32),
+    /// All input pushed so far has been decoded, more is
+    /// needed to make progress
+    ///
+    /// Only returned by [`DeflateDecoderStream`](crate::DeflateDecoderStream)
+    NeedMoreInput
 }
 
 impl Debug for DecodeErrorStatus
@@ -104,6 +112,7 @@ impl Debug for DecodeErrorStatus
             {
                 writeln!(f, "Mismatched Adler, expected {expected} but found {found}")
             }
+            Self::NeedMoreInput => writeln!(f, "Need more input")
         }
     }
 }
@@ -9,13 +9,14 @@
 //! Use it if
 //! - You want a smaller library footprint when compared to flate/miniz-oxide
 //! - You want faster speeds than zlib-ng/zlib/miniz-oxide.
-//! - You do full buffer decompression and not streaming decompression.
-//! - You don't need compression support for now, it will come soon enough.
+//! - You do full buffer decompression, or want simple streaming decompression
+//!   with [`DeflateDecoderStream`].
+//! - You need basic compression support, the encoder favours simplicity over ratio.
 //! - You want a 100% safe, pure rust implementation with above.
 //!
 //!Do not use it if
-//!  - You want compression support, not yet there
-//!  - You stream your data, not compatible with this library
+//!  - You want the best compression ratios, the encoder uses a simple greedy matcher
+//!  - You need fast streaming decompression, the streaming decoder favours simplicity over speed
 //!
 //! ## Alternatives
 //!- For the fastest speeds, check out [libdeflate] (C), if using Rust there is [libdeflater] which
@@ -76,6 +77,29 @@
 //!
 //! ```
 //!
+//! Decoding data compressed with a preset dictionary
+//! ```no_run
+//! use zune_inflate::DeflateDecoder;
+//! let dictionary = b"words the stream was compressed against";
+//! let totally_valid_data = [0;23];
+//! let mut decoder = DeflateDecoder::new(&totally_valid_data);
+//! // used by raw deflate streams, and zlib streams that request it
+//! decoder.set_dictionary(dictionary);
+//!
+//! let decompressed = decoder.decode_deflate().unwrap();
+//! ```
+//!
+//! Compressing data into a zlib stream, raw deflate and gzip
+//! streams are created with `encode_deflate` and `encode_gzip`
+//! ```
+//! use zune_inflate::{DeflateDecoder, DeflateEncoder};
+//! let data = b"hello hello hello hello";
+//! let compressed = DeflateEncoder::new(data).encode_zlib();
+//!
+//! let decompressed = DeflateDecoder::new(&compressed).decode_zlib().unwrap();
+//! assert_eq!(decompressed, data);
+//! ```
+//!
 //! [libdeflate]: https://github.com/ebiggers/libdeflate
 //! [libdeflater]: https://github.com/adamkewley/libdeflater
 //! [flate2-rs]: https://github.com/rust-lang/flate2-rs
@@ -84,11 +108,16 @@
 extern crate alloc;
 
 pub use crate::decoder::{DeflateDecoder, DeflateOptions};
+pub use crate::encoder::{DeflateEncodeOptions, DeflateEncoder};
+pub use crate::stream::DeflateDecoderStream;
 
 mod bitstream;
+pub mod checksums;
 mod constants;
 mod crc;
 mod decoder;
+mod encoder;
 pub mod errors;
 mod gzip_constants;
+mod stream;
 mod utils;
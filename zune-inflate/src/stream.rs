@@ -0,0 +1,668 @@
+//! Incremental deflate decoding
+//!
+//! Unlike [`DeflateDecoder`](crate::DeflateDecoder) which needs the whole
+//! compressed stream up front, this decoder accepts input in pieces and hands out
+//! decompressed bytes as they become available, keeping only the 32 KiB window
+//! and whatever input hasn't been consumed yet in memory.
+//!
+//! Every step of decoding (a block header, a literal, a match, a piece of an
+//! uncompressed block) either completes or is retried from its start once more
+//! input arrives, so decoding can stop at any byte of the input.
+//!
+//! Huffman codes are decoded a bit at a time, trading speed for simplicity,
+//! use [`DeflateDecoder`](crate::DeflateDecoder) when the whole stream is available.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::constants::{
+    DEFLATE_MAX_CODEWORD_LENGTH, DEFLATE_NUM_LITLEN_SYMS, DEFLATE_NUM_OFFSET_SYMS,
+    DEFLATE_NUM_PRECODE_SYMS, DEFLATE_PRECODE_LENS_PERMUTATION, DIST_BASE, DIST_EXTRA,
+    LENGTH_BASE, LENGTH_EXTRA
+};
+use crate::errors::{DecodeErrorStatus, InflateDecodeErrors};
+use crate::DeflateOptions;
+
+/// Size of the sliding window, matches can refer at most this far back
+const WINDOW_SIZE: usize = 32768;
+
+/// Largest piece of an uncompressed block copied in one step
+const MAX_STORED_COPY: usize = 4096;
+
+/// Reasons a decoding step couldn't complete
+enum Halt
+{
+    /// The step needs more input, it'll be retried from its start
+    NeedMoreInput,
+    /// The stream is corrupt
+    Error(DecodeErrorStatus)
+}
+
+impl From<DecodeErrorStatus> for Halt
+{
+    fn from(error: DecodeErrorStatus) -> Self
+    {
+        Halt::Error(error)
+    }
+}
+
+/// Reads bits least significant bit first, starting at a bit position
+/// that is only committed once a step completes
+struct BitCursor<'a>
+{
+    data:     &'a [u8],
+    position: usize
+}
+
+impl<'a> BitCursor<'a>
+{
+    fn get_bits(&mut self, count: u8) -> Result<u32, Halt>
+    {
+        if self.position + usize::from(count) > self.data.len() * 8
+        {
+            return Err(Halt::NeedMoreInput);
+        }
+        let mut value = 0;
+
+        for i in 0..count
+        {
+            let bit = (self.data[self.position / 8] >> (self.position % 8)) & 1;
+
+            value |= u32::from(bit) << i;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self)
+    {
+        self.position = (self.position + 7) & !7;
+    }
+}
+
+/// A canonical huffman code, decoded a bit at a time
+struct Huffman
+{
+    /// Number of codes of each length
+    counts:  [u16; DEFLATE_MAX_CODEWORD_LENGTH + 1],
+    /// Symbols ordered by their codes
+    symbols: Vec<u16>
+}
+
+impl Huffman
+{
+    fn new(lengths: &[u8]) -> Result<Huffman, DecodeErrorStatus>
+    {
+        let mut counts = [0_u16; DEFLATE_MAX_CODEWORD_LENGTH + 1];
+
+        for length in lengths
+        {
+            counts[usize::from(*length)] += 1;
+        }
+        counts[0] = 0;
+
+        // incomplete codes are allowed, e.g a single distance code,
+        // decoding an unused code is an error
+        let mut left = 1_i32;
+
+        for count in &counts[1..]
+        {
+            left = (left << 1) - i32::from(*count);
+
+            if left < 0
+            {
+                return Err(DecodeErrorStatus::Generic("Over-subscribed huffman code"));
+            }
+        }
+
+        let mut offsets = [0_u16; DEFLATE_MAX_CODEWORD_LENGTH + 1];
+
+        for length in 1..DEFLATE_MAX_CODEWORD_LENGTH
+        {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0; lengths.len()];
+
+        for (symbol, length) in lengths.iter().enumerate()
+        {
+            if *length != 0
+            {
+                let offset = &mut offsets[usize::from(*length)];
+
+                symbols[usize::from(*offset)] = symbol as u16;
+                *offset += 1;
+            }
+        }
+        Ok(Huffman { counts, symbols })
+    }
+
+    fn decode(&self, cursor: &mut BitCursor) -> Result<u16, Halt>
+    {
+        // codes of each length are consecutive, starting at `first`
+        let (mut code, mut first, mut index) = (0_i32, 0_i32, 0_i32);
+
+        for count in &self.counts[1..]
+        {
+            code |= cursor.get_bits(1)? as i32;
+
+            let count = i32::from(*count);
+
+            if code - first < count
+            {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(Halt::Error(DecodeErrorStatus::Generic("Invalid huffman code")))
+    }
+}
+
+/// Where in the stream the decoder is
+enum State
+{
+    #[cfg_attr(not(feature = "zlib"), allow(dead_code))]
+    ZlibHeader,
+    BlockHeader,
+    /// Inside an uncompressed block with this many bytes left
+    Stored(usize),
+    /// Inside a huffman compressed block
+    Compressed(Huffman, Huffman),
+    ZlibTrailer,
+    Done
+}
+
+/// An incremental deflate/zlib decoder
+///
+/// Compressed data is added with [`push`](Self::push) as it becomes available and
+/// decompressed data retrieved with [`read`](Self::read), which reports
+/// [`NeedMoreInput`](DecodeErrorStatus::NeedMoreInput) when it has decoded
+/// everything pushed so far. Memory use is bounded by the 32 KiB window,
+/// the unconsumed input and the size of the buffers passed to `read`.
+///
+/// # Example
+/// ```
+/// use zune_inflate::errors::DecodeErrorStatus;
+/// use zune_inflate::{DeflateDecoderStream, DeflateEncoder};
+///
+/// let data = b"hello hello hello hello hello";
+/// let compressed = DeflateEncoder::new(data).encode_zlib();
+///
+/// let mut stream = DeflateDecoderStream::new_zlib();
+/// let mut decompressed = Vec::new();
+/// let mut buffer = [0; 8];
+///
+/// // feed the data a few bytes at a time, as if it was arriving over a network
+/// let mut chunks = compressed.chunks(5);
+///
+/// loop
+/// {
+///     match stream.read(&mut buffer)
+///     {
+///         Ok(0) => break,
+///         Ok(n) => decompressed.extend_from_slice(&buffer[..n]),
+///         Err(err) if matches!(err.error, DecodeErrorStatus::NeedMoreInput) =>
+///         {
+///             stream.push(chunks.next().unwrap());
+///         }
+///         Err(err) => panic!("{:?}", err)
+///     }
+/// }
+/// assert_eq!(decompressed, data);
+/// ```
+pub struct DeflateDecoderStream
+{
+    input:          Vec<u8>,
+    /// Bit position in `input` up to which data is consumed
+    bit_position:   usize,
+    state:          State,
+    is_last_block:  bool,
+    /// Decompressed data, the window followed by bytes not yet read
+    output:         Vec<u8>,
+    /// Start of the bytes not yet read in `output`
+    read_position:  usize,
+    /// Total number of bytes decompressed
+    total_out:      usize,
+    options:        DeflateOptions,
+    is_zlib:        bool,
+    /// Adler hash of `output` up to `adler_position`
+    adler:          u32,
+    adler_position: usize
+}
+
+impl DeflateDecoderStream
+{
+    /// Create a decoder for a raw deflate stream
+    pub fn new() -> DeflateDecoderStream
+    {
+        Self::new_with_options(DeflateOptions::default())
+    }
+    /// Create a decoder for a raw deflate stream with the specified options
+    ///
+    /// The output limit and whether checksums are confirmed are respected,
+    /// the size hint is ignored.
+    pub fn new_with_options(options: DeflateOptions) -> DeflateDecoderStream
+    {
+        DeflateDecoderStream {
+            input: vec![],
+            bit_position: 0,
+            state: State::BlockHeader,
+            is_last_block: false,
+            output: vec![],
+            read_position: 0,
+            total_out: 0,
+            options,
+            is_zlib: false,
+            adler: 1,
+            adler_position: 0
+        }
+    }
+    /// Create a decoder for a zlib stream
+    ///
+    /// # Note
+    /// This needs the `zlib` feature enabled to be available otherwise it's a
+    /// compile time error
+    #[cfg(feature = "zlib")]
+    pub fn new_zlib() -> DeflateDecoderStream
+    {
+        Self::new_zlib_with_options(DeflateOptions::default())
+    }
+    /// Create a decoder for a zlib stream with the specified options
+    ///
+    /// Zlib streams needing a preset dictionary are not supported.
+    ///
+    /// # Note
+    /// This needs the `zlib` feature enabled to be available otherwise it's a
+    /// compile time error
+    #[cfg(feature = "zlib")]
+    pub fn new_zlib_with_options(options: DeflateOptions) -> DeflateDecoderStream
+    {
+        let mut decoder = Self::new_with_options(options);
+        decoder.state = State::ZlibHeader;
+        decoder.is_zlib = true;
+
+        decoder
+    }
+
+    /// Add compressed data to the end of the stream
+    pub fn push(&mut self, input: &[u8])
+    {
+        // drop input that was consumed
+        let consumed = self.bit_position / 8;
+
+        self.input.drain(..consumed);
+        self.bit_position -= consumed * 8;
+
+        self.input.extend_from_slice(input);
+    }
+
+    /// Return true if the end of the stream was reached
+    /// and all decompressed data has been read
+    pub fn is_finished(&self) -> bool
+    {
+        matches!(self.state, State::Done) && self.read_position == self.output.len()
+    }
+
+    /// Decompress data into `out` returning the number of bytes written
+    ///
+    /// # Returns
+    /// - `Ok(0)`: The end of the stream was reached (or `out` is empty)
+    /// - `Ok(n)`: `n` bytes were written to `out`, fewer than `out.len()` if the input
+    ///   ran out or the stream ended
+    /// - `Err(err)`: With [`DecodeErrorStatus::NeedMoreInput`] if nothing could be
+    ///   decompressed from the input pushed so far, in which case more input should be pushed
+    ///   before trying again, or another status if the stream is corrupt
+    ///
+    /// Errors are only reported once all data decompressed before them has been read
+    pub fn read(&mut self, out: &mut [u8]) -> Result<usize, InflateDecodeErrors>
+    {
+        let mut halt = None;
+
+        while self.output.len() - self.read_position < out.len()
+        {
+            match self.step()
+            {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(err) =>
+                {
+                    halt = Some(err);
+                    break;
+                }
+            }
+        }
+
+        let available = &self.output[self.read_position..];
+        let count = available.len().min(out.len());
+
+        out[..count].copy_from_slice(&available[..count]);
+        self.read_position += count;
+
+        self.discard_old_output();
+
+        match halt
+        {
+            Some(_) if count > 0 => Ok(count),
+            Some(Halt::NeedMoreInput) => Err(InflateDecodeErrors::new_with_error(
+                DecodeErrorStatus::NeedMoreInput
+            )),
+            Some(Halt::Error(err)) => Err(InflateDecodeErrors::new_with_error(err)),
+            None => Ok(count)
+        }
+    }
+
+    /// Drop output that is neither unread nor part of the window
+    fn discard_old_output(&mut self)
+    {
+        if self.read_position > 2 * WINDOW_SIZE
+        {
+            let old = self.read_position - WINDOW_SIZE;
+
+            self.update_adler();
+            self.output.drain(..old);
+            self.read_position -= old;
+            self.adler_position -= old;
+        }
+    }
+
+    fn update_adler(&mut self)
+    {
+        #[cfg(feature = "zlib")]
+        if self.options.get_confirm_checksum()
+        {
+            let data = &self.output[self.adler_position..];
+
+            self.adler = crate::utils::update_adler_hash(self.adler, data);
+        }
+        self.adler_position = self.output.len();
+    }
+
+    /// Decode the next piece of the stream
+    ///
+    /// Returns false if the end of the stream was reached
+    fn step(&mut self) -> Result<bool, Halt>
+    {
+        let mut cursor = BitCursor {
+            data:     &self.input,
+            position: self.bit_position
+        };
+
+        let next_state = match &self.state
+        {
+            State::Done => return Ok(false),
+            State::ZlibHeader =>
+            {
+                let cmf = cursor.get_bits(8)?;
+                let flg = cursor.get_bits(8)?;
+
+                if cmf & 0xF != 8 || cmf >> 4 > 7
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::Generic(
+                        "Unknown zlib compression method"
+                    )));
+                }
+                if (cmf * 256 + flg) % 31 != 0
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::Generic(
+                        "FCHECK integrity not preserved"
+                    )));
+                }
+                if (flg >> 5) & 1 == 1
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::Generic(
+                        "Preset dictionaries are not supported when streaming"
+                    )));
+                }
+                State::BlockHeader
+            }
+            State::BlockHeader =>
+            {
+                self.is_last_block = cursor.get_bits(1)? == 1;
+
+                match cursor.get_bits(2)?
+                {
+                    0 =>
+                    {
+                        cursor.align_to_byte();
+
+                        let len = cursor.get_bits(16)?;
+                        let nlen = cursor.get_bits(16)?;
+
+                        if len != !nlen & 0xFFFF
+                        {
+                            return Err(Halt::Error(DecodeErrorStatus::Generic(
+                                "Len and nlen do not match"
+                            )));
+                        }
+                        State::Stored(len as usize)
+                    }
+                    1 => Self::fixed_codes(),
+                    2 => Self::read_dynamic_codes(&mut cursor)?,
+                    _ =>
+                    {
+                        return Err(Halt::Error(DecodeErrorStatus::Generic(
+                            "Reserved block type 3 encountered"
+                        )));
+                    }
+                }
+            }
+            State::Stored(remaining) =>
+            {
+                let remaining = *remaining;
+                let start = cursor.position / 8;
+                let available = self.input.len() - start;
+
+                if remaining == 0
+                {
+                    self.bit_position = cursor.position;
+                    self.end_block();
+                    return Ok(true);
+                }
+                if available == 0
+                {
+                    return Err(Halt::NeedMoreInput);
+                }
+                let count = remaining.min(available).min(MAX_STORED_COPY);
+
+                check_limit(&mut self.total_out, self.options.get_limit(), count)?;
+                self.output
+                    .extend_from_slice(&self.input[start..start + count]);
+                self.bit_position = (start + count) * 8;
+                self.state = State::Stored(remaining - count);
+
+                return Ok(true);
+            }
+            State::Compressed(litlen, offset) =>
+            {
+                let symbol = usize::from(litlen.decode(&mut cursor)?);
+
+                if symbol < 256
+                {
+                    check_limit(&mut self.total_out, self.options.get_limit(), 1)?;
+                    self.output.push(symbol as u8);
+                    self.bit_position = cursor.position;
+
+                    return Ok(true);
+                }
+                if symbol == 256
+                {
+                    self.bit_position = cursor.position;
+                    self.end_block();
+
+                    return Ok(true);
+                }
+                let symbol = symbol - 257;
+
+                if symbol >= LENGTH_BASE.len()
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::CorruptData));
+                }
+                let length = usize::from(LENGTH_BASE[symbol])
+                    + cursor.get_bits(LENGTH_EXTRA[symbol])? as usize;
+
+                let symbol = usize::from(offset.decode(&mut cursor)?);
+
+                if symbol >= DIST_BASE.len()
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::CorruptData));
+                }
+                let distance = usize::from(DIST_BASE[symbol])
+                    + cursor.get_bits(DIST_EXTRA[symbol])? as usize;
+
+                if distance > self.output.len()
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::Generic(
+                        "Match distance is further back than the start of the stream"
+                    )));
+                }
+                check_limit(&mut self.total_out, self.options.get_limit(), length)?;
+
+                // matches may overlap the bytes they produce
+                let start = self.output.len() - distance;
+
+                for i in 0..length
+                {
+                    let byte = self.output[start + i];
+                    self.output.push(byte);
+                }
+                self.bit_position = cursor.position;
+
+                return Ok(true);
+            }
+            State::ZlibTrailer =>
+            {
+                cursor.align_to_byte();
+
+                let expected = (0..4).try_fold(0, |adler, _| {
+                    cursor.get_bits(8).map(|byte| (adler << 8) | byte)
+                })?;
+                let position = cursor.position;
+
+                self.update_adler();
+
+                if self.options.get_confirm_checksum() && expected != self.adler
+                {
+                    return Err(Halt::Error(DecodeErrorStatus::MismatchedAdler(
+                        expected, self.adler
+                    )));
+                }
+                self.bit_position = position;
+                self.state = State::Done;
+
+                return Ok(true);
+            }
+        };
+
+        self.bit_position = cursor.position;
+        self.state = next_state;
+
+        Ok(true)
+    }
+
+    /// Move past the end of the current block
+    fn end_block(&mut self)
+    {
+        self.state = match (self.is_last_block, self.is_zlib)
+        {
+            (false, _) => State::BlockHeader,
+            (true, false) => State::Done,
+            (true, true) => State::ZlibTrailer
+        };
+    }
+
+    fn fixed_codes() -> State
+    {
+        let mut lengths = [0; DEFLATE_NUM_LITLEN_SYMS];
+
+        lengths[..144].fill(8);
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        lengths[280..].fill(8);
+
+        let litlen = Huffman::new(&lengths).unwrap();
+        let offset = Huffman::new(&[5; DEFLATE_NUM_OFFSET_SYMS]).unwrap();
+
+        State::Compressed(litlen, offset)
+    }
+
+    fn read_dynamic_codes(cursor: &mut BitCursor) -> Result<State, Halt>
+    {
+        let num_litlen = cursor.get_bits(5)? as usize + 257;
+        let num_offset = cursor.get_bits(5)? as usize + 1;
+        let num_precode = cursor.get_bits(4)? as usize + 4;
+
+        let mut precode_lengths = [0; DEFLATE_NUM_PRECODE_SYMS];
+
+        for position in &DEFLATE_PRECODE_LENS_PERMUTATION[..num_precode]
+        {
+            precode_lengths[usize::from(*position)] = cursor.get_bits(3)? as u8;
+        }
+        let precode = Huffman::new(&precode_lengths)?;
+
+        let mut lengths = vec![0_u8; num_litlen + num_offset];
+        let mut i = 0;
+
+        while i < lengths.len()
+        {
+            let symbol = precode.decode(cursor)?;
+
+            let (value, repeat) = match symbol
+            {
+                0..=15 => (symbol as u8, 1),
+                16 =>
+                {
+                    if i == 0
+                    {
+                        return Err(Halt::Error(DecodeErrorStatus::Generic(
+                            "Repeat code with no previous length"
+                        )));
+                    }
+                    (lengths[i - 1], 3 + cursor.get_bits(2)? as usize)
+                }
+                17 => (0, 3 + cursor.get_bits(3)? as usize),
+                _ => (0, 11 + cursor.get_bits(7)? as usize)
+            };
+            if i + repeat > lengths.len()
+            {
+                return Err(Halt::Error(DecodeErrorStatus::Generic(
+                    "Code lengths overrun the number of symbols"
+                )));
+            }
+            lengths[i..i + repeat].fill(value);
+            i += repeat;
+        }
+        if lengths[256] == 0
+        {
+            return Err(Halt::Error(DecodeErrorStatus::Generic(
+                "Block has no end of block code"
+            )));
+        }
+        let litlen = Huffman::new(&lengths[..num_litlen])?;
+        let offset = Huffman::new(&lengths[num_litlen..])?;
+
+        Ok(State::Compressed(litlen, offset))
+    }
+}
+
+/// Account for `count` more output bytes, failing if that exceeds `limit`
+fn check_limit(total_out: &mut usize, limit: usize, count: usize) -> Result<(), Halt>
+{
+    if *total_out + count > limit
+    {
+        return Err(Halt::Error(DecodeErrorStatus::OutputLimitExceeded(
+            limit,
+            *total_out + count
+        )));
+    }
+    *total_out += count;
+
+    Ok(())
+}
+
+impl Default for DeflateDecoderStream
+{
+    fn default() -> Self
+    {
+        Self::new()
+    }
+}
@@ -77,3 +77,15 @@ pub fn calc_adler_hash(data: &[u8]) -> u32
 
     hasher.finish()
 }
+
+/// Update a running adler hash with more data
+#[cfg(feature = "zlib")]
+pub fn update_adler_hash(adler: u32, data: &[u8]) -> u32
+{
+    use simd_adler32::Adler32;
+    let mut hasher = Adler32::from_checksum(adler);
+
+    hasher.write(data);
+
+    hasher.finish()
+}
@@ -0,0 +1,473 @@
+//! Animated PNG (APNG) support
+//!
+//! This contains routines to reconstruct the frames of an animated
+//! png.
+//!
+//! The specification for APNG can be found [here](https://wiki.mozilla.org/APNG_Specification)
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::time::Duration;
+
+use zune_core::utils::convert_be_to_target_endian_u16;
+
+use crate::decoder::strip_16_bits;
+use crate::enums::{BlendOp, DisposeOp, InterlaceMethod};
+use crate::error::PngDecodeErrors;
+use crate::PngDecoder;
+
+/// Animation control details
+///
+/// Extracted from the acTL chunk
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct ActlChunk
+{
+    pub num_frames: u32,
+    pub num_plays:  u32
+}
+
+/// Frame control details
+///
+/// Extracted from the fcTL chunk of an animated PNG,
+/// this describes the region of the canvas a frame updates
+/// and how it is composited into the canvas
+#[derive(Copy, Clone, Debug, Default)]
+pub struct FrameInfo
+{
+    /// Sequence number of the animation chunk
+    pub seq_number:  u32,
+    /// Width of the frame region
+    pub width:       usize,
+    /// Height of the frame region
+    pub height:      usize,
+    /// X position at which to render the frame
+    pub x_offset:    usize,
+    /// Y position at which to render the frame
+    pub y_offset:    usize,
+    /// Frame delay fraction numerator
+    pub delay_num:   u16,
+    /// Frame delay fraction denominator
+    ///
+    /// If this is zero, the denominator is to be treated as 100
+    /// (i.e the numerator specifies 1/100ths of a second)
+    pub delay_denom: u16,
+    /// How the frame region is disposed after the frame is rendered
+    pub dispose_op:  DisposeOp,
+    /// How the frame is blended into the canvas
+    pub blend_op:    BlendOp
+}
+
+impl FrameInfo
+{
+    /// Return how long this frame should be displayed
+    pub fn delay(&self) -> Duration
+    {
+        let denom = if self.delay_denom == 0 { 100 } else { self.delay_denom };
+
+        Duration::from_nanos(u64::from(self.delay_num) * 1_000_000_000 / u64::from(denom))
+    }
+}
+
+/// A fully composited APNG frame
+///
+/// The pixels cover the whole canvas (image width and height as given by
+/// the IHDR chunk) and have the same layout as pixels returned by
+/// [`decode_raw`](PngDecoder::decode_raw)
+#[derive(Clone)]
+pub struct PngFrame
+{
+    /// Frame pixels after applying the blend operation
+    /// of this frame
+    pub pixels: Vec<u8>,
+    /// Frame control information for this frame
+    pub info:   FrameInfo
+}
+
+/// Frame control information plus the compressed data
+/// of the frame.
+#[derive(Clone, Default)]
+pub(crate) struct ApngFrameData
+{
+    pub info:      FrameInfo,
+    /// True if the frame data is stored in the IDAT
+    /// chunks, i.e the default image is the first frame
+    /// of the animation
+    pub uses_idat: bool,
+    /// Concatenated fdAT chunk data, with sequence numbers
+    /// removed
+    pub fdat:      Vec<u8>
+}
+
+impl<'a> PngDecoder<'a>
+{
+    /// Return the number of frames in an animated png
+    ///
+    /// # Returns
+    /// - `Some(frames)`: The number of frames as specified in the acTL chunk
+    /// - `None`: The headers haven't been decoded or the image is not an animated png
+    pub const fn num_frames(&self) -> Option<u32>
+    {
+        match self.actl_info
+        {
+            Some(actl) => Some(actl.num_frames),
+            None => None
+        }
+    }
+
+    /// Return the number of times an animated png should be played
+    ///
+    /// # Returns
+    /// - `Some(plays)`: Number of times to loop the animation, zero indicates
+    ///   that the animation should be looped infinitely
+    /// - `None`: The headers haven't been decoded or the image is not an animated png
+    pub const fn num_plays(&self) -> Option<u32>
+    {
+        match self.actl_info
+        {
+            Some(actl) => Some(actl.num_plays),
+            None => None
+        }
+    }
+
+    /// Decode all frames of an animated png
+    ///
+    /// Each frame is composited onto the canvas honoring its `blend_op`
+    /// and the canvas is updated with the previous frame's `dispose_op` before rendering
+    /// the next frame, hence each returned frame is complete and can be displayed as is.
+    ///
+    /// If the default image (IDAT) is not part of the animation, it is not returned
+    /// by this function, use [`decode_raw`](Self::decode_raw) to get it.
+    ///
+    /// For images which are not animated, this returns a single frame containing
+    /// the whole image.
+    ///
+    /// # Returns
+    /// - `Ok(frames)`: All frames in the animated image
+    /// - `Err(e)`: Any error that occurred during decoding
+    pub fn decode_apng(&mut self) -> Result<Vec<PngFrame>, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        let (width, height) = (self.png_info.width, self.png_info.height);
+
+        if self.keep_native_depth() && self.actl_info.is_some()
+        {
+            return Err(PngDecodeErrors::GenericStatic(
+                "Animated images cannot be decoded at a native depth less than 8 bits"
+            ));
+        }
+
+        if self.actl_info.is_none() || self.apng_frames.is_empty()
+        {
+            // not animated, the image is the only frame
+            let pixels = self.decode_raw()?;
+            // the image may have been scaled down
+            let (width, height) = self.get_output_dimensions().unwrap();
+            let info = FrameInfo {
+                width,
+                height,
+                ..Default::default()
+            };
+            return Ok(vec![PngFrame { pixels, info }]);
+        }
+
+        let colorspace = self.get_colorspace().unwrap();
+        // frames are composited at full precision, 16 bit samples
+        // are reduced to 8 bits when returning frames
+        let sixteen_bits = self.png_info.depth == 16;
+        let strip_16 = self.will_strip_16();
+        let components = colorspace.num_components();
+        let pixel_bytes = components * if sixteen_bits { 2 } else { 1 };
+
+        // The canvas is initialized to fully transparent black
+        // before the first frame is rendered.
+        // animation frames are never scaled down
+        let mut canvas = vec![0_u8; width * height * pixel_bytes];
+
+        let frames = core::mem::take(&mut self.apng_frames);
+        let idat = core::mem::take(&mut self.idat_chunks);
+
+        let mut output = Vec::with_capacity(frames.len());
+
+        for (i, frame) in frames.iter().enumerate()
+        {
+            let info = frame.info;
+
+            let data = if frame.uses_idat { &idat } else { &frame.fdat };
+
+            let region = self.decode_frame_region(data, &info)?;
+
+            let mut dispose_op = info.dispose_op;
+
+            if i == 0 && dispose_op == DisposeOp::Previous
+            {
+                // If the first fcTL chunk uses a dispose_op of APNG_DISPOSE_OP_PREVIOUS
+                // it should be treated as APNG_DISPOSE_OP_BACKGROUND.
+                dispose_op = DisposeOp::Background;
+            }
+            let previous =
+                if dispose_op == DisposeOp::Previous { Some(canvas.clone()) } else { None };
+
+            let stride = info.width * pixel_bytes;
+
+            for (y, row) in region.chunks_exact(stride).enumerate()
+            {
+                let start = ((info.y_offset + y) * width + info.x_offset) * pixel_bytes;
+                let canvas_row = &mut canvas[start..start + stride];
+
+                if info.blend_op == BlendOp::Source || !colorspace.has_alpha()
+                {
+                    canvas_row.copy_from_slice(row);
+                }
+                else if sixteen_bits
+                {
+                    blend_over::<true>(row, canvas_row, components);
+                }
+                else
+                {
+                    blend_over::<false>(row, canvas_row, components);
+                }
+            }
+
+            let pixels = if strip_16
+            {
+                let mut pixels = vec![0_u8; canvas.len() / 2];
+                strip_16_bits(&canvas, &mut pixels, self.options.png_get_downsample_rounding());
+                pixels
+            }
+            else
+            {
+                let mut pixels = canvas.clone();
+
+                if sixteen_bits
+                {
+                    convert_be_to_target_endian_u16(
+                        &mut pixels,
+                        self.byte_endian(),
+                        self.options.use_sse41()
+                    );
+                }
+                pixels
+            };
+            output.push(PngFrame { pixels, info });
+
+            match dispose_op
+            {
+                DisposeOp::None =>
+                {}
+                DisposeOp::Background =>
+                {
+                    for y in info.y_offset..info.y_offset + info.height
+                    {
+                        let start = (y * width + info.x_offset) * pixel_bytes;
+                        canvas[start..start + stride].fill(0);
+                    }
+                }
+                DisposeOp::Previous =>
+                {
+                    canvas = previous.unwrap();
+                }
+            }
+        }
+        debug_assert_eq!(canvas.len(), width * height * pixel_bytes);
+
+        Ok(output)
+    }
+
+    /// Decode the frame of an animated png that is displayed at `time`
+    /// after the animation starts
+    ///
+    /// This accounts for each frame's delay, and the animation is looped as many
+    /// times as specified by [`num_plays`](Self::num_plays), after the last play
+    /// the final frame is returned. Use [`decode_blended_at_with_plays`](Self::decode_blended_at_with_plays)
+    /// to override the number of plays.
+    ///
+    /// The returned frame is composited as described in [`decode_apng`](Self::decode_apng),
+    /// for images which are not animated, the whole image is returned.
+    ///
+    /// # Note
+    /// This decodes the animation, so the decoder cannot be used to decode frames again
+    pub fn decode_blended_at(&mut self, time: Duration) -> Result<PngFrame, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        let num_plays = self.num_plays().unwrap_or(0);
+
+        self.decode_blended_at_with_plays(time, num_plays)
+    }
+
+    /// Decode the frame of an animated png that is displayed at `time`
+    /// after the animation starts, playing the animation `num_plays` times
+    ///
+    /// A `num_plays` of zero loops the animation infinitely, this is otherwise
+    /// the same as [`decode_blended_at`](Self::decode_blended_at)
+    pub fn decode_blended_at_with_plays(
+        &mut self, time: Duration, num_plays: u32
+    ) -> Result<PngFrame, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        let delays: Vec<u128> = self
+            .apng_frames
+            .iter()
+            .map(|frame| frame.info.delay().as_nanos())
+            .collect();
+
+        let index = frame_index_at(&delays, time.as_nanos(), num_plays);
+
+        let mut frames = self.decode_apng()?;
+        let index = index.min(frames.len() - 1);
+
+        Ok(frames.swap_remove(index))
+    }
+
+    /// Decode a single frame region returning de-filtered and post-processed
+    /// pixels in big endian
+    fn decode_frame_region(
+        &mut self, data: &[u8], frame: &FrameInfo
+    ) -> Result<Vec<u8>, PngDecodeErrors>
+    {
+        let mut info = self.png_info.clone();
+
+        info.width = frame.width;
+        info.height = frame.height;
+
+        let bytes = if info.depth == 16 { 2 } else { 1 };
+        // frames are composited in the output colorspace
+        let out_n = self.get_colorspace().unwrap().num_components();
+
+        let mut out = vec![0_u8; frame.width * frame.height * out_n * bytes];
+
+        let (deflate_data, _) = self.inflate(data, frame.width, frame.height)?;
+
+        match info.interlace_method
+        {
+            InterlaceMethod::Standard =>
+            {
+                self.create_png_image_raw(
+                    &deflate_data,
+                    frame.width,
+                    frame.height,
+                    &mut out,
+                    &info,
+                    &mut |_| ()
+                )?;
+            }
+            InterlaceMethod::Adam7 =>
+            {
+                self.decode_interlaced(&deflate_data, &mut out, &info, &mut |_| ())?;
+            }
+            InterlaceMethod::Unknown =>
+            {
+                return Err(PngDecodeErrors::Generic(format!(
+                    "Unknown interlace method for frame {}",
+                    frame.seq_number
+                )));
+            }
+        }
+        if self.will_force_rgba()
+        {
+            self.expand_to_rgba(&mut out);
+        }
+
+        Ok(out)
+    }
+}
+
+/// Alpha composite `src` over `dst` storing the result in `dst`
+///
+/// Both `src` and `dst` are expected to be interleaved non-premultiplied samples
+/// whose last component is the alpha channel, for 16 bit images,
+/// samples are expected to be in big endian
+fn blend_over<const SIXTEEN_BITS: bool>(src: &[u8], dst: &mut [u8], components: usize)
+{
+    let bytes = if SIXTEEN_BITS { 2 } else { 1 };
+    let max: u64 = if SIXTEEN_BITS { 65535 } else { 255 };
+
+    let read = |px: &[u8], pos: usize| -> u64 {
+        if SIXTEEN_BITS
+        {
+            u64::from(u16::from_be_bytes([px[pos * 2], px[pos * 2 + 1]]))
+        }
+        else
+        {
+            u64::from(px[pos])
+        }
+    };
+    let write = |px: &mut [u8], pos: usize, value: u64| {
+        if SIXTEEN_BITS
+        {
+            px[pos * 2..pos * 2 + 2].copy_from_slice(&(value as u16).to_be_bytes());
+        }
+        else
+        {
+            px[pos] = value as u8;
+        }
+    };
+    let alpha_pos = components - 1;
+
+    for (src_px, dst_px) in src
+        .chunks_exact(components * bytes)
+        .zip(dst.chunks_exact_mut(components * bytes))
+    {
+        let src_a = read(src_px, alpha_pos);
+
+        if src_a == max
+        {
+            dst_px.copy_from_slice(src_px);
+            continue;
+        }
+        if src_a == 0
+        {
+            continue;
+        }
+        let dst_a = read(dst_px, alpha_pos);
+        // destination contribution scaled by the coverage
+        // the source leaves behind
+        let dst_weight = dst_a * (max - src_a) / max;
+        let out_a = src_a + dst_weight;
+
+        for pos in 0..alpha_pos
+        {
+            let value =
+                (read(src_px, pos) * src_a + read(dst_px, pos) * dst_weight + out_a / 2) / out_a;
+
+            write(dst_px, pos, value);
+        }
+        write(dst_px, alpha_pos, out_a);
+    }
+}
+
+/// Return the index of the frame displayed at `time`, given the delay
+/// of each frame, all in nanoseconds
+fn frame_index_at(delays: &[u128], time: u128, num_plays: u32) -> usize
+{
+    let last = delays.len().saturating_sub(1);
+    let total: u128 = delays.iter().sum();
+
+    if total == 0
+    {
+        return last;
+    }
+    if num_plays != 0 && time / total >= u128::from(num_plays)
+    {
+        // animation is over, the last frame stays on screen
+        return last;
+    }
+    let mut remaining = time % total;
+
+    for (i, delay) in delays.iter().enumerate()
+    {
+        if remaining < *delay
+        {
+            return i;
+        }
+        remaining -= delay;
+    }
+    last
+}
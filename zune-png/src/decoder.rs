@@ -1,45 +1,58 @@
+use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::vec::Vec;
 use alloc::{format, vec};
-use core::cmp::min;
 
-use log::info;
+use log::{info, warn};
 use zune_core::bit_depth::{BitDepth, ByteEndian};
 use zune_core::bytestream::ZByteReader;
 use zune_core::colorspace::ColorSpace;
-use zune_core::options::DecoderOptions;
+use zune_core::options::{DecoderOptions, Rounding};
 use zune_core::result::DecodingResult;
 use zune_core::utils::{convert_be_to_target_endian_u16, is_le};
+use zune_inflate::errors::DecodeErrorStatus;
 use zune_inflate::DeflateOptions;
 
+use crate::apng::{ActlChunk, ApngFrameData};
 use crate::constants::PNG_SIGNATURE;
-use crate::enums::{FilterMethod, InterlaceMethod, PngChunkType, PngColor};
+use crate::enums::{
+    FilterMethod, InterlaceMethod, PhysicalUnit, PngChunkType, PngColor, RenderingIntent
+};
 use crate::error::PngDecodeErrors;
 use crate::filters::{
     handle_avg, handle_avg_first, handle_paeth, handle_paeth_first, handle_sub, handle_up
 };
-use crate::options::{default_chunk_handler, UnkownChunkHandler};
-use crate::utils::{expand_bits_to_byte, expand_palette, expand_trns};
+use crate::options::{
+    default_chunk_handler, ChunkObserver, CrcMismatchHandler, ProgressCallback, UnkownChunkHandler
+};
+use crate::report::ChunkSummary;
+use crate::utils::{copy_packed_pass, expand_bits_to_byte, expand_palette, expand_trns};
 
 /// A palette entry.
 ///
 /// The alpha field is used if the image has a tRNS
 /// chunk and pLTE chunk.
-#[derive(Copy, Clone, Debug)]
-pub(crate) struct PLTEEntry
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PaletteEntry
 {
+    /// Red component
     pub red:   u8,
+    /// Green component
     pub green: u8,
+    /// Blue component
     pub blue:  u8,
+    /// Alpha component, taken from the tRNS chunk
+    /// or 255 if the entry has no transparency information
     pub alpha: u8
 }
 
-impl Default for PLTEEntry
+impl Default for PaletteEntry
 {
     fn default() -> Self
     {
         // but a tRNS chunk may contain fewer values than there are palette entries.
         // In this case, the alpha value for all remaining palette entries is assumed to be 255
-        PLTEEntry {
+        PaletteEntry {
             red:   0,
             green: 0,
             blue:  0,
@@ -57,6 +70,525 @@ pub(crate) struct PngChunk
     pub crc:        u32
 }
 
+/// Starting column and row of each Adam7 pass
+const XORIG: [usize; 7] = [0, 4, 0, 2, 0, 1, 0];
+const YORIG: [usize; 7] = [0, 0, 4, 0, 2, 0, 1];
+
+/// Column and row increments of each Adam7 pass
+const XSPC: [usize; 7] = [8, 8, 4, 4, 2, 2, 1];
+const YSPC: [usize; 7] = [8, 8, 8, 4, 4, 2, 2];
+
+/// Number of pixels an interlaced image needs before its passes are
+/// reconstructed in parallel
+///
+/// Below this, spawning threads costs more than the passes themselves
+#[cfg(feature = "threads")]
+const THREADED_INTERLACE_PIXELS: usize = 512 * 512;
+
+/// Return the width and height of an Adam7 pass of an image
+/// with the given dimensions
+const fn adam7_pass_dimensions(width: usize, height: usize, pass: usize) -> (usize, usize)
+{
+    let x = (width + XSPC[pass] - 1).saturating_sub(XORIG[pass]) / XSPC[pass];
+    let y = (height + YSPC[pass] - 1).saturating_sub(YORIG[pass]) / YSPC[pass];
+
+    (x, y)
+}
+
+/// Convert an sRGB encoded sample in `[0, 1]` to linear light
+fn srgb_to_linear(value: f32) -> f32
+{
+    if value <= 0.04045
+    {
+        value / 12.92
+    }
+    else
+    {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Split interleaved samples into one vector per component
+fn deinterleave<T: Copy>(samples: &[T], components: usize) -> Vec<Vec<T>>
+{
+    let mut planes = vec![Vec::with_capacity(samples.len() / components); components];
+
+    for pixel in samples.chunks_exact(components)
+    {
+        for (plane, sample) in planes.iter_mut().zip(pixel)
+        {
+            plane.push(*sample);
+        }
+    }
+    planes
+}
+
+/// Reduce big endian 16 bit samples to 8 bits
+///
+/// Samples either keep their most significant byte or are rounded to the
+/// nearest 8 bit value, depending on `rounding`
+pub(crate) fn strip_16_bits(wide: &[u8], out: &mut [u8], rounding: Rounding)
+{
+    match rounding
+    {
+        Rounding::Truncate =>
+        {
+            for (sample, wide_sample) in out.iter_mut().zip(wide.chunks_exact(2))
+            {
+                *sample = wide_sample[0];
+            }
+        }
+        Rounding::Round =>
+        {
+            for (sample, wide_sample) in out.iter_mut().zip(wide.chunks_exact(2))
+            {
+                let value = u32::from(u16::from_be_bytes([wide_sample[0], wide_sample[1]]));
+
+                *sample = ((value + 128) >> 8).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Report progress after `rows` of `height` rows were decoded, about
+/// every hundredth of the image
+///
+/// Completion is reported once the whole image is processed, so the last row isn't reported
+#[allow(clippy::cast_precision_loss, clippy::manual_is_multiple_of)]
+fn report_row_progress(progress: &mut dyn FnMut(f32), rows: usize, height: usize)
+{
+    let interval = (height / 100).max(1);
+
+    if rows % interval == 0 && rows < height
+    {
+        progress(rows as f32 / height as f32);
+    }
+}
+
+/// Report progress after interlace passes covering `pixels` pixels were decoded
+#[allow(clippy::cast_precision_loss)]
+fn report_pass_progress(progress: &mut dyn FnMut(f32), pixels: usize, info: &PngInfo)
+{
+    let total = info.width * info.height;
+
+    if pixels < total
+    {
+        progress(pixels as f32 / total as f32);
+    }
+}
+
+/// Averages blocks of decoded rows for scaling images down
+///
+/// Rows are added one at a time, and once all rows of a block
+/// have been added their averages are written as a single output row.
+/// 16 bit samples are big endian.
+struct BoxScaler
+{
+    sums:        Vec<u32>,
+    rows:        u32,
+    width:       usize,
+    components:  usize,
+    sixteen_bit: bool,
+    scale:       usize
+}
+
+impl BoxScaler
+{
+    fn new(width: usize, components: usize, sixteen_bit: bool, scale: usize) -> BoxScaler
+    {
+        BoxScaler {
+            sums: vec![0; width.div_ceil(scale) * components],
+            rows: 0,
+            width,
+            components,
+            sixteen_bit,
+            scale
+        }
+    }
+
+    /// Add the samples of a full resolution row to the current block
+    fn add_row(&mut self, row: &[u8])
+    {
+        let bytes = if self.sixteen_bit { 2 } else { 1 };
+        let block_size = self.scale * self.components * bytes;
+
+        for (block, sums) in row[..self.width * self.components * bytes]
+            .chunks(block_size)
+            .zip(self.sums.chunks_exact_mut(self.components))
+        {
+            for pixel in block.chunks_exact(self.components * bytes)
+            {
+                for (sum, sample) in sums.iter_mut().zip(pixel.chunks_exact(bytes))
+                {
+                    *sum += if self.sixteen_bit
+                    {
+                        u32::from(u16::from_be_bytes([sample[0], sample[1]]))
+                    }
+                    else
+                    {
+                        u32::from(sample[0])
+                    };
+                }
+            }
+        }
+        self.rows += 1;
+    }
+
+    /// Write the averages of the current block to `out` and start a new block
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_row(&mut self, out: &mut [u8])
+    {
+        let bytes = if self.sixteen_bit { 2 } else { 1 };
+
+        for (i, (sums, pixel)) in self
+            .sums
+            .chunks_exact_mut(self.components)
+            .zip(out.chunks_exact_mut(self.components * bytes))
+            .enumerate()
+        {
+            // blocks at the right edge may be narrower
+            let columns = (self.width - i * self.scale).min(self.scale) as u32;
+            let count = columns * self.rows;
+
+            for (sum, sample) in sums.iter_mut().zip(pixel.chunks_exact_mut(bytes))
+            {
+                let average = (*sum + count / 2) / count;
+
+                if self.sixteen_bit
+                {
+                    sample.copy_from_slice(&(average as u16).to_be_bytes());
+                }
+                else
+                {
+                    sample[0] = average as u8;
+                }
+                *sum = 0;
+            }
+        }
+        self.rows = 0;
+    }
+}
+
+/// The decoder state needed to de-filter and post process rows
+///
+/// Unlike the decoder this can be shared between threads, so that interlace
+/// passes can be reconstructed in parallel
+#[derive(Copy, Clone)]
+pub(crate) struct RowContext<'p>
+{
+    palette:           &'p [PaletteEntry],
+    trns_bytes:        [u16; 4],
+    seen_trns:         bool,
+    seen_ptle:         bool,
+    keep_indices:      bool,
+    keep_native_depth: bool,
+    /// Number of components of a post processed pixel
+    out_components:    usize,
+    use_sse2:          bool,
+    use_sse41:         bool
+}
+
+impl RowContext<'_>
+{
+    /// Un-filter a single scanline
+    ///
+    /// `in_stride` is the filter byte followed by the filtered row, `prev_row` is the
+    /// previous un-filtered row, which is ignored if `first_row` is true.
+    /// The un-filtered row is written to `current`
+    fn unfilter_row(
+        &self, in_stride: &[u8], prev_row: &[u8], current: &mut [u8], components: usize,
+        first_row: bool
+    ) -> Result<(), PngDecodeErrors>
+    {
+        let use_sse4 = self.use_sse41;
+        let use_sse2 = self.use_sse2;
+
+        // take filter
+        let filter_byte = in_stride[0];
+        // raw image bytes
+        let raw = &in_stride[1..];
+
+        // get it's type
+        let mut filter = FilterMethod::from_int(filter_byte)
+            .ok_or_else(|| PngDecodeErrors::Generic(format!("Unknown filter {filter_byte}")))?;
+
+        if first_row
+        {
+            // match our filters to special filters for first row
+            // these special filters do not need the previous scanline and treat it
+            // as zero
+
+            if filter == FilterMethod::Paeth
+            {
+                filter = FilterMethod::PaethFirst;
+            }
+            if filter == FilterMethod::Up
+            {
+                // up for the first row becomes a memcpy
+                filter = FilterMethod::None;
+            }
+            if filter == FilterMethod::Average
+            {
+                filter = FilterMethod::AvgFirst;
+            }
+        }
+
+        match filter
+        {
+            FilterMethod::None => current[0..raw.len()].copy_from_slice(raw),
+
+            FilterMethod::Average => handle_avg(prev_row, raw, current, components, use_sse4),
+
+            FilterMethod::Sub => handle_sub(raw, current, components, use_sse2),
+
+            FilterMethod::Up => handle_up(prev_row, raw, current),
+
+            FilterMethod::Paeth => handle_paeth(prev_row, raw, current, components, use_sse4),
+
+            FilterMethod::PaethFirst => handle_paeth_first(raw, current, components),
+
+            FilterMethod::AvgFirst => handle_avg_first(raw, current, components),
+
+            FilterMethod::Unknown => unreachable!()
+        }
+        Ok(())
+    }
+
+    /// Run post processing on a single un-filtered row
+    ///
+    /// This expands bit depths less than 8, tRNS chunks and palettes,
+    /// `to_filter_row` contains the un-filtered row at its start and must be large enough to
+    /// hold the expanded row, `previous_stride` is scratch space of at least the same size
+    fn post_process_row(
+        &self, previous_stride: &mut [u8], to_filter_row: &mut [u8], width: usize,
+        width_stride: usize, n_components: usize, info: &PngInfo
+    ) -> Result<(), PngDecodeErrors>
+    {
+        // palette indices are returned as is, so they need
+        // neither palette nor transparency expansion
+        let keep_indices = self.keep_indices;
+
+        if info.depth < 8
+        {
+            // check if we will run any other transform
+            let extra_transform = (self.seen_ptle | self.seen_trns) && !keep_indices;
+
+            if extra_transform
+            {
+                // input data is  in_to_filter_row,
+                // we write output to previous_stride
+                // since other parts use previous_stride
+                expand_bits_to_byte(
+                    width,
+                    usize::from(info.depth),
+                    0,
+                    n_components,
+                    self.seen_ptle,
+                    to_filter_row,
+                    previous_stride
+                )
+            }
+            else
+            {
+                // no extra transform, just depth upscaling, so let's
+                // do that,
+
+                // copy the row to a temporary space
+                previous_stride[..width_stride].copy_from_slice(&to_filter_row[..width_stride]);
+
+                expand_bits_to_byte(
+                    width,
+                    usize::from(info.depth),
+                    0,
+                    n_components,
+                    self.seen_ptle,
+                    previous_stride,
+                    to_filter_row
+                )
+            }
+        }
+        else if !keep_indices
+        {
+            // copy the row to a temporary space
+            previous_stride[..width_stride].copy_from_slice(&to_filter_row[..width_stride]);
+        }
+
+        if self.seen_trns && info.color != PngColor::Palette
+        {
+            // the expansion is a trns expansion
+            // bytes are already in position, so finish the business
+
+            if info.depth <= 8
+            {
+                expand_trns::<false>(
+                    previous_stride,
+                    to_filter_row,
+                    info.color,
+                    self.trns_bytes,
+                    info.depth
+                );
+            }
+            else if info.depth == 16
+            {
+                // Tested by test_palette_trns_16bit.
+                expand_trns::<true>(
+                    previous_stride,
+                    to_filter_row,
+                    info.color,
+                    self.trns_bytes,
+                    info.depth
+                );
+            }
+        }
+
+        if self.seen_ptle && info.color == PngColor::Palette && !keep_indices
+        {
+            if self.palette.is_empty()
+            {
+                return Err(PngDecodeErrors::EmptyPalette);
+            }
+            let plte_entry: &[PaletteEntry; 256] = self.palette[..256].try_into().unwrap();
+
+            // so now we have two things
+            // the palette entries stored in previous_stride
+            // the row to fill the palette sored in to_filter row,
+            // so we can finally expand the entries
+            if self.seen_trns
+            {
+                // if tRNS chunk is present in paletted images, it contains
+                // alpha byte values, so that means we create alpha data from
+                // raw bytes
+                expand_palette(previous_stride, to_filter_row, plte_entry, 4);
+            }
+            else
+            {
+                // Normal expansion
+                expand_palette(previous_stride, to_filter_row, plte_entry, 3);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// De-filters and post processes rows one after the other
+///
+/// The previous un-filtered row is kept here, so post processed rows
+/// can be written anywhere, e.g. straight into the image or into a single row buffer
+struct RowDecoder
+{
+    /// Un-filtered previous row, used by the filters
+    prev_row:          Vec<u8>,
+    /// Scratch space for post processing
+    scratch:           Vec<u8>,
+    first_row:         bool,
+    width:             usize,
+    /// Size of a filtered row, excluding the filter byte
+    width_stride:      usize,
+    /// Size of a post processed row
+    out_stride:        usize,
+    /// Number of bytes before a sample used by the filters
+    filter_components: usize,
+    n_components:      usize,
+    will_post_process: bool
+}
+
+impl RowDecoder
+{
+    /// Create a decoder for rows of `width` pixels, `scratch` is reused as
+    /// scratch space for post processing
+    fn new(context: &RowContext, width: usize, info: &PngInfo, mut scratch: Vec<u8>) -> RowDecoder
+    {
+        let bytes = if info.depth == 16 { 2 } else { 1 };
+        let n_components = usize::from(info.color.num_components());
+        let width_stride = (width * n_components * usize::from(info.depth)).div_ceil(8);
+
+        let out_stride = if context.keep_native_depth
+        {
+            // rows stay packed
+            width_stride
+        }
+        else
+        {
+            width * context.out_components * bytes
+        };
+        let will_post_process = !context.keep_native_depth
+            && (context.seen_trns | context.seen_ptle | (info.depth < 8));
+
+        if will_post_process && scratch.len() < out_stride
+        {
+            scratch.resize(out_stride, 0);
+        }
+        RowDecoder {
+            prev_row: vec![0; width_stride],
+            scratch,
+            first_row: true,
+            width,
+            width_stride,
+            out_stride,
+            // for depths less than 8, the filters use the previous byte
+            filter_components: if info.depth < 8 { 1 } else { n_components * bytes },
+            n_components,
+            will_post_process
+        }
+    }
+
+    /// Size of a filtered row including its filter byte
+    const fn in_stride(&self) -> usize
+    {
+        self.width_stride + 1
+    }
+
+    /// De-filter `in_stride`, a filter byte followed by a filtered row, and
+    /// write the post processed row to the start of `out`
+    fn decode_row(
+        &mut self, context: &RowContext, in_stride: &[u8], out: &mut [u8], info: &PngInfo
+    ) -> Result<(), PngDecodeErrors>
+    {
+        let out = &mut out[..self.out_stride];
+
+        context.unfilter_row(
+            in_stride,
+            &self.prev_row,
+            out,
+            self.filter_components,
+            self.first_row
+        )?;
+        self.first_row = false;
+
+        // post processing modifies the row, but the next
+        // row is de-filtered against the un-filtered one
+        self.prev_row.copy_from_slice(&out[..self.width_stride]);
+
+        if self.will_post_process
+        {
+            context.post_process_row(
+                &mut self.scratch,
+                out,
+                self.width,
+                self.width_stride,
+                self.n_components,
+                info
+            )?;
+        }
+        Ok(())
+    }
+
+    /// De-filter and post process `height` rows into `out`, one after the other
+    fn decode_image(
+        &mut self, context: &RowContext, deflate_data: &[u8], height: usize, out: &mut [u8],
+        info: &PngInfo, progress: &mut dyn FnMut(f32)
+    ) -> Result<(), PngDecodeErrors>
+    {
+        for (i, in_stride) in deflate_data.chunks_exact(self.in_stride()).take(height).enumerate()
+        {
+            self.decode_row(context, in_stride, &mut out[i * self.out_stride..], info)?;
+
+            report_row_progress(progress, i + 1, height);
+        }
+        Ok(())
+    }
+}
+
 /// Time information data
 ///
 /// Extracted from tIME chunk
@@ -71,6 +603,93 @@ pub struct TimeInfo
     pub second: u8
 }
 
+/// Suggested background color
+///
+/// Extracted from the bKGD chunk, the variant depends
+/// on the image colour type and samples are kept at the image bit depth,
+/// i.e 16 bit images have values in the range 0..=65535
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BackgroundColor
+{
+    /// Index into the palette, for paletted images
+    PaletteIndex(u8),
+    /// Gray level, for grayscale images with or without alpha
+    Gray(u16),
+    /// Red, green and blue samples, for truecolour images with or without alpha
+    Rgb(u16, u16, u16)
+}
+
+/// CIE 1931 x,y chromaticities of the display primaries and white point
+///
+/// Extracted from the cHRM chunk, each value is stored in the file
+/// as an integer times 100000 and is converted back to a float here,
+/// e.g a stored value of 31270 becomes 0.3127
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Chromaticities
+{
+    pub white_x: f32,
+    pub white_y: f32,
+    pub red_x:   f32,
+    pub red_y:   f32,
+    pub green_x: f32,
+    pub green_y: f32,
+    pub blue_x:  f32,
+    pub blue_y:  f32
+}
+
+/// Intended pixel size or aspect ratio
+///
+/// Extracted from the pHYs chunk
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PhysicalDimensions
+{
+    /// Pixels per unit in the x direction
+    pub x_pixels_per_unit: u32,
+    /// Pixels per unit in the y direction
+    pub y_pixels_per_unit: u32,
+    /// Unit of the pixel dimensions
+    pub unit:              PhysicalUnit
+}
+
+/// An entry of a suggested palette
+///
+/// Samples are kept at the sample depth of the palette, i.e palettes
+/// with a depth of 16 have values in the range 0..=65535
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SuggestedPaletteEntry
+{
+    pub red:       u16,
+    pub green:     u16,
+    pub blue:      u16,
+    pub alpha:     u16,
+    /// Relative frequency of the colour in the image, scaled
+    /// so that higher values mean more frequent, or zero if unknown
+    pub frequency: u16
+}
+
+/// A suggested palette, used to quantize an image for
+/// displays that can only show a limited number of colours
+///
+/// Extracted from sPLT chunks where present
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestedPalette<'a>
+{
+    /// Name of the palette, Latin-1 encoded
+    pub name:         &'a [u8],
+    /// Sample depth of the palette, either 8 or 16
+    pub sample_depth: u8,
+    pub entries:      Vec<SuggestedPaletteEntry>
+}
+
+impl<'a> SuggestedPalette<'a>
+{
+    /// Return the name converted from Latin-1 to UTF-8
+    pub fn name_utf8(&self) -> String
+    {
+        latin1_to_utf8(self.name)
+    }
+}
+
 /// iTXt details
 ///
 /// UTF-8 encoded text
@@ -79,8 +698,14 @@ pub struct TimeInfo
 #[derive(Clone)]
 pub struct ItxtChunk<'a>
 {
-    pub keyword: &'a [u8],
-    pub text:    &'a [u8]
+    pub keyword:            &'a [u8],
+    /// Language of the text, e.g `en-GB`, empty if unspecified
+    pub language_tag:       &'a [u8],
+    /// Keyword translated to the language of the text,
+    /// UTF-8 encoded
+    pub translated_keyword: &'a [u8],
+    /// Text, decompressed if it was stored compressed
+    pub text:               Vec<u8>
 }
 
 /// tEXt chunk details
@@ -106,39 +731,107 @@ pub struct ZtxtChunk<'a>
     pub text:    Vec<u8>
 }
 
+/// Decode Latin-1 (ISO 8859-1) bytes into a UTF-8 string
+///
+/// Every Latin-1 byte maps to the Unicode code point with the same value,
+/// so bytes above 0x7F become U+0080..U+00FF
+fn latin1_to_utf8(bytes: &[u8]) -> String
+{
+    bytes.iter().map(|x| char::from(*x)).collect()
+}
+
+impl<'a> TextChunk<'a>
+{
+    /// Return the keyword converted from Latin-1 to UTF-8
+    pub fn keyword_utf8(&self) -> String
+    {
+        latin1_to_utf8(self.keyword)
+    }
+    /// Return the text converted from Latin-1 to UTF-8
+    pub fn text_utf8(&self) -> String
+    {
+        latin1_to_utf8(self.text)
+    }
+}
+
+impl<'a> ZtxtChunk<'a>
+{
+    /// Return the keyword converted from Latin-1 to UTF-8
+    pub fn keyword_utf8(&self) -> String
+    {
+        latin1_to_utf8(self.keyword)
+    }
+    /// Return the uncompressed text converted from Latin-1 to UTF-8
+    pub fn text_utf8(&self) -> String
+    {
+        latin1_to_utf8(&self.text)
+    }
+}
+
 /// Represents PNG information that can be extracted
 /// from a png file.
 #[derive(Default, Clone)]
 pub struct PngInfo<'a>
 {
     /// Image width
-    pub width:                usize,
+    pub width:                  usize,
     /// Image height
-    pub height:               usize,
+    pub height:                 usize,
     /// Image gamma
-    pub gamma:                Option<f32>,
+    pub gamma:                  Option<f32>,
     /// Image interlace method
-    pub interlace_method:     InterlaceMethod,
+    pub interlace_method:       InterlaceMethod,
     /// Image time info
-    pub time_info:            Option<TimeInfo>,
+    pub time_info:              Option<TimeInfo>,
     /// Image exif data
-    pub exif:                 Option<&'a [u8]>,
+    pub exif:                   Option<&'a [u8]>,
     /// Icc profile
-    pub icc_profile:          Option<Vec<u8>>,
+    pub icc_profile:            Option<Vec<u8>>,
+    /// Zlib compressed Icc profile, exactly as stored in the iCCP chunk
+    ///
+    /// This is only present if [`png_set_keep_raw_iccp`](zune_core::options::DecoderOptions::png_set_keep_raw_iccp)
+    /// is enabled
+    pub icc_profile_compressed: Option<Vec<u8>>,
     /// UTF-8 encoded text chunk
-    pub itxt_chunk:           Vec<ItxtChunk<'a>>,
+    pub itxt_chunk:             Vec<ItxtChunk<'a>>,
     /// ztxt chunk
-    pub ztxt_chunk:           Vec<ZtxtChunk<'a>>,
+    pub ztxt_chunk:             Vec<ZtxtChunk<'a>>,
     /// tEXt chunk
-    pub text_chunk:           Vec<TextChunk<'a>>,
+    pub text_chunk:             Vec<TextChunk<'a>>,
+    /// Number of significant bits for each channel of
+    /// the stored image, extracted from the sBIT chunk
+    ///
+    /// Entries are ordered according to the channels of the png colour type
+    /// (RGB for paletted images) and unused entries are zero
+    pub sbit:                   Option<[u8; 4]>,
+    /// Suggested background color to present the image against,
+    /// extracted from the bKGD chunk
+    pub background:             Option<BackgroundColor>,
+    /// Chromaticities of the primaries and white point,
+    /// extracted from the cHRM chunk
+    pub chromaticities:         Option<Chromaticities>,
+    /// Rendering intent, extracted from the sRGB chunk.
+    ///
+    /// If present, the image samples are in the sRGB colour space
+    pub srgb_intent:            Option<RenderingIntent>,
+    /// Physical pixel dimensions, extracted from the pHYs chunk
+    pub pixel_dims:             Option<PhysicalDimensions>,
+    /// Suggested palettes, extracted from sPLT chunks
+    pub suggested_palettes:     Vec<SuggestedPalette<'a>>,
+    /// Approximate usage frequency of each palette entry, extracted
+    /// from the hIST chunk
+    ///
+    /// Entries are relative frequencies, one per palette entry,
+    /// useful for weighting colors when quantizing the palette
+    pub histogram:              Option<Vec<u16>>,
     // no need to expose these ones
-    pub(crate) depth:         u8,
+    pub(crate) depth:           u8,
     // use bit_depth
-    pub(crate) color:         PngColor,
+    pub(crate) color:           PngColor,
     // use get_colorspace
-    pub(crate) component:     u8,
+    pub(crate) component:       u8,
     // use get_colorspace().num_components()
-    pub(crate) filter_method: FilterMethod // for internal use,no need to expose
+    pub(crate) filter_method:   FilterMethod // for internal use,no need to expose
 }
 
 /// A PNG decoder instance.
@@ -151,18 +844,28 @@ pub struct PngInfo<'a>
 /// [`decode`](PngDecoder::decode) will return pixels present in that image
 ///
 /// # Note
-/// The decoder currently expands images less than 8 bits per pixels to 8 bits per pixel
-/// if this is not desired, then I'd suggest another png decoder
+/// By default, the decoder expands images less than 8 bits per pixels to 8 bits per pixel,
+/// if this is not desired, use [`png_set_expand_depth`](zune_core::options::DecoderOptions::png_set_expand_depth)
+/// to get packed samples at the native depth
+///
+/// For animated images, [`decode_raw`](PngDecoder::decode_raw) and friends return the default
+/// image, use [`decode_apng`](PngDecoder::decode_apng) to get all animation frames
 ///
 /// To get extra details such as exif data and ICC profile if present, use [`get_info`](PngDecoder::get_info)
 /// and access the relevant fields exposed
+///
+/// To decode images from a `std::io::Read` source, e.g a file, use `PngSource`
+/// which reads and owns the data and creates decoders borrowing it
 pub struct PngDecoder<'a>
 {
     pub(crate) stream:          ZByteReader<'a>,
     pub(crate) options:         DecoderOptions,
     pub(crate) png_info:        PngInfo<'a>,
-    pub(crate) palette:         Vec<PLTEEntry>,
+    pub(crate) palette:         Vec<PaletteEntry>,
+    pub(crate) palette_len:     usize,
+    pub(crate) pending_trns:    &'a [u8],
     pub(crate) idat_chunks:     Vec<u8>,
+    pub(crate) inflated_data:   Option<Vec<u8>>,
     pub(crate) expanded_stride: Vec<u8>,
     pub(crate) previous_stride: Vec<u8>,
     pub(crate) trns_bytes:      [u16; 4],
@@ -170,7 +873,20 @@ pub struct PngDecoder<'a>
     pub(crate) seen_hdr:        bool,
     pub(crate) seen_ptle:       bool,
     pub(crate) seen_headers:    bool,
-    pub(crate) seen_trns:       bool
+    pub(crate) seen_trns:       bool,
+    pub(crate) seen_idat:       bool,
+    pub(crate) actl_info:       Option<ActlChunk>,
+    pub(crate) apng_frames:     Vec<ApngFrameData>,
+    pub(crate) chunk_summary:   Vec<ChunkSummary>,
+    pub(crate) decoded_rows:    Option<usize>,
+    pub(crate) crc_handler:     Option<CrcMismatchHandler>,
+    pub(crate) chunk_observer:  Option<ChunkObserver>,
+    pub(crate) progress:        Option<ProgressCallback>,
+    pub(crate) gamma_table:     Vec<u16>,
+    pub(crate) unknown_chunks:  Vec<(u32, Vec<u8>)>,
+    pub(crate) seen_chunks:     Vec<PngChunkType>,
+    pub(crate) preset_dict:     &'a [u8],
+    pub(crate) last_adler32:    Option<u32>
 }
 
 impl<'a> PngDecoder<'a>
@@ -208,23 +924,105 @@ impl<'a> PngDecoder<'a>
             stream:          ZByteReader::new(data),
             options:         options,
             palette:         Vec::new(),
+            palette_len:     0,
+            pending_trns:    &[],
             png_info:        PngInfo::default(),
             previous_stride: vec![],
             idat_chunks:     Vec::with_capacity(37), // randomly chosen size, my favourite number,
+            inflated_data:   None,
             expanded_stride: vec![],
             seen_ptle:       false,
             seen_trns:       false,
             seen_headers:    false,
             trns_bytes:      [0; 4],
-            chunk_handler:   default_chunk_handler
+            chunk_handler:   default_chunk_handler,
+            seen_idat:       false,
+            actl_info:       None,
+            apng_frames:     vec![],
+            chunk_summary:   vec![],
+            decoded_rows:    None,
+            crc_handler:     None,
+            chunk_observer:  None,
+            progress:        None,
+            gamma_table:     vec![],
+            unknown_chunks:  vec![],
+            seen_chunks:     vec![],
+            preset_dict:     &[],
+            last_adler32:    None
         }
     }
 
-    /// Get image dimensions or none if they aren't decoded
+    /// Reset the decoder to decode a new image from `data`
     ///
-    /// # Returns
-    /// - `Some((width,height))`
-    /// - `None`: The image headers haven't been decoded
+    /// This is equivalent to creating a new decoder with the same options, crc handler,
+    /// chunk handler, chunk observer, progress callback and preset dictionary, except that internal buffers
+    /// (e.g the buffer holding compressed image data and the palette) are cleared but their
+    /// allocations retained, so decoding many images with one decoder avoids re-allocating
+    /// them for every image.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zune_png::PngDecoder;
+    /// let images: Vec<Vec<u8>> = vec![];
+    ///
+    /// let mut decoder = PngDecoder::new(&[]);
+    ///
+    /// for image in &images
+    /// {
+    ///     decoder.reset(image);
+    ///     let pixels = decoder.decode_raw().unwrap();
+    /// }
+    /// ```
+    pub fn reset(&mut self, data: &'a [u8])
+    {
+        self.stream = ZByteReader::new(data);
+        self.png_info = PngInfo::default();
+        self.palette.clear();
+        self.palette_len = 0;
+        self.pending_trns = &[];
+        self.idat_chunks.clear();
+        self.inflated_data = None;
+        self.expanded_stride.clear();
+        self.previous_stride.clear();
+        self.trns_bytes = [0; 4];
+        self.seen_hdr = false;
+        self.seen_ptle = false;
+        self.seen_headers = false;
+        self.seen_trns = false;
+        self.seen_idat = false;
+        self.actl_info = None;
+        self.apng_frames.clear();
+        self.chunk_summary.clear();
+        self.decoded_rows = None;
+        self.gamma_table.clear();
+        self.unknown_chunks.clear();
+        self.seen_chunks.clear();
+        self.last_adler32 = None;
+    }
+
+    /// Free buffers used for decoding pixels while keeping header information
+    ///
+    /// This drops the compressed image data and scratch space used when de-filtering,
+    /// releasing their memory, while the dimensions, colorspace, palette and
+    /// [`get_info`](Self::get_info) remain available. It is useful for tools that read the headers
+    /// of many images but only occasionally decode pixels.
+    ///
+    /// Since the compressed image data is dropped, pixels can no longer be decoded
+    /// until the decoder is [`reset`](Self::reset), which re-reads the image.
+    pub fn release_pixel_buffers(&mut self)
+    {
+        self.idat_chunks = Vec::new();
+        self.inflated_data = None;
+        self.expanded_stride = Vec::new();
+        self.previous_stride = Vec::new();
+        self.gamma_table = Vec::new();
+    }
+
+    /// Get image dimensions or none if they aren't decoded
+    ///
+    /// # Returns
+    /// - `Some((width,height))`
+    /// - `None`: The image headers haven't been decoded
     ///   or there was an error decoding them
     pub const fn get_dimensions(&self) -> Option<(usize, usize)>
     {
@@ -238,6 +1036,8 @@ impl<'a> PngDecoder<'a>
     /// Return the depth of the image
     ///
     /// Bit depths less than 8 will be returned as [`BitDepth::Eight`](zune_core::bit_depth::BitDepth::Eight)
+    /// unless the decoder was configured to keep the native depth via
+    /// [`png_set_expand_depth`](zune_core::options::DecoderOptions::png_set_expand_depth)
     ///
     /// # Returns
     /// - `Some(depth)`:  The bit depth of the image.
@@ -248,13 +1048,44 @@ impl<'a> PngDecoder<'a>
         {
             return None;
         }
+        if self.keep_native_depth()
+        {
+            return match self.png_info.depth
+            {
+                1 => Some(BitDepth::One),
+                2 => Some(BitDepth::Two),
+                _ => Some(BitDepth::Four)
+            };
+        }
         match self.png_info.depth
         {
             1 | 2 | 4 | 8 => Some(BitDepth::Eight),
+            16 if self.options.png_get_strip_16() => Some(BitDepth::Eight),
             16 => Some(BitDepth::Sixteen),
             _ => unreachable!()
         }
     }
+    /// Return true if the image has a bit depth less than 8
+    /// and the decoder was configured not to expand it
+    pub(crate) const fn keep_native_depth(&self) -> bool
+    {
+        self.png_info.depth < 8 && !self.options.png_get_expand_depth()
+    }
+    /// Return true if the image has 16 bit samples and the decoder
+    /// was configured to reduce them to 8 bits
+    ///
+    /// Samples are processed at 16 bits and reduced as the final step
+    pub(crate) const fn will_strip_16(&self) -> bool
+    {
+        self.png_info.depth == 16 && self.options.png_get_strip_16()
+    }
+    /// Return true if the image is paletted and the decoder was
+    /// configured to return palette indices instead of expanding them
+    pub(crate) const fn keep_palette_indices(&self) -> bool
+    {
+        matches!(self.png_info.color, PngColor::Palette)
+            && self.options.png_get_keep_palette_indices()
+    }
     /// Get image colorspace
     ///
     /// If an image is a palette type, the colorspace is
@@ -263,16 +1094,45 @@ impl<'a> PngDecoder<'a>
     /// If an image has a transparency chunk, the colorspace
     /// will include that
     ///
+    /// If the image is kept at a native depth less than 8 bits,
+    /// this is always [`ColorSpace::Luma`], as pixels are returned as
+    /// stored, i.e a single grayscale sample or palette index per pixel.
+    ///
+    /// Paletted images whose indices are kept via
+    /// [`png_set_keep_palette_indices`](zune_core::options::DecoderOptions::png_set_keep_palette_indices)
+    /// are also reported as [`ColorSpace::Luma`], with one palette index per pixel,
+    /// the colours are available from [`get_palette`](Self::get_palette).
+    ///
+    /// Otherwise, if the decoder was configured to convert images to RGBA via
+    /// [`png_set_force_rgba`](zune_core::options::DecoderOptions::png_set_force_rgba)
+    /// this is always [`ColorSpace::RGBA`]
+    ///
     /// # Returns
     ///  - `Some(colorspace)`: The colorspace which the decoded bytes will be in
     ///  - `None`: If the image headers haven't been decoded, or there was an error
     ///     during decoding
     pub fn get_colorspace(&self) -> Option<ColorSpace>
+    {
+        if self.seen_hdr && self.will_force_rgba()
+        {
+            return Some(ColorSpace::RGBA);
+        }
+        self.decoded_colorspace()
+    }
+    /// Return the colorspace of pixels after palette and transparency
+    /// expansion, before any conversion to RGBA
+    pub(crate) fn decoded_colorspace(&self) -> Option<ColorSpace>
     {
         if !self.seen_hdr
         {
             return None;
         }
+        if self.keep_native_depth() || self.keep_palette_indices()
+        {
+            // samples are returned as stored, one grayscale sample or
+            // palette index per pixel
+            return Some(ColorSpace::Luma);
+        }
         if !self.seen_trns
         {
             match self.png_info.color
@@ -324,13 +1184,20 @@ impl<'a> PngDecoder<'a>
             b"pHYs" => PngChunkType::pHYs,
             b"tIME" => PngChunkType::tIME,
             b"gAMA" => PngChunkType::gAMA,
+            b"sBIT" => PngChunkType::sBIT,
+            b"bKGD" => PngChunkType::bKGD,
+            b"cHRM" => PngChunkType::cHRM,
+            b"sRGB" => PngChunkType::sRGB,
             b"acTL" => PngChunkType::acTL,
             b"fcTL" => PngChunkType::fcTL,
+            b"fdAT" => PngChunkType::fdAT,
             b"iCCP" => PngChunkType::iCCP,
             b"iTXt" => PngChunkType::iTXt,
             b"eXIf" => PngChunkType::eXIf,
             b"zTXt" => PngChunkType::zTXt,
             b"tEXt" => PngChunkType::tEXt,
+            b"sPLT" => PngChunkType::sPLT,
+            b"hIST" => PngChunkType::hIST,
             _ => PngChunkType::unkn
         };
 
@@ -362,7 +1229,21 @@ impl<'a> PngDecoder<'a>
 
                 if crc != calc_crc
                 {
-                    return Err(PngDecodeErrors::BadCrc(crc, calc_crc));
+                    // critical chunks have an uppercase first letter
+                    let is_critical = chunk_type_int[0] & (1 << 5) == 0;
+
+                    if is_critical || !self.options.png_get_lenient_crc()
+                    {
+                        return Err(PngDecodeErrors::BadCrc(crc, calc_crc));
+                    }
+                    warn!(
+                        "CRC mismatch in chunk {:?}, expected {} but found {}, continuing",
+                        chunk_type, crc, calc_crc
+                    );
+                    if let Some(handler) = &mut self.crc_handler
+                    {
+                        handler(chunk_type, crc, calc_crc);
+                    }
                 }
                 // go point after the chunk type
                 // The other parts expect the bit-reader to point to the
@@ -404,10 +1285,67 @@ impl<'a> PngDecoder<'a>
                 "First chunk not IHDR, Corrupt PNG"
             ));
         }
-        let mut seen_first_fctl = false;
         loop
         {
-            let header = self.read_chunk_header()?;
+            let chunk_start = self.stream.get_position();
+
+            let header = match self.read_chunk_header()
+            {
+                Ok(header) => header,
+                Err(err) if self.options.png_get_allow_truncated() =>
+                {
+                    let position = self.stream.get_position();
+                    self.stream.rewind(position - chunk_start);
+
+                    if !self.read_truncated_chunk()
+                    {
+                        return Err(err);
+                    }
+                    break;
+                }
+                Err(err) => return Err(err)
+            };
+
+            if self.options.png_get_record_chunks()
+            {
+                self.chunk_summary.push(ChunkSummary {
+                    chunk_type: header.chunk_type,
+                    name:       header.chunk,
+                    crc:        header.crc,
+                    length:     header.length
+                });
+            }
+            if let Some(observer) = &mut self.chunk_observer
+            {
+                // the stream points to the start of the chunk data
+                let data = self.stream.peek_at(0, header.length)?;
+
+                observer(&header.chunk, data, header.crc);
+            }
+            if header.chunk_type.is_once_only()
+            {
+                if self.seen_chunks.contains(&header.chunk_type)
+                {
+                    if self.options.get_strict_mode()
+                    {
+                        return Err(PngDecodeErrors::DuplicateChunk(header.chunk));
+                    }
+                    warn!(
+                        "Ignoring duplicate {:?} chunk, only the first one is used",
+                        header.chunk_type
+                    );
+                    // skip chunk data and crc
+                    self.stream.skip(header.length + 4);
+                    continue;
+                }
+                self.seen_chunks.push(header.chunk_type);
+            }
+            if self.options.png_get_skip_ancillary() && header.chunk_type.is_metadata()
+            {
+                // skip chunk data and crc
+                self.stream.skip(header.length + 4);
+                continue;
+            }
 
             match header.chunk_type
             {
@@ -431,6 +1369,26 @@ impl<'a> PngDecoder<'a>
                 {
                     self.parse_gama(header)?;
                 }
+                PngChunkType::sBIT =>
+                {
+                    self.parse_sbit(header)?;
+                }
+                PngChunkType::bKGD =>
+                {
+                    self.parse_bkgd(header)?;
+                }
+                PngChunkType::cHRM =>
+                {
+                    self.parse_chrm(header)?;
+                }
+                PngChunkType::sRGB =>
+                {
+                    self.parse_srgb(header)?;
+                }
+                PngChunkType::pHYs =>
+                {
+                    self.parse_phys(header)?;
+                }
                 PngChunkType::acTL =>
                 {
                     self.parse_actl(header)?;
@@ -449,7 +1407,7 @@ impl<'a> PngDecoder<'a>
                 }
                 PngChunkType::iTXt =>
                 {
-                    self.parse_itxt(header);
+                    self.parse_itxt(header)?;
                 }
                 PngChunkType::zTXt =>
                 {
@@ -459,26 +1417,21 @@ impl<'a> PngDecoder<'a>
                 {
                     self.parse_text(header);
                 }
+                PngChunkType::sPLT =>
+                {
+                    self.parse_splt(header)?;
+                }
+                PngChunkType::hIST =>
+                {
+                    self.parse_hist(header)?;
+                }
                 PngChunkType::fcTL =>
                 {
-                    // If we have seen a fcTL chunk and we are
-                    // about to see another one, means we
-                    // have another frame incoming,
-                    // so just exit since we do not support animated
-                    // png
-                    if seen_first_fctl
-                    {
-                        break;
-                    }
-
-                    (self.chunk_handler)(
-                        header.length,
-                        header.chunk,
-                        &mut self.stream,
-                        header.crc
-                    )?;
-
-                    seen_first_fctl = true;
+                    self.parse_fctl(header)?;
+                }
+                PngChunkType::fdAT =>
+                {
+                    self.parse_fdat(header)?;
                 }
                 PngChunkType::IEND =>
                 {
@@ -486,6 +1439,18 @@ impl<'a> PngDecoder<'a>
                 }
                 _ =>
                 {
+                    // critical chunks have an uppercase first letter, a decoder
+                    // that doesn't understand one cannot decode the image
+                    let is_critical = header.chunk[0] & (1 << 5) == 0;
+
+                    if is_critical && self.options.get_strict_mode()
+                    {
+                        return Err(PngDecodeErrors::UnknownCriticalChunk(header.chunk));
+                    }
+                    if !is_critical && self.options.png_get_keep_unknown_chunks()
+                    {
+                        self.keep_unknown_chunk(&header);
+                    }
                     (self.chunk_handler)(header.length, header.chunk, &mut self.stream, header.crc)?
                 }
             }
@@ -493,6 +1458,75 @@ impl<'a> PngDecoder<'a>
         self.seen_headers = true;
         Ok(())
     }
+
+    /// Retain the data of an unknown ancillary chunk if it fits
+    /// within the configured limit
+    fn keep_unknown_chunk(&mut self, header: &PngChunk)
+    {
+        let retained: usize = self.unknown_chunks.iter().map(|(_, data)| data.len()).sum();
+
+        if retained + header.length > self.options.png_get_unknown_chunks_limit()
+        {
+            warn!(
+                "Not retaining {} chunk, retained chunk data would exceed the limit of {} bytes",
+                core::str::from_utf8(&header.chunk).unwrap_or("XXXX"),
+                self.options.png_get_unknown_chunks_limit()
+            );
+            return;
+        }
+        if let Ok(data) = self.stream.peek_at(0, header.length)
+        {
+            self.unknown_chunks
+                .push((u32::from_be_bytes(header.chunk), data.to_vec()));
+        }
+    }
+
+    /// Handle a chunk cut short by the end of the file
+    ///
+    /// The available data of a truncated `IDAT` chunk is kept, other
+    /// truncated chunks are ignored if they come after image data.
+    ///
+    /// Returns false if the chunk isn't truncated or there is no image data to
+    /// recover, in which case the error that led here should be reported
+    fn read_truncated_chunk(&mut self) -> bool
+    {
+        let remaining = self.stream.remaining_bytes();
+
+        let header = remaining.get(..8).map(|header| {
+            let length = u32::from_be_bytes(header[..4].try_into().unwrap()) as usize;
+
+            (length, &header[4..])
+        });
+
+        match header
+        {
+            Some((length, b"IDAT")) if remaining.len() < length.saturating_add(12) =>
+            {
+                let available = &remaining[8..];
+
+                warn!(
+                    "IDAT chunk truncated, {} of {} bytes present",
+                    available.len().min(length),
+                    length
+                );
+                self.idat_chunks
+                    .extend_from_slice(&available[..available.len().min(length)]);
+                self.seen_idat = true;
+            }
+            Some((length, _)) if self.seen_idat && remaining.len() < length.saturating_add(12) =>
+            {
+                warn!("Ignoring truncated chunk after image data");
+            }
+            None if self.seen_idat =>
+            {
+                warn!("Ignoring truncated chunk header after image data");
+            }
+            _ => return false
+        }
+        self.stream.skip(remaining.len());
+
+        true
+    }
     /// Return the configured image byte endian which the pixels
     /// will be in if the image is in 16 bit
     ///
@@ -518,22 +1552,210 @@ impl<'a> PngDecoder<'a>
         {
             return None;
         }
+        Some(
+            self.checked_output_buffer_size()
+                .expect("Output buffer size overflows usize")
+        )
+    }
+
+    /// Return the number of bytes required to hold a decoded image frame
+    /// or `None` if the calculation overflows
+    fn checked_output_buffer_size(&self) -> Option<usize>
+    {
+        let (width, height) = self.get_output_dimensions()?;
 
+        self.frame_buffer_size(width, height)
+    }
+
+    /// Return the number of bytes required to hold decoded pixels of
+    /// the given dimensions, or `None` if the calculation overflows
+    pub(crate) fn frame_buffer_size(&self, width: usize, height: usize) -> Option<usize>
+    {
         let info = &self.png_info;
-        let bytes = if info.depth == 16 { 2 } else { 1 };
+
+        if self.keep_native_depth()
+        {
+            // packed samples, each row starts on a byte boundary
+            let stride = width.checked_mul(usize::from(info.depth))?.checked_add(7)? / 8;
+
+            return stride.checked_mul(height);
+        }
+        let bytes = if info.depth == 16 && !self.will_strip_16() { 2 } else { 1 };
 
         let out_n = self.get_colorspace().unwrap().num_components();
 
-        let new_len = info
-            .width
-            .checked_mul(info.height)
-            .unwrap()
-            .checked_mul(out_n)
-            .unwrap()
+        width
+            .checked_mul(height)?
+            .checked_mul(out_n)?
             .checked_mul(bytes)
-            .unwrap();
+    }
+
+    /// Return the factor images are scaled down by when decoding
+    ///
+    /// Packed samples and palette indices cannot be averaged, so they are never scaled
+    fn scale_factor(&self) -> usize
+    {
+        if self.keep_native_depth() || self.keep_palette_indices()
+        {
+            return 1;
+        }
+        self.options.png_get_scale_down().factor()
+    }
+
+    /// Return the dimensions of decoded images
+    ///
+    /// This is the same as [`get_dimensions`](Self::get_dimensions) unless images are scaled
+    /// down while decoding, see [`png_set_scale_down`](zune_core::options::DecoderOptions::png_set_scale_down),
+    /// in which case it's the dimensions of the scaled image.
+    ///
+    /// # Returns
+    /// - `Some((width, height))`: Dimensions of decoded images
+    /// - `None`: The headers haven't been decoded
+    pub fn get_output_dimensions(&self) -> Option<(usize, usize)>
+    {
+        if !self.seen_hdr
+        {
+            return None;
+        }
+        let scale = self.scale_factor();
+
+        Some((
+            self.png_info.width.div_ceil(scale),
+            self.png_info.height.div_ceil(scale)
+        ))
+    }
+
+    /// Get the image palette
+    ///
+    /// This contains the entries of the PLTE chunk, with alpha values
+    /// filled from the tRNS chunk if present.
+    ///
+    /// Pixels of paletted images are expanded to RGB(A) when decoding, this
+    /// is useful for callers who want to work with the color table directly.
+    ///
+    /// # Returns
+    /// - `Some(palette)`: The palette entries of the image
+    /// - `None`: The headers haven't been decoded or the image is not a paletted image
+    pub fn get_palette(&self) -> Option<&[PaletteEntry]>
+    {
+        if !self.seen_headers || !self.seen_ptle || self.png_info.color != PngColor::Palette
+        {
+            return None;
+        }
+        Some(&self.palette[..self.palette_len])
+    }
+
+    /// Set a function called for each ancillary chunk whose CRC doesn't
+    /// match its contents
+    ///
+    /// The function receives the chunk type, the CRC stored in the file
+    /// and the CRC calculated from the chunk.
+    ///
+    /// It's only called when lenient CRC handling is enabled via
+    /// [`png_set_lenient_crc`](zune_core::options::DecoderOptions::png_set_lenient_crc),
+    /// otherwise mismatches are errors
+    pub fn set_crc_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(PngChunkType, u32, u32) + Send + 'static
+    {
+        self.crc_handler = Some(Box::new(handler));
+    }
+
+    /// Set a function called for every chunk read by [`decode_headers`](Self::decode_headers),
+    /// before the decoder processes it
+    ///
+    /// The function receives the chunk type, the chunk data and the CRC stored in the file.
+    /// The data is borrowed from the input, so it isn't copied.
+    ///
+    /// This can be used to extract chunks the decoder doesn't understand,
+    /// e.g vendor specific chunks, or to inspect chunk ordering
+    pub fn set_chunk_observer<F>(&mut self, observer: F)
+    where
+        F: FnMut(&[u8; 4], &[u8], u32) + Send + 'static
+    {
+        self.chunk_observer = Some(Box::new(observer));
+    }
+
+    /// Set a function called periodically while decoding pixels, with an estimate
+    /// of the fraction of the image decoded so far, from `0.0` to `1.0`
+    ///
+    /// For non-interlaced images progress is reported about every hundredth of
+    /// the rows, for interlaced images after each of the seven passes.
+    /// The function is called with `1.0` once the image is decoded.
+    ///
+    /// This is only reported for whole images, i.e not when decoding single
+    /// interlace passes, rows or animation frames.
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(f32) + Send + 'static
+    {
+        self.progress = Some(Box::new(callback));
+    }
+
+    /// Set a preset dictionary used to decompress the image data
+    ///
+    /// **Experimental and non-standard**: the png specification doesn't allow image data
+    /// to be compressed with a preset dictionary, so this only helps with images
+    /// custom-encoded with a matching dictionary, e.g batches of tiny sprites
+    /// sharing most of their bytes, which compress poorly on their own.
+    /// Standard pngs never reference a dictionary and decode as usual with one set.
+    ///
+    /// Decoding image data that needs a dictionary fails if none was set or if it doesn't
+    /// match the one the data was compressed with.
+    ///
+    /// The dictionary isn't used for compressed metadata, e.g text and ICC profiles.
+    pub fn set_preset_dictionary(&mut self, dictionary: &'a [u8])
+    {
+        self.preset_dict = dictionary;
+    }
+
+    /// Return the number of complete rows decoded from the image data
+    ///
+    /// This is the image height unless the image data was truncated
+    /// or corrupt and decoding continued because of
+    /// [`png_set_allow_truncated`](zune_core::options::DecoderOptions::png_set_allow_truncated),
+    /// in which case rows from this one onwards were filled with zeroes.
+    ///
+    /// For interlaced images this counts rows from the top of the image
+    /// up to the first one missing pixels from any pass.
+    ///
+    /// # Returns
+    /// - `Some(rows)`: Number of complete rows
+    /// - `None`: Indicates the image data hasn't been decoded
+    pub const fn get_decoded_rows(&self) -> Option<usize>
+    {
+        self.decoded_rows
+    }
+
+    /// Return the adler32 checksum computed over the decompressed image data
+    ///
+    /// This is the checksum of what the image data actually inflated to, which
+    /// can be compared with the value other tools report, and is useful when
+    /// debugging encoders that write subtly wrong checksums. If the checksum didn't match
+    /// the one stored in the image data and decoding continued because of
+    /// [`png_set_allow_truncated`](zune_core::options::DecoderOptions::png_set_allow_truncated),
+    /// this is the computed value and not the stored one.
+    ///
+    /// # Returns
+    /// - `Some(adler32)`: The checksum computed over the decompressed image data
+    /// - `None`: The image data hasn't been decoded, or checksum confirmation is disabled via
+    ///   [`inflate_set_confirm_adler`](zune_core::options::DecoderOptions::inflate_set_confirm_adler)
+    pub const fn last_adler32(&self) -> Option<u32>
+    {
+        self.last_adler32
+    }
 
-        Some(new_len)
+    /// Return unknown ancillary chunks retained while decoding headers, in
+    /// the order they appear in the file
+    ///
+    /// Each entry holds the chunk type as a big endian integer, i.e the four
+    /// type bytes as they appear in the file, and the chunk data.
+    ///
+    /// This is only populated if [`png_set_keep_unknown_chunks`](zune_core::options::DecoderOptions::png_set_keep_unknown_chunks)
+    /// is enabled, and is empty before headers are decoded.
+    pub fn get_unknown_chunks(&self) -> &[(u32, Vec<u8>)]
+    {
+        &self.unknown_chunks
     }
 
     /// Get png information which was extracted from the headers
@@ -558,7 +1780,8 @@ impl<'a> PngDecoder<'a>
     ///
     /// # Arguments
     /// - `out`: The slice which we will write our values into.
-    ///         If the slice length is smaller than [`output_buffer_size`](Self::output_buffer_size), it's an error
+    ///   If the slice length is smaller than [`output_buffer_size`](Self::output_buffer_size), it's an error
+    ///   and the decoder can be retried with a larger slice
     ///
     /// # Endianness
     ///
@@ -575,127 +1798,728 @@ impl<'a> PngDecoder<'a>
         {
             self.decode_headers()?;
         }
-
-        if self.expanded_stride.is_empty() && self.png_info.depth < 8
-        {
-            // add space for single stride
-            // this will be used for small bit depths of less than 8 to expand
-            // to 8 bits
-            self.expanded_stride.resize(
-                self.png_info.width * self.get_colorspace().unwrap().num_components(),
-                0
-            );
-            self.previous_stride.resize(
-                self.png_info.width * self.get_colorspace().unwrap().num_components(),
-                0
-            );
-        }
-        info!("Input Colorspace: {:?} ", self.png_info.color);
-
-        info!("Output Colorspace: {:?} ", self.get_colorspace().unwrap());
-
-        let info = self.png_info.clone();
-
         let image_len = self.output_buffer_size().unwrap();
 
+        // check before inflating, so a buffer that is too small fails early
         if out.len() < image_len
         {
             return Err(PngDecodeErrors::TooSmallOutput(image_len, out.len()));
         }
-
-        let out = &mut out[..image_len];
-
         // go parse IDAT chunks returning the inflate
-        let deflate_data = self.inflate()?;
+        let deflate_data = self.inflate_idat()?;
 
-        // remove idat chunks from memory
-        // we are already done with them.
-        self.idat_chunks = Vec::new();
+        // the image data may change how the image is interpreted and with it the
+        // output size, keep the data so the decoder can be retried with a larger buffer
+        let image_len = self.output_buffer_size().unwrap();
 
-        if info.interlace_method == InterlaceMethod::Standard
+        if out.len() < image_len
         {
-            // allocate out to be enough to hold raw decoded bytes
+            self.inflated_data = Some(deflate_data);
 
-            self.create_png_image_raw(&deflate_data, info.width, info.height, out, &info)?;
+            return Err(PngDecodeErrors::TooSmallOutput(image_len, out.len()));
         }
-        else if info.interlace_method == InterlaceMethod::Adam7
+        self.decode_inflated_into(&deflate_data, out)
+    }
+
+    /// Decode PNG encoded images into a buffer that is reused across images
+    ///
+    /// `buffer` is grown if it's smaller than [`output_buffer_size`](Self::output_buffer_size)
+    /// but never shrunk, so a caller decoding many images can cycle through a small pool of
+    /// buffers without reallocating once they are large enough. Unlike
+    /// [`decode_raw`](Self::decode_raw), no new buffer is allocated for the pixels.
+    ///
+    /// The pixels are written to the start of `buffer`, bytes past the image are left as they were.
+    /// Endianness of 16 bit samples is the same as for [`decode_into`](Self::decode_into)
+    ///
+    /// # Returns
+    /// - `Ok(usize)`: The number of bytes of `buffer` holding the decoded image
+    /// - `Err(PngDecodeErrors)`: Any error encountered during decoding
+    pub fn decode_into_reused(&mut self, buffer: &mut Vec<u8>) -> Result<usize, PngDecodeErrors>
+    {
+        if !self.seen_headers
         {
-            self.decode_interlaced(&deflate_data, out, &info)?;
+            self.decode_headers()?;
         }
+        let deflate_data = self.inflate_idat()?;
 
-        // convert to set endian if need be
-        if self.get_depth().unwrap() == BitDepth::Sixteen
+        // size after inflating since the data may change how the image is interpreted
+        let image_len = self.output_buffer_size().unwrap();
+
+        if buffer.len() < image_len
         {
-            convert_be_to_target_endian_u16(out, self.byte_endian(), self.options.use_sse41());
+            buffer.resize(image_len, 0);
         }
+        self.decode_inflated_into(&deflate_data, &mut buffer[..image_len])?;
 
-        Ok(())
+        Ok(image_len)
     }
 
-    /// Decode data returning it into `Vec<u8>`, endianness of
-    /// returned bytes in case of image being 16 bits is given
-    /// [`byte_endian()`](Self::byte_endian) method
+    /// Reconstruct an image from already decompressed image data
+    /// and write raw pixels into `out`
     ///
+    /// `inflated` holds the filtered scanlines as they come out of the zlib
+    /// stream of the `IDAT` chunks, each row preceded by its filter type.
+    /// This runs only the unfiltering and post-processing stages of
+    /// [`decode_into`](Self::decode_into), which is useful when the decompressed
+    /// data is cached, or for testing the filters in isolation.
     ///
-    /// returns: `Result<Vec<u8, Global>, PngErrors>`
+    /// Headers are decoded if they haven't been, the image data in the
+    /// file itself is not decompressed.
     ///
-    pub fn decode_raw(&mut self) -> Result<Vec<u8>, PngDecodeErrors>
+    /// # Arguments
+    /// - `inflated`: Decompressed image data, for interlaced images this
+    ///   contains all seven passes
+    /// - `out`: The slice which we will write our values into.
+    ///   If the slice length is smaller than [`output_buffer_size`](Self::output_buffer_size), it's an error
+    pub fn unfilter_from(&mut self, inflated: &[u8], out: &mut [u8])
+        -> Result<(), PngDecodeErrors>
     {
         if !self.seen_headers
         {
             self.decode_headers()?;
         }
-
-        // allocate
-        let new_len = self.output_buffer_size().unwrap();
-        let mut out: Vec<u8> = vec![0; new_len];
-        //decode
-        self.decode_into(&mut out)?;
-
-        Ok(out)
+        self.decode_inflated_into(inflated, out)
     }
 
-    fn decode_interlaced(
-        &mut self, deflate_data: &[u8], out: &mut [u8], info: &PngInfo
+    /// Decode already decompressed image data into `out`
+    fn decode_inflated_into(
+        &mut self, deflate_data: &[u8], out: &mut [u8]
     ) -> Result<(), PngDecodeErrors>
     {
-        const XORIG: [usize; 7] = [0, 4, 0, 2, 0, 1, 0];
-        const YORIG: [usize; 7] = [0, 0, 4, 0, 2, 0, 1];
-
-        const XSPC: [usize; 7] = [8, 8, 4, 4, 2, 2, 1];
-        const YSPC: [usize; 7] = [8, 8, 8, 4, 4, 2, 2];
+        if self.expanded_stride.is_empty() && self.png_info.depth < 8
+        {
+            // add space for single stride
+            // this will be used for small bit depths of less than 8 to expand
+            // to 8 bits
+            self.expanded_stride.resize(
+                self.png_info.width * self.decoded_colorspace().unwrap().num_components(),
+                0
+            );
+            self.previous_stride.resize(
+                self.png_info.width * self.decoded_colorspace().unwrap().num_components(),
+                0
+            );
+        }
+        info!("Input Colorspace: {:?} ", self.png_info.color);
+
+        info!("Output Colorspace: {:?} ", self.get_colorspace().unwrap());
+
+        let info = self.png_info.clone();
+
+        let image_len = self.output_buffer_size().unwrap();
+
+        if out.len() < image_len
+        {
+            return Err(PngDecodeErrors::TooSmallOutput(image_len, out.len()));
+        }
+
+        let final_out = &mut out[..image_len];
+
+        // 16 bit samples are processed at full precision and reduced at the end
+        let mut wide = Vec::new();
+
+        let out: &mut [u8] = if self.will_strip_16()
+        {
+            wide.resize(image_len * 2, 0);
+            &mut wide
+        }
+        else
+        {
+            &mut *final_out
+        };
+        let image_len = out.len();
+
+        let force_rgba = self.will_force_rgba();
+
+        // pixels are decoded to the start of the output, and expanded to RGBA at the end
+        let decoded_len = if force_rgba
+        {
+            image_len / 4 * self.decoded_colorspace().unwrap().num_components()
+        }
+        else
+        {
+            image_len
+        };
+        let decoded = &mut out[..decoded_len];
+
+        let scale = self.scale_factor();
+
+        let mut callback = self.progress.take();
+        let mut progress = |fraction: f32| {
+            if let Some(callback) = &mut callback
+            {
+                callback(fraction);
+            }
+        };
+
+        let result = if scale > 1
+        {
+            self.create_scaled_png_image(deflate_data, decoded, &info, scale, &mut progress)
+        }
+        else if info.interlace_method == InterlaceMethod::Standard
+        {
+            // allocate out to be enough to hold raw decoded bytes
+            self.create_png_image_raw(
+                deflate_data,
+                info.width,
+                info.height,
+                decoded,
+                &info,
+                &mut progress
+            )
+        }
+        else if info.interlace_method == InterlaceMethod::Adam7
+        {
+            self.decode_interlaced(deflate_data, decoded, &info, &mut progress)
+        }
+        else
+        {
+            Ok(())
+        };
+        self.progress = callback;
+        result?;
+
+        if self.will_apply_gamma()
+        {
+            self.apply_gamma(decoded);
+        }
+        if self.will_premultiply_alpha()
+        {
+            self.premultiply_alpha(decoded);
+        }
+        if force_rgba
+        {
+            self.expand_to_rgba(out);
+        }
+        // convert to set endian if need be
+        if self.get_depth().unwrap() == BitDepth::Sixteen
+        {
+            convert_be_to_target_endian_u16(out, self.byte_endian(), self.options.use_sse41());
+        }
+        if self.will_strip_16()
+        {
+            strip_16_bits(&wide, final_out, self.options.png_get_downsample_rounding());
+        }
+        if let Some(callback) = &mut self.progress
+        {
+            callback(1.0);
+        }
+        Ok(())
+    }
+
+    /// Return true if decoded samples should be gamma corrected
+    fn will_apply_gamma(&self) -> bool
+    {
+        self.options.png_get_apply_gamma()
+            && self.png_info.gamma.is_some()
+            && !self.keep_native_depth()
+            && !self.keep_palette_indices()
+    }
+
+    /// Gamma correct the colour samples of decoded pixels, leaving alpha untouched
+    ///
+    /// 16 bit samples are expected to be big endian
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn apply_gamma(&mut self, pixels: &mut [u8])
+    {
+        let sixteen_bit = self.png_info.depth == 16;
+
+        if self.gamma_table.is_empty()
+        {
+            let file_gamma = self.png_info.gamma.unwrap_or(1.0 / 2.2);
+            let exponent = 1.0 / (file_gamma * self.options.png_get_display_gamma());
+
+            let max = if sixteen_bit { 65535.0 } else { 255.0 };
+            let entries = if sixteen_bit { 65536 } else { 256 };
+
+            self.gamma_table = (0..entries)
+                .map(|x| ((x as f32 / max).powf(exponent) * max).round() as u16)
+                .collect();
+        }
+        let colorspace = self.decoded_colorspace().unwrap();
+        let components = colorspace.num_components();
+        let color_components = components - usize::from(colorspace.has_alpha());
+
+        let table = &self.gamma_table;
+
+        if sixteen_bit
+        {
+            for pixel in pixels.chunks_exact_mut(components * 2)
+            {
+                for sample in pixel[..color_components * 2].chunks_exact_mut(2)
+                {
+                    let value = u16::from_be_bytes([sample[0], sample[1]]);
+
+                    sample.copy_from_slice(&table[usize::from(value)].to_be_bytes());
+                }
+            }
+        }
+        else
+        {
+            for pixel in pixels.chunks_exact_mut(components)
+            {
+                for sample in &mut pixel[..color_components]
+                {
+                    *sample = table[usize::from(*sample)] as u8;
+                }
+            }
+        }
+    }
+
+    /// Return true if colour samples should be multiplied by alpha
+    fn will_premultiply_alpha(&self) -> bool
+    {
+        self.options.png_get_premultiply_alpha() && self.decoded_colorspace().unwrap().has_alpha()
+    }
+
+    /// Multiply the colour samples of decoded pixels by their alpha
+    ///
+    /// 16 bit samples are expected to be big endian
+    #[allow(clippy::cast_possible_truncation)]
+    fn premultiply_alpha(&self, pixels: &mut [u8])
+    {
+        let components = self.decoded_colorspace().unwrap().num_components();
+
+        if self.png_info.depth == 16
+        {
+            for pixel in pixels.chunks_exact_mut(components * 2)
+            {
+                let (color, alpha) = pixel.split_at_mut((components - 1) * 2);
+                let alpha = u32::from(u16::from_be_bytes([alpha[0], alpha[1]]));
+
+                for sample in color.chunks_exact_mut(2)
+                {
+                    let value = u32::from(u16::from_be_bytes([sample[0], sample[1]]));
+                    let value = (value * alpha + 32767) / 65535;
+
+                    sample.copy_from_slice(&(value as u16).to_be_bytes());
+                }
+            }
+        }
+        else
+        {
+            for pixel in pixels.chunks_exact_mut(components)
+            {
+                let (color, alpha) = pixel.split_at_mut(components - 1);
+                let alpha = u16::from(alpha[0]);
+
+                for sample in color
+                {
+                    *sample = ((u16::from(*sample) * alpha + 127) / 255) as u8;
+                }
+            }
+        }
+    }
+
+    /// Return true if decoded pixels should be converted to RGBA
+    pub(crate) fn will_force_rgba(&self) -> bool
+    {
+        self.options.png_get_force_rgba()
+            && !self.keep_native_depth()
+            && !self.keep_palette_indices()
+    }
+
+    /// Convert decoded pixels to RGBA in place
+    ///
+    /// `pixels` holds the decoded pixels at its start and must be exactly large
+    /// enough to hold them as RGBA. Grayscale is replicated to all colour
+    /// channels and missing alpha is set to fully opaque.
+    pub(crate) fn expand_to_rgba(&self, pixels: &mut [u8])
+    {
+        let colorspace = self.decoded_colorspace().unwrap();
+
+        if colorspace == ColorSpace::RGBA
+        {
+            return;
+        }
+        let bytes = if self.png_info.depth == 16 { 2 } else { 1 };
+
+        let in_size = colorspace.num_components() * bytes;
+        let out_size = 4 * bytes;
+
+        // work backwards so that pixels are moved before they are overwritten
+        for i in (0..pixels.len() / out_size).rev()
+        {
+            // opaque alpha is all ones in either endianness
+            let mut pixel = [u8::MAX; 8];
+            let input = &pixels[i * in_size..(i + 1) * in_size];
+
+            match colorspace
+            {
+                ColorSpace::Luma | ColorSpace::LumaA =>
+                {
+                    for channel in pixel[..3 * bytes].chunks_exact_mut(bytes)
+                    {
+                        channel.copy_from_slice(&input[..bytes]);
+                    }
+                    if colorspace == ColorSpace::LumaA
+                    {
+                        pixel[3 * bytes..out_size].copy_from_slice(&input[bytes..]);
+                    }
+                }
+                ColorSpace::RGB => pixel[..in_size].copy_from_slice(input),
+                _ => unreachable!()
+            }
+            pixels[i * out_size..(i + 1) * out_size].copy_from_slice(&pixel[..out_size]);
+        }
+    }
+
+    /// Decode data returning it into `Vec<u8>`, endianness of
+    /// returned bytes in case of image being 16 bits is given
+    /// [`byte_endian()`](Self::byte_endian) method
+    ///
+    ///
+    /// returns: `Result<Vec<u8, Global>, PngErrors>`
+    ///
+    pub fn decode_raw(&mut self) -> Result<Vec<u8>, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+
+        let deflate_data = self.inflate_idat()?;
+
+        // allocate, after inflating since the data may change how the image is interpreted
+        let new_len = self.output_buffer_size().unwrap();
+        let mut out: Vec<u8> = vec![0; new_len];
+        //decode
+        self.decode_inflated_into(&deflate_data, &mut out)?;
+
+        Ok(out)
+    }
+
+    /// Decode data returning it into `Vec<u8>`, returning an error instead
+    /// of aborting if memory for the image cannot be allocated
+    ///
+    /// This behaves like [`decode_raw`](Self::decode_raw), but the output buffer is
+    /// allocated with [`Vec::try_reserve_exact`] and the decoder checks that the buffer for
+    /// decompressed image data can be reserved before decompressing.
+    ///
+    /// This allows services to reject images too large to fit in memory
+    /// gracefully.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<u8>)`: The decoded pixels
+    /// - `Err(PngDecodeErrors::AllocationFailed)`: If memory for decoding could not be
+    ///   allocated, `bytes` is `usize::MAX` if the required size overflows a usize
+    /// - `Err(PngDecodeErrors)`: Any other error encountered during decoding
+    pub fn try_decode_raw(&mut self) -> Result<Vec<u8>, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        let new_len = self
+            .checked_output_buffer_size()
+            .ok_or(PngDecodeErrors::AllocationFailed { bytes: usize::MAX })?;
+
+        let mut out = Vec::new();
+
+        out.try_reserve_exact(new_len)
+            .map_err(|_| PngDecodeErrors::AllocationFailed { bytes: new_len })?;
+        out.resize(new_len, 0);
+
+        // the inflate decoder grows its buffer up to this size, ensure
+        // we can hold it alongside the output
+        let info = &self.png_info;
+        let depth_scale = if info.depth == 16 { 2 } else { 1 };
+
+        let inflate_len = (info.width + 1)
+            .checked_mul(info.height)
+            .and_then(|x| x.checked_mul(depth_scale))
+            .and_then(|x| x.checked_mul(usize::from(info.color.num_components())))
+            .ok_or(PngDecodeErrors::AllocationFailed { bytes: usize::MAX })?;
+
+        Vec::<u8>::new()
+            .try_reserve_exact(inflate_len)
+            .map_err(|_| PngDecodeErrors::AllocationFailed { bytes: inflate_len })?;
+
+        let deflate_data = self.inflate_idat()?;
+
+        // the image may have been reinterpreted with a different number of channels
+        let new_len = self.output_buffer_size().unwrap();
+
+        if new_len > out.len()
+        {
+            out.try_reserve_exact(new_len - out.len())
+                .map_err(|_| PngDecodeErrors::AllocationFailed { bytes: new_len })?;
+        }
+        out.resize(new_len, 0);
+
+        self.decode_inflated_into(&deflate_data, &mut out)?;
+
+        Ok(out)
+    }
+
+    /// Decode the image calling `callback` with each output row and its index
+    ///
+    /// Rows are passed in the same format as [`decode_raw`](Self::decode_raw) would
+    /// return them, i.e after de-filtering and expanding palettes, tRNS chunks and
+    /// small bit depths, and with 16 bit samples in the configured [`byte_endian`](Self::byte_endian).
+    ///
+    /// For non-interlaced images only a few rows are kept in memory at a time, so the whole output
+    /// image is never allocated, this is useful for processing large images.
+    ///
+    /// # Note
+    /// - The decompressed image data is still fully buffered before rows are produced
+    /// - Interlaced images cannot be produced row by row, for them the whole image is
+    ///   decoded first and then each row is passed to the callback
+    pub fn decode_rows(
+        &mut self, mut callback: impl FnMut(usize, &[u8])
+    ) -> Result<(), PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        if self.png_info.interlace_method != InterlaceMethod::Standard
+        {
+            let out = self.decode_raw()?;
+            let out_stride = out.len() / self.png_info.height.max(1);
+
+            for (i, row) in out.chunks_exact(out_stride).enumerate()
+            {
+                callback(i, row);
+            }
+            return Ok(());
+        }
+        let deflate_data = self.inflate_idat()?;
+
+        let info = self.png_info.clone();
+
+        let force_rgba = self.will_force_rgba();
+
+        let strip_16 = self.will_strip_16();
+
+        // rows are never scaled down, 16 bit samples are reduced to 8 bits last
+        let mut out_stride = self.frame_buffer_size(info.width, 1).unwrap()
+            * usize::from(info.height > 0)
+            * if strip_16 { 2 } else { 1 };
+        // rows are expanded to RGBA after all other processing
+        let rgba_stride = out_stride;
+
+        if force_rgba
+        {
+            out_stride = rgba_stride / 4 * self.decoded_colorspace().unwrap().num_components();
+        }
+
+        self.check_inflated_len(&deflate_data, info.width, info.height, &info)?;
+
+        let convert_endian = self.get_depth().unwrap() == BitDepth::Sixteen;
+        let apply_gamma = self.will_apply_gamma();
+        let premultiply_alpha = self.will_premultiply_alpha();
+
+        let mut rows = RowDecoder::new(&self.row_context(), info.width, &info, Vec::new());
+        let mut out_row = vec![0_u8; out_stride];
+        let mut rgba_row = vec![0_u8; if force_rgba { rgba_stride } else { 0 }];
+        let mut narrow_row = vec![0_u8; if strip_16 { rgba_stride / 2 } else { 0 }];
+
+        for (i, in_stride) in deflate_data
+            .chunks_exact(rows.in_stride())
+            .take(info.height)
+            .enumerate()
+        {
+            // gamma correction needs the decoder mutably, so the
+            // context can't be held across rows
+            rows.decode_row(&self.row_context(), in_stride, &mut out_row, &info)?;
+
+            if apply_gamma
+            {
+                self.apply_gamma(&mut out_row);
+            }
+            if premultiply_alpha
+            {
+                self.premultiply_alpha(&mut out_row);
+            }
+            let row = if force_rgba
+            {
+                rgba_row[..out_stride].copy_from_slice(&out_row);
+                self.expand_to_rgba(&mut rgba_row);
+                &mut rgba_row
+            }
+            else
+            {
+                &mut out_row
+            };
+            if convert_endian
+            {
+                convert_be_to_target_endian_u16(row, self.byte_endian(), self.options.use_sse41());
+            }
+            if strip_16
+            {
+                strip_16_bits(row, &mut narrow_row, self.options.png_get_downsample_rounding());
+                callback(i, &narrow_row);
+            }
+            else
+            {
+                callback(i, row);
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the dimensions of a single Adam7 pass of an interlaced image
+    ///
+    /// Passes are numbered from 0 to 6, early passes contain a coarse subset of the image
+    /// pixels, some passes may be empty (i.e have a width or height of zero) for small images.
+    ///
+    /// # Returns
+    /// - `Some((width, height))`: Dimensions of the pass
+    /// - `None`: The headers haven't been decoded or `pass` is not less than 7
+    pub fn interlace_pass_dimensions(&self, pass: usize) -> Option<(usize, usize)>
+    {
+        if !self.seen_hdr || pass >= 7
+        {
+            return None;
+        }
+        Some(adam7_pass_dimensions(
+            self.png_info.width,
+            self.png_info.height,
+            pass
+        ))
+    }
+
+    /// Decode a single Adam7 pass of an interlaced image into `out`
+    ///
+    /// The pass is written as a small image whose dimensions are given by
+    /// [`interlace_pass_dimensions`](Self::interlace_pass_dimensions), with the same pixel layout
+    /// as [`decode_into`](Self::decode_into) uses for the full image.
+    ///
+    /// This is useful for quickly showing a preview of an image using the first few passes,
+    /// unlike other decoding methods, this can be called multiple times to decode different passes.
+    ///
+    /// # Errors
+    /// - If the image is not interlaced
+    /// - If `pass` is not less than 7
+    /// - If `out` is too small to hold the pass
+    /// - Any error encountered during decoding
+    pub fn decode_interlace_pass(
+        &mut self, pass: usize, out: &mut [u8]
+    ) -> Result<(), PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        if self.png_info.interlace_method != InterlaceMethod::Adam7
+        {
+            return Err(PngDecodeErrors::GenericStatic(
+                "Image is not interlaced, cannot decode an interlace pass"
+            ));
+        }
+        if pass >= 7
+        {
+            return Err(PngDecodeErrors::Generic(format!(
+                "Invalid interlace pass {pass}, passes are numbered from 0 to 6"
+            )));
+        }
+        let info = self.png_info.clone();
+        let (width, height) = adam7_pass_dimensions(info.width, info.height, pass);
 
         let bytes = if info.depth == 16 { 2 } else { 1 };
 
-        let out_n = self.get_colorspace().unwrap().num_components();
+        let pass_len = if self.keep_native_depth()
+        {
+            // packed samples, each row starts on a byte boundary
+            (width * usize::from(info.component) * usize::from(info.depth)).div_ceil(8) * height
+        }
+        else
+        {
+            width * height * self.get_colorspace().unwrap().num_components() * bytes
+        };
+        let decoded_len = if self.will_force_rgba()
+        {
+            pass_len / 4 * self.decoded_colorspace().unwrap().num_components()
+        }
+        else
+        {
+            pass_len
+        };
 
-        let new_len = info.width * info.height * out_n * bytes;
+        // 16 bit samples are processed at full precision and reduced at the end
+        let out_len = if self.will_strip_16() { pass_len / 2 } else { pass_len };
 
-        // A mad idea would be to make this multithreaded :)
-        // They called me a mad man - Thanos
-        let out_bytes = out_n * bytes;
+        if out.len() < out_len
+        {
+            return Err(PngDecodeErrors::TooSmallOutput(out_len, out.len()));
+        }
+        if pass_len == 0
+        {
+            return Ok(());
+        }
+        // don't consume the IDAT chunks, so that other passes can be decoded
+        let (deflate_data, _) = self.inflate(&self.idat_chunks, info.width, info.height)?;
+
+        // skip data of earlier passes
+        let offset: usize = (0..pass)
+            .map(|p| {
+                let (x, y) = adam7_pass_dimensions(info.width, info.height, p);
+
+                self.inflated_rows_size(info.component, x, y)
+            })
+            .sum();
+
+        let pass_data = deflate_data
+            .get(offset..)
+            .ok_or(PngDecodeErrors::GenericStatic("Too short data"))?;
 
-        // temporary space for  holding interlaced images
-        let mut final_out = vec![0_u8; new_len];
+        let final_out = &mut out[..out_len];
+        let mut wide = Vec::new();
 
+        let out: &mut [u8] = if self.will_strip_16()
+        {
+            wide.resize(pass_len, 0);
+            &mut wide
+        }
+        else
+        {
+            &mut *final_out
+        };
+
+        self.create_png_image_raw(
+            pass_data,
+            width,
+            height,
+            &mut out[..decoded_len],
+            &info,
+            &mut |_| ()
+        )?;
+
+        if self.will_force_rgba()
+        {
+            self.expand_to_rgba(out);
+        }
+
+        if self.get_depth().unwrap() == BitDepth::Sixteen
+        {
+            convert_be_to_target_endian_u16(out, self.byte_endian(), self.options.use_sse41());
+        }
+        if self.will_strip_16()
+        {
+            strip_16_bits(&wide, final_out, self.options.png_get_downsample_rounding());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn decode_interlaced(
+        &mut self, deflate_data: &[u8], out: &mut [u8], info: &PngInfo,
+        progress: &mut dyn FnMut(f32)
+    ) -> Result<(), PngDecodeErrors>
+    {
+        let bytes = if info.depth == 16 { 2 } else { 1 };
+
+        let out_n = self.decoded_colorspace().unwrap().num_components();
+
+        let out_bytes = out_n * bytes;
+
+        // find the data of each non-empty pass, passes are stored one after the other
+        let mut passes = Vec::with_capacity(7);
         let mut image_offset = 0;
 
-        // get the maximum height and width for the whole interlace part
         for p in 0..7
         {
-            let x = (info
-                .width
-                .saturating_sub(XORIG[p])
-                .saturating_add(XSPC[p])
-                .saturating_sub(1))
-                / XSPC[p];
-
-            let y = (info
-                .height
-                .saturating_sub(YORIG[p])
-                .saturating_add(YSPC[p])
-                .saturating_sub(1))
-                / YSPC[p];
+            let (x, y) = adam7_pass_dimensions(info.width, info.height, p);
 
             if x != 0 && y != 0
             {
@@ -714,28 +2538,135 @@ impl<'a> PngDecoder<'a>
 
                 let deflate_slice = &deflate_data[image_offset..image_offset + image_len];
 
-                self.create_png_image_raw(deflate_slice, x, y, &mut final_out, info)?;
+                passes.push((p, x, y, deflate_slice));
+
+                image_offset += image_len;
+            }
+        }
+
+        // each pass is reconstructed into its own buffer, which is then
+        // scattered into the final image
+        let mut pass_pixels: Vec<Vec<u8>> = passes
+            .iter()
+            .map(|(_, x, y, _)| vec![0_u8; x * y * out_bytes])
+            .collect();
+
+        self.create_passes(&passes, &mut pass_pixels, info, progress)?;
 
-                for j in 0..y
+        for ((p, x, y, _), pass_out) in passes.iter().zip(&pass_pixels)
+        {
+            let (p, x, y) = (*p, *x, *y);
+
+            if self.keep_native_depth()
+            {
+                copy_packed_pass(
+                    pass_out,
+                    out,
+                    (x, y),
+                    (XORIG[p], YORIG[p]),
+                    (XSPC[p], YSPC[p]),
+                    info.width,
+                    usize::from(info.depth)
+                );
+                continue;
+            }
+
+            for j in 0..y
+            {
+                for i in 0..x
                 {
-                    for i in 0..x
-                    {
-                        let out_y = j * YSPC[p] + YORIG[p];
-                        let out_x = i * XSPC[p] + XORIG[p];
+                    let out_y = j * YSPC[p] + YORIG[p];
+                    let out_x = i * XSPC[p] + XORIG[p];
 
-                        let final_start = out_y * info.width * out_bytes + out_x * out_bytes;
-                        let out_start = (j * x + i) * out_bytes;
+                    let final_start = out_y * info.width * out_bytes + out_x * out_bytes;
+                    let out_start = (j * x + i) * out_bytes;
 
-                        out[final_start..final_start + out_bytes]
-                            .copy_from_slice(&final_out[out_start..out_start + out_bytes]);
-                    }
+                    out[final_start..final_start + out_bytes]
+                        .copy_from_slice(&pass_out[out_start..out_start + out_bytes]);
                 }
-                image_offset += image_len;
             }
         }
         Ok(())
     }
 
+    /// De-filter and post process the pixels of Adam7 passes
+    ///
+    /// Progress is reported as passes complete, weighted by their number of pixels
+    fn create_passes(
+        &mut self, passes: &[(usize, usize, usize, &[u8])], pass_pixels: &mut [Vec<u8>],
+        info: &PngInfo, progress: &mut dyn FnMut(f32)
+    ) -> Result<(), PngDecodeErrors>
+    {
+        #[cfg(feature = "threads")]
+        {
+            if info.width * info.height >= THREADED_INTERLACE_PIXELS
+            {
+                return self.create_passes_threaded(passes, pass_pixels, info, progress);
+            }
+        }
+        let mut decoded_pixels = 0;
+
+        for ((_, x, y, data), pass_out) in passes.iter().zip(pass_pixels.iter_mut())
+        {
+            self.create_png_image_raw(data, *x, *y, pass_out, info, &mut |_| ())?;
+
+            decoded_pixels += x * y;
+            report_pass_progress(progress, decoded_pixels, info);
+        }
+        Ok(())
+    }
+
+    /// Reconstruct each Adam7 pass on its own thread
+    ///
+    /// Passes are independent of each other, so they can be de-filtered
+    /// in parallel
+    #[cfg(feature = "threads")]
+    fn create_passes_threaded(
+        &self, passes: &[(usize, usize, usize, &[u8])], pass_pixels: &mut [Vec<u8>],
+        info: &PngInfo, progress: &mut dyn FnMut(f32)
+    ) -> Result<(), PngDecodeErrors>
+    {
+        for (_, x, y, data) in passes
+        {
+            self.check_inflated_len(data, *x, *y, info)?;
+        }
+        let context = self.row_context();
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = passes
+                .iter()
+                .zip(pass_pixels.iter_mut())
+                .map(|((_, x, y, data), pass_out)| {
+                    // each thread only needs the context and its own scratch space
+                    scope.spawn(move || {
+                        RowDecoder::new(&context, *x, info, Vec::new()).decode_image(
+                            &context,
+                            data,
+                            *y,
+                            pass_out,
+                            info,
+                            &mut |_| ()
+                        )
+                    })
+                })
+                .collect();
+
+            let mut decoded_pixels = 0;
+
+            for (handle, (_, x, y, _)) in handles.into_iter().zip(passes)
+            {
+                // propagate panics of a pass to the caller, as if it was decoded on this thread
+                handle
+                    .join()
+                    .unwrap_or_else(|payload| std::panic::resume_unwind(payload))?;
+
+                decoded_pixels += x * y;
+                report_pass_progress(progress, decoded_pixels, info);
+            }
+            Ok(())
+        })
+    }
+
     /// Decode PNG encoded images and return the vector of raw pixels but for 16-bit images
     /// represent them in a `Vec<u16>`
     ///
@@ -743,83 +2674,302 @@ impl<'a> PngDecoder<'a>
     /// This returns an enum type [`DecodingResult`](zune_core::result::DecodingResult) which
     /// one can de-sugar to extract actual values.
     ///
-    /// # Example
+    /// # Example
+    ///
+    /// ```no_run
+    /// use zune_core::result::DecodingResult;
+    /// use zune_png::PngDecoder;
+    /// let mut decoder = PngDecoder::new(&[]);
+    ///
+    /// match decoder.decode().unwrap(){
+    ///     DecodingResult::U16(value)=>{
+    ///         // deal with 16 bit images
+    ///     }
+    ///     DecodingResult::U8(value)=>{
+    ///         // deal with <8 bit image
+    ///     }
+    ///     _=>{}
+    /// }
+    /// ```
+    pub fn decode(&mut self) -> Result<DecodingResult, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        // configure that the decoder converts samples to native endian
+        if is_le()
+        {
+            self.options = self.options.set_byte_endian(ByteEndian::LE);
+        }
+        else
+        {
+            self.options = self.options.set_byte_endian(ByteEndian::BE);
+        }
+
+        let deflate_data = self.inflate_idat()?;
+
+        if self.get_depth().unwrap() == BitDepth::Sixteen
+        {
+            let mut out = vec![0_u16; self.output_buffer_size().unwrap() / 2];
+            self.decode_inflated_into(&deflate_data, bytemuck::cast_slice_mut(&mut out))?;
+
+            return Ok(DecodingResult::U16(out));
+        }
+        let mut out = vec![0_u8; self.output_buffer_size().unwrap()];
+        self.decode_inflated_into(&deflate_data, &mut out)?;
+
+        Ok(DecodingResult::U8(out))
+    }
+
+    /// Decode the image to linear light `f32` samples
+    ///
+    /// Samples are normalized to `[0, 1]` and the inverse sRGB transfer function
+    /// is applied to colour samples, giving values proportional to light intensity,
+    /// which is what compositing, filtering and physically based rendering expect.
+    /// Alpha samples are normalized but otherwise unchanged, as alpha is already linear.
+    ///
+    /// The image is assumed to be sRGB encoded, which holds for the vast majority
+    /// of images, gAMA, cHRM and iCCP chunks are not taken into account.
+    /// Combining this with [`png_set_apply_gamma`](zune_core::options::DecoderOptions::png_set_apply_gamma)
+    /// linearizes samples twice.
+    ///
+    /// The layout of samples is the same as [`decode`](Self::decode), i.e
+    /// [`get_colorspace`](Self::get_colorspace) gives the order of samples in each pixel
+    pub fn decode_linear_f32(&mut self) -> Result<Vec<f32>, PngDecodeErrors>
+    {
+        let result = self.decode()?;
+
+        let colorspace = self.get_colorspace().unwrap();
+        let components = colorspace.num_components();
+        let color_components = components - usize::from(colorspace.has_alpha());
+
+        let (samples, max): (Vec<u16>, u16) = match result
+        {
+            DecodingResult::U16(samples) => (samples, u16::MAX),
+            DecodingResult::U8(samples) => (
+                samples.into_iter().map(u16::from).collect(),
+                u16::from(u8::MAX)
+            ),
+            _ => unreachable!()
+        };
+        let scale = 1.0 / f32::from(max);
+
+        // the transfer function only depends on the sample, so compute it once
+        let table: Vec<f32> = (0..=max)
+            .map(|x| srgb_to_linear(f32::from(x) * scale))
+            .collect();
+
+        let mut out = vec![0.0; samples.len()];
+
+        for (pixel, out_pixel) in samples
+            .chunks_exact(components)
+            .zip(out.chunks_exact_mut(components))
+        {
+            for (i, (sample, out_sample)) in pixel.iter().zip(out_pixel).enumerate()
+            {
+                *out_sample = if i < color_components
+                {
+                    table[usize::from(*sample)]
+                }
+                else
+                {
+                    f32::from(*sample) * scale
+                };
+            }
+        }
+        Ok(out)
+    }
+
+    /// Decode an image with 8 bit samples and return one vector per component
+    ///
+    /// Instead of interleaving samples (e.g `RGBRGB...`), all samples of the first
+    /// component are returned, then all samples of the second and so on, which suits
+    /// code that processes images a channel at a time.
+    /// [`get_colorspace`](Self::get_colorspace) gives the order of the components.
+    ///
+    /// Use [`decode_planar_u16`](Self::decode_planar_u16) for 16 bit images
+    ///
+    /// # Errors
+    /// - If the image is a 16 bit image
+    /// - If the decoder was configured to keep sub 8 bit samples packed, see
+    ///   [`png_set_expand_depth`](zune_core::options::DecoderOptions::png_set_expand_depth)
+    /// - Any error encountered during decoding
+    pub fn decode_planar(&mut self) -> Result<Vec<Vec<u8>>, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        if self.get_depth().unwrap() == BitDepth::Sixteen
+        {
+            return Err(PngDecodeErrors::GenericStatic(
+                "decode_planar expects an 8 bit image, use decode_planar_u16 for 16 bit images"
+            ));
+        }
+        if self.keep_native_depth()
+        {
+            return Err(PngDecodeErrors::GenericStatic(
+                "Cannot split packed samples into planes, enable depth expansion"
+            ));
+        }
+        let components = self.get_colorspace().unwrap().num_components();
+
+        match self.decode()?
+        {
+            DecodingResult::U8(samples) => Ok(deinterleave(&samples, components)),
+            _ => unreachable!()
+        }
+    }
+
+    /// Decode a 16 bit image and return one vector per component,
+    /// with samples in native endian
+    ///
+    /// This is the 16 bit counterpart of [`decode_planar`](Self::decode_planar)
+    ///
+    /// # Errors
+    /// - If the image is not a 16 bit image
+    /// - Any error encountered during decoding
+    pub fn decode_planar_u16(&mut self) -> Result<Vec<Vec<u16>>, PngDecodeErrors>
+    {
+        if !self.seen_headers
+        {
+            self.decode_headers()?;
+        }
+        if self.get_depth().unwrap() != BitDepth::Sixteen
+        {
+            return Err(PngDecodeErrors::GenericStatic(
+                "decode_planar_u16 expects a 16 bit image, use decode_planar instead"
+            ));
+        }
+        let components = self.get_colorspace().unwrap().num_components();
+
+        match self.decode()?
+        {
+            DecodingResult::U16(samples) => Ok(deinterleave(&samples, components)),
+            _ => unreachable!()
+        }
+    }
+
+    /// Decode a 16 bit image into `out`, with samples in native endian
+    ///
+    /// This is like [`decode_into`](Self::decode_into) but writes `u16` samples directly,
+    /// so callers don't have to convert bytes to samples themselves.
+    /// The configured [`byte_endian`](Self::byte_endian) is ignored.
     ///
-    /// ```no_run
-    /// use zune_core::result::DecodingResult;
-    /// use zune_png::PngDecoder;
-    /// let mut decoder = PngDecoder::new(&[]);
+    /// # Arguments
+    /// - `out`: Output buffer, it must be able to hold
+    ///   [`output_buffer_size`](Self::output_buffer_size)`/2` samples
     ///
-    /// match decoder.decode().unwrap(){
-    ///     DecodingResult::U16(value)=>{
-    ///         // deal with 16 bit images
-    ///     }
-    ///     DecodingResult::U8(value)=>{
-    ///         // deal with <8 bit image
-    ///     }
-    ///     _=>{}
-    /// }
-    /// ```
-    #[rustfmt::skip]
-    pub fn decode(&mut self) -> Result<DecodingResult, PngDecodeErrors>
+    /// # Errors
+    /// - If the image is not a 16 bit image
+    /// - If `out` is too small to hold the image samples
+    /// - Any error encountered during decoding
+    pub fn decode_into_u16(&mut self, out: &mut [u16]) -> Result<(), PngDecodeErrors>
     {
-        // Here we want to either return a `u8` or a `u16` depending on the
-        // headers, so we pull two tricks
-        //  1 - We either allocate u8 or u16 depending on the output
-        //      We actually allocate both, but one of the vectors ends up being
-        //      zero, and in creating an empty vec nothing is allocated on the heap
-        //  2 - We convert samples to native endian, so that transmuting is a no-op in case of
-        //      16 bit images in the next step
-        //  3 - We use bytemuck to to safe align, hence keeping the no unsafe mantra except
-        //      for platform specific intrinsics
-
-        if !self.seen_headers {
+        if !self.seen_headers
+        {
             self.decode_headers()?;
         }
-        // configure that the decoder converts samples to native endian
-        if is_le()
+        if self.get_depth().unwrap() != BitDepth::Sixteen
         {
-            self.options = self.options.set_byte_endian(ByteEndian::LE);
-        } else {
-            self.options = self.options.set_byte_endian(ByteEndian::BE);
+            return Err(PngDecodeErrors::Generic(format!(
+                "decode_into_u16 expects a 16 bit image but the image is decoded with a depth of {:?}, use decode_into instead",
+                self.get_depth().unwrap()
+            )));
         }
+        if out.len() < self.output_buffer_size().unwrap() / 2
+        {
+            return Err(PngDecodeErrors::TooSmallOutput(
+                self.output_buffer_size().unwrap() / 2,
+                out.len()
+            ));
+        }
+        let deflate_data = self.inflate_idat()?;
 
-        let info = &self.png_info;
-        let bytes = if info.depth == 16 { 2 } else { 1 };
+        // the image may have been reinterpreted with a different number of channels
+        let image_len = self.output_buffer_size().unwrap() / 2;
 
-        let out_n = self.get_colorspace().unwrap().num_components();
-        let new_len = info.width * info.height * out_n;
+        if out.len() < image_len
+        {
+            return Err(PngDecodeErrors::TooSmallOutput(image_len, out.len()));
+        }
+        let endian = self.byte_endian();
 
-        let mut out_u8: Vec<u8> = vec![0; new_len * usize::from(info.depth != 16)];
-        let mut out_u16: Vec<u16> = vec![0; new_len * usize::from(info.depth == 16)];
+        let native_endian = if is_le() { ByteEndian::LE } else { ByteEndian::BE };
+        self.options = self.options.set_byte_endian(native_endian);
 
-        // use either out_u8 or out_u16 depending on the expected type for the output
-        let out = if bytes == 1
-        {
-            &mut out_u8
-        } else {
-            let (a, b, c) = bytemuck::pod_align_to_mut::<u16, u8>(&mut out_u16);
+        let result = self.decode_inflated_into(
+            &deflate_data,
+            bytemuck::cast_slice_mut(&mut out[..image_len])
+        );
 
-            // a and c should be empty since we do not expect slop bytes on either edge
-            assert!(a.is_empty());
-            assert!(c.is_empty());
-            assert_eq!(b.len(), new_len * 2); // length should be twice that of u8
-            b
-        };
-        self.decode_into(out)?;
+        self.options = self.options.set_byte_endian(endian);
+
+        result
+    }
+    /// Decode an image scaled down by `scale`, averaging each block of
+    /// `scale x scale` pixels into one output pixel
+    ///
+    /// Non-interlaced images are scaled as rows are de-filtered, so only a
+    /// few full resolution rows are in memory at a time. Interlaced images are decoded at
+    /// full resolution first, since their rows are only complete after the last pass.
+    fn create_scaled_png_image(
+        &mut self, deflate_data: &[u8], out: &mut [u8], info: &PngInfo, scale: usize,
+        progress: &mut dyn FnMut(f32)
+    ) -> Result<(), PngDecodeErrors>
+    {
+        let sixteen_bit = info.depth == 16;
+        let bytes = if sixteen_bit { 2 } else { 1 };
+        let components = self.decoded_colorspace().unwrap().num_components();
+
+        let row_size = info.width * components * bytes;
+        let out_width = info.width.div_ceil(scale);
+        let out_stride = out_width * components * bytes;
 
-        if self.png_info.depth <= 8
+        let mut scaler = BoxScaler::new(info.width, components, sixteen_bit, scale);
+
+        if info.interlace_method == InterlaceMethod::Adam7
         {
-            return Ok(DecodingResult::U8(out_u8));
+            let mut full = vec![0_u8; row_size * info.height];
+
+            self.decode_interlaced(deflate_data, &mut full, info, progress)?;
+
+            for (i, row) in full.chunks_exact(row_size).enumerate()
+            {
+                scaler.add_row(row);
+
+                if (i + 1) % scale == 0 || i + 1 == info.height
+                {
+                    scaler.write_row(&mut out[(i / scale) * out_stride..][..out_stride]);
+                }
+            }
+            return Ok(());
         }
+        self.check_inflated_len(deflate_data, info.width, info.height, info)?;
 
-        if self.png_info.depth == 16
+        let context = self.row_context();
+        let mut rows = RowDecoder::new(&context, info.width, info, Vec::new());
+        let mut out_row = vec![0_u8; row_size];
+
+        for (i, in_stride) in deflate_data
+            .chunks_exact(rows.in_stride())
+            .take(info.height)
+            .enumerate()
         {
-            return Ok(DecodingResult::U16(out_u16));
-        }
+            rows.decode_row(&context, in_stride, &mut out_row, info)?;
 
-        Err(PngDecodeErrors::GenericStatic("Not implemented"))
+            scaler.add_row(&out_row);
+
+            if (i + 1) % scale == 0 || i + 1 == info.height
+            {
+                scaler.write_row(&mut out[(i / scale) * out_stride..][..out_stride]);
+            }
+            report_row_progress(progress, i + 1, info.height);
+        }
+        Ok(())
     }
+
     /// Create the png data from post deflated data
     ///
     /// `out` needs to have enough space to hold data, otherwise
@@ -828,402 +2978,330 @@ impl<'a> PngDecoder<'a>
     /// This is to allow reuse e.g interlaced images use one big allocation
     /// to and since that ends up calling this multiple times, allocation was moved
     /// away from this method to the caller of this method
-    #[allow(clippy::manual_memcpy, clippy::comparison_chain)]
-    fn create_png_image_raw(
-        &mut self, deflate_data: &[u8], width: usize, height: usize, out: &mut [u8], info: &PngInfo
+    pub(crate) fn create_png_image_raw(
+        &mut self, deflate_data: &[u8], width: usize, height: usize, out: &mut [u8],
+        info: &PngInfo, progress: &mut dyn FnMut(f32)
     ) -> Result<(), PngDecodeErrors>
     {
-        let use_sse4 = self.options.use_sse41();
-        let use_sse2 = self.options.use_sse2();
+        self.check_inflated_len(deflate_data, width, height, info)?;
 
-        let bytes = if info.depth == 16 { 2 } else { 1 };
+        let scratch = core::mem::take(&mut self.previous_stride);
 
-        let out_colorspace = self.get_colorspace().unwrap();
+        let context = self.row_context();
+        let mut rows = RowDecoder::new(&context, width, info, scratch);
+        let result = rows.decode_image(&context, deflate_data, height, out, info, progress);
 
-        let mut img_width_bytes;
+        self.previous_stride = rows.scratch;
 
-        img_width_bytes = usize::from(info.component) * width;
-        img_width_bytes *= usize::from(info.depth);
-        img_width_bytes += 7;
-        img_width_bytes /= 8;
+        result
+    }
 
-        let out_n = usize::from(info.color.num_components());
+    /// Return the state needed to de-filter and post process rows
+    pub(crate) fn row_context(&self) -> RowContext<'_>
+    {
+        RowContext {
+            palette:           &self.palette,
+            trns_bytes:        self.trns_bytes,
+            seen_trns:         self.seen_trns,
+            seen_ptle:         self.seen_ptle,
+            keep_indices:      self.keep_palette_indices(),
+            keep_native_depth: self.keep_native_depth(),
+            out_components:    self.decoded_colorspace().unwrap().num_components(),
+            use_sse2:          self.options.use_sse2(),
+            use_sse41:         self.options.use_sse41()
+        }
+    }
 
-        let image_len = img_width_bytes * height;
+    /// Return an error if `deflate_data` is too short to
+    /// hold `height` filtered rows of `width` pixels
+    fn check_inflated_len(
+        &self, deflate_data: &[u8], width: usize, height: usize, info: &PngInfo
+    ) -> Result<(), PngDecodeErrors>
+    {
+        let n_components = usize::from(info.color.num_components());
+        let width_stride = (width * n_components * usize::from(info.depth)).div_ceil(8);
 
-        if deflate_data.len() < image_len + height
         // account for filter bytes
+        if deflate_data.len() < (width_stride + 1) * height
         {
             let msg = format!(
-                "Not enough pixels, expected {} but found {}",
-                image_len,
-                deflate_data.len()
+                "Not enough pixels, expected {} but found {}{}",
+                width_stride * height,
+                deflate_data.len(),
+                self.channel_count_hint(deflate_data.len())
             );
             return Err(PngDecodeErrors::Generic(msg));
         }
-        // do png  un-filtering
-        let mut chunk_size;
-        let mut components = usize::from(info.color.num_components()) * bytes;
-
-        if info.depth < 8
-        {
-            // if the bit depth is 8, the spec says the byte before
-            // X to be used by the filter
-            components = 1;
-        }
-
-        // add width plus colour component, this gives us number of bytes per every scan line
-        chunk_size = width * out_n;
-        chunk_size *= usize::from(info.depth);
-        chunk_size += 7;
-        chunk_size /= 8;
-        // filter type
-        chunk_size += 1;
-
-        let out_chunk_size = width * out_colorspace.num_components() * bytes;
+        Ok(())
+    }
 
-        // each chunk is a width stride of unfiltered data
-        let chunks = deflate_data.chunks_exact(chunk_size);
+    /// Undo deflate decoding
+    ///
+    /// `width` and `height` are the dimensions of the image the
+    /// data decodes to and are used to size the output
+    #[allow(clippy::manual_memcpy)]
+    pub(crate) fn inflate(
+        &self, data: &[u8], width: usize, height: usize
+    ) -> Result<(Vec<u8>, Option<u32>), PngDecodeErrors>
+    {
+        // An annoying thing is that deflate doesn't
+        // store its uncompressed size,
+        // so we can't pre-allocate storage and pass that willy nilly
+        //
+        // Meaning we are left with some design choices
+        // 1. Have deflate resize at will
+        // 2. Have deflate return incomplete, to indicate we need to extend
+        // the vec, extend and go back to inflate.
+        //
+        //
+        // so choose point 1.
+        //
+        // This allows the zlib decoder to optimize its own paths(which it does)
+        // because it controls the allocation and doesn't have to check for near EOB
+        // runs.
+        //
+        let depth_scale = if self.png_info.depth == 16 { 2 } else { 1 };
 
-        // Begin doing loop un-filtering.
-        let width_stride = chunk_size - 1;
+        let components = usize::from(self.png_info.color.num_components());
 
-        let mut prev_row_start = 0;
-        let mut first_row = true;
-        let mut out_position = 0;
+        let size_hint = (width + 1) * height * depth_scale * components;
 
-        let will_post_process = self.seen_trns | self.seen_ptle | (info.depth < 8);
+        // allow enough space for the data to be the colour type with(out) alpha,
+        // so that we can detect images with a wrong colour type
+        let max_components = self
+            .png_info
+            .color
+            .alpha_counterpart()
+            .map_or(components, |color| usize::from(color.num_components()))
+            .max(components);
 
-        if will_post_process && self.previous_stride.len() < out_chunk_size
-        {
-            self.previous_stride.resize(out_chunk_size, 0);
-        }
-        let n_components = usize::from(info.color.num_components());
+        let limit = (width + 1) * height * depth_scale * max_components;
 
-        for (i, in_stride) in chunks.take(height).enumerate()
-        {
-            // Split output into current and previous
-            // current points to the start of the row where we are writing de-filtered output to
-            // prev is all rows we already wrote output to.
+        let option = DeflateOptions::default()
+            .set_size_hint(size_hint)
+            .set_limit(limit + 4 * height)
+            .set_confirm_checksum(self.options.inflate_get_confirm_adler());
 
-            let (prev, mut current) = out.split_at_mut(out_position);
+        let mut decoder = zune_inflate::DeflateDecoder::new_with_options(data, option);
+        decoder.set_dictionary(self.preset_dict);
 
-            current = &mut current[0..out_chunk_size];
+        let data = decoder
+            .decode_zlib()
+            .map_err(PngDecodeErrors::ZlibDecodeErrors)?;
 
-            // get the previous row.
-            //Set this to a dummy to handle special case of first row, if we aren't in the first
-            // row, we actually take the real slice a line down
-            let mut prev_row: &[u8] = &[0_u8];
+        Ok((data, decoder.adler32()))
+    }
 
-            if !first_row
+    /// Decompress the image data stored in the IDAT chunks
+    ///
+    /// Some broken encoders write grayscale + alpha data but declare
+    /// the image as grayscale (or vice versa, and similarly for RGB and RGBA), this
+    /// checks whether the data matches the colour type with(out) an alpha channel
+    /// instead of the declared one.
+    ///
+    /// If so, in strict mode an error is returned and in permissive mode
+    /// the image is reinterpreted with that colour type
+    pub(crate) fn inflate_idat(&mut self) -> Result<Vec<u8>, PngDecodeErrors>
+    {
+        // data kept by a decode that failed because its output was too small
+        if let Some(data) = self.inflated_data.take()
+        {
+            return Ok(data);
+        }
+        let (deflate_data, adler32) =
+            match self.inflate(&self.idat_chunks, self.png_info.width, self.png_info.height)
             {
-                // normal bit depth, use the previous row as normal
-                prev_row = &prev[prev_row_start..prev_row_start + out_chunk_size];
-                prev_row_start += out_chunk_size;
-            }
+                Ok(result) => result,
+                Err(PngDecodeErrors::ZlibDecodeErrors(err))
+                    if self.options.png_get_allow_truncated() =>
+                {
+                    warn!("Image data is truncated or corrupt: {:?}", err.error);
 
-            out_position += out_chunk_size;
+                    let adler32 = match err.error
+                    {
+                        DecodeErrorStatus::MismatchedAdler(_, found) => Some(found),
+                        _ => None
+                    };
+                    (err.data, adler32)
+                }
+                Err(err) => return Err(err)
+            };
 
-            // take filter
-            let filter_byte = in_stride[0];
-            // raw image bytes
-            let raw = &in_stride[1..];
+        self.last_adler32 = adler32;
 
-            // get it's type
-            let mut filter = FilterMethod::from_int(filter_byte)
-                .ok_or_else(|| PngDecodeErrors::Generic(format!("Unknown filter {filter_byte}")))?;
+        // remove idat chunks from memory
+        // we are already done with them.
+        self.idat_chunks = Vec::new();
+        self.decoded_rows = Some(self.png_info.height);
 
-            if first_row
-            {
-                // match our filters to special filters for first row
-                // these special filters do not need the previous scanline and treat it
-                // as zero
+        let color = self.png_info.color;
+        let expected_size = self.inflated_size(color.num_components());
 
-                if filter == FilterMethod::Paeth
-                {
-                    filter = FilterMethod::PaethFirst;
-                }
-                if filter == FilterMethod::Up
-                {
-                    // up for the first row becomes a memcpy
-                    filter = FilterMethod::None;
-                }
-                if filter == FilterMethod::Average
-                {
-                    filter = FilterMethod::AvgFirst;
-                }
+        if deflate_data.len() == expected_size
+        {
+            return Ok(deflate_data);
+        }
+        if deflate_data.len() < expected_size && self.options.png_get_allow_truncated()
+        {
+            return Ok(self.fill_truncated_data(deflate_data));
+        }
+        // colour types with alpha only support depths of 8 and 16
+        let counterpart = color.alpha_counterpart().filter(|counterpart| {
+            self.png_info.depth >= 8
+                && deflate_data.len() == self.inflated_size(counterpart.num_components())
+        });
 
-                first_row = false;
+        if let Some(counterpart) = counterpart
+        {
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(format!(
+                    "Image declares colour type {:?} with {} channels, but the image data fits colour type {:?} with {} channels",
+                    color,
+                    color.num_components(),
+                    counterpart,
+                    counterpart.num_components()
+                )));
             }
-
-            match filter
+            warn!(
+                "Image declares colour type {:?} but the image data fits colour type {:?}, decoding as {:?}",
+                color, counterpart, counterpart
+            );
+            if self.seen_trns
             {
-                FilterMethod::None => current[0..width_stride].copy_from_slice(raw),
-
-                FilterMethod::Average => handle_avg(prev_row, raw, current, components, use_sse4),
-
-                FilterMethod::Sub => handle_sub(raw, current, components, use_sse2),
-
-                FilterMethod::Up => handle_up(prev_row, raw, current),
+                warn!("Ignoring tRNS chunk of reinterpreted image");
+                self.seen_trns = false;
+            }
+            self.png_info.color = counterpart;
+            self.png_info.component = counterpart.num_components();
+        }
+        Ok(deflate_data)
+    }
 
-                FilterMethod::Paeth => handle_paeth(prev_row, raw, current, components, use_sse4),
+    /// Keep the complete rows of truncated image data and fill the rest
+    /// of the image with zeroes
+    ///
+    /// Zeroed rows have no filter and all their samples zero, the number of
+    /// leading complete rows of the image is stored in `decoded_rows`
+    #[allow(clippy::manual_is_multiple_of)]
+    fn fill_truncated_data(&mut self, mut data: Vec<u8>) -> Vec<u8>
+    {
+        let info = &self.png_info;
+        let components = info.color.num_components();
 
-                FilterMethod::PaethFirst => handle_paeth_first(raw, current, components),
+        let passes: Vec<(usize, usize)> = if info.interlace_method == InterlaceMethod::Adam7
+        {
+            (0..7)
+                .map(|p| adam7_pass_dimensions(info.width, info.height, p))
+                .collect()
+        }
+        else
+        {
+            vec![(info.width, info.height)]
+        };
+        // number of complete rows in each pass
+        let mut complete_rows = vec![0; passes.len()];
+        let mut complete_size = 0;
 
-                FilterMethod::AvgFirst => handle_avg_first(raw, current, components),
+        for (&(width, height), complete) in passes.iter().zip(complete_rows.iter_mut())
+        {
+            let row_size = self.inflated_rows_size(components, width, 1);
 
-                FilterMethod::Unknown => unreachable!()
+            if row_size == 0
+            {
+                *complete = height;
+                continue;
             }
+            *complete = ((data.len() - complete_size) / row_size).min(height);
+            complete_size += *complete * row_size;
 
-            if will_post_process && i > 0
+            if *complete < height
             {
-                // run the post processor two scanlines behind so that we
-                // don't mess with any filters that require previous row
-
-                // read the row we are about to filter
-                let to_filter_row = &mut prev[(i - 1) * out_chunk_size..(i) * out_chunk_size];
-
-                if info.depth < 8
-                {
-                    // check if we will run any other transform
-                    let extra_transform = self.seen_ptle | self.seen_trns;
-
-                    if extra_transform
-                    {
-                        // input data is  in_to_filter_row,
-                        // we write output to previous_stride
-                        // since other parts use previous_stride
-                        expand_bits_to_byte(
-                            width,
-                            usize::from(info.depth),
-                            0,
-                            n_components,
-                            self.seen_ptle,
-                            to_filter_row,
-                            &mut self.previous_stride
-                        )
-                    }
-                    else
-                    {
-                        // no extra transform, just depth upscaling, so let's
-                        // do that,
-
-                        // copy the row to a temporary space
-                        self.previous_stride[..width_stride]
-                            .copy_from_slice(&to_filter_row[..width_stride]);
-
-                        expand_bits_to_byte(
-                            width,
-                            usize::from(info.depth),
-                            0,
-                            n_components,
-                            self.seen_ptle,
-                            &self.previous_stride,
-                            to_filter_row
-                        )
-                    }
-                }
-                else
-                {
-                    // copy the row to a temporary space
-                    self.previous_stride[..width_stride]
-                        .copy_from_slice(&to_filter_row[..width_stride]);
-                }
-
-                if self.seen_trns && self.png_info.color != PngColor::Palette
-                {
-                    // the expansion is a trns expansion
-                    // bytes are already in position, so finish the business
-
-                    if info.depth <= 8
-                    {
-                        expand_trns::<false>(
-                            &self.previous_stride,
-                            to_filter_row,
-                            info.color,
-                            self.trns_bytes,
-                            info.depth
-                        );
-                    }
-                    else if info.depth == 16
-                    {
-                        // Tested by test_palette_trns_16bit.
-                        expand_trns::<true>(
-                            &self.previous_stride,
-                            to_filter_row,
-                            info.color,
-                            self.trns_bytes,
-                            info.depth
-                        );
-                    }
-                }
-
-                if self.seen_ptle && self.png_info.color == PngColor::Palette
-                {
-                    if self.palette.is_empty()
-                    {
-                        return Err(PngDecodeErrors::EmptyPalette);
-                    }
-                    let plte_entry: &[PLTEEntry; 256] = self.palette[..256].try_into().unwrap();
-
-                    // so now we have two things
-                    // the palette entries stored in self.previous_stride
-                    // the row to fill the palette sored in to_filter row,
-                    // so we can finally expand the entries
-                    if self.seen_trns
-                    {
-                        // if tRNS chunk is present in paletted images, it contains
-                        // alpha byte values, so that means we create alpha data from
-                        // raw bytes
-                        expand_palette(&self.previous_stride, to_filter_row, plte_entry, 4);
-                    }
-                    else
-                    {
-                        // Normal expansion
-                        expand_palette(&self.previous_stride, to_filter_row, plte_entry, 3);
-                    }
-                }
+                break;
             }
         }
+        data.truncate(complete_size);
+        data.resize(self.inflated_size(components), 0);
 
-        if will_post_process
-        {
-            for i in height..height + min(height, 1)
-            {
-                let to_filter_row = &mut out[(i - 1) * out_chunk_size..i * out_chunk_size];
+        let info = &self.png_info;
 
-                // check if we will run any other transform
-                let extra_transform = self.seen_ptle | self.seen_trns;
+        let decoded_rows = if info.interlace_method == InterlaceMethod::Adam7
+        {
+            // a row is complete once every pass with pixels in it
+            // has decoded its part of the row
+            (0..info.height)
+                .find(|&y| {
+                    passes.iter().zip(&complete_rows).enumerate().any(
+                        |(p, (&(width, _), &complete))| {
+                            width > 0
+                                && y >= YORIG[p]
+                                && (y - YORIG[p]) % YSPC[p] == 0
+                                && (y - YORIG[p]) / YSPC[p] >= complete
+                        }
+                    )
+                })
+                .unwrap_or(info.height)
+        }
+        else
+        {
+            complete_rows[0]
+        };
+        warn!(
+            "Decoded {} of {} rows, filling the remaining rows with zeroes",
+            decoded_rows, info.height
+        );
+        self.decoded_rows = Some(decoded_rows);
 
-                if info.depth < 8
-                {
-                    if extra_transform
-                    {
-                        // input data is  in_to_filter_row,
-                        // we write output to previous_stride
-                        // since other parts use previous_stride
-                        expand_bits_to_byte(
-                            width,
-                            usize::from(info.depth),
-                            0,
-                            n_components,
-                            self.seen_ptle,
-                            to_filter_row,
-                            &mut self.previous_stride
-                        )
-                    }
-                    else
-                    {
-                        // no extra transform, just depth upscaling, so let's
-                        // do that,
-
-                        // copy the row to a temporary space
-                        self.previous_stride[..width_stride]
-                            .copy_from_slice(&to_filter_row[..width_stride]);
-
-                        expand_bits_to_byte(
-                            width,
-                            usize::from(info.depth),
-                            0,
-                            n_components,
-                            self.seen_ptle,
-                            &self.previous_stride,
-                            to_filter_row
-                        )
-                    }
-                }
-                else
-                {
-                    // copy the row to a temporary space
-                    self.previous_stride[..width_stride]
-                        .copy_from_slice(&to_filter_row[..width_stride]);
-                }
-                if self.seen_trns && self.png_info.color != PngColor::Palette
-                {
-                    // the expansion is a trns expansion
-                    // bytes are already in position, so finish the business
+        data
+    }
 
-                    if info.depth <= 8
-                    {
-                        expand_trns::<false>(
-                            &self.previous_stride,
-                            to_filter_row,
-                            info.color,
-                            self.trns_bytes,
-                            info.depth
-                        );
-                    }
-                    else if info.depth == 16
-                    {
-                        // Tested by test_palette_trns_16bit.
-                        expand_trns::<true>(
-                            &self.previous_stride,
-                            to_filter_row,
-                            info.color,
-                            self.trns_bytes,
-                            info.depth
-                        );
-                    }
-                }
-                if self.seen_ptle && self.png_info.color == PngColor::Palette
-                {
-                    if self.palette.is_empty()
-                    {
-                        return Err(PngDecodeErrors::EmptyPalette);
-                    }
+    /// Return the size of the decompressed image data if each
+    /// pixel had `components` channels
+    pub(crate) fn inflated_size(&self, components: u8) -> usize
+    {
+        let info = &self.png_info;
 
-                    let plte_entry: &[PLTEEntry; 256] = self.palette[..256].try_into().unwrap();
+        if info.interlace_method == InterlaceMethod::Adam7
+        {
+            (0..7)
+                .map(|p| {
+                    let (x, y) = adam7_pass_dimensions(info.width, info.height, p);
 
-                    if self.seen_trns
-                    {
-                        expand_palette(&self.previous_stride, to_filter_row, plte_entry, 4);
-                    }
-                    else
-                    {
-                        expand_palette(&self.previous_stride, to_filter_row, plte_entry, 3);
-                    }
-                }
-            }
+                    self.inflated_rows_size(components, x, y)
+                })
+                .sum()
+        }
+        else
+        {
+            self.inflated_rows_size(components, info.width, info.height)
         }
-        Ok(())
     }
 
-    /// Undo deflate decoding
-    #[allow(clippy::manual_memcpy)]
-    fn inflate(&mut self) -> Result<Vec<u8>, PngDecodeErrors>
+    /// Return the size of the decompressed data of `height` rows of `width` pixels,
+    /// each with `components` channels, including filter bytes
+    fn inflated_rows_size(&self, components: u8, width: usize, height: usize) -> usize
     {
-        // An annoying thing is that deflate doesn't
-        // store its uncompressed size,
-        // so we can't pre-allocate storage and pass that willy nilly
-        //
-        // Meaning we are left with some design choices
-        // 1. Have deflate resize at will
-        // 2. Have deflate return incomplete, to indicate we need to extend
-        // the vec, extend and go back to inflate.
-        //
-        //
-        // so choose point 1.
-        //
-        // This allows the zlib decoder to optimize its own paths(which it does)
-        // because it controls the allocation and doesn't have to check for near EOB
-        // runs.
-        //
-        let depth_scale = if self.png_info.depth == 16 { 2 } else { 1 };
-
-        let size_hint = (self.png_info.width + 1)
-            * self.png_info.height
-            * depth_scale
-            * usize::from(self.png_info.color.num_components());
-
-        let option = DeflateOptions::default()
-            .set_size_hint(size_hint)
-            .set_limit(size_hint + 4 * (self.png_info.height))
-            .set_confirm_checksum(self.options.inflate_get_confirm_adler());
+        if width == 0 || height == 0
+        {
+            // empty passes have no filter bytes
+            return 0;
+        }
+        // filter byte plus the packed row
+        let row_size =
+            (width * usize::from(components) * usize::from(self.png_info.depth)).div_ceil(8) + 1;
 
-        let mut decoder = zune_inflate::DeflateDecoder::new_with_options(&self.idat_chunks, option);
+        row_size * height
+    }
 
-        decoder
-            .decode_zlib()
-            .map_err(PngDecodeErrors::ZlibDecodeErrors)
+    /// Return a hint about the number of channels that would fit
+    /// `data_len` bytes of decompressed image data, or an empty string if none fits
+    fn channel_count_hint(&self, data_len: usize) -> String
+    {
+        (1..=4)
+            .find(|components| self.inflated_size(*components) == data_len)
+            .map(|components| {
+                format!(", the data would fit an image with {components} channels per pixel")
+            })
+            .unwrap_or_default()
     }
 }
@@ -0,0 +1,369 @@
+//! A png encoder
+#![cfg(feature = "crc")]
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use zune_core::bit_depth::BitDepth;
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::EncoderOptions;
+use zune_inflate::{DeflateEncodeOptions, DeflateEncoder};
+
+use crate::decoder::PhysicalDimensions;
+use crate::enums::PhysicalUnit;
+use crate::error::PngEncodeErrors;
+use crate::writer::{write_chunk, write_idat, write_ihdr, write_signature};
+
+/// Starting column, starting row, column increment and row increment
+/// of each Adam7 pass
+const ADAM7_PASSES: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2)
+];
+
+/// A png encoder
+///
+/// The image dimensions, colorspace and depth are read from the
+/// [`EncoderOptions`], supported colorspaces are `Luma`, `LumaA`, `RGB` and `RGBA`,
+/// with 8 or 16 bit depths.
+///
+/// The encoder also respects the following options
+/// - [`get_effort`](EncoderOptions::get_effort): The compression level, values above 9 are treated as 9
+/// - [`png_encode_interlaced`](EncoderOptions::png_encode_interlaced): Whether to use Adam7 interlacing
+///
+/// Each scanline is filtered using the filter that gives the smallest
+/// sum of absolute differences, which is a good heuristic for smaller output.
+///
+/// # Example
+/// ```
+/// use zune_core::bit_depth::BitDepth;
+/// use zune_core::colorspace::ColorSpace;
+/// use zune_core::options::EncoderOptions;
+/// use zune_png::PngEncoder;
+///
+/// let pixels = [255_u8; 16 * 16 * 3];
+/// let options = EncoderOptions::default()
+///     .set_width(16)
+///     .set_height(16)
+///     .set_colorspace(ColorSpace::RGB)
+///     .set_depth(BitDepth::Eight);
+///
+/// let png = PngEncoder::new(&pixels, options).encode().unwrap();
+/// ```
+pub struct PngEncoder<'a>
+{
+    data:         &'a [u8],
+    options:      EncoderOptions,
+    text_chunks:  Vec<(String, String)>,
+    pixel_dims:   Option<PhysicalDimensions>,
+    icc_profile:  Option<&'a [u8]>,
+    extra_chunks: Vec<([u8; 4], &'a [u8])>
+}
+
+impl<'a> PngEncoder<'a>
+{
+    /// Create a new encoder which will encode the specified
+    /// data whose format is contained in the options.
+    ///
+    /// # Note
+    /// To encode 16 bit data, it still must be provided as u8 bytes
+    /// in native endian.
+    ///
+    /// One can use [`u16::to_ne_bytes`] for this if data is in a u16 slice
+    pub fn new(data: &'a [u8], options: EncoderOptions) -> PngEncoder<'a>
+    {
+        PngEncoder {
+            data,
+            options,
+            text_chunks: Vec::new(),
+            pixel_dims: None,
+            icc_profile: None,
+            extra_chunks: Vec::new()
+        }
+    }
+
+    /// Add a `tEXt` chunk with the given keyword and text
+    ///
+    /// The keyword must be between 1 and 79 characters and both keyword and text
+    /// must only contain Latin-1 characters, otherwise encoding will fail
+    pub fn add_text_chunk(&mut self, keyword: &str, text: &str)
+    {
+        self.text_chunks.push((keyword.into(), text.into()));
+    }
+
+    /// Write a `pHYs` chunk containing the physical pixel dimensions
+    pub fn set_physical_dimensions(&mut self, dimensions: PhysicalDimensions)
+    {
+        self.pixel_dims = Some(dimensions);
+    }
+
+    /// Write an `iCCP` chunk containing an already zlib compressed ICC profile
+    ///
+    /// The bytes are written verbatim without recompression, so a profile from
+    /// [`PngInfo::icc_profile_compressed`](crate::PngInfo::icc_profile_compressed)
+    /// can be re-embedded unchanged
+    pub fn set_icc_profile_compressed(&mut self, profile: &'a [u8])
+    {
+        self.icc_profile = Some(profile);
+    }
+
+    /// Add a chunk that will be written as is before the image data
+    pub(crate) fn add_raw_chunk(&mut self, name: [u8; 4], data: &'a [u8])
+    {
+        self.extra_chunks.push((name, data));
+    }
+
+    /// Encode the image returning the png file
+    pub fn encode(&self) -> Result<Vec<u8>, PngEncodeErrors>
+    {
+        let width = self.options.get_width();
+        let height = self.options.get_height();
+        let colorspace = self.options.get_colorspace();
+        let depth = self.options.get_depth();
+
+        let color = match colorspace
+        {
+            ColorSpace::Luma => 0,
+            ColorSpace::RGB => 2,
+            ColorSpace::LumaA => 4,
+            ColorSpace::RGBA => 6,
+            _ => return Err(PngEncodeErrors::UnsupportedColorspace(colorspace))
+        };
+        let bit_depth = match depth
+        {
+            BitDepth::Eight => 8,
+            BitDepth::Sixteen => 16,
+            _ => return Err(PngEncodeErrors::UnsupportedDepth(depth))
+        };
+        if width == 0 || width > (1 << 31) - 1 || height > (1 << 31) - 1
+        {
+            return Err(PngEncodeErrors::GenericStatic(
+                "Image dimensions must be between 1 and 2^31-1"
+            ));
+        }
+        let pixel_size = colorspace.num_components() * depth.size_of();
+        let expected = width * height * pixel_size;
+
+        if self.data.len() != expected
+        {
+            return Err(PngEncodeErrors::TooShortInput(expected, self.data.len()));
+        }
+        let text_chunks = self.encode_text_chunks()?;
+
+        // png stores samples in big endian
+        let pixels = if depth == BitDepth::Sixteen
+        {
+            self.data
+                .chunks_exact(2)
+                .flat_map(|x| u16::from_ne_bytes([x[0], x[1]]).to_be_bytes())
+                .collect()
+        }
+        else
+        {
+            self.data.to_vec()
+        };
+        let interlaced = self.options.png_encode_interlaced();
+
+        let mut scanlines = Vec::with_capacity(expected + height * 2);
+
+        if interlaced
+        {
+            for (x_start, y_start, x_step, y_step) in ADAM7_PASSES
+            {
+                let pass_width = (width + x_step - 1 - x_start) / x_step;
+                let pass_height = (height + y_step - 1 - y_start) / y_step;
+
+                if pass_width == 0 || pass_height == 0
+                {
+                    continue;
+                }
+                let mut pass = Vec::with_capacity(pass_width * pass_height * pixel_size);
+
+                for y in (y_start..height).step_by(y_step)
+                {
+                    for x in (x_start..width).step_by(x_step)
+                    {
+                        let start = (y * width + x) * pixel_size;
+                        pass.extend_from_slice(&pixels[start..start + pixel_size]);
+                    }
+                }
+                filter_scanlines(&pass, pass_width * pixel_size, pixel_size, &mut scanlines);
+            }
+        }
+        else
+        {
+            filter_scanlines(&pixels, width * pixel_size, pixel_size, &mut scanlines);
+        }
+
+        let deflate_options = DeflateEncodeOptions::default().set_level(self.options.get_effort());
+        let compressed =
+            DeflateEncoder::new_with_options(&scanlines, deflate_options).encode_zlib();
+
+        let mut out = Vec::with_capacity(compressed.len() + 1024);
+
+        write_signature(&mut out);
+        write_ihdr(&mut out, width, height, bit_depth, color, interlaced);
+
+        if let Some(profile) = self.icc_profile
+        {
+            // profile name, null separator and compression method (zlib)
+            let mut iccp = b"ICC profile\0\0".to_vec();
+            iccp.extend_from_slice(profile);
+
+            write_chunk(&mut out, b"iCCP", &iccp);
+        }
+
+        if let Some(dims) = self.pixel_dims
+        {
+            let mut phys = [0; 9];
+
+            phys[0..4].copy_from_slice(&dims.x_pixels_per_unit.to_be_bytes());
+            phys[4..8].copy_from_slice(&dims.y_pixels_per_unit.to_be_bytes());
+            phys[8] = u8::from(dims.unit == PhysicalUnit::Meter);
+
+            write_chunk(&mut out, b"pHYs", &phys);
+        }
+        for (name, data) in &self.extra_chunks
+        {
+            write_chunk(&mut out, name, data);
+        }
+        for text in &text_chunks
+        {
+            write_chunk(&mut out, b"tEXt", text);
+        }
+        write_idat(&mut out, &compressed);
+        write_chunk(&mut out, b"IEND", &[]);
+
+        Ok(out)
+    }
+
+    /// Convert text chunks to their Latin-1 representation
+    fn encode_text_chunks(&self) -> Result<Vec<Vec<u8>>, PngEncodeErrors>
+    {
+        let to_latin1 = |string: &str| -> Result<Vec<u8>, PngEncodeErrors> {
+            string
+                .chars()
+                .map(|c| {
+                    u8::try_from(c).map_err(|_| {
+                        PngEncodeErrors::Generic(format!(
+                            "Character {c:?} in text chunk cannot be represented in Latin-1"
+                        ))
+                    })
+                })
+                .collect()
+        };
+
+        let mut chunks = Vec::with_capacity(self.text_chunks.len());
+
+        for (keyword, text) in &self.text_chunks
+        {
+            let mut chunk = to_latin1(keyword)?;
+
+            if chunk.is_empty() || chunk.len() > 79 || chunk.contains(&0)
+            {
+                return Err(PngEncodeErrors::Generic(format!(
+                    "Invalid text chunk keyword {keyword:?}, keywords must be 1-79 characters without nulls"
+                )));
+            }
+            chunk.push(0);
+            chunk.extend(to_latin1(text)?);
+
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+}
+
+/// Filter each row of an image, appending the filter type and filtered row to `out`
+///
+/// The filter for each row is chosen by the minimum sum of absolute
+/// differences heuristic
+fn filter_scanlines(pixels: &[u8], stride: usize, pixel_size: usize, out: &mut Vec<u8>)
+{
+    let zero_row = vec![0; stride];
+    let mut candidate = vec![0; stride];
+    let mut best = vec![0; stride];
+
+    let mut prev_row = zero_row.as_slice();
+
+    for row in pixels.chunks_exact(stride)
+    {
+        let mut best_filter = 0;
+        let mut best_sum = u64::MAX;
+
+        for filter in 0..5
+        {
+            filter_row(filter, row, prev_row, pixel_size, &mut candidate);
+
+            // treat filtered bytes as signed, values close to zero compress better
+            let sum: u64 = candidate
+                .iter()
+                .map(|x| u64::from((*x as i8).unsigned_abs()))
+                .sum();
+
+            if sum < best_sum
+            {
+                best_sum = sum;
+                best_filter = filter;
+                core::mem::swap(&mut best, &mut candidate);
+            }
+        }
+        out.push(best_filter);
+        out.extend_from_slice(&best);
+
+        prev_row = row;
+    }
+}
+
+/// Apply a single png filter to a row
+fn filter_row(filter: u8, row: &[u8], prev_row: &[u8], pixel_size: usize, out: &mut [u8])
+{
+    for (i, (out_px, (current, up))) in out.iter_mut().zip(row.iter().zip(prev_row)).enumerate()
+    {
+        let (left, up_left) = if i >= pixel_size
+        {
+            (row[i - pixel_size], prev_row[i - pixel_size])
+        }
+        else
+        {
+            (0, 0)
+        };
+        let predicted = match filter
+        {
+            0 => 0,
+            1 => left,
+            2 => *up,
+            3 => ((u16::from(left) + u16::from(*up)) / 2) as u8,
+            _ => paeth(left, *up, up_left)
+        };
+        *out_px = current.wrapping_sub(predicted);
+    }
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8
+{
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+
+    let pa = (p - i16::from(a)).abs();
+    let pb = (p - i16::from(b)).abs();
+    let pc = (p - i16::from(c)).abs();
+
+    if pa <= pb && pa <= pc
+    {
+        a
+    }
+    else if pb <= pc
+    {
+        b
+    }
+    else
+    {
+        c
+    }
+}
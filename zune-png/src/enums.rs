@@ -2,7 +2,7 @@
 #![allow(clippy::upper_case_acronyms, non_camel_case_types)]
 
 /// Chunk type according to table 5.3 of
-/// the jpeg spec, see https://www.w3.org/TR/2003/REC-PNG-20031110/
+/// the png spec, see https://www.w3.org/TR/2003/REC-PNG-20031110/
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PngChunkType
 {
@@ -14,7 +14,7 @@ pub enum PngChunkType
     cHRM,
     gAMA,
     iCCP,
-    sBit,
+    sBIT,
     sRGB,
     bKGD,
     hIST,
@@ -27,6 +27,7 @@ pub enum PngChunkType
     zTXt,
     fcTL,
     acTL,
+    fdAT,
     unkn
 }
 
@@ -38,7 +39,7 @@ impl PngChunkType
     {
         matches!(
             self,
-            Self::cHRM | Self::gAMA | Self::iCCP | Self::sBit | Self::sRGB
+            Self::cHRM | Self::gAMA | Self::iCCP | Self::sBIT | Self::sRGB
         )
     }
     /// Return true if a chunk should appear
@@ -48,6 +49,50 @@ impl PngChunkType
         matches!(self, Self::bKGD | Self::hIST | Self::tRNS)
     }
 
+    /// Return true if the png specification allows at
+    /// most one chunk of this type
+    pub const fn is_once_only(self) -> bool
+    {
+        matches!(
+            self,
+            Self::PLTE
+                | Self::cHRM
+                | Self::gAMA
+                | Self::iCCP
+                | Self::sBIT
+                | Self::sRGB
+                | Self::bKGD
+                | Self::hIST
+                | Self::tRNS
+                | Self::pHYs
+                | Self::tIME
+                | Self::eXIf
+                | Self::acTL
+        )
+    }
+
+    /// Return true if this chunk only carries metadata,
+    /// i.e pixels can be decoded without it
+    pub const fn is_metadata(self) -> bool
+    {
+        matches!(
+            self,
+            Self::tEXt
+                | Self::zTXt
+                | Self::iTXt
+                | Self::iCCP
+                | Self::eXIf
+                | Self::tIME
+                | Self::pHYs
+                | Self::bKGD
+                | Self::cHRM
+                | Self::sRGB
+                | Self::sBIT
+                | Self::sPLT
+                | Self::hIST
+        )
+    }
+
     /// Return true if a chunk should appear
     /// before the IDAT chunk
     pub const fn should_appear_before_idat(self) -> bool
@@ -58,7 +103,7 @@ impl PngChunkType
                 | Self::cHRM
                 | Self::gAMA
                 | Self::iCCP
-                | Self::sBit
+                | Self::sBIT
                 | Self::sRGB
                 | Self::bKGD
                 | Self::hIST
@@ -142,14 +187,83 @@ impl InterlaceMethod
     }
 }
 
+/// Rendering intent for images in the sRGB colour space
+///
+/// Extracted from the sRGB chunk, this tells a colour management
+/// system how to map colours that are outside the gamut of the output device
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RenderingIntent
+{
+    /// For images preferring good adaptation to the output device
+    /// gamut at the expense of colorimetric accuracy, such as photographs.
+    Perceptual,
+    /// For images requiring colour appearance matching
+    /// relative to the output device white point, such as logos.
+    RelativeColorimetric,
+    /// For images preferring preservation of saturation at the expense
+    /// of hue and lightness, such as charts and graphs.
+    Saturation,
+    /// For images requiring preservation of absolute colorimetry,
+    /// such as previews of images destined for a different output device.
+    AbsoluteColorimetric
+}
+
+impl RenderingIntent
+{
+    pub fn from_int(int: u8) -> Option<RenderingIntent>
+    {
+        match int
+        {
+            0 => Some(Self::Perceptual),
+            1 => Some(Self::RelativeColorimetric),
+            2 => Some(Self::Saturation),
+            3 => Some(Self::AbsoluteColorimetric),
+            _ => None
+        }
+    }
+}
+
+/// Unit for the physical pixel dimensions of an image
+///
+/// Extracted from the pHYs chunk
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PhysicalUnit
+{
+    /// Unit is not specified, the pixel dimensions
+    /// only define the pixel aspect ratio
+    Unknown,
+    /// Pixel dimensions are in pixels per meter
+    Meter
+}
+
+impl PhysicalUnit
+{
+    pub fn from_int(int: u8) -> Option<PhysicalUnit>
+    {
+        match int
+        {
+            0 => Some(Self::Unknown),
+            1 => Some(Self::Meter),
+            _ => None
+        }
+    }
+}
+
+/// Colour type of a png image as declared in its IHDR chunk
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PngColor
 {
+    /// Grayscale
     Luma,
+    /// Indices into a palette
     Palette,
+    /// Grayscale with alpha
     LumaA,
+    /// Red, green and blue
     RGB,
+    /// Red, green and blue with alpha
     RGBA,
+    /// Colour type hasn't been read yet
     Unknown
 }
 impl Default for PngColor
@@ -173,6 +287,31 @@ impl PngColor
             PngColor::Unknown => unreachable!()
         }
     }
+    /// Return true if the png specification allows images of this
+    /// colour type to have the given bit depth
+    pub(crate) const fn is_valid_depth(self, depth: u8) -> bool
+    {
+        match self
+        {
+            PngColor::Luma => matches!(depth, 1 | 2 | 4 | 8 | 16),
+            PngColor::Palette => matches!(depth, 1 | 2 | 4 | 8),
+            PngColor::LumaA | PngColor::RGB | PngColor::RGBA => matches!(depth, 8 | 16),
+            PngColor::Unknown => false
+        }
+    }
+    /// Return the colour type that differs from this one only by
+    /// the presence of an alpha channel
+    pub(crate) fn alpha_counterpart(self) -> Option<PngColor>
+    {
+        match self
+        {
+            PngColor::Luma => Some(PngColor::LumaA),
+            PngColor::LumaA => Some(PngColor::Luma),
+            PngColor::RGB => Some(PngColor::RGBA),
+            PngColor::RGBA => Some(PngColor::RGB),
+            PngColor::Palette | PngColor::Unknown => None
+        }
+    }
     pub(crate) fn from_int(int: u8) -> Option<PngColor>
     {
         match int
@@ -186,3 +325,65 @@ impl PngColor
         }
     }
 }
+
+/// Dispose operation for an APNG frame
+///
+/// Specifies how the output buffer should be changed at the end of the delay
+/// (before rendering the next frame)
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DisposeOp
+{
+    /// No disposal is done on this frame before rendering the next,
+    /// the contents of the output buffer are left as is.
+    #[default]
+    None,
+    /// The frame's region of the output buffer is to be cleared to fully
+    /// transparent black before rendering the next frame.
+    Background,
+    /// The frame's region of the output buffer is to be reverted to the
+    /// previous contents before rendering the next frame.
+    Previous
+}
+
+impl DisposeOp
+{
+    pub fn from_int(int: u8) -> Option<DisposeOp>
+    {
+        match int
+        {
+            0 => Some(Self::None),
+            1 => Some(Self::Background),
+            2 => Some(Self::Previous),
+            _ => None
+        }
+    }
+}
+
+/// Blend operation for an APNG frame
+///
+/// Specifies whether the frame is to be alpha blended into the current output
+/// buffer content, or whether it should completely replace its region in the output buffer.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BlendOp
+{
+    /// All color components of the frame, including alpha, overwrite
+    /// the current contents of the frame's output buffer region
+    #[default]
+    Source,
+    /// The frame is composited onto the output buffer
+    /// based on its alpha
+    Over
+}
+
+impl BlendOp
+{
+    pub fn from_int(int: u8) -> Option<BlendOp>
+    {
+        match int
+        {
+            0 => Some(Self::Source),
+            1 => Some(Self::Over),
+            _ => None
+        }
+    }
+}
@@ -2,6 +2,11 @@
 use alloc::string::String;
 use core::fmt::{Debug, Formatter};
 
+use zune_core::bit_depth::BitDepth;
+use zune_core::colorspace::ColorSpace;
+
+use crate::enums::PngColor;
+
 /// Errors possible during decoding
 pub enum PngDecodeErrors
 {
@@ -13,14 +18,28 @@ pub enum PngDecodeErrors
     Generic(String),
     /// Calculated CRC does not match expected crc
     BadCrc(u32, u32),
+    /// A chunk the decoder doesn't know was marked as critical,
+    /// so the image cannot be decoded correctly
+    UnknownCriticalChunk([u8; 4]),
+    /// A chunk that may appear only once appeared more than once
+    DuplicateChunk([u8; 4]),
     /// error decoding zlib stream
     ZlibDecodeErrors(zune_inflate::errors::InflateDecodeErrors),
+    /// The bit depth is not allowed for the colour type,
+    /// e.g 16 bit palette images or 4 bit RGB images
+    InvalidColorDepthCombo(PngColor, u8),
     /// Palette is empty yet was expected
     EmptyPalette,
     /// Unsupported Animated PNG
     UnsupportedAPNGImage,
     /// Too small output slice
-    TooSmallOutput(usize, usize)
+    TooSmallOutput(usize, usize),
+    /// Memory needed to decode the image could not be allocated
+    AllocationFailed
+    {
+        /// Number of bytes that could not be allocated
+        bytes: usize
+    }
 }
 
 impl Debug for PngDecodeErrors
@@ -36,10 +55,29 @@ impl Debug for PngDecodeErrors
                 f,
                 "CRC does not match, expected {expected} but found {found}",
             ),
+            Self::UnknownCriticalChunk(name) =>
+            {
+                let name = core::str::from_utf8(name).unwrap_or("XXXX");
+
+                writeln!(f, "Unknown critical chunk {name:?}")
+            }
+            Self::DuplicateChunk(name) =>
+            {
+                let name = core::str::from_utf8(name).unwrap_or("XXXX");
+
+                writeln!(f, "Chunk {name:?} appears more than once")
+            }
             Self::ZlibDecodeErrors(err) =>
             {
                 writeln!(f, "Error decoding idat chunks {err:?}")
             }
+            Self::InvalidColorDepthCombo(color, depth) =>
+            {
+                writeln!(
+                    f,
+                    "Bit depth {depth} is not allowed for colour type {color:?}"
+                )
+            }
             Self::EmptyPalette =>
             {
                 writeln!(f, "Empty palette but image is indexed")
@@ -52,6 +90,13 @@ impl Debug for PngDecodeErrors
             {
                 write!(f, "Too small output, expected buffer with at least {expected} bytes but got one with {found} bytes")
             }
+            Self::AllocationFailed { bytes } =>
+            {
+                writeln!(
+                    f,
+                    "Could not allocate {bytes} bytes needed to decode the image"
+                )
+            }
         }
     }
 }
@@ -79,3 +124,59 @@ impl From<zune_inflate::errors::InflateDecodeErrors> for PngDecodeErrors
         Self::ZlibDecodeErrors(val)
     }
 }
+
+/// Errors possible during encoding
+pub enum PngEncodeErrors
+{
+    /// Generic message
+    GenericStatic(&'static str),
+    /// Generic message
+    Generic(String),
+    /// The input length does not match the length expected
+    /// from the image dimensions, colorspace and depth
+    TooShortInput(usize, usize),
+    /// The colorspace cannot be stored in a png
+    UnsupportedColorspace(ColorSpace),
+    /// The bit depth cannot be stored in a png
+    UnsupportedDepth(BitDepth)
+}
+
+impl Debug for PngEncodeErrors
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result
+    {
+        match self
+        {
+            Self::GenericStatic(val) => writeln!(f, "{val:?}"),
+            Self::Generic(val) => writeln!(f, "{val:?}"),
+            Self::TooShortInput(expected, found) =>
+            {
+                writeln!(f, "Expected input of length {expected} but found {found}")
+            }
+            Self::UnsupportedColorspace(colorspace) =>
+            {
+                writeln!(f, "Unsupported colorspace {colorspace:?} for png")
+            }
+            Self::UnsupportedDepth(depth) =>
+            {
+                writeln!(f, "Unsupported bit depth {depth:?} for png")
+            }
+        }
+    }
+}
+
+impl From<&'static str> for PngEncodeErrors
+{
+    fn from(val: &'static str) -> Self
+    {
+        Self::GenericStatic(val)
+    }
+}
+
+impl From<String> for PngEncodeErrors
+{
+    fn from(val: String) -> Self
+    {
+        Self::Generic(val)
+    }
+}
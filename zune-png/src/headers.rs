@@ -1,10 +1,17 @@
-use alloc::format;
+use alloc::string::String;
+use alloc::{format, vec};
 
-use log::{error, info, warn};
+use log::{info, warn};
 use zune_inflate::DeflateDecoder;
 
-use crate::decoder::{ItxtChunk, PLTEEntry, PngChunk, TextChunk, TimeInfo, ZtxtChunk};
-use crate::enums::{FilterMethod, InterlaceMethod, PngColor};
+use crate::apng::{ActlChunk, ApngFrameData, FrameInfo};
+use crate::decoder::{
+    BackgroundColor, Chromaticities, ItxtChunk, PaletteEntry, PhysicalDimensions, PngChunk,
+    SuggestedPalette, SuggestedPaletteEntry, TextChunk, TimeInfo, ZtxtChunk
+};
+use crate::enums::{
+    BlendOp, DisposeOp, FilterMethod, InterlaceMethod, PhysicalUnit, PngColor, RenderingIntent
+};
 use crate::error::PngDecodeErrors;
 use crate::PngDecoder;
 
@@ -52,6 +59,19 @@ impl<'a> PngDecoder<'a>
             )));
         }
 
+        let pixels = self.png_info.width.saturating_mul(self.png_info.height);
+
+        if pixels > self.options.get_max_pixels()
+        {
+            return Err(PngDecodeErrors::Generic(format!(
+                "Image has {} pixels ({}x{}), more than maximum configured pixels {}, aborting",
+                pixels,
+                self.png_info.width,
+                self.png_info.height,
+                self.options.get_max_pixels()
+            )));
+        }
+
         self.png_info.depth = self.stream.get_u8();
         let color = self.stream.get_u8();
 
@@ -67,26 +87,12 @@ impl<'a> PngDecoder<'a>
         }
         self.png_info.component = self.png_info.color.num_components();
         // verify colors plus bit depths
-        match self.png_info.depth
+        if !self.png_info.color.is_valid_depth(self.png_info.depth)
         {
-            1 | 2 | 4 | 8 =>
-            { /*silent pass through since all color types support it */ }
-            16 =>
-            {
-                if self.png_info.color == PngColor::Palette
-                {
-                    return Err(PngDecodeErrors::GenericStatic(
-                        "Indexed colour cannot have 16 bit depth"
-                    ));
-                }
-            }
-            _ =>
-            {
-                return Err(PngDecodeErrors::Generic(format!(
-                    "Unknown bit depth {}",
-                    self.png_info.depth
-                )))
-            }
+            return Err(PngDecodeErrors::InvalidColorDepthCombo(
+                self.png_info.color,
+                self.png_info.depth
+            ));
         }
 
         if self.stream.get_u8() != 0
@@ -148,14 +154,23 @@ impl<'a> PngDecoder<'a>
         }
 
         // allocate palette
-        self.palette.resize(256, PLTEEntry::default());
+        self.palette.resize(256, PaletteEntry::default());
 
-        for pal_chunk in self.palette.iter_mut().take(chunk.length / 3)
+        self.palette_len = (chunk.length / 3).min(256);
+
+        for pal_chunk in self.palette.iter_mut().take(self.palette_len)
         {
             pal_chunk.red = self.stream.get_u8();
             pal_chunk.green = self.stream.get_u8();
             pal_chunk.blue = self.stream.get_u8();
         }
+        // apply alpha values of a tRNS chunk which appeared before
+        // the palette
+        for (pal_chunk, alpha) in self.palette.iter_mut().zip(self.pending_trns)
+        {
+            pal_chunk.alpha = *alpha;
+        }
+        self.pending_trns = &[];
 
         // skip crc chunk
         self.stream.skip(4);
@@ -168,10 +183,14 @@ impl<'a> PngDecoder<'a>
         // get a reference to the IDAT chunk stream and push it,
         // we will later pass these to the deflate decoder as a whole, to get the whole
         // uncompressed stream.
+        //
+        // Some encoders write empty IDAT chunks, these contribute no
+        // bytes and the zlib stream continues in the next chunk.
 
         let idat_stream = self.stream.get(png_chunk.length)?;
 
         self.idat_chunks.extend_from_slice(idat_stream);
+        self.seen_idat = true;
 
         // skip crc
         self.stream.skip(4);
@@ -194,12 +213,28 @@ impl<'a> PngDecoder<'a>
                 self.trns_bytes[1] = self.stream.get_u16_be();
                 self.trns_bytes[2] = self.stream.get_u16_be();
             }
-            PngColor::Palette =>
+            PngColor::Palette if !self.seen_ptle =>
             {
-                if self.palette.is_empty()
+                // The spec says tRNS must come after PLTE, but some encoders
+                // write it before, so in permissive mode keep the data around and
+                // apply it once we see the palette
+                if self.options.get_strict_mode()
                 {
-                    return Err(PngDecodeErrors::GenericStatic("tRNS chunk before plTE"));
+                    return Err(PngDecodeErrors::GenericStatic("tRNS chunk before PLTE"));
                 }
+                if chunk.length > 256
+                {
+                    return Err(PngDecodeErrors::Generic(format!(
+                        "tRNS chunk with too long entries {}",
+                        chunk.length
+                    )));
+                }
+                warn!("tRNS chunk before PLTE, alpha values will be applied to the palette");
+
+                self.pending_trns = self.stream.get(chunk.length)?;
+            }
+            PngColor::Palette =>
+            {
                 if self.palette.len() < chunk.length
                 {
                     return Err(PngDecodeErrors::Generic(format!(
@@ -225,6 +260,289 @@ impl<'a> PngDecoder<'a>
 
         Ok(())
     }
+    pub(crate) fn parse_sbit(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        // one entry per channel in the stored colour type, palette
+        // images store RGB entries
+        let expected_len = match self.png_info.color
+        {
+            PngColor::Palette => 3,
+            color => usize::from(color.num_components())
+        };
+        // palette entries are always 8 bits
+        let max_bits = match self.png_info.color
+        {
+            PngColor::Palette => 8,
+            _ => self.png_info.depth
+        };
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        let error = if chunk.length != expected_len
+        {
+            Some(format!(
+                "sBIT chunk length {} does not match expected length {} for colour type {:?}",
+                chunk.length, expected_len, self.png_info.color
+            ))
+        }
+        else if data.iter().any(|x| *x == 0 || *x > max_bits)
+        {
+            Some(format!(
+                "Invalid sBIT values {:?}, values should be between 1 and {}",
+                data, max_bits
+            ))
+        }
+        else
+        {
+            None
+        };
+
+        if let Some(error) = error
+        {
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(error));
+            }
+            warn!("{}, ignoring sBIT chunk", error);
+
+            return Ok(());
+        }
+        let mut sbit = [0; 4];
+        sbit[..data.len()].copy_from_slice(data);
+
+        self.png_info.sbit = Some(sbit);
+
+        Ok(())
+    }
+
+    pub(crate) fn parse_bkgd(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        let background = match (self.png_info.color, data)
+        {
+            (PngColor::Palette, [index]) =>
+            {
+                if self.seen_ptle && usize::from(*index) >= self.palette_len
+                {
+                    let error = format!(
+                        "bKGD palette index {} out of range for palette with {} entries",
+                        index, self.palette_len
+                    );
+                    if self.options.get_strict_mode()
+                    {
+                        return Err(PngDecodeErrors::Generic(error));
+                    }
+                    warn!("{}", error);
+                }
+                Some(BackgroundColor::PaletteIndex(*index))
+            }
+            (PngColor::Luma | PngColor::LumaA, [a, b]) =>
+            {
+                Some(BackgroundColor::Gray(u16::from_be_bytes([*a, *b])))
+            }
+            (PngColor::RGB | PngColor::RGBA, [r1, r2, g1, g2, b1, b2]) =>
+            {
+                Some(BackgroundColor::Rgb(
+                    u16::from_be_bytes([*r1, *r2]),
+                    u16::from_be_bytes([*g1, *g2]),
+                    u16::from_be_bytes([*b1, *b2])
+                ))
+            }
+            _ => None
+        };
+
+        if background.is_none()
+        {
+            let error = format!(
+                "Invalid bKGD chunk length {} for colour type {:?}",
+                chunk.length, self.png_info.color
+            );
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(error));
+            }
+            warn!("{}, ignoring bKGD chunk", error);
+        }
+        self.png_info.background = background;
+
+        Ok(())
+    }
+
+    pub(crate) fn parse_chrm(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        if chunk.length != 32
+        {
+            let error = format!("cHRM chunk length is not 32 but {}", chunk.length);
+
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(error));
+            }
+            warn!("{}, ignoring cHRM chunk", error);
+            return Ok(());
+        }
+        let mut values = [0.0_f32; 8];
+
+        for (value, bytes) in values.iter_mut().zip(data.chunks_exact(4))
+        {
+            let raw = u32::from_be_bytes(bytes.try_into().unwrap());
+            *value = (f64::from(raw) / 100000.0) as f32;
+        }
+        let [white_x, white_y, red_x, red_y, green_x, green_y, blue_x, blue_y] = values;
+
+        self.png_info.chromaticities = Some(Chromaticities {
+            white_x,
+            white_y,
+            red_x,
+            red_y,
+            green_x,
+            green_y,
+            blue_x,
+            blue_y
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn parse_srgb(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        let intent = match data
+        {
+            [intent] => RenderingIntent::from_int(*intent),
+            _ => None
+        };
+
+        if intent.is_none()
+        {
+            let error = format!("Invalid sRGB chunk {:?}, expected a single byte 0-3", data);
+
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(error));
+            }
+            warn!("{}, ignoring sRGB chunk", error);
+        }
+        self.png_info.srgb_intent = intent;
+
+        Ok(())
+    }
+
+    pub(crate) fn parse_phys(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        let dimensions = match data
+        {
+            [x @ .., unit] if chunk.length == 9 =>
+            {
+                PhysicalUnit::from_int(*unit).map(|unit| PhysicalDimensions {
+                    x_pixels_per_unit: u32::from_be_bytes(x[0..4].try_into().unwrap()),
+                    y_pixels_per_unit: u32::from_be_bytes(x[4..8].try_into().unwrap()),
+                    unit
+                })
+            }
+            _ => None
+        };
+
+        if dimensions.is_none()
+        {
+            let error = if chunk.length == 9
+            {
+                format!("Unknown pHYs unit specifier {}", data[8])
+            }
+            else
+            {
+                format!("pHYs chunk length is not 9 but {}", chunk.length)
+            };
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(error));
+            }
+            warn!("{}, ignoring pHYs chunk", error);
+        }
+        self.png_info.pixel_dims = dimensions;
+
+        Ok(())
+    }
+
+    pub(crate) fn parse_splt(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        match parse_suggested_palette(data)
+        {
+            Ok(palette) => self.png_info.suggested_palettes.push(palette),
+            Err(error) =>
+            {
+                if self.options.get_strict_mode()
+                {
+                    return Err(PngDecodeErrors::Generic(error));
+                }
+                warn!("{}, ignoring sPLT chunk", error);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parse_hist(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
+
+        let error = if !self.seen_ptle
+        {
+            Some(String::from("hIST chunk before PLTE chunk"))
+        }
+        else if data.len() != self.palette_len * 2
+        {
+            Some(format!(
+                "hIST chunk has {} bytes, expected {} for palette with {} entries",
+                data.len(),
+                self.palette_len * 2,
+                self.palette_len
+            ))
+        }
+        else
+        {
+            None
+        };
+
+        if let Some(error) = error
+        {
+            if self.options.get_strict_mode()
+            {
+                return Err(PngDecodeErrors::Generic(error));
+            }
+            warn!("{}, ignoring hIST chunk", error);
+
+            return Ok(());
+        }
+        self.png_info.histogram = Some(
+            data.chunks_exact(2)
+                .map(|x| u16::from_be_bytes([x[0], x[1]]))
+                .collect()
+        );
+
+        Ok(())
+    }
+
     pub(crate) fn parse_gama(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
     {
         if self.options.get_strict_mode() && chunk.length != 4
@@ -251,16 +569,125 @@ impl<'a> PngDecoder<'a>
     /// Parse the animation control chunk
     pub(crate) fn parse_actl(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
     {
-        if self.options.get_strict_mode()
+        if chunk.length != 8
         {
-            return Err(PngDecodeErrors::UnsupportedAPNGImage);
+            return Err(PngDecodeErrors::Generic(format!(
+                "acTL chunk length is not 8 but {}",
+                chunk.length
+            )));
         }
-        else
+        let num_frames = self.stream.get_u32_be();
+        let num_plays = self.stream.get_u32_be();
+
+        if num_frames == 0
         {
-            error!("APNG support is not yet present,this will only decode the first frame of the image");
+            return Err(PngDecodeErrors::GenericStatic(
+                "acTL chunk with zero frames"
+            ));
         }
-        // skip bytes plus CRC
-        self.stream.skip(chunk.length + 4);
+        info!("APNG frames: {}", num_frames);
+        info!("APNG plays: {}", num_plays);
+
+        self.actl_info = Some(ActlChunk {
+            num_frames,
+            num_plays
+        });
+        // skip crc
+        self.stream.skip(4);
+
+        Ok(())
+    }
+
+    /// Parse the frame control chunk
+    pub(crate) fn parse_fctl(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        if chunk.length != 26
+        {
+            return Err(PngDecodeErrors::Generic(format!(
+                "fcTL chunk length is not 26 but {}",
+                chunk.length
+            )));
+        }
+        let seq_number = self.stream.get_u32_be();
+        let width = self.stream.get_u32_be() as usize;
+        let height = self.stream.get_u32_be() as usize;
+        let x_offset = self.stream.get_u32_be() as usize;
+        let y_offset = self.stream.get_u32_be() as usize;
+        let delay_num = self.stream.get_u16_be();
+        let delay_denom = self.stream.get_u16_be();
+        let dispose = self.stream.get_u8();
+        let blend = self.stream.get_u8();
+
+        if width == 0 || height == 0
+        {
+            return Err(PngDecodeErrors::GenericStatic(
+                "fcTL width or height cannot be zero"
+            ));
+        }
+        // frame region must lie completely within the canvas
+        if x_offset.saturating_add(width) > self.png_info.width
+            || y_offset.saturating_add(height) > self.png_info.height
+        {
+            return Err(PngDecodeErrors::Generic(format!(
+                "fcTL region {width}x{height} at ({x_offset},{y_offset}) outside the image dimensions {}x{}",
+                self.png_info.width, self.png_info.height
+            )));
+        }
+        let dispose_op = DisposeOp::from_int(dispose)
+            .ok_or_else(|| PngDecodeErrors::Generic(format!("Unknown dispose op {dispose}")))?;
+
+        let blend_op = BlendOp::from_int(blend)
+            .ok_or_else(|| PngDecodeErrors::Generic(format!("Unknown blend op {blend}")))?;
+
+        let info = FrameInfo {
+            seq_number,
+            width,
+            height,
+            x_offset,
+            y_offset,
+            delay_num,
+            delay_denom,
+            dispose_op,
+            blend_op
+        };
+        // an fcTL before the first IDAT means the default
+        // image is the first frame of the animation.
+        let frame = ApngFrameData {
+            info,
+            uses_idat: !self.seen_idat,
+            fdat: vec![]
+        };
+        self.apng_frames.push(frame);
+        // skip crc
+        self.stream.skip(4);
+
+        Ok(())
+    }
+
+    /// Parse the frame data chunk
+    pub(crate) fn parse_fdat(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
+    {
+        if chunk.length < 4
+        {
+            return Err(PngDecodeErrors::GenericStatic("Too short fdAT chunk"));
+        }
+        // sequence number
+        let _ = self.stream.get_u32_be();
+
+        let data = self.stream.get(chunk.length - 4)?;
+
+        match self.apng_frames.last_mut()
+        {
+            Some(frame) if !frame.uses_idat => frame.fdat.extend_from_slice(data),
+            _ =>
+            {
+                return Err(PngDecodeErrors::GenericStatic(
+                    "fdAT chunk without a preceding fcTL chunk"
+                ))
+            }
+        }
+        // skip crc
+        self.stream.skip(4);
 
         Ok(())
     }
@@ -364,6 +791,10 @@ impl<'a> PngDecoder<'a>
             // read remaining chunk
             let data = self.stream.peek_at(0, remainder).unwrap();
 
+            if self.options.png_get_keep_raw_iccp()
+            {
+                self.png_info.icc_profile_compressed = Some(data.to_vec());
+            }
             // decode to vec
             if let Ok(icc_uncompressed) = DeflateDecoder::new(data).decode_zlib()
             {
@@ -419,41 +850,84 @@ impl<'a> PngDecoder<'a>
         self.stream.skip(4);
     }
     /// Parse the itXT chunk
-    pub(crate) fn parse_itxt(&mut self, chunk: PngChunk)
+    pub(crate) fn parse_itxt(&mut self, chunk: PngChunk) -> Result<(), PngDecodeErrors>
     {
-        let length = core::cmp::min(chunk.length, 79);
-        let keyword_bytes = self.stream.peek_at(0, length).unwrap();
-        let keyword_position = keyword_bytes.iter().position(|x| *x == 0);
+        let data = self.stream.get(chunk.length)?;
+        // skip crc
+        self.stream.skip(4);
 
-        if let Some(pos) = keyword_position
+        // keyword, null separator, compression flag and compression method,
+        // language tag, null separator, translated keyword, null separator, text
+        let keyword_end = match data.iter().position(|x| *x == 0)
         {
-            let keyword = &keyword_bytes[..pos];
-            // skip name plus null byte
-            let bytes_to_skip = pos + 1 // null separator
-                + 1  // compression flag
-                + 1  // compression method
-                + 1  // null separator
-                + 1; // null separator
-
-            self.stream.skip(bytes_to_skip);
-            let remainder = chunk.length.saturating_sub(bytes_to_skip);
-            let raw_data = self.stream.peek_at(0, remainder).unwrap();
-
-            let itxt_chunk = ItxtChunk {
-                keyword,
-                text: raw_data
-            };
-            self.png_info.itxt_chunk.push(itxt_chunk);
-            // skip bytes we read
-            self.stream.skip(remainder);
-        }
-        else
+            Some(pos) if pos + 3 <= data.len() => pos,
+            _ =>
+            {
+                warn!("Could not find keyword in iTXt chunk, possibly corrupt chunk");
+                return Ok(());
+            }
+        };
+        let keyword = &data[..keyword_end];
+        let compression_flag = data[keyword_end + 1];
+        let compression_method = data[keyword_end + 2];
+
+        let rest = &data[keyword_end + 3..];
+
+        let language_end = rest.iter().position(|x| *x == 0);
+        let translated_end = language_end.and_then(|start| {
+            rest[start + 1..]
+                .iter()
+                .position(|x| *x == 0)
+                .map(|pos| start + 1 + pos)
+        });
+
+        let (language_end, translated_end) = match (language_end, translated_end)
         {
-            warn!("Possibly corrupt iTXT chunk");
-            self.stream.skip(chunk.length);
-        }
-        // skip crc
-        self.stream.skip(4);
+            (Some(language_end), Some(translated_end)) => (language_end, translated_end),
+            _ =>
+            {
+                warn!("Possibly corrupt iTXt chunk, missing null separators");
+                return Ok(());
+            }
+        };
+        let raw_text = &rest[translated_end + 1..];
+
+        let text = match (compression_flag, compression_method)
+        {
+            (0, _) => raw_text.to_vec(),
+            (1, 0) => match DeflateDecoder::new(raw_text).decode_zlib()
+            {
+                Ok(text) => text,
+                Err(_) =>
+                {
+                    warn!("Could not decode iTXt text, error with zlib stream");
+                    return Ok(());
+                }
+            },
+            _ =>
+            {
+                if self.options.get_strict_mode()
+                {
+                    return Err(PngDecodeErrors::Generic(format!(
+                        "Unknown iTXt compression flag {compression_flag} or method {compression_method}"
+                    )));
+                }
+                warn!(
+                    "Unknown iTXt compression flag {} or method {}, ignoring chunk",
+                    compression_flag, compression_method
+                );
+                return Ok(());
+            }
+        };
+
+        self.png_info.itxt_chunk.push(ItxtChunk {
+            keyword,
+            language_tag: &rest[..language_end],
+            translated_keyword: &rest[language_end + 1..translated_end],
+            text
+        });
+
+        Ok(())
     }
 
     /// Parse zTxt chunk
@@ -507,3 +981,67 @@ impl<'a> PngDecoder<'a>
         self.stream.skip(4);
     }
 }
+
+/// Parse the contents of an sPLT chunk
+#[allow(clippy::manual_is_multiple_of)]
+fn parse_suggested_palette(data: &[u8]) -> Result<SuggestedPalette<'_>, String>
+{
+    // palette name, 1-79 bytes followed by a null separator
+    let name_length = data
+        .iter()
+        .take(80)
+        .position(|x| *x == 0)
+        .filter(|length| *length > 0)
+        .ok_or_else(|| String::from("sPLT palette name is not 1-79 bytes"))?;
+
+    let name = &data[..name_length];
+    let sample_depth = *data
+        .get(name_length + 1)
+        .ok_or_else(|| String::from("sPLT chunk is missing the sample depth"))?;
+
+    let stride = match sample_depth
+    {
+        8 => 6,
+        16 => 10,
+        _ => return Err(format!("Unknown sPLT sample depth {sample_depth}"))
+    };
+    let entries = &data[name_length + 2..];
+
+    if entries.len() % stride != 0
+    {
+        return Err(format!(
+            "sPLT entries length {} is not a multiple of {stride} for sample depth {sample_depth}",
+            entries.len()
+        ));
+    }
+    let entries = entries
+        .chunks_exact(stride)
+        .map(|entry| {
+            let (samples, frequency) = entry.split_at(stride - 2);
+
+            let sample = |i: usize| -> u16 {
+                if sample_depth == 8
+                {
+                    u16::from(samples[i])
+                }
+                else
+                {
+                    u16::from_be_bytes([samples[2 * i], samples[2 * i + 1]])
+                }
+            };
+            SuggestedPaletteEntry {
+                red:       sample(0),
+                green:     sample(1),
+                blue:      sample(2),
+                alpha:     sample(3),
+                frequency: u16::from_be_bytes([frequency[0], frequency[1]])
+            }
+        })
+        .collect();
+
+    Ok(SuggestedPalette {
+        name,
+        sample_depth,
+        entries
+    })
+}
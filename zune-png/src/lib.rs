@@ -1,4 +1,4 @@
-//! A png decoder
+//! A png decoder and encoder
 //!
 //! This features a simple PNG reader in Rust which supports decoding of valid
 //! ISO/IEC 15948:2003 (E) or PNG images
@@ -8,6 +8,10 @@
 //! - Fast inflate decoder
 //! - Platform specific intrinsics for accelerated decoding on x86
 //! - Endian aware decoding support.
+//! - Animated PNG (APNG) decoding support
+//! - Chunk level inspection via [`PngDecoder::chunk_report`]
+//! - Encoding via [`PngEncoder`] (requires the `crc` feature)
+//! - Lossless rotation via [`rotate_lossless`] (requires the `crc` feature)
 //!
 //! # Usage
 //! Add the library to `Cargo.toml`
@@ -79,6 +83,22 @@
 //! Some data is usually borrowed from the underlying reader, so the lifetime of the [`PngInfo`] struct is tied
 //! to the lifetime of the [`PngDecoder`] struct from which it was derived
 //!
+//! # Animated images
+//!
+//! Frames of an animated png can be decoded via [`decode_apng`](PngDecoder::decode_apng)
+//! which returns fully composited frames together with their frame control information
+//! such as the frame delay.
+//!
+//!```no_run
+//! use zune_png::PngDecoder;
+//! let mut decoder = PngDecoder::new(&[]);
+//!
+//! for frame in decoder.decode_apng().unwrap(){
+//!     let delay = (frame.info.delay_num,frame.info.delay_denom);
+//!     // do something with frame.pixels
+//! }
+//! ```
+//!
 //! # Alternatives
 //! - [png](https://crates.io/crates/png) crate
 //!
@@ -86,16 +106,37 @@
 #![allow(clippy::op_ref, clippy::identity_op)]
 extern crate alloc;
 
-pub use decoder::{ItxtChunk, PngDecoder, PngInfo, TextChunk, TimeInfo, ZtxtChunk};
-pub use enums::InterlaceMethod;
+pub use apng::{FrameInfo, PngFrame};
+pub use decoder::{
+    BackgroundColor, Chromaticities, ItxtChunk, PaletteEntry, PhysicalDimensions, PngDecoder,
+    PngInfo, SuggestedPalette, SuggestedPaletteEntry, TextChunk, TimeInfo, ZtxtChunk
+};
+#[cfg(feature = "crc")]
+pub use encoder::PngEncoder;
+pub use enums::{
+    BlendOp, DisposeOp, InterlaceMethod, PhysicalUnit, PngChunkType, PngColor, RenderingIntent
+};
+#[cfg(feature = "std")]
+pub use reader::PngSource;
+pub use report::{ChunkReport, ChunkSummary, IntegrityReport};
+#[cfg(feature = "crc")]
+pub use rotate::{rotate_lossless, RotationAngle};
 pub use zune_core;
 
+mod apng;
 mod constants;
 mod crc;
 mod decoder;
+mod encoder;
 mod enums;
 pub mod error;
 mod filters;
 mod headers;
 mod options;
+#[cfg(feature = "std")]
+mod reader;
+mod report;
+mod rotate;
+mod serde;
 mod utils;
+mod writer;
@@ -1,8 +1,10 @@
+use alloc::boxed::Box;
 use alloc::format;
 
 use log::trace;
 use zune_core::bytestream::ZByteReader;
 
+use crate::enums::PngChunkType;
 use crate::error::PngDecodeErrors;
 
 ///
@@ -13,6 +15,17 @@ pub type UnkownChunkHandler = fn(
     crc: u32
 ) -> Result<(), PngDecodeErrors>;
 
+/// Function called with the chunk type, stored CRC and calculated
+/// CRC of chunks whose CRC doesn't match
+pub type CrcMismatchHandler = Box<dyn FnMut(PngChunkType, u32, u32) + Send>;
+
+/// Function called with the chunk type, chunk data and stored CRC
+/// of every chunk read while decoding headers
+pub type ChunkObserver = Box<dyn FnMut(&[u8; 4], &[u8], u32) + Send>;
+
+/// Function called with the fraction of the image decoded so far
+pub type ProgressCallback = Box<dyn FnMut(f32) + Send>;
+
 pub fn default_chunk_handler(
     length: usize, chunk_type: [u8; 4], reader: &mut ZByteReader, _crc: u32
 ) -> Result<(), PngDecodeErrors>
@@ -0,0 +1,129 @@
+//! Reading png images from `std::io::Read` sources
+use std::io::{Error, ErrorKind, Read};
+
+use zune_core::options::DecoderOptions;
+
+use crate::constants::PNG_SIGNATURE;
+use crate::PngDecoder;
+
+/// An IEND chunk, with its length, type and crc
+const IEND_CHUNK: [u8; 12] = [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82];
+
+/// Png data read from a [`Read`] source
+///
+/// A [`PngDecoder`] borrows the bytes it decodes, this owns them so images
+/// can be decoded from files or network streams without buffering them manually.
+///
+/// # Example
+/// ```no_run
+/// use zune_png::PngSource;
+///
+/// let file = std::fs::File::open("image.png").unwrap();
+/// let source = PngSource::from_reader(file).unwrap();
+///
+/// let pixels = source.decoder().decode_raw().unwrap();
+/// ```
+pub struct PngSource
+{
+    data:    Vec<u8>,
+    options: DecoderOptions
+}
+
+impl PngSource
+{
+    /// Read the whole png from `reader`
+    ///
+    /// # Errors
+    /// Any error returned by `reader`
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<PngSource, Error>
+    {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        Ok(PngSource {
+            data,
+            options: DecoderOptions::default()
+        })
+    }
+
+    /// Read only the chunks needed to decode the headers of the png from `reader`
+    ///
+    /// Chunks are read up to the first image data chunk, so dimensions, colorspace,
+    /// the palette and metadata placed before the image data can be queried without
+    /// reading the rest of the stream. Metadata stored after the image data,
+    /// e.g text chunks some encoders write at the end, is not available.
+    ///
+    /// Decoding pixels from the returned source returns an error as there is no image data.
+    ///
+    /// # Errors
+    /// - Any error returned by `reader`
+    /// - If the stream is not a png or ends before the image data
+    pub fn headers_from_reader<R: Read>(mut reader: R) -> Result<PngSource, Error>
+    {
+        let mut data = vec![0; 8];
+        reader.read_exact(&mut data)?;
+
+        if data[..] != PNG_SIGNATURE.to_be_bytes()
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Bad PNG signature, not a png"
+            ));
+        }
+        loop
+        {
+            let mut header = [0; 8];
+            reader.read_exact(&mut header)?;
+
+            if &header[4..] == b"IDAT" || &header[4..] == b"IEND"
+            {
+                break;
+            }
+            let length = u64::from(u32::from_be_bytes(header[..4].try_into().unwrap()));
+
+            data.extend_from_slice(&header);
+
+            // chunk data and crc, read through take so a corrupt length doesn't
+            // allocate more than the stream contains
+            let expected = length + 4;
+            let read = reader.by_ref().take(expected).read_to_end(&mut data)?;
+
+            if read as u64 != expected
+            {
+                return Err(Error::from(ErrorKind::UnexpectedEof));
+            }
+        }
+        data.extend_from_slice(&IEND_CHUNK);
+
+        Ok(PngSource {
+            data,
+            options: DecoderOptions::default()
+        })
+    }
+
+    /// Set the options used by decoders created from this source
+    #[must_use]
+    pub fn set_options(mut self, options: DecoderOptions) -> PngSource
+    {
+        self.options = options;
+        self
+    }
+
+    /// Create a decoder for the png
+    pub fn decoder(&self) -> PngDecoder<'_>
+    {
+        PngDecoder::new_with_options(&self.data, self.options)
+    }
+
+    /// Return the bytes read
+    pub fn data(&self) -> &[u8]
+    {
+        &self.data
+    }
+
+    /// Consume the source, returning the bytes read
+    pub fn into_inner(self) -> Vec<u8>
+    {
+        self.data
+    }
+}
@@ -0,0 +1,262 @@
+//! Chunk level inspection of png files
+//!
+//! This contains routines that walk the chunks of a png without
+//! decoding pixels, useful for tools that want to display
+//! information about how a png file is laid out.
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::constants::PNG_SIGNATURE;
+use crate::enums::PngChunkType;
+use crate::PngDecoder;
+
+/// Information about a single chunk in a png file
+///
+/// This is returned by [`PngDecoder::chunk_report`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkReport
+{
+    /// Chunk name, e.g `IHDR`
+    pub name:      String,
+    /// Length of the chunk data, excluding the length, chunk type and crc fields
+    pub length:    usize,
+    /// CRC stored in the file for this chunk
+    pub crc:       u32,
+    /// Whether the stored CRC matches the CRC calculated from
+    /// the chunk type and chunk data.
+    ///
+    /// If the `crc` feature is disabled, the CRC cannot be calculated
+    /// and this is always true
+    pub crc_valid: bool,
+    /// Whether the chunk is critical, i.e a decoder must understand it
+    /// to correctly decode the image.
+    ///
+    /// This is derived from bit 5 of the first byte of the chunk name,
+    /// an uppercase first letter indicates a critical chunk
+    pub critical:  bool
+}
+
+/// Summary of a chunk read while decoding headers
+///
+/// These are recorded if [`png_set_record_chunks`](zune_core::options::DecoderOptions::png_set_record_chunks)
+/// is enabled and returned by [`PngDecoder::chunk_summary`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ChunkSummary
+{
+    /// Chunk type, chunks the decoder doesn't know are `PngChunkType::unkn`
+    pub chunk_type: PngChunkType,
+    /// Chunk name as stored in the file, e.g `IHDR`
+    pub name:       [u8; 4],
+    /// CRC stored in the file for this chunk
+    pub crc:        u32,
+    /// Length of the chunk data, excluding the length, chunk type and crc fields
+    pub length:     usize
+}
+
+/// Result of verifying the checksums of a png file
+///
+/// This is returned by [`PngDecoder::integrity_check`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegrityReport
+{
+    /// Whether the CRC of every chunk matches its contents
+    pub all_crcs_valid: bool,
+    /// Whether the Adler-32 checksum of the compressed image data
+    /// matches the decompressed data.
+    ///
+    /// This is false if the image data could not be decompressed
+    pub adler32_valid:  bool,
+    /// Names of chunks whose CRC does not match, in the order
+    /// they appear in the file
+    pub bad_chunks:     Vec<String>
+}
+
+impl<'a> PngDecoder<'a>
+{
+    /// Return a summary of each chunk read while decoding headers, in
+    /// the order they appear in the file
+    ///
+    /// This is only populated if [`png_set_record_chunks`](zune_core::options::DecoderOptions::png_set_record_chunks)
+    /// is enabled, and is empty before headers are decoded.
+    ///
+    /// Unlike [`chunk_report`](Self::chunk_report), this does not walk the file again
+    pub fn chunk_summary(&self) -> &[ChunkSummary]
+    {
+        &self.chunk_summary
+    }
+
+    /// Walk all chunks in the png and return information about each chunk
+    ///
+    /// This does not decode headers or pixels and does not modify
+    /// the decoder state, so it can be called at any time.
+    ///
+    /// The walk stops after the `IEND` chunk or when the stream
+    /// does not contain enough bytes for the next chunk, chunks read up to that
+    /// point are returned.
+    ///
+    /// # Returns
+    /// Information about each chunk in the order they appear in the file,
+    /// if the file does not start with a png signature, this is empty.
+    pub fn chunk_report(&mut self) -> Vec<ChunkReport>
+    {
+        let position = self.stream.get_position();
+        // go to the start of the stream
+        self.stream.rewind(position);
+
+        let mut reports = Vec::new();
+
+        if self.stream.get_u64_be_err() == Ok(PNG_SIGNATURE)
+        {
+            while let Some(report) = self.read_chunk_report()
+            {
+                let is_end = report.name == "IEND";
+
+                reports.push(report);
+
+                if is_end
+                {
+                    break;
+                }
+            }
+        }
+        // restore the previous position
+        self.stream.rewind(self.stream.get_position());
+        self.stream.skip(position);
+
+        reports
+    }
+
+    /// Walk all chunks in the png and return the byte range of each chunk
+    /// within the input
+    ///
+    /// Each range covers the whole chunk, from the start of its length field to the
+    /// end of its CRC, so chunks can be stripped, replaced or copied by splicing the input
+    /// without re-encoding the image. The 8 byte signature precedes the first range and
+    /// consecutive ranges are adjacent, bytes after the last range are trailing data
+    /// not belonging to any chunk.
+    ///
+    /// Like [`chunk_report`](Self::chunk_report) this does not decode headers or pixels and
+    /// does not modify the decoder state. The walk stops after the `IEND` chunk or when the stream
+    /// does not contain enough bytes for the next chunk.
+    ///
+    /// # Returns
+    /// The name and byte range of each chunk in the order they appear in the file,
+    /// if the file does not start with a png signature, this is empty.
+    pub fn chunk_ranges(&mut self) -> Vec<(String, Range<usize>)>
+    {
+        let position = self.stream.get_position();
+        // go to the start of the stream
+        self.stream.rewind(position);
+
+        let mut ranges = Vec::new();
+
+        if self.stream.get_u64_be_err() == Ok(PNG_SIGNATURE)
+        {
+            let mut start = self.stream.get_position();
+
+            while let Some(report) = self.read_chunk_report()
+            {
+                let end = self.stream.get_position();
+                let is_end = report.name == "IEND";
+
+                ranges.push((report.name, start..end));
+                start = end;
+
+                if is_end
+                {
+                    break;
+                }
+            }
+        }
+        // restore the previous position
+        self.stream.rewind(self.stream.get_position());
+        self.stream.skip(position);
+
+        ranges
+    }
+
+    /// Read a single chunk returning its report or `None` if there
+    /// are not enough bytes for the chunk
+    fn read_chunk_report(&mut self) -> Option<ChunkReport>
+    {
+        let length = self.stream.get_u32_be_err().ok()? as usize;
+
+        if !self.stream.has(length + 8 /*chunk type + crc*/)
+        {
+            return None;
+        }
+        // chunk type + chunk data, which is what the crc covers
+        let bytes = self.stream.peek_at(0, length + 4).ok()?;
+        let name = String::from_utf8_lossy(&bytes[0..4]).into_owned();
+        // bit 5 of the first byte is the ancillary bit
+        let critical = bytes[0] & (1 << 5) == 0;
+
+        self.stream.skip(length + 4);
+
+        let crc = self.stream.get_u32_be_err().ok()?;
+
+        #[cfg(feature = "crc")]
+        let crc_valid = !crate::crc::crc32_slice8(bytes, u32::MAX) == crc;
+        #[cfg(not(feature = "crc"))]
+        let crc_valid = true;
+
+        Some(ChunkReport {
+            name,
+            length,
+            crc,
+            crc_valid,
+            critical
+        })
+    }
+
+    /// Verify all chunk CRCs and the Adler-32 checksum of the image data
+    ///
+    /// Unlike normal decoding, which stops at the first mismatch, this checks every
+    /// chunk and reports all failures, irrespective of the CRC and Adler
+    /// options the decoder was configured with.
+    ///
+    /// This does not modify the decoder state, so it can be called at any time.
+    ///
+    /// If the `crc` feature is disabled, chunk CRCs cannot be calculated and are
+    /// treated as valid
+    pub fn integrity_check(&mut self) -> IntegrityReport
+    {
+        let bad_chunks: Vec<String> = self
+            .chunk_report()
+            .into_iter()
+            .filter(|chunk| !chunk.crc_valid)
+            .map(|chunk| chunk.name)
+            .collect();
+
+        // a separate decoder so that we do not interfere with this decoder's state
+        let position = self.stream.get_position();
+        self.stream.rewind(position);
+        let data = self.stream.remaining_bytes();
+        self.stream.skip(position);
+
+        // CRC's were already checked above, ignore them so that a bad ancillary chunk
+        // doesn't prevent us from checking the image data
+        let options = self
+            .options
+            .png_set_confirm_crc(false)
+            .inflate_set_confirm_adler(true);
+
+        let mut decoder = PngDecoder::new_with_options(data, options);
+
+        let adler32_valid = decoder.decode_headers().is_ok()
+            && decoder
+                .inflate(
+                    &decoder.idat_chunks,
+                    decoder.png_info.width,
+                    decoder.png_info.height
+                )
+                .is_ok();
+
+        IntegrityReport {
+            all_crcs_valid: bad_chunks.is_empty(),
+            adler32_valid,
+            bad_chunks
+        }
+    }
+}
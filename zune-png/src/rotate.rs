@@ -0,0 +1,148 @@
+//! Lossless rotation of png files
+#![cfg(feature = "crc")]
+
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use zune_core::bit_depth::{BitDepth, ByteEndian};
+use zune_core::options::{DecoderOptions, EncoderOptions};
+
+use crate::constants::PNG_SIGNATURE;
+use crate::error::PngDecodeErrors;
+use crate::{PngDecoder, PngEncoder};
+
+/// Ancillary chunks carried over to the rotated image
+///
+/// These do not depend on the image dimensions or pixel layout,
+/// so they stay valid after rotation
+const PRESERVED_CHUNKS: [&[u8; 4]; 8] = [
+    b"gAMA", b"cHRM", b"sRGB", b"iCCP", b"tEXt", b"zTXt", b"iTXt", b"tIME"
+];
+
+/// Clockwise rotation angles supported by [`rotate_lossless`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RotationAngle
+{
+    /// Rotate by 90 degrees clockwise
+    Rotate90,
+    /// Rotate by 180 degrees
+    Rotate180,
+    /// Rotate by 270 degrees clockwise, i.e 90 degrees anti-clockwise
+    Rotate270
+}
+
+/// Rotate a png image, returning the rotated image as a new png
+///
+/// The image is decoded, rotated and re-encoded, since png is lossless,
+/// pixels are moved exactly and no quality is lost.
+///
+/// The metadata chunks `gAMA`, `cHRM`, `sRGB`, `iCCP`, `tEXt`, `zTXt`, `iTXt`
+/// and `tIME` are copied as is to the new image, other ancillary chunks are dropped.
+///
+/// The output is never interlaced, paletted images are written as RGB(A)
+/// and images with less than 8 bits per sample are written as 8 bit images.
+/// Animated images are written as a single still image containing the default image.
+///
+/// # Arguments
+/// - data: The png file
+/// - angle: Angle to rotate by
+///
+/// # Returns
+/// - `Ok(Vec<u8>)`: The rotated png file
+/// - `Err(PngDecodeErrors)`: If the input could not be decoded
+pub fn rotate_lossless(data: &[u8], angle: RotationAngle) -> Result<Vec<u8>, PngDecodeErrors>
+{
+    // png stores 16 bit samples as big endian
+    let options = DecoderOptions::default().set_byte_endian(ByteEndian::BE);
+
+    let mut decoder = PngDecoder::new_with_options(data, options);
+
+    let pixels = decoder.decode_raw()?;
+
+    let (width, height) = decoder.get_dimensions().unwrap();
+    let colorspace = decoder.get_colorspace().unwrap();
+    let depth = decoder.get_depth().unwrap();
+
+    let pixel_size = colorspace.num_components() * depth.size_of();
+
+    let (out_width, out_height) = match angle
+    {
+        RotationAngle::Rotate180 => (width, height),
+        RotationAngle::Rotate90 | RotationAngle::Rotate270 => (height, width)
+    };
+
+    let mut rotated = vec![0; pixels.len()];
+
+    for (y, out_row) in rotated.chunks_exact_mut(out_width * pixel_size).enumerate()
+    {
+        for (x, out_px) in out_row.chunks_exact_mut(pixel_size).enumerate()
+        {
+            let (src_x, src_y) = match angle
+            {
+                RotationAngle::Rotate90 => (y, height - 1 - x),
+                RotationAngle::Rotate180 => (width - 1 - x, height - 1 - y),
+                RotationAngle::Rotate270 => (width - 1 - y, x)
+            };
+            let start = (src_y * width + src_x) * pixel_size;
+
+            out_px.copy_from_slice(&pixels[start..start + pixel_size]);
+        }
+    }
+
+    let options = EncoderOptions::default()
+        .set_width(out_width)
+        .set_height(out_height)
+        .set_colorspace(colorspace)
+        .set_depth(depth);
+
+    let rotated = if depth == BitDepth::Sixteen
+    {
+        // the encoder expects native endian samples
+        rotated
+            .chunks_exact(2)
+            .flat_map(|x| u16::from_be_bytes([x[0], x[1]]).to_ne_bytes())
+            .collect()
+    }
+    else
+    {
+        rotated
+    };
+    let mut encoder = PngEncoder::new(&rotated, options);
+
+    for (name, chunk_data) in iterate_chunks(data)
+    {
+        if PRESERVED_CHUNKS.contains(&name)
+        {
+            encoder.add_raw_chunk(*name, chunk_data);
+        }
+    }
+    encoder
+        .encode()
+        .map_err(|err| PngDecodeErrors::Generic(format!("{err:?}")))
+}
+
+/// Iterate over the name and data of each chunk in a png file
+///
+/// Iteration stops at the first chunk that doesn't fit in the file
+fn iterate_chunks(data: &[u8]) -> impl Iterator<Item = (&[u8; 4], &[u8])>
+{
+    let mut position = 8;
+
+    if data.len() < 8 || data[..8] != PNG_SIGNATURE.to_be_bytes()
+    {
+        position = data.len();
+    }
+
+    core::iter::from_fn(move || {
+        let header = data.get(position..position + 8)?;
+
+        let length = u32::from_be_bytes(header[0..4].try_into().unwrap()) as usize;
+        let name: &[u8; 4] = header[4..8].try_into().unwrap();
+        let chunk_data = data.get(position + 8..position + 8 + length)?;
+
+        // length + name + data + crc
+        position += length + 12;
+
+        Some((name, chunk_data))
+    })
+}
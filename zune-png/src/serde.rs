@@ -0,0 +1,67 @@
+#![cfg(feature = "serde")]
+//! Serde support for serializing
+//! crate datastructures
+//!
+//! Implements serialize for
+//!  - ChunkReport
+//!  - IntegrityReport
+use alloc::string::String;
+
+use serde::ser::*;
+
+use crate::report::{ChunkReport, IntegrityReport};
+
+impl Serialize for ChunkReport
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        const STRUCT_FIELDS: usize = 5;
+        let mut state = serializer.serialize_struct("ChunkReport", STRUCT_FIELDS)?;
+
+        state.serialize_field("name", self.name.as_str())?;
+        state.serialize_field("length", &self.length)?;
+        state.serialize_field("crc", &self.crc)?;
+        state.serialize_field("crc_valid", &self.crc_valid)?;
+        state.serialize_field("critical", &self.critical)?;
+
+        state.end()
+    }
+}
+
+impl Serialize for IntegrityReport
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        const STRUCT_FIELDS: usize = 3;
+        let mut state = serializer.serialize_struct("IntegrityReport", STRUCT_FIELDS)?;
+
+        state.serialize_field("all_crcs_valid", &self.all_crcs_valid)?;
+        state.serialize_field("adler32_valid", &self.adler32_valid)?;
+        state.serialize_field("bad_chunks", &BadChunks(&self.bad_chunks))?;
+
+        state.end()
+    }
+}
+
+/// Serialize chunk names without requiring serde's `alloc` feature
+struct BadChunks<'a>(&'a [String]);
+
+impl Serialize for BadChunks<'_>
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+
+        for name in self.0
+        {
+            seq.serialize_element(name.as_str())?;
+        }
+        seq.end()
+    }
+}
@@ -1,7 +1,9 @@
-use crate::decoder::PLTEEntry;
+use crate::decoder::PaletteEntry;
 use crate::enums::PngColor;
 
-pub(crate) fn expand_palette(input: &[u8], out: &mut [u8], palette: &[PLTEEntry], components: usize)
+pub(crate) fn expand_palette(
+    input: &[u8], out: &mut [u8], palette: &[PaletteEntry], components: usize
+)
 {
     if components == 0
     {
@@ -282,3 +284,47 @@ pub(crate) fn expand_bits_to_byte(
         }
     }
 }
+
+/// Scatter pixels of a packed Adam7 pass into their positions in
+/// the packed output image
+///
+/// Samples are `depth` bits wide with the leftmost pixel in the high order bits
+/// and each row starting on a byte boundary, for both `pass` and `out`
+///
+/// # Arguments
+/// - pass: De-filtered pass data
+/// - out: Output image
+/// - pass_dims: Width and height of the pass
+/// - origin: Position of the first pass pixel in the image
+/// - spacing: Distance between pass pixels in the image
+/// - width: Image width
+/// - depth: Bit depth, 1, 2 or 4
+pub(crate) fn copy_packed_pass(
+    pass: &[u8], out: &mut [u8], pass_dims: (usize, usize), origin: (usize, usize),
+    spacing: (usize, usize), width: usize, depth: usize
+)
+{
+    let mask = (1_u8 << depth) - 1;
+    let pass_stride = (pass_dims.0 * depth).div_ceil(8);
+    let out_stride = (width * depth).div_ceil(8);
+
+    for j in 0..pass_dims.1
+    {
+        let out_y = j * spacing.1 + origin.1;
+
+        for i in 0..pass_dims.0
+        {
+            let out_x = i * spacing.0 + origin.0;
+
+            let in_bit = i * depth;
+            let in_shift = 8 - depth - (in_bit % 8);
+            let value = (pass[j * pass_stride + in_bit / 8] >> in_shift) & mask;
+
+            let out_bit = out_x * depth;
+            let out_shift = 8 - depth - (out_bit % 8);
+            let out_byte = &mut out[out_y * out_stride + out_bit / 8];
+
+            *out_byte = (*out_byte & !(mask << out_shift)) | (value << out_shift);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Low level routines for writing png chunks
+#![cfg(feature = "crc")]
+
+use alloc::vec::Vec;
+
+use crate::constants::PNG_SIGNATURE;
+use crate::crc::crc32_slice8;
+
+/// Maximum number of bytes written to a single IDAT chunk
+const MAX_IDAT_SIZE: usize = 1 << 20;
+
+/// Write the png signature
+pub(crate) fn write_signature(out: &mut Vec<u8>)
+{
+    out.extend_from_slice(&PNG_SIGNATURE.to_be_bytes());
+}
+
+/// Write a chunk with the given name and data, calculating its crc
+pub(crate) fn write_chunk(out: &mut Vec<u8>, name: &[u8; 4], data: &[u8])
+{
+    let length = u32::try_from(data.len()).expect("Chunk data too large");
+
+    out.extend_from_slice(&length.to_be_bytes());
+
+    let start = out.len();
+
+    out.extend_from_slice(name);
+    out.extend_from_slice(data);
+
+    let crc = !crc32_slice8(&out[start..], u32::MAX);
+
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Write the IHDR chunk
+pub(crate) fn write_ihdr(
+    out: &mut Vec<u8>, width: usize, height: usize, depth: u8, color: u8, interlaced: bool
+)
+{
+    let mut ihdr = [0; 13];
+
+    ihdr[0..4].copy_from_slice(&(width as u32).to_be_bytes());
+    ihdr[4..8].copy_from_slice(&(height as u32).to_be_bytes());
+    ihdr[8] = depth;
+    ihdr[9] = color;
+    // compression and filter methods are always zero
+    ihdr[12] = u8::from(interlaced);
+
+    write_chunk(out, b"IHDR", &ihdr);
+}
+
+/// Write a zlib stream of compressed scanlines into one or more IDAT chunks
+pub(crate) fn write_idat(out: &mut Vec<u8>, zlib_data: &[u8])
+{
+    for chunk in zlib_data.chunks(MAX_IDAT_SIZE)
+    {
+        write_chunk(out, b"IDAT", chunk);
+    }
+}
@@ -0,0 +1,46 @@
+//! Helpers for building and taking apart png files in tests
+#![allow(dead_code)]
+
+use zune_inflate::checksums::crc32;
+
+/// Create a chunk with its length, chunk type, data and crc
+pub fn chunk(name: &[u8; 4], data: &[u8]) -> Vec<u8>
+{
+    let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(name);
+    chunk.extend_from_slice(data);
+
+    let crc = crc32(&chunk[4..]);
+    chunk.extend_from_slice(&crc.to_be_bytes());
+    chunk
+}
+
+/// Split a png into its chunks, each chunk includes the length,
+/// chunk type, data and crc
+pub fn split_chunks(data: &[u8]) -> Vec<&[u8]>
+{
+    let mut chunks = vec![];
+    let mut position = 8;
+
+    while position + 12 <= data.len()
+    {
+        let length = u32::from_be_bytes(data[position..position + 4].try_into().unwrap()) as usize;
+        let end = position + length + 12;
+
+        chunks.push(&data[position..end]);
+        position = end;
+    }
+    chunks
+}
+
+/// Insert a chunk immediately after the IHDR chunk
+pub fn insert_chunk(png: &[u8], name: &[u8; 4], data: &[u8]) -> Vec<u8>
+{
+    // signature + IHDR chunk (length, type, 13 byte data and crc)
+    let ihdr_end = 8 + 4 + 4 + 13 + 4;
+
+    let mut out = png[..ihdr_end].to_vec();
+    out.extend_from_slice(&chunk(name, data));
+    out.extend_from_slice(&png[ihdr_end..]);
+    out
+}
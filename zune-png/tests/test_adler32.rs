@@ -0,0 +1,86 @@
+//! Tests for reporting the adler32 checksum of the image data
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn encode_gray(width: u32, height: u32) -> Vec<u8>
+{
+    let pixels: Vec<u8> = (0..width * height).map(|x| (x % 251) as u8).collect();
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    writer.finish().unwrap();
+
+    out
+}
+
+/// Return the position of the adler32 checksum stored at the end of the
+/// image data, assuming it's in a single IDAT chunk
+fn stored_adler_position(data: &[u8]) -> usize
+{
+    let position = data.windows(4).position(|x| x == b"IDAT").unwrap();
+    let length = u32::from_be_bytes(data[position - 4..position].try_into().unwrap()) as usize;
+
+    position + 4 + length - 4
+}
+
+fn stored_adler(data: &[u8]) -> u32
+{
+    let position = stored_adler_position(data);
+
+    u32::from_be_bytes(data[position..position + 4].try_into().unwrap())
+}
+
+#[test]
+fn test_adler32_matches_stored_checksum()
+{
+    let data = encode_gray(40, 30);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.last_adler32(), None);
+
+    decoder.decode_raw().unwrap();
+
+    assert_eq!(decoder.last_adler32(), Some(stored_adler(&data)));
+}
+
+#[test]
+fn test_adler32_none_without_confirmation()
+{
+    let data = encode_gray(40, 30);
+
+    let options = DecoderOptions::default().inflate_set_confirm_adler(false);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    decoder.decode_raw().unwrap();
+
+    assert_eq!(decoder.last_adler32(), None);
+}
+
+#[test]
+fn test_adler32_reports_computed_value_on_mismatch()
+{
+    let data = encode_gray(40, 30);
+    let expected = stored_adler(&data);
+
+    let mut corrupt = data.clone();
+    let position = stored_adler_position(&corrupt);
+    corrupt[position] ^= 0xFF;
+
+    let options = DecoderOptions::default()
+        .png_set_confirm_crc(false)
+        .png_set_allow_truncated(true);
+    let mut decoder = PngDecoder::new_with_options(&corrupt, options);
+
+    decoder.decode_raw().unwrap();
+
+    // the checksum of the data, not the corrupt one in the file
+    assert_eq!(decoder.last_adler32(), Some(expected));
+    assert_ne!(stored_adler(&corrupt), expected);
+}
@@ -0,0 +1,91 @@
+use zune_core::options::DecoderOptions;
+use zune_png::error::PngDecodeErrors;
+use zune_png::PngDecoder;
+
+/// Create a 4x4 RGBA png and patch the IHDR chunk to declare the given dimensions
+fn create_png_with_dimensions(width: u32, height: u32) -> Vec<u8>
+{
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, 4, 4);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0; 4 * 4 * 4]).unwrap();
+    }
+    // signature(8) + length(4) + chunk type(4)
+    out[16..20].copy_from_slice(&width.to_be_bytes());
+    out[20..24].copy_from_slice(&height.to_be_bytes());
+
+    out
+}
+
+#[test]
+fn test_try_decode_raw_huge_dimensions()
+{
+    // declares a 2^30 x 2^30 RGBA image, needing 4 EiB of memory
+    let data = create_png_with_dimensions(1 << 30, 1 << 30);
+
+    let options = DecoderOptions::default()
+        .set_max_width(usize::MAX)
+        .set_max_height(usize::MAX)
+        .set_max_pixels(usize::MAX)
+        .png_set_confirm_crc(false);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    match decoder.try_decode_raw()
+    {
+        Err(PngDecodeErrors::AllocationFailed { bytes }) => assert_eq!(bytes, 1 << 62),
+        Err(err) => panic!("Expected allocation failure but got {err:?}"),
+        Ok(_) => panic!("Expected allocation failure but decoding succeeded")
+    }
+}
+
+#[test]
+fn test_try_decode_raw_matches_decode_raw()
+{
+    let data = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/png_suite/basn6a16.png")
+    )
+    .unwrap();
+
+    let expected = PngDecoder::new(&data).decode_raw().unwrap();
+    let found = PngDecoder::new(&data).try_decode_raw().unwrap();
+
+    assert_eq!(expected, found);
+}
+
+#[test]
+fn test_max_pixels_default_rejects_huge_images()
+{
+    // within the default maximum width and height, but over the pixel limit
+    let data = create_png_with_dimensions(16000, 16000);
+    let options = DecoderOptions::default().png_set_confirm_crc(false);
+
+    assert!(PngDecoder::new_with_options(&data, options)
+        .decode_headers()
+        .is_err());
+}
+
+#[test]
+fn test_max_pixels_configurable()
+{
+    let data = std::fs::read(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/png_suite/basn6a16.png")
+    )
+    .unwrap();
+    // image is 32x32
+    let options = DecoderOptions::default().set_max_pixels(32 * 32 - 1);
+
+    assert!(PngDecoder::new_with_options(&data, options)
+        .decode_headers()
+        .is_err());
+
+    let options = DecoderOptions::default().set_max_pixels(32 * 32);
+
+    assert!(PngDecoder::new_with_options(&data, options)
+        .decode_headers()
+        .is_ok());
+}
@@ -0,0 +1,633 @@
+//! Tests for ancillary chunks which carry image metadata
+use zune_core::options::DecoderOptions;
+use zune_inflate::checksums::adler32;
+use zune_png::error::PngDecodeErrors;
+use zune_png::{PngDecoder, SuggestedPaletteEntry};
+
+mod common;
+
+use common::insert_chunk;
+
+/// Create a png with the given colour type and depth whose pixels are all zero
+fn create_png(color: png::ColorType, depth: png::BitDepth) -> Vec<u8>
+{
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, 4, 4);
+        encoder.set_color(color);
+        encoder.set_depth(depth);
+
+        let mut writer = encoder.write_header().unwrap();
+        let size = 4 * 4 * color.samples() * if depth == png::BitDepth::Sixteen { 2 } else { 1 };
+        writer.write_image_data(&vec![0; size]).unwrap();
+    }
+    out
+}
+
+#[test]
+fn test_sbit_rgb16()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Sixteen);
+    let data = insert_chunk(&png, b"sBIT", &[12, 12, 12]);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_info().unwrap().sbit, Some([12, 12, 12, 0]));
+}
+
+#[test]
+fn test_sbit_invalid()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    // larger than the bit depth
+    let data = insert_chunk(&png, b"sBIT", &[12, 8, 8]);
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    // permissive mode ignores the chunk
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_info().unwrap().sbit, None);
+}
+
+#[test]
+fn test_bkgd()
+{
+    use zune_png::BackgroundColor;
+
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Sixteen);
+    let data = insert_chunk(&png, b"bKGD", &[0xFF, 0xFE, 0x00, 0x01, 0x80, 0x00]);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_info().unwrap().background,
+        Some(BackgroundColor::Rgb(0xFFFE, 0x0001, 0x8000))
+    );
+
+    let png = create_png(png::ColorType::Grayscale, png::BitDepth::Eight);
+    let data = insert_chunk(&png, b"bKGD", &[0x00, 0x7F]);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_info().unwrap().background,
+        Some(BackgroundColor::Gray(0x7F))
+    );
+}
+
+#[test]
+fn test_chrm()
+{
+    // sRGB primaries and D65 white point
+    let values: [u32; 8] = [31270, 32900, 64000, 33000, 30000, 60000, 15000, 6000];
+    let chrm: Vec<u8> = values.iter().flat_map(|x| x.to_be_bytes()).collect();
+
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let data = insert_chunk(&png, b"cHRM", &chrm);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    let chromaticities = decoder.get_info().unwrap().chromaticities.unwrap();
+
+    assert!((chromaticities.white_x - 0.3127).abs() < 1e-6);
+    assert!((chromaticities.white_y - 0.3290).abs() < 1e-6);
+    assert!((chromaticities.red_x - 0.64).abs() < 1e-6);
+    assert!((chromaticities.blue_y - 0.06).abs() < 1e-6);
+
+    // wrong length
+    let data = insert_chunk(&png, b"cHRM", &chrm[..28]);
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    assert!(decoder.get_info().unwrap().chromaticities.is_none());
+}
+
+#[test]
+fn test_srgb_intent()
+{
+    use zune_png::RenderingIntent;
+
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let data = insert_chunk(&png, b"sRGB", &[2]);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_info().unwrap().srgb_intent,
+        Some(RenderingIntent::Saturation)
+    );
+
+    // out of range intent
+    let data = insert_chunk(&png, b"sRGB", &[4]);
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_info().unwrap().srgb_intent, None);
+}
+
+#[test]
+fn test_phys()
+{
+    use zune_png::{PhysicalDimensions, PhysicalUnit};
+
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    // 300 dpi is 11811 pixels per meter
+    let mut phys = Vec::new();
+    phys.extend_from_slice(&11811_u32.to_be_bytes());
+    phys.extend_from_slice(&5906_u32.to_be_bytes());
+    phys.push(1);
+
+    let data = insert_chunk(&png, b"pHYs", &phys);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_info().unwrap().pixel_dims,
+        Some(PhysicalDimensions {
+            x_pixels_per_unit: 11811,
+            y_pixels_per_unit: 5906,
+            unit:              PhysicalUnit::Meter
+        })
+    );
+
+    // wrong length
+    let data = insert_chunk(&png, b"pHYs", &phys[..8]);
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_info().unwrap().pixel_dims, None);
+}
+
+/// Wrap `data` in a zlib stream using a single stored (uncompressed) block
+fn zlib_stored(data: &[u8]) -> Vec<u8>
+{
+    let length = data.len() as u16;
+
+    let mut out = vec![0x78, 0x01, 0x01];
+    out.extend_from_slice(&length.to_le_bytes());
+    out.extend_from_slice(&(!length).to_le_bytes());
+    out.extend_from_slice(data);
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Build the contents of an iTXt chunk
+fn itxt(compression_flag: u8, compression_method: u8, text: &[u8]) -> Vec<u8>
+{
+    let mut itxt = b"Title\0".to_vec();
+    itxt.extend_from_slice(&[compression_flag, compression_method]);
+    itxt.extend_from_slice(b"en-GB\0Heading\0");
+    itxt.extend_from_slice(text);
+    itxt
+}
+
+#[test]
+fn test_itxt_uncompressed()
+{
+    let data =
+        std::fs::read(env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/ctjn0g04.png")
+            .unwrap();
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    let chunks = &decoder.get_info().unwrap().itxt_chunk;
+    let title = chunks.iter().find(|x| x.keyword == b"Title").unwrap();
+
+    assert_eq!(title.language_tag, b"ja");
+    assert_eq!(title.translated_keyword, "タイトル".as_bytes());
+    assert_eq!(title.text, b"PngSuite");
+}
+
+#[test]
+fn test_itxt_compressed()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let text = "Ünïcödé text".as_bytes();
+
+    let data = insert_chunk(&png, b"iTXt", &itxt(1, 0, &zlib_stored(text)));
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    let chunks = &decoder.get_info().unwrap().itxt_chunk;
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].keyword, b"Title");
+    assert_eq!(chunks[0].language_tag, b"en-GB");
+    assert_eq!(chunks[0].translated_keyword, b"Heading");
+    assert_eq!(chunks[0].text, text);
+}
+
+#[test]
+fn test_itxt_unknown_compression_method()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let data = insert_chunk(&png, b"iTXt", &itxt(1, 3, b"unknown"));
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    assert!(decoder.get_info().unwrap().itxt_chunk.is_empty());
+}
+
+#[test]
+fn test_text_latin1_to_utf8()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+
+    // "Café" and "© 2023 Zoë" in Latin-1
+    let latin1_keyword = b"Caf\xE9";
+    let latin1_text = b"\xA9 2023 Zo\xEB";
+
+    let mut text = latin1_keyword.to_vec();
+    text.push(0);
+    text.extend_from_slice(latin1_text);
+
+    let mut ztxt = latin1_keyword.to_vec();
+    ztxt.extend_from_slice(&[0, 0]);
+    ztxt.extend_from_slice(&zlib_stored(latin1_text));
+
+    let data = insert_chunk(&png, b"tEXt", &text);
+    let data = insert_chunk(&data, b"zTXt", &ztxt);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    let info = decoder.get_info().unwrap();
+
+    assert_eq!(info.text_chunk.len(), 1);
+    assert_eq!(info.text_chunk[0].keyword, latin1_keyword);
+    assert_eq!(info.text_chunk[0].keyword_utf8(), "Café");
+    assert_eq!(info.text_chunk[0].text_utf8(), "© 2023 Zoë");
+
+    assert_eq!(info.ztxt_chunk.len(), 1);
+    assert_eq!(info.ztxt_chunk[0].keyword_utf8(), "Café");
+    assert_eq!(info.ztxt_chunk[0].text_utf8(), "© 2023 Zoë");
+}
+
+#[test]
+fn test_unknown_critical_chunk()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+
+    // uppercase first letter, critical
+    let data = insert_chunk(&png, b"VnDr", b"must understand");
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    let err = PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .unwrap_err();
+
+    assert!(
+        matches!(err, PngDecodeErrors::UnknownCriticalChunk(name) if &name == b"VnDr"),
+        "{err:?}"
+    );
+
+    // lowercase first letter, ancillary chunks are skipped
+    let data = insert_chunk(&png, b"vnDr", b"safe to ignore");
+
+    let mut decoder = PngDecoder::new_with_options(&data, strict);
+    decoder.decode_headers().unwrap();
+    assert_eq!(decoder.decode_raw().unwrap(), vec![0; 4 * 4 * 3]);
+}
+
+#[test]
+fn test_splt()
+{
+    for (name, depth) in [("ps1n0g08.png", 8), ("ps2n0g08.png", 16)]
+    {
+        let data =
+            std::fs::read(env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name)
+                .unwrap();
+
+        let mut decoder = PngDecoder::new(&data);
+        decoder.decode_headers().unwrap();
+
+        let palettes = &decoder.get_info().unwrap().suggested_palettes;
+        assert_eq!(palettes.len(), 1);
+
+        let palette = &palettes[0];
+        assert_eq!(palette.name, b"six-cube");
+        assert_eq!(palette.name_utf8(), "six-cube");
+        assert_eq!(palette.sample_depth, depth);
+
+        // a 6x6x6 colour cube
+        assert_eq!(palette.entries.len(), 216);
+
+        for entry in &palette.entries
+        {
+            for sample in [entry.red, entry.green, entry.blue]
+            {
+                assert_eq!(sample % 0x33, 0, "{name}: {entry:?}");
+            }
+        }
+        assert_eq!(
+            palette.entries[1],
+            SuggestedPaletteEntry {
+                red:       0,
+                green:     0,
+                blue:      0x33,
+                alpha:     0xff,
+                frequency: 0
+            }
+        );
+    }
+}
+
+#[test]
+fn test_splt_invalid_entry_stride()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+
+    // a single 8 bit entry, which is 6 bytes, declared as 16 bit
+    let mut splt = b"palette\0".to_vec();
+    splt.push(16);
+    splt.extend_from_slice(&[10, 20, 30, 255, 0, 1]);
+
+    let data = insert_chunk(&png, b"sPLT", &splt);
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    assert!(decoder.get_info().unwrap().suggested_palettes.is_empty());
+
+    // declared correctly it parses
+    splt[8] = 8;
+    let data = insert_chunk(&png, b"sPLT", &splt);
+
+    let mut decoder = PngDecoder::new_with_options(&data, strict);
+    decoder.decode_headers().unwrap();
+
+    let palette = &decoder.get_info().unwrap().suggested_palettes[0];
+    assert_eq!(
+        palette.entries,
+        [SuggestedPaletteEntry {
+            red:       10,
+            green:     20,
+            blue:      30,
+            alpha:     255,
+            frequency: 1
+        }]
+    );
+}
+
+#[test]
+fn test_hist()
+{
+    for (name, entries) in [("ch1n3p04.png", 15), ("ch2n3p08.png", 256)]
+    {
+        let data =
+            std::fs::read(env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name)
+                .unwrap();
+
+        let mut decoder = PngDecoder::new(&data);
+        decoder.decode_headers().unwrap();
+
+        let palette_len = decoder.get_palette().unwrap().len();
+        let histogram = decoder.get_info().unwrap().histogram.clone().unwrap();
+
+        assert_eq!(palette_len, entries, "{name}");
+        assert_eq!(histogram.len(), palette_len, "{name}");
+        assert!(histogram.iter().any(|x| *x != 0), "{name}");
+    }
+}
+
+#[test]
+fn test_hist_invalid()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+
+    // hIST without a PLTE chunk
+    let data = insert_chunk(&png, b"hIST", &[0, 1, 0, 2]);
+
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+    assert!(decoder.get_info().unwrap().histogram.is_none());
+
+    // a suggested palette with two entries, chunks are inserted after IHDR so
+    // inserting PLTE second places it before hIST
+    let plte = [255, 0, 0, 0, 0, 255];
+
+    // three entries for a two entry palette
+    let data = insert_chunk(
+        &insert_chunk(&png, b"hIST", &[0, 1, 0, 2, 0, 3]),
+        b"PLTE",
+        &plte
+    );
+
+    assert!(PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .is_err());
+
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+    assert!(decoder.get_info().unwrap().histogram.is_none());
+
+    // matching lengths parse
+    let data = insert_chunk(&insert_chunk(&png, b"hIST", &[0, 1, 0, 2]), b"PLTE", &plte);
+
+    let mut decoder = PngDecoder::new_with_options(&data, strict);
+    decoder.decode_headers().unwrap();
+    assert_eq!(decoder.get_info().unwrap().histogram, Some(vec![1, 2]));
+}
+
+#[test]
+fn test_keep_unknown_chunks()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let data = insert_chunk(&insert_chunk(&png, b"caBx", b"second"), b"orNT", &[1]);
+
+    // not retained by default
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+    assert!(decoder.get_unknown_chunks().is_empty());
+
+    let options = DecoderOptions::default().png_set_keep_unknown_chunks(true);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_unknown_chunks(),
+        &[
+            (u32::from_be_bytes(*b"orNT"), vec![1]),
+            (u32::from_be_bytes(*b"caBx"), b"second".to_vec())
+        ]
+    );
+    assert_eq!(decoder.decode_raw().unwrap(), vec![0; 4 * 4 * 3]);
+
+    // chunks that would exceed the limit are skipped
+    let options = options.png_set_unknown_chunks_limit(4);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_unknown_chunks(),
+        &[(u32::from_be_bytes(*b"orNT"), vec![1])]
+    );
+}
+
+#[test]
+fn test_keep_unknown_chunks_ignores_critical()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+    let data = insert_chunk(&png, b"VnDr", b"must understand");
+
+    let options = DecoderOptions::default()
+        .set_strict_mode(false)
+        .png_set_keep_unknown_chunks(true);
+
+    // the default chunk handler still rejects the chunk, but it must
+    // not be retained before that
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    assert!(decoder.decode_headers().is_err());
+    assert!(decoder.get_unknown_chunks().is_empty());
+}
+
+#[test]
+fn test_duplicate_gama()
+{
+    let png = create_png(png::ColorType::Rgb, png::BitDepth::Eight);
+
+    // chunks are inserted after IHDR, so the second insert appears first
+    let data = insert_chunk(
+        &insert_chunk(&png, b"gAMA", &100_000_u32.to_be_bytes()),
+        b"gAMA",
+        &45_455_u32.to_be_bytes()
+    );
+
+    let strict = DecoderOptions::default().set_strict_mode(true);
+    let err = PngDecoder::new_with_options(&data, strict)
+        .decode_headers()
+        .unwrap_err();
+
+    assert!(
+        matches!(err, PngDecodeErrors::DuplicateChunk(name) if &name == b"gAMA"),
+        "{err:?}"
+    );
+
+    let permissive = DecoderOptions::default().set_strict_mode(false);
+
+    let mut decoder = PngDecoder::new_with_options(&data, permissive);
+    decoder.decode_headers().unwrap();
+
+    let gamma = decoder.get_info().unwrap().gamma.unwrap();
+    assert!((gamma - 0.45455).abs() < 1e-6, "{gamma}");
+    assert_eq!(decoder.decode_raw().unwrap(), vec![0; 4 * 4 * 3]);
+}
+
+#[test]
+fn test_skip_ancillary()
+{
+    let pixels: Vec<u8> = (0..4 * 4 * 3).map(|x| (x * 5) as u8).collect();
+
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, 4, 4);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .add_text_chunk("Title".to_string(), "A title".to_string())
+            .unwrap();
+        encoder
+            .add_ztxt_chunk("Comment".to_string(), "A comment".repeat(50))
+            .unwrap();
+        encoder
+            .add_itxt_chunk("Author".to_string(), "Ünïcödé".to_string())
+            .unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels).unwrap();
+    }
+    let mut phys = Vec::new();
+    phys.extend_from_slice(&11811_u32.to_be_bytes());
+    phys.extend_from_slice(&11811_u32.to_be_bytes());
+    phys.push(1);
+
+    let png = insert_chunk(&png, b"pHYs", &phys);
+    let png = insert_chunk(&png, b"eXIf", b"MM\0\x2a\0\0\0\x08");
+    let mut iccp = b"profile\0\0".to_vec();
+    iccp.extend_from_slice(&zlib_stored(b"not really a profile"));
+    let png = insert_chunk(&png, b"iCCP", &iccp);
+
+    // sanity check, all chunks are parsed by default
+    let mut decoder = PngDecoder::new(&png);
+    decoder.decode_headers().unwrap();
+    {
+        let info = decoder.get_info().unwrap();
+
+        assert_eq!(info.text_chunk.len(), 1);
+        assert_eq!(info.ztxt_chunk.len(), 1);
+        assert_eq!(info.itxt_chunk.len(), 1);
+        assert!(info.pixel_dims.is_some());
+        assert!(info.exif.is_some());
+        assert!(info.icc_profile.is_some());
+    }
+
+    let options = DecoderOptions::default().png_set_skip_ancillary(true);
+    let mut decoder = PngDecoder::new_with_options(&png, options);
+
+    assert_eq!(decoder.decode_raw().unwrap(), pixels);
+
+    let info = decoder.get_info().unwrap();
+
+    assert!(info.text_chunk.is_empty());
+    assert!(info.ztxt_chunk.is_empty());
+    assert!(info.itxt_chunk.is_empty());
+    assert_eq!(info.pixel_dims, None);
+    assert_eq!(info.exif, None);
+    assert_eq!(info.icc_profile, None);
+}
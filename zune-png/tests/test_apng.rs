@@ -0,0 +1,184 @@
+use png::{BlendOp, DisposeOp};
+use zune_png::PngDecoder;
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+const BLUE: [u8; 4] = [0, 0, 255, 255];
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+fn solid(color: [u8; 4], width: usize, height: usize) -> Vec<u8>
+{
+    color.repeat(width * height)
+}
+
+fn pixel_at(pixels: &[u8], width: usize, x: usize, y: usize) -> [u8; 4]
+{
+    let start = (y * width + x) * 4;
+    pixels[start..start + 4].try_into().unwrap()
+}
+
+/// Create a 4x4 RGBA animation with three frames
+///
+/// - frame 0: whole canvas red, stored in IDAT
+/// - frame 1: 2x2 half transparent green at (1,1) blended over, disposed to background
+/// - frame 2: 1x1 blue at (0,0), disposed to previous
+fn create_animation() -> Vec<u8>
+{
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, 4, 4);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(3, 0).unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+
+        writer.set_frame_delay(1, 10).unwrap();
+        writer.write_image_data(&solid(RED, 4, 4)).unwrap();
+
+        writer.set_frame_delay(2, 10).unwrap();
+        writer.set_frame_dimension(2, 2).unwrap();
+        writer.set_frame_position(1, 1).unwrap();
+        writer.set_blend_op(BlendOp::Over).unwrap();
+        writer.set_dispose_op(DisposeOp::Background).unwrap();
+        writer
+            .write_image_data(&solid([0, 255, 0, 128], 2, 2))
+            .unwrap();
+
+        writer.set_frame_delay(3, 10).unwrap();
+        writer.set_frame_position(0, 0).unwrap();
+        writer.set_frame_dimension(1, 1).unwrap();
+        writer.set_blend_op(BlendOp::Source).unwrap();
+        writer.set_dispose_op(DisposeOp::Previous).unwrap();
+        writer.write_image_data(&BLUE).unwrap();
+
+        writer.finish().unwrap();
+    }
+    out
+}
+
+#[test]
+fn test_apng_frames()
+{
+    let data = create_animation();
+    let mut decoder = PngDecoder::new(&data);
+    let frames = decoder.decode_apng().unwrap();
+
+    assert_eq!(decoder.num_frames(), Some(3));
+    assert_eq!(decoder.num_plays(), Some(0));
+    assert_eq!(frames.len(), 3);
+
+    for (i, frame) in frames.iter().enumerate()
+    {
+        assert_eq!(frame.info.delay_num, i as u16 + 1);
+        assert_eq!(frame.info.delay_denom, 10);
+        assert_eq!(frame.pixels.len(), 4 * 4 * 4);
+    }
+    assert_eq!(frames[0].pixels, solid(RED, 4, 4));
+
+    // green blended over red
+    let blended = [127, 128, 0, 255];
+    assert_eq!(pixel_at(&frames[1].pixels, 4, 0, 0), RED);
+    assert_eq!(pixel_at(&frames[1].pixels, 4, 1, 1), blended);
+    assert_eq!(pixel_at(&frames[1].pixels, 4, 2, 2), blended);
+    assert_eq!(pixel_at(&frames[1].pixels, 4, 3, 3), RED);
+
+    // frame 1 region was disposed to the background
+    assert_eq!(pixel_at(&frames[2].pixels, 4, 0, 0), BLUE);
+    assert_eq!(pixel_at(&frames[2].pixels, 4, 1, 1), CLEAR);
+    assert_eq!(pixel_at(&frames[2].pixels, 4, 2, 2), CLEAR);
+    assert_eq!(pixel_at(&frames[2].pixels, 4, 3, 3), RED);
+}
+
+#[test]
+fn test_apng_separate_default_image()
+{
+    let mut data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut data, 2, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(1, 3).unwrap();
+        encoder.set_sep_def_img(true).unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+        // default image, not part of the animation
+        writer.write_image_data(&solid(WHITE, 2, 2)).unwrap();
+        writer.write_image_data(&solid(BLUE, 2, 2)).unwrap();
+        writer.finish().unwrap();
+    }
+    let frames = PngDecoder::new(&data).decode_apng().unwrap();
+
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].pixels, solid(BLUE, 2, 2));
+
+    // normal decoding returns the default image
+    let default_image = PngDecoder::new(&data).decode_raw().unwrap();
+    assert_eq!(default_image, solid(WHITE, 2, 2));
+}
+
+#[test]
+fn test_non_animated_single_frame()
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/basn6a08.png";
+    let data = std::fs::read(path).unwrap();
+
+    let mut decoder = PngDecoder::new(&data);
+    let frames = decoder.decode_apng().unwrap();
+
+    assert_eq!(decoder.num_frames(), None);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(
+        frames[0].pixels,
+        PngDecoder::new(&data).decode_raw().unwrap()
+    );
+}
+
+#[test]
+fn test_apng_decode_blended_at()
+{
+    use std::time::Duration;
+
+    // red for 100ms then blue for 200ms, played twice
+    let mut data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut data, 2, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(2, 2).unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+
+        writer.set_frame_delay(1, 10).unwrap();
+        writer.write_image_data(&solid(RED, 2, 2)).unwrap();
+
+        writer.set_frame_delay(2, 10).unwrap();
+        writer.write_image_data(&solid(BLUE, 2, 2)).unwrap();
+
+        writer.finish().unwrap();
+    }
+    let frame_at = |millis: u64| {
+        PngDecoder::new(&data)
+            .decode_blended_at(Duration::from_millis(millis))
+            .unwrap()
+            .pixels
+    };
+
+    assert_eq!(frame_at(0), solid(RED, 2, 2));
+    assert_eq!(frame_at(99), solid(RED, 2, 2));
+    assert_eq!(frame_at(100), solid(BLUE, 2, 2));
+    assert_eq!(frame_at(299), solid(BLUE, 2, 2));
+    // second play
+    assert_eq!(frame_at(300), solid(RED, 2, 2));
+    assert_eq!(frame_at(450), solid(BLUE, 2, 2));
+    // animation is over, the last frame stays
+    assert_eq!(frame_at(600), solid(BLUE, 2, 2));
+    assert_eq!(frame_at(10_000), solid(BLUE, 2, 2));
+
+    // looping infinitely
+    let frame = PngDecoder::new(&data)
+        .decode_blended_at_with_plays(Duration::from_millis(9_050), 0)
+        .unwrap();
+    assert_eq!(frame.pixels, solid(RED, 2, 2));
+    assert_eq!(frame.info.delay(), Duration::from_millis(100));
+}
@@ -130,3 +130,43 @@ fn test_basn6a16()
 
     test_decoding(path);
 }
+
+#[test]
+fn test_decode_into_u16()
+{
+    use zune_core::bit_depth::ByteEndian;
+    use zune_core::options::DecoderOptions;
+
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/basn6a16.png";
+    let data = open_and_read(path);
+
+    // samples are in big endian, as in the file
+    let options = DecoderOptions::default().set_byte_endian(ByteEndian::BE);
+    let mut decoder = zune_png::PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    let mut out = vec![0_u16; decoder.output_buffer_size().unwrap() / 2];
+
+    // too small
+    assert!(decoder.decode_into_u16(&mut out[1..]).is_err());
+
+    decoder.decode_into_u16(&mut out).unwrap();
+    // the configured endianness is left untouched
+    assert_eq!(decoder.byte_endian(), ByteEndian::BE);
+
+    let expected: Vec<u16> = decode_ref(&data)
+        .chunks_exact(2)
+        .map(|x| u16::from_be_bytes([x[0], x[1]]))
+        .collect();
+
+    assert_eq!(out, expected);
+
+    // 8 bit images are rejected
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/basn2c08.png";
+    let data = open_and_read(path);
+
+    let mut out = vec![0_u16; 32 * 32 * 3];
+    assert!(zune_png::PngDecoder::new(&data)
+        .decode_into_u16(&mut out)
+        .is_err());
+}
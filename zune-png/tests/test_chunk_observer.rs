@@ -0,0 +1,98 @@
+//! Tests for observing every chunk while decoding headers
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+
+use zune_png::PngDecoder;
+
+mod common;
+
+use common::{insert_chunk, split_chunks};
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+type Chunk = ([u8; 4], Vec<u8>, u32);
+
+/// Split a png file into the name, data and crc of each chunk
+fn parse_chunks(data: &[u8]) -> Vec<Chunk>
+{
+    split_chunks(data)
+        .iter()
+        .map(|chunk| {
+            let (body, crc) = chunk[8..].split_at(chunk.len() - 12);
+
+            (
+                chunk[4..8].try_into().unwrap(),
+                body.to_vec(),
+                u32::from_be_bytes(crc.try_into().unwrap())
+            )
+        })
+        .collect()
+}
+
+fn observe(data: &[u8]) -> Vec<Chunk>
+{
+    let chunks = Arc::new(Mutex::new(Vec::new()));
+    let observed = chunks.clone();
+
+    let mut decoder = PngDecoder::new(data);
+    decoder.set_chunk_observer(move |name, body, crc| {
+        observed.lock().unwrap().push((*name, body.to_vec(), crc));
+    });
+    decoder.decode_headers().unwrap();
+
+    let chunks = chunks.lock().unwrap().clone();
+    chunks
+}
+
+#[test]
+fn test_observer_sees_every_chunk()
+{
+    // multiple IDAT chunks and text chunks
+    for name in ["oi4n2c16.png", "ct1n0g04.png", "ctjn0g04.png"]
+    {
+        let data = open_file(name);
+
+        assert_eq!(observe(&data), parse_chunks(&data), "{name}");
+    }
+}
+
+#[test]
+fn test_observer_sees_vendor_chunks()
+{
+    let data = open_file("basn0g08.png");
+
+    // insert a private caNv chunk after IHDR
+    let body = b"vendor data";
+    let modified = insert_chunk(&data, b"caNv", body);
+
+    let chunks = observe(&modified);
+
+    assert_eq!(&chunks[0].0, b"IHDR");
+    assert_eq!(chunks[1], parse_chunks(&modified)[1]);
+    assert_eq!(chunks[1].0, *b"caNv");
+    assert_eq!(chunks[1].1, body);
+}
+
+#[test]
+fn test_observer_does_not_need_sync()
+{
+    let data = open_file("ct1n0g04.png");
+
+    let count = Arc::new(Mutex::new(0));
+    let observed = count.clone();
+    // a Cell is Send but not Sync
+    let seen = Cell::new(0);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.set_chunk_observer(move |_, _, _| {
+        seen.set(seen.get() + 1);
+        *observed.lock().unwrap() = seen.get();
+    });
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(*count.lock().unwrap(), observe(&data).len());
+}
@@ -0,0 +1,199 @@
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+#[test]
+fn test_chunk_report_criticality()
+{
+    let data = open_file("basn0g01.png");
+    let mut decoder = PngDecoder::new(&data);
+    let report = decoder.chunk_report();
+
+    let names: Vec<&str> = report.iter().map(|x| x.name.as_str()).collect();
+
+    assert_eq!(names.first(), Some(&"IHDR"));
+    assert!(names.contains(&"IDAT"));
+    assert_eq!(names.last(), Some(&"IEND"));
+
+    for chunk in &report
+    {
+        let expected = matches!(chunk.name.as_str(), "IHDR" | "PLTE" | "IDAT" | "IEND");
+
+        assert_eq!(
+            chunk.critical, expected,
+            "Wrong criticality for {}",
+            chunk.name
+        );
+        assert!(chunk.crc_valid, "Bad crc for {}", chunk.name);
+    }
+    // png suite images carry a gAMA chunk, which is ancillary
+    assert!(report.iter().any(|x| x.name == "gAMA" && !x.critical));
+    assert_eq!(report[0].length, 13);
+    assert_eq!(report.last().unwrap().length, 0);
+
+    // the walk should not disturb decoding
+    assert!(decoder.decode_raw().is_ok());
+    assert_eq!(decoder.chunk_report(), report);
+}
+
+#[test]
+fn test_integrity_check_bad_ancillary_crc()
+{
+    let mut data = open_file("basn0g01.png");
+
+    // corrupt the CRC of the gAMA chunk
+    let report = PngDecoder::new(&data).chunk_report();
+    let mut position = 8;
+
+    for chunk in &report
+    {
+        if chunk.name == "gAMA"
+        {
+            data[position + 8 + chunk.length] ^= 0xFF;
+        }
+        position += chunk.length + 12;
+    }
+
+    let mut decoder = PngDecoder::new(&data);
+    let integrity = decoder.integrity_check();
+
+    assert!(!integrity.all_crcs_valid);
+    assert!(integrity.adler32_valid);
+    assert_eq!(integrity.bad_chunks, vec!["gAMA".to_string()]);
+
+    // an intact file passes
+    let data = open_file("basn0g01.png");
+    let integrity = PngDecoder::new(&data).integrity_check();
+
+    assert!(integrity.all_crcs_valid);
+    assert!(integrity.adler32_valid);
+    assert!(integrity.bad_chunks.is_empty());
+}
+
+#[test]
+fn test_chunk_summary_matches_chunk_report()
+{
+    use zune_core::options::DecoderOptions;
+    use zune_png::PngChunkType;
+
+    let mut data = open_file("basn0g01.png");
+
+    // not recorded by default
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+    assert!(decoder.chunk_summary().is_empty());
+
+    // corrupt the CRC of the gAMA chunk, with CRC checking disabled
+    // the stored CRC should still be recorded
+    let report = PngDecoder::new(&data).chunk_report();
+    let mut position = 8;
+
+    for chunk in &report
+    {
+        if chunk.name == "gAMA"
+        {
+            data[position + 8 + chunk.length] ^= 0xFF;
+            break;
+        }
+        position += chunk.length + 12;
+    }
+    let report = PngDecoder::new(&data).chunk_report();
+
+    let options = DecoderOptions::default()
+        .png_set_record_chunks(true)
+        .png_set_confirm_crc(false);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    let summary = decoder.chunk_summary();
+
+    assert_eq!(summary.len(), report.len());
+
+    for (chunk, reported) in summary.iter().zip(&report)
+    {
+        assert_eq!(chunk.name, reported.name.as_bytes());
+        assert_eq!(chunk.crc, reported.crc);
+        assert_eq!(chunk.length, reported.length);
+    }
+    assert_eq!(summary[0].chunk_type, PngChunkType::IHDR);
+    assert_eq!(summary.last().unwrap().chunk_type, PngChunkType::IEND);
+    assert!(summary.iter().any(|x| x.chunk_type == PngChunkType::gAMA));
+}
+
+#[test]
+fn test_chunk_ranges_reproduce_file()
+{
+    for name in [
+        "basn0g01.png",
+        "basn3p08.png",
+        "ct1n0g04.png",
+        "basi6a16.png"
+    ]
+    {
+        let data = open_file(name);
+        let mut decoder = PngDecoder::new(&data);
+        let ranges = decoder.chunk_ranges();
+
+        let names: Vec<&str> = ranges.iter().map(|(name, _)| name.as_str()).collect();
+        let report_names: Vec<String> = decoder
+            .chunk_report()
+            .into_iter()
+            .map(|chunk| chunk.name)
+            .collect();
+
+        assert_eq!(names, report_names, "{name}");
+
+        let mut file = data[..8].to_vec();
+
+        for (chunk, range) in &ranges
+        {
+            // length, chunk type and crc fields around the chunk data
+            let length = u32::from_be_bytes(data[range.start..range.start + 4].try_into().unwrap());
+
+            assert_eq!(range.len(), length as usize + 12, "{name}: {chunk}");
+            assert_eq!(&data[range.start + 4..range.start + 8], chunk.as_bytes());
+
+            file.extend_from_slice(&data[range.clone()]);
+        }
+        assert_eq!(file, data, "{name}");
+    }
+}
+
+#[test]
+fn test_chunk_ranges_strip_chunk()
+{
+    let mut data = open_file("ct1n0g04.png");
+    // trailing data is not part of any chunk
+    data.extend_from_slice(b"trailing");
+
+    let mut decoder = PngDecoder::new(&data);
+    let ranges = decoder.chunk_ranges();
+
+    let end = ranges.last().unwrap().1.end;
+    assert_eq!(&data[end..], b"trailing");
+
+    // drop all text chunks by splicing the input
+    let mut stripped = data[..8].to_vec();
+
+    for (chunk, range) in &ranges
+    {
+        if chunk != "tEXt"
+        {
+            stripped.extend_from_slice(&data[range.clone()]);
+        }
+    }
+    assert!(stripped.len() < end);
+
+    let mut decoder = PngDecoder::new(&stripped);
+
+    assert_eq!(
+        decoder.decode_raw().unwrap(),
+        PngDecoder::new(&data).decode_raw().unwrap()
+    );
+    assert!(decoder.get_info().unwrap().text_chunk.is_empty());
+}
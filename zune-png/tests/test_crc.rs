@@ -0,0 +1,133 @@
+//! Tests for handling chunks with mismatched CRCs
+#![cfg(feature = "crc")]
+
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+
+use zune_core::options::DecoderOptions;
+use zune_png::error::PngDecodeErrors;
+use zune_png::{PngChunkType, PngDecoder};
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Corrupt the stored CRC of the first chunk named `name`,
+/// returning the corrupted file and the original CRC
+fn corrupt_crc(mut data: Vec<u8>, name: &[u8; 4]) -> (Vec<u8>, u32)
+{
+    let mut position = 8;
+
+    loop
+    {
+        let length = u32::from_be_bytes(data[position..position + 4].try_into().unwrap()) as usize;
+        let crc_start = position + 8 + length;
+
+        if &data[position + 4..position + 8] == name
+        {
+            let crc = u32::from_be_bytes(data[crc_start..crc_start + 4].try_into().unwrap());
+            data[crc_start] ^= 0xFF;
+
+            return (data, crc);
+        }
+        position = crc_start + 4;
+    }
+}
+
+#[test]
+fn test_bad_crc_is_an_error_by_default()
+{
+    let (data, _) = corrupt_crc(open_file("ct1n0g04.png"), b"tEXt");
+
+    let err = PngDecoder::new(&data).decode_raw().unwrap_err();
+
+    assert!(matches!(err, PngDecodeErrors::BadCrc(..)), "{err:?}");
+}
+
+#[test]
+fn test_lenient_crc_calls_handler()
+{
+    let expected = PngDecoder::new(&open_file("ct1n0g04.png"))
+        .decode_raw()
+        .unwrap();
+    let (data, crc) = corrupt_crc(open_file("ct1n0g04.png"), b"tEXt");
+
+    let mismatches = Arc::new(Mutex::new(Vec::new()));
+
+    let options = DecoderOptions::default().png_set_lenient_crc(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    let handler_mismatches = Arc::clone(&mismatches);
+
+    decoder.set_crc_handler(move |chunk, stored, calculated| {
+        handler_mismatches
+            .lock()
+            .unwrap()
+            .push((chunk, stored, calculated));
+    });
+    let decoded = decoder.decode_raw().unwrap();
+
+    assert_eq!(decoded, expected);
+
+    let mismatches = mismatches.lock().unwrap();
+
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].0, PngChunkType::tEXt);
+    assert_eq!(mismatches[0].1, crc ^ 0xFF00_0000);
+    assert_eq!(mismatches[0].2, crc);
+}
+
+#[test]
+fn test_lenient_crc_critical_chunks_fail()
+{
+    for name in [b"IHDR", b"IDAT"]
+    {
+        let (data, _) = corrupt_crc(open_file("ct1n0g04.png"), name);
+
+        let options = DecoderOptions::default().png_set_lenient_crc(true);
+        let err = PngDecoder::new_with_options(&data, options)
+            .decode_raw()
+            .unwrap_err();
+
+        assert!(matches!(err, PngDecodeErrors::BadCrc(..)), "{err:?}");
+    }
+}
+
+#[test]
+fn test_strict_mode_disables_lenient_crc()
+{
+    let (data, _) = corrupt_crc(open_file("ct1n0g04.png"), b"tEXt");
+
+    let options = DecoderOptions::default()
+        .png_set_lenient_crc(true)
+        .set_strict_mode(true);
+
+    assert!(!options.png_get_lenient_crc());
+    assert!(PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .is_err());
+}
+
+#[test]
+fn test_crc_handler_does_not_need_sync()
+{
+    let (data, _) = corrupt_crc(open_file("ct1n0g04.png"), b"tEXt");
+
+    let calls = Arc::new(Mutex::new(0));
+    let handler_calls = Arc::clone(&calls);
+    // a Cell is Send but not Sync
+    let seen = Cell::new(0);
+
+    let options = DecoderOptions::default().png_set_lenient_crc(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    decoder.set_crc_handler(move |_, _, _| {
+        seen.set(seen.get() + 1);
+        *handler_calls.lock().unwrap() = seen.get();
+    });
+    decoder.decode_raw().unwrap();
+
+    assert_eq!(*calls.lock().unwrap(), 1);
+}
@@ -0,0 +1,59 @@
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+/// Decode a file row by row, returning the concatenated rows
+fn decode_by_rows(data: &[u8], options: DecoderOptions) -> Vec<u8>
+{
+    let mut decoder = PngDecoder::new_with_options(data, options);
+    let mut out = Vec::new();
+    let mut expected_row = 0;
+
+    decoder
+        .decode_rows(|row_index, row| {
+            assert_eq!(row_index, expected_row);
+            expected_row += 1;
+
+            out.extend_from_slice(row);
+        })
+        .unwrap();
+
+    assert_eq!(expected_row, decoder.get_dimensions().unwrap().1);
+
+    out
+}
+
+fn compare_with_decode_raw(options: DecoderOptions)
+{
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/png_suite");
+
+    for entry in std::fs::read_dir(path).unwrap()
+    {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+        // files starting with x are corrupt
+        if !name.ends_with(".png") || name.starts_with('x')
+        {
+            continue;
+        }
+        let data = std::fs::read(&path).unwrap();
+
+        let expected = PngDecoder::new_with_options(&data, options)
+            .decode_raw()
+            .unwrap();
+
+        assert_eq!(decode_by_rows(&data, options), expected, "{name}");
+    }
+}
+
+#[test]
+fn test_decode_rows_matches_decode_raw()
+{
+    compare_with_decode_raw(DecoderOptions::default());
+}
+
+#[test]
+fn test_decode_rows_native_depth()
+{
+    compare_with_decode_raw(DecoderOptions::default().png_set_expand_depth(false));
+}
@@ -0,0 +1,89 @@
+//! Tests for files containing zero length IDAT chunks
+use std::sync::{Arc, Mutex};
+
+use zune_png::PngDecoder;
+
+mod common;
+
+use common::chunk;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Rewrite a png so that its image data is split into IDAT chunks of the given sizes,
+/// any remaining data goes in a final chunk which is followed by an empty chunk
+fn split_idat(data: &[u8], sizes: &[usize]) -> Vec<u8>
+{
+    let mut out = data[..8].to_vec();
+    let mut idat = Vec::new();
+    let mut position = 8;
+
+    while position < data.len()
+    {
+        let length = u32::from_be_bytes(data[position..position + 4].try_into().unwrap()) as usize;
+        let name: [u8; 4] = data[position + 4..position + 8].try_into().unwrap();
+        let end = position + 12 + length;
+
+        if &name == b"IDAT"
+        {
+            idat.extend_from_slice(&data[position + 8..position + 8 + length]);
+        }
+        else
+        {
+            if &name == b"IEND"
+            {
+                let mut remaining = &idat[..];
+
+                for size in sizes
+                {
+                    let (current, rest) = remaining.split_at(*size);
+                    out.extend_from_slice(&chunk(b"IDAT", current));
+                    remaining = rest;
+                }
+                out.extend_from_slice(&chunk(b"IDAT", remaining));
+                out.extend_from_slice(&chunk(b"IDAT", &[]));
+            }
+            out.extend_from_slice(&data[position..end]);
+        }
+        position = end;
+    }
+    out
+}
+
+#[test]
+fn test_empty_idat_chunks()
+{
+    for name in ["basn2c08.png", "basi0g16.png", "oi9n2c16.png"]
+    {
+        let data = open_file(name);
+        let expected = PngDecoder::new(&data).decode_raw().unwrap();
+
+        // empty chunks before, between and after real ones
+        let with_empty = split_idat(&data, &[0, 20, 0, 0, 10]);
+
+        let lengths = Arc::new(Mutex::new(Vec::new()));
+        let observed = lengths.clone();
+
+        let mut decoder = PngDecoder::new(&with_empty);
+        decoder.set_chunk_observer(move |name, data, _| {
+            if name == b"IDAT"
+            {
+                observed.lock().unwrap().push(data.len());
+            }
+        });
+        decoder.decode_headers().unwrap();
+
+        let lengths = lengths.lock().unwrap().clone();
+        assert_eq!(&lengths[..5], [0, 20, 0, 0, 10]);
+        assert_eq!(lengths.last(), Some(&0));
+
+        assert_eq!(
+            PngDecoder::new(&with_empty).decode_raw().unwrap(),
+            expected,
+            "{name}"
+        );
+    }
+}
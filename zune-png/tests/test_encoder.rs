@@ -0,0 +1,226 @@
+#![cfg(feature = "crc")]
+use std::path::Path;
+
+use zune_core::options::EncoderOptions;
+use zune_core::result::DecodingResult;
+use zune_png::{PngDecoder, PngEncoder};
+
+/// Decode an image returning its pixels as native endian bytes and
+/// the options needed to encode it
+fn decode(data: &[u8]) -> (Vec<u8>, EncoderOptions)
+{
+    let mut decoder = PngDecoder::new(data);
+
+    let pixels = match decoder.decode().unwrap()
+    {
+        DecodingResult::U8(pixels) => pixels,
+        DecodingResult::U16(pixels) => pixels.iter().flat_map(|x| x.to_ne_bytes()).collect(),
+        _ => unreachable!()
+    };
+    let (width, height) = decoder.get_dimensions().unwrap();
+
+    let options = EncoderOptions::default()
+        .set_width(width)
+        .set_height(height)
+        .set_colorspace(decoder.get_colorspace().unwrap())
+        .set_depth(decoder.get_depth().unwrap());
+
+    (pixels, options)
+}
+
+/// Decode with the reference decoder, 16 bit samples are big endian
+fn decode_ref(data: &[u8]) -> Vec<u8>
+{
+    let mut reader = png::Decoder::new(data).read_info().unwrap();
+
+    let mut buf = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut buf).unwrap();
+
+    buf
+}
+
+fn test_round_trip(file: &str, options: impl Fn(EncoderOptions) -> EncoderOptions)
+{
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/png_suite")
+        .join(file);
+    let data = std::fs::read(path).unwrap();
+
+    let (pixels, encoder_options) = decode(&data);
+    let encoder_options = options(encoder_options);
+
+    let encoded = PngEncoder::new(&pixels, encoder_options).encode().unwrap();
+
+    assert_eq!(decode(&encoded).0, pixels, "{file}");
+
+    // the reference decoder returns 16 bit samples in big endian
+    let mut ref_pixels = decode_ref(&encoded);
+
+    if encoder_options.get_depth() == zune_core::bit_depth::BitDepth::Sixteen
+    {
+        ref_pixels = ref_pixels
+            .chunks_exact(2)
+            .flat_map(|x| u16::from_be_bytes([x[0], x[1]]).to_ne_bytes())
+            .collect();
+    }
+    assert_eq!(ref_pixels, pixels, "{file}");
+}
+
+const FILES: [&str; 9] = [
+    "basn0g08.png",
+    "basn0g16.png",
+    "basn2c08.png",
+    "basn2c16.png",
+    "basn4a08.png",
+    "basn4a16.png",
+    "basn6a08.png",
+    "basn6a16.png",
+    "basn3p08.png"
+];
+
+#[test]
+fn test_encode_round_trip()
+{
+    for file in FILES
+    {
+        test_round_trip(file, |options| options);
+    }
+}
+
+#[test]
+fn test_encode_round_trip_interlaced()
+{
+    for file in FILES
+    {
+        test_round_trip(file, |options| options.png_set_encode_interlaced(true));
+    }
+}
+
+#[test]
+fn test_encode_round_trip_interlaced_large()
+{
+    use zune_core::colorspace::ColorSpace;
+
+    // large enough for passes to be reconstructed in parallel
+    // with the `threads` feature
+    let (width, height) = (600, 600);
+    let pixels: Vec<u8> = (0..width * height * 3).map(|x| (x % 251) as u8).collect();
+
+    let options = EncoderOptions::default()
+        .set_width(width)
+        .set_height(height)
+        .set_colorspace(ColorSpace::RGB)
+        .png_set_encode_interlaced(true);
+
+    let encoded = PngEncoder::new(&pixels, options).encode().unwrap();
+
+    assert_eq!(decode(&encoded).0, pixels);
+    assert_eq!(decode_ref(&encoded), pixels);
+}
+
+#[test]
+fn test_encode_compression_levels()
+{
+    for effort in [0, 1, 9]
+    {
+        test_round_trip("basn6a16.png", |options| options.set_effort(effort));
+    }
+}
+
+#[test]
+fn test_encode_text_and_phys()
+{
+    use zune_core::colorspace::ColorSpace;
+    use zune_png::{PhysicalDimensions, PhysicalUnit};
+
+    let pixels = [0_u8; 4 * 3];
+    let options = EncoderOptions::default()
+        .set_width(4)
+        .set_height(3)
+        .set_colorspace(ColorSpace::Luma);
+
+    let dims = PhysicalDimensions {
+        x_pixels_per_unit: 2835,
+        y_pixels_per_unit: 2835,
+        unit:              PhysicalUnit::Meter
+    };
+
+    let mut encoder = PngEncoder::new(&pixels, options);
+    encoder.add_text_chunk("Author", "Zune");
+    encoder.set_physical_dimensions(dims);
+
+    let encoded = encoder.encode().unwrap();
+
+    let mut decoder = PngDecoder::new(&encoded);
+    decoder.decode_headers().unwrap();
+
+    let info = decoder.get_info().unwrap();
+
+    assert_eq!(info.pixel_dims, Some(dims));
+    assert_eq!(info.text_chunk[0].keyword, b"Author");
+    assert_eq!(info.text_chunk[0].text, b"Zune");
+
+    // invalid keyword
+    let mut encoder = PngEncoder::new(&pixels, options);
+    encoder.add_text_chunk("", "Zune");
+    assert!(encoder.encode().is_err());
+
+    // wrong input length
+    assert!(PngEncoder::new(&pixels[1..], options).encode().is_err());
+}
+
+#[test]
+fn test_encode_raw_iccp_round_trip()
+{
+    use zune_core::colorspace::ColorSpace;
+    use zune_core::options::DecoderOptions;
+    use zune_inflate::{DeflateEncodeOptions, DeflateEncoder};
+
+    let profile: Vec<u8> = (0..1024_u32).map(|x| (x % 251) as u8).collect();
+    // a level the encoder wouldn't pick by default, so recompressing would change the bytes
+    let compressed =
+        DeflateEncoder::new_with_options(&profile, DeflateEncodeOptions::default().set_level(1))
+            .encode_zlib();
+
+    let pixels = [0_u8; 4 * 3 * 3];
+    let options = EncoderOptions::default()
+        .set_width(4)
+        .set_height(3)
+        .set_colorspace(ColorSpace::RGB);
+
+    let mut encoder = PngEncoder::new(&pixels, options);
+    encoder.set_icc_profile_compressed(&compressed);
+    let encoded = encoder.encode().unwrap();
+
+    // not kept by default
+    let mut decoder = PngDecoder::new(&encoded);
+    decoder.decode_headers().unwrap();
+    assert!(decoder.get_info().unwrap().icc_profile_compressed.is_none());
+
+    let decoder_options = DecoderOptions::default().png_set_keep_raw_iccp(true);
+
+    let mut decoder = PngDecoder::new_with_options(&encoded, decoder_options);
+    decoder.decode_headers().unwrap();
+    let info = decoder.get_info().unwrap();
+
+    assert_eq!(info.icc_profile.as_deref(), Some(profile.as_slice()));
+    assert_eq!(
+        info.icc_profile_compressed.as_deref(),
+        Some(compressed.as_slice())
+    );
+
+    // decode then encode, the compressed bytes should survive unchanged
+    let raw_iccp = info.icc_profile_compressed.clone().unwrap();
+
+    let mut encoder = PngEncoder::new(&pixels, options);
+    encoder.set_icc_profile_compressed(&raw_iccp);
+    let re_encoded = encoder.encode().unwrap();
+
+    let mut decoder = PngDecoder::new_with_options(&re_encoded, decoder_options);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(
+        decoder.get_info().unwrap().icc_profile_compressed,
+        Some(compressed)
+    );
+}
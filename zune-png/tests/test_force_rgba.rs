@@ -0,0 +1,117 @@
+//! Tests for converting all decoded images to RGBA
+use zune_core::bit_depth::BitDepth;
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Convert pixels of the given colorspace to RGBA
+fn to_rgba(pixels: &[u8], colorspace: ColorSpace, bytes: usize) -> Vec<u8>
+{
+    let opaque = vec![0xFF; bytes];
+
+    pixels
+        .chunks_exact(colorspace.num_components() * bytes)
+        .flat_map(|pixel| {
+            let samples: Vec<&[u8]> = pixel.chunks_exact(bytes).collect();
+
+            let rgba = match colorspace
+            {
+                ColorSpace::Luma => [samples[0], samples[0], samples[0], &opaque],
+                ColorSpace::LumaA => [samples[0], samples[0], samples[0], samples[1]],
+                ColorSpace::RGB => [samples[0], samples[1], samples[2], &opaque],
+                ColorSpace::RGBA => [samples[0], samples[1], samples[2], samples[3]],
+                _ => unreachable!()
+            };
+            rgba.concat()
+        })
+        .collect()
+}
+
+#[test]
+fn test_force_rgba_matches_conversion()
+{
+    let options = DecoderOptions::default().png_set_force_rgba(true);
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/png_suite");
+
+    for entry in std::fs::read_dir(path).unwrap()
+    {
+        let name = entry.unwrap().file_name().to_string_lossy().into_owned();
+
+        // files starting with x are corrupt
+        if !name.ends_with(".png") || name.starts_with('x')
+        {
+            continue;
+        }
+        let data = open_file(&name);
+
+        let mut decoder = PngDecoder::new(&data);
+        let pixels = decoder.decode_raw().unwrap();
+        let colorspace = decoder.get_colorspace().unwrap();
+        let bytes = if decoder.get_depth() == Some(BitDepth::Sixteen) { 2 } else { 1 };
+
+        let mut decoder = PngDecoder::new_with_options(&data, options);
+        decoder.decode_headers().unwrap();
+
+        assert_eq!(decoder.get_colorspace(), Some(ColorSpace::RGBA), "{name}");
+
+        let (width, height) = decoder.get_dimensions().unwrap();
+        assert_eq!(
+            decoder.output_buffer_size(),
+            Some(width * height * 4 * bytes),
+            "{name}"
+        );
+
+        let rgba = decoder.decode_raw().unwrap();
+
+        assert_eq!(rgba, to_rgba(&pixels, colorspace, bytes), "{name}");
+    }
+}
+
+#[test]
+fn test_force_rgba_rows_match_decode_raw()
+{
+    let options = DecoderOptions::default().png_set_force_rgba(true);
+
+    // grayscale, grayscale with alpha, RGB and grayscale with tRNS
+    for name in [
+        "basn0g08.png",
+        "basn4a16.png",
+        "basn2c16.png",
+        "tbwn0g16.png"
+    ]
+    {
+        let data = open_file(name);
+
+        let expected = PngDecoder::new_with_options(&data, options)
+            .decode_raw()
+            .unwrap();
+
+        let mut rows = Vec::new();
+        PngDecoder::new_with_options(&data, options)
+            .decode_rows(|_, row| rows.extend_from_slice(row))
+            .unwrap();
+
+        assert_eq!(rows, expected, "{name}");
+    }
+}
+
+#[test]
+fn test_force_rgba_keeps_palette_indices()
+{
+    // indices can't be converted, so they take precedence
+    let options = DecoderOptions::default()
+        .png_set_force_rgba(true)
+        .png_set_keep_palette_indices(true);
+
+    let data = open_file("basn3p08.png");
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_colorspace(), Some(ColorSpace::Luma));
+}
@@ -0,0 +1,145 @@
+//! Tests for gamma correcting decoded images
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Create an RGBA image, with a gAMA chunk if `gamma` is set, whose pixels cover all values
+fn create_png(gamma: Option<f32>) -> (Vec<u8>, Vec<u8>)
+{
+    let pixels: Vec<u8> = (0..=255).flat_map(|x| [x, 255 - x, x / 2, x]).collect();
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, 16, 16);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        if let Some(gamma) = gamma
+        {
+            encoder.set_source_gamma(png::ScaledFloat::new(gamma));
+        }
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels).unwrap();
+    }
+    (out, pixels)
+}
+
+fn correct(value: u8, exponent: f32) -> u8
+{
+    ((f32::from(value) / 255.0).powf(exponent) * 255.0).round() as u8
+}
+
+#[test]
+fn test_gamma_not_applied_by_default()
+{
+    let (data, pixels) = create_png(Some(1.0));
+
+    assert_eq!(PngDecoder::new(&data).decode_raw().unwrap(), pixels);
+}
+
+#[test]
+fn test_gamma_applied_to_color_samples()
+{
+    let (data, pixels) = create_png(Some(1.0));
+
+    let options = DecoderOptions::default().png_set_apply_gamma(true);
+    let decoded = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+
+    // a linear image shown on a 2.2 display
+    for (out, px) in decoded.chunks_exact(4).zip(pixels.chunks_exact(4))
+    {
+        for i in 0..3
+        {
+            assert_eq!(out[i], correct(px[i], 1.0 / 2.2));
+        }
+        // alpha is untouched
+        assert_eq!(out[3], px[3]);
+    }
+}
+
+#[test]
+fn test_gamma_matching_display_is_identity()
+{
+    let (data, pixels) = create_png(Some(1.0 / 1.8));
+
+    let options = DecoderOptions::default()
+        .png_set_apply_gamma(true)
+        .png_set_display_gamma(1.8);
+
+    let decoded = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+
+    assert_eq!(decoded, pixels);
+}
+
+#[test]
+fn test_gamma_rows_match_decode_raw()
+{
+    let (data, _) = create_png(Some(0.7));
+
+    let options = DecoderOptions::default().png_set_apply_gamma(true);
+
+    let expected = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+
+    let mut rows = Vec::new();
+    PngDecoder::new_with_options(&data, options)
+        .decode_rows(|_, row| rows.extend_from_slice(row))
+        .unwrap();
+
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn test_gamma_sixteen_bit()
+{
+    // 16 bit grayscale with a gamma of 0.35
+    let data = open_file("g03n0g16.png");
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+
+    let mut raw = vec![0; decoder.output_buffer_size().unwrap() / 2];
+    decoder.decode_into_u16(&mut raw).unwrap();
+
+    let options = DecoderOptions::default().png_set_apply_gamma(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    let gamma = decoder.get_info().unwrap().gamma.unwrap();
+    let exponent = 1.0 / (gamma * 2.2);
+
+    let mut corrected = vec![0; raw.len()];
+    decoder.decode_into_u16(&mut corrected).unwrap();
+
+    for (out, px) in corrected.iter().zip(&raw)
+    {
+        let expected = ((f32::from(*px) / 65535.0).powf(exponent) * 65535.0).round() as u16;
+
+        assert_eq!(*out, expected);
+    }
+}
+
+#[test]
+fn test_gamma_without_gama_chunk_unchanged()
+{
+    let (data, pixels) = create_png(None);
+
+    let options = DecoderOptions::default().png_set_apply_gamma(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    decoder.decode_headers().unwrap();
+    assert!(decoder.get_info().unwrap().gamma.is_none());
+
+    assert_eq!(decoder.decode_raw().unwrap(), pixels);
+}
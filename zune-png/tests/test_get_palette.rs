@@ -0,0 +1,89 @@
+//! Tests for reading the palette of paletted images
+use zune_png::{PaletteEntry, PngDecoder};
+
+mod common;
+
+use common::split_chunks;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Return the body of the first chunk named `name`
+fn find_chunk<'a>(data: &'a [u8], name: &[u8; 4]) -> Option<&'a [u8]>
+{
+    split_chunks(data)
+        .into_iter()
+        .find(|chunk| &chunk[4..8] == name)
+        .map(|chunk| &chunk[8..chunk.len() - 4])
+}
+
+/// Palette built from the raw PLTE and tRNS chunks
+fn expected_palette(data: &[u8]) -> Vec<PaletteEntry>
+{
+    let plte = find_chunk(data, b"PLTE").unwrap();
+    let trns = find_chunk(data, b"tRNS").unwrap_or(&[]);
+
+    plte.chunks_exact(3)
+        .enumerate()
+        .map(|(i, rgb)| PaletteEntry {
+            red:   rgb[0],
+            green: rgb[1],
+            blue:  rgb[2],
+            alpha: trns.get(i).copied().unwrap_or(255)
+        })
+        .collect()
+}
+
+fn decoded_palette(data: &[u8]) -> Option<Vec<PaletteEntry>>
+{
+    let mut decoder = PngDecoder::new(data);
+    decoder.decode_headers().unwrap();
+
+    decoder.get_palette().map(<[PaletteEntry]>::to_vec)
+}
+
+#[test]
+fn test_palette_with_trns()
+{
+    // every entry of tm3n3p02 has an alpha value, only some of tbbn3p08's do
+    for name in ["tm3n3p02.png", "tbbn3p08.png", "tp1n3p08.png"]
+    {
+        let data = open_file(name);
+        let palette = decoded_palette(&data).unwrap();
+
+        assert!(find_chunk(&data, b"tRNS").is_some(), "{name}");
+        assert_eq!(palette, expected_palette(&data), "{name}");
+        assert!(palette.iter().any(|x| x.alpha != 255), "{name}");
+    }
+}
+
+#[test]
+fn test_palette_without_trns()
+{
+    for name in ["basn3p01.png", "basn3p04.png", "basn3p08.png"]
+    {
+        let data = open_file(name);
+        let palette = decoded_palette(&data).unwrap();
+
+        assert!(find_chunk(&data, b"tRNS").is_none(), "{name}");
+        assert_eq!(palette, expected_palette(&data), "{name}");
+        assert!(palette.iter().all(|x| x.alpha == 255), "{name}");
+    }
+}
+
+#[test]
+fn test_no_palette()
+{
+    // grayscale and RGB images, including ones with a tRNS chunk
+    for name in ["basn0g08.png", "basn2c08.png", "tbrn2c08.png", "tp0n2c08.png"]
+    {
+        assert_eq!(decoded_palette(&open_file(name)), None, "{name}");
+    }
+    // headers not decoded yet
+    assert!(PngDecoder::new(&open_file("basn3p08.png"))
+        .get_palette()
+        .is_none());
+}
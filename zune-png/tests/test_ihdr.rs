@@ -0,0 +1,64 @@
+//! Tests for validation of the IHDR chunk
+use zune_core::options::DecoderOptions;
+use zune_png::error::PngDecodeErrors;
+use zune_png::{PngColor, PngDecoder};
+
+/// Create a png with only an IHDR and IEND chunk, with invalid CRCs
+fn create_header(color: u8, depth: u8) -> Vec<u8>
+{
+    let mut out = vec![137, 80, 78, 71, 13, 10, 26, 10];
+
+    out.extend_from_slice(&13_u32.to_be_bytes());
+    out.extend_from_slice(b"IHDR");
+    out.extend_from_slice(&4_u32.to_be_bytes());
+    out.extend_from_slice(&4_u32.to_be_bytes());
+    out.extend_from_slice(&[depth, color, 0, 0, 0]);
+    out.extend_from_slice(&[0; 4]);
+
+    out.extend_from_slice(&0_u32.to_be_bytes());
+    out.extend_from_slice(b"IEND");
+    out.extend_from_slice(&[0; 4]);
+
+    out
+}
+
+#[test]
+fn test_invalid_color_depth_combinations()
+{
+    let options = DecoderOptions::default().png_set_confirm_crc(false);
+
+    let colors = [
+        (0, PngColor::Luma),
+        (2, PngColor::RGB),
+        (3, PngColor::Palette),
+        (4, PngColor::LumaA),
+        (6, PngColor::RGBA)
+    ];
+    for (color_type, color) in colors
+    {
+        for depth in [0, 1, 2, 3, 4, 5, 8, 12, 16, 32]
+        {
+            let valid = match color
+            {
+                PngColor::Luma => matches!(depth, 1 | 2 | 4 | 8 | 16),
+                PngColor::Palette => matches!(depth, 1 | 2 | 4 | 8),
+                _ => matches!(depth, 8 | 16)
+            };
+            let data = create_header(color_type, depth);
+            let result = PngDecoder::new_with_options(&data, options).decode_headers();
+
+            match result
+            {
+                Err(PngDecodeErrors::InvalidColorDepthCombo(found_color, found_depth)) =>
+                {
+                    assert!(!valid, "{color:?} {depth} should be accepted");
+                    assert_eq!((found_color, found_depth), (color, depth));
+                }
+                _ => assert!(
+                    valid,
+                    "{color:?} {depth} should be rejected, got {result:?}"
+                )
+            }
+        }
+    }
+}
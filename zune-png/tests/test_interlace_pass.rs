@@ -0,0 +1,118 @@
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Starting column, starting row, column increment and row increment
+/// of each Adam7 pass
+const PASSES: [(usize, usize, usize, usize); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2)
+];
+
+fn test_passes(name: &str)
+{
+    test_passes_with_options(name, DecoderOptions::default());
+}
+
+fn test_passes_with_options(name: &str, options: DecoderOptions)
+{
+    let data = open_file(name);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    let full = decoder.decode_raw().unwrap();
+    let (width, height) = decoder.get_dimensions().unwrap();
+    let pixel_size = full.len() / (width * height);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    for (pass, (x_start, y_start, x_step, y_step)) in PASSES.into_iter().enumerate()
+    {
+        let (pass_width, pass_height) = decoder.interlace_pass_dimensions(pass).unwrap();
+
+        assert_eq!(pass_width, (x_start..width).step_by(x_step).count());
+        assert_eq!(pass_height, (y_start..height).step_by(y_step).count());
+
+        let mut out = vec![0; pass_width * pass_height * pixel_size];
+        decoder.decode_interlace_pass(pass, &mut out).unwrap();
+
+        // every pixel of the pass is the corresponding pixel in the full image
+        for (j, y) in (y_start..height).step_by(y_step).enumerate()
+        {
+            for (i, x) in (x_start..width).step_by(x_step).enumerate()
+            {
+                let pass_start = (j * pass_width + i) * pixel_size;
+                let full_start = (y * width + x) * pixel_size;
+
+                assert_eq!(
+                    out[pass_start..pass_start + pixel_size],
+                    full[full_start..full_start + pixel_size],
+                    "{name}: pass {pass}, pixel ({x},{y})"
+                );
+            }
+        }
+    }
+    assert_eq!(decoder.interlace_pass_dimensions(7), None);
+    assert!(decoder.decode_interlace_pass(7, &mut []).is_err());
+}
+
+#[test]
+fn test_interlace_pass_rgb8()
+{
+    test_passes("basi2c08.png");
+}
+
+#[test]
+fn test_interlace_pass_luma4()
+{
+    test_passes("basi0g04.png");
+}
+
+#[test]
+fn test_interlace_pass_rgba16()
+{
+    test_passes("basi6a16.png");
+}
+
+#[test]
+fn test_interlace_pass_palette()
+{
+    test_passes("basi3p02.png");
+}
+
+#[test]
+fn test_interlace_pass_small_image()
+{
+    // 1x1 image, only the first pass has pixels
+    test_passes("s01i3p01.png");
+}
+
+#[test]
+fn test_interlace_pass_non_interlaced()
+{
+    let data = open_file("basn2c08.png");
+    let mut out = vec![0; 32 * 32 * 3];
+
+    assert!(PngDecoder::new(&data)
+        .decode_interlace_pass(0, &mut out)
+        .is_err());
+}
+
+#[test]
+fn test_interlace_pass_force_rgba()
+{
+    let options = DecoderOptions::default().png_set_force_rgba(true);
+
+    test_passes_with_options("basi0g16.png", options);
+    test_passes_with_options("basi2c08.png", options);
+}
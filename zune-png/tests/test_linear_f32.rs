@@ -0,0 +1,91 @@
+//! Tests for decoding to linear light floats
+use zune_core::result::DecodingResult;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+fn encode(pixels: &[u8], width: u32, color: png::ColorType, depth: png::BitDepth) -> Vec<u8>
+{
+    let samples_per_pixel = color.samples() * usize::from(depth as u8).max(8) / 8;
+    let height = (pixels.len() / samples_per_pixel) as u32 / width;
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(depth);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(pixels).unwrap();
+    writer.finish().unwrap();
+
+    out
+}
+
+#[test]
+fn test_linear_mid_gray()
+{
+    // black, mid gray and white with mid alpha
+    let data = encode(
+        &[0, 0x80, 0x80, 0x80, 255, 0x80],
+        3,
+        png::ColorType::GrayscaleAlpha,
+        png::BitDepth::Eight
+    );
+    let pixels = PngDecoder::new(&data).decode_linear_f32().unwrap();
+
+    assert_eq!(pixels.len(), 6);
+    assert_eq!(pixels[0], 0.0);
+    assert!((pixels[2] - 0.2158).abs() < 1e-4, "{}", pixels[2]);
+    assert_eq!(pixels[4], 1.0);
+
+    // alpha is normalized but not linearized
+    for alpha in [pixels[1], pixels[3], pixels[5]]
+    {
+        assert!((alpha - 128.0 / 255.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_linear_16_bit()
+{
+    // 0x8080 is the 16 bit equivalent of 0x80
+    let data = encode(
+        &[0x80, 0x80, 0, 0, 0xFF, 0xFF],
+        1,
+        png::ColorType::Rgb,
+        png::BitDepth::Sixteen
+    );
+    let pixels = PngDecoder::new(&data).decode_linear_f32().unwrap();
+
+    assert!((pixels[0] - 0.2158).abs() < 1e-4, "{}", pixels[0]);
+    assert_eq!(&pixels[1..], &[0.0, 1.0]);
+}
+
+#[test]
+fn test_linear_matches_decode()
+{
+    for name in ["basn2c08.png", "basn3p04.png", "basn6a16.png"]
+    {
+        let data = open_file(name);
+
+        let mut decoder = PngDecoder::new(&data);
+        let linear = decoder.decode_linear_f32().unwrap();
+
+        let mut decoder = PngDecoder::new(&data);
+        let samples = decoder.decode().unwrap();
+
+        let length = match samples
+        {
+            DecodingResult::U8(samples) => samples.len(),
+            DecodingResult::U16(samples) => samples.len(),
+            _ => unreachable!()
+        };
+
+        assert_eq!(linear.len(), length, "{name}");
+        assert!(linear.iter().all(|x| (0.0..=1.0).contains(x)), "{name}");
+    }
+}
@@ -0,0 +1,132 @@
+//! Tests for images whose declared colour type doesn't match the image data
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::DecoderOptions;
+use zune_inflate::checksums::crc32;
+use zune_png::error::PngDecodeErrors;
+use zune_png::PngDecoder;
+
+/// Encode `pixels` as an 8 bit image with the given colour type
+fn create_png(pixels: &[u8], width: u32, height: u32, color: png::ColorType) -> Vec<u8>
+{
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut info = encoder.write_header().unwrap();
+        info.write_image_data(pixels).unwrap();
+    }
+    out
+}
+
+/// Patch the IHDR chunk of a png to declare `declared` as its colour type
+fn mislabel(mut out: Vec<u8>, declared: u8) -> Vec<u8>
+{
+    // signature(8) + length(4) + chunk type(4) + width(4) + height(4) + depth(1)
+    out[25] = declared;
+
+    let crc = crc32(&out[12..29]);
+    out[29..33].copy_from_slice(&crc.to_be_bytes());
+
+    out
+}
+
+fn luma_alpha_pixels(width: usize, height: usize) -> Vec<u8>
+{
+    (0..width * height)
+        .flat_map(|i| [(i * 7) as u8, 255 - (i * 3) as u8])
+        .collect()
+}
+
+#[test]
+fn test_mislabeled_luma_alpha_strict_error()
+{
+    let pixels = luma_alpha_pixels(8, 4);
+    // grayscale alpha data, declared as grayscale
+    let data = mislabel(create_png(&pixels, 8, 4, png::ColorType::GrayscaleAlpha), 0);
+
+    let err = PngDecoder::new(&data).decode_raw().unwrap_err();
+    let message = format!("{err:?}");
+
+    assert!(message.contains("2 channels"), "{message}");
+}
+
+#[test]
+fn test_mislabeled_luma_strict_error()
+{
+    let pixels: Vec<u8> = (0..8 * 4).map(|x| x as u8).collect();
+    // grayscale data, declared as grayscale alpha
+    let data = mislabel(create_png(&pixels, 8, 4, png::ColorType::Grayscale), 4);
+
+    let err = PngDecoder::new(&data).decode_raw().unwrap_err();
+    let message = format!("{err:?}");
+
+    assert!(message.contains("1 channels"), "{message}");
+}
+
+#[test]
+fn test_mislabeled_luma_alpha_permissive()
+{
+    let pixels = luma_alpha_pixels(8, 4);
+    let data = mislabel(create_png(&pixels, 8, 4, png::ColorType::GrayscaleAlpha), 0);
+
+    let options = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    let decoded = decoder.decode_raw().unwrap();
+
+    assert_eq!(decoder.get_colorspace(), Some(ColorSpace::LumaA));
+    assert_eq!(decoded, pixels);
+}
+
+#[test]
+#[cfg(feature = "crc")]
+fn test_mislabeled_luma_alpha_interlaced_permissive()
+{
+    use zune_core::options::EncoderOptions;
+
+    let pixels = luma_alpha_pixels(9, 7);
+    let options = EncoderOptions::default()
+        .set_width(9)
+        .set_height(7)
+        .set_colorspace(ColorSpace::LumaA)
+        .png_set_encode_interlaced(true);
+
+    let encoded = zune_png::PngEncoder::new(&pixels, options)
+        .encode()
+        .unwrap();
+    let data = mislabel(encoded, 0);
+
+    let options = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    let decoded = decoder.decode_raw().unwrap();
+
+    assert_eq!(decoder.get_colorspace(), Some(ColorSpace::LumaA));
+    assert_eq!(decoded, pixels);
+}
+
+#[test]
+fn test_mislabeled_decode_into_retry()
+{
+    let pixels = luma_alpha_pixels(8, 4);
+    let data = mislabel(create_png(&pixels, 8, 4, png::ColorType::GrayscaleAlpha), 0);
+
+    let options = DecoderOptions::default().set_strict_mode(false);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    decoder.decode_headers().unwrap();
+    // sized for the declared colour type, the image data needs twice as much
+    let mut out = vec![0; decoder.output_buffer_size().unwrap()];
+
+    let err = decoder.decode_into(&mut out).unwrap_err();
+
+    assert!(matches!(err, PngDecodeErrors::TooSmallOutput(64, 32)), "{err:?}");
+    assert_eq!(decoder.output_buffer_size(), Some(64));
+
+    let mut out = vec![0; 64];
+    decoder.decode_into(&mut out).unwrap();
+
+    assert_eq!(out, pixels);
+}
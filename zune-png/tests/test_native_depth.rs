@@ -0,0 +1,69 @@
+use zune_core::bit_depth::BitDepth;
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn decode_ref(data: &[u8]) -> Vec<u8>
+{
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::IDENTITY);
+    let mut reader = decoder.read_info().unwrap();
+
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+    buf.truncate(info.buffer_size());
+
+    buf
+}
+
+fn test_native_depth(name: &str, expected_depth: BitDepth)
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    let data = std::fs::read(path).unwrap();
+
+    let options = DecoderOptions::default().png_set_expand_depth(false);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    let pixels = decoder.decode_raw().unwrap();
+
+    assert_eq!(decoder.get_depth(), Some(expected_depth));
+    assert_eq!(decoder.get_colorspace(), Some(ColorSpace::Luma));
+    assert_eq!(decoder.output_buffer_size(), Some(pixels.len()));
+    assert_eq!(pixels, decode_ref(&data));
+}
+
+#[test]
+fn test_native_depth_grayscale()
+{
+    test_native_depth("basn0g01.png", BitDepth::One);
+    test_native_depth("basn0g02.png", BitDepth::Two);
+    test_native_depth("basn0g04.png", BitDepth::Four);
+}
+
+#[test]
+fn test_native_depth_interlaced()
+{
+    test_native_depth("basi0g01.png", BitDepth::One);
+    test_native_depth("basi0g02.png", BitDepth::Two);
+    test_native_depth("basi0g04.png", BitDepth::Four);
+}
+
+#[test]
+fn test_native_depth_palette()
+{
+    test_native_depth("basn3p01.png", BitDepth::One);
+    test_native_depth("basn3p02.png", BitDepth::Two);
+    test_native_depth("basi3p04.png", BitDepth::Four);
+}
+
+#[test]
+fn test_expand_depth_default()
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/basn0g01.png";
+    let data = std::fs::read(path).unwrap();
+
+    let mut decoder = PngDecoder::new(&data);
+    let pixels = decoder.decode_raw().unwrap();
+
+    assert_eq!(decoder.get_depth(), Some(BitDepth::Eight));
+    assert_eq!(pixels.len(), 32 * 32);
+}
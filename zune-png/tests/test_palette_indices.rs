@@ -0,0 +1,103 @@
+//! Tests for returning palette indices of paletted images
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Paletted images in the suite, of all depths, interlaced and with transparency
+fn paletted_images() -> Vec<String>
+{
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/png_suite");
+
+    let mut names: Vec<String> = std::fs::read_dir(path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".png") && !name.starts_with('x') && name.contains("3p"))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_indices_map_to_expanded_pixels()
+{
+    let options = DecoderOptions::default().png_set_keep_palette_indices(true);
+
+    for name in paletted_images()
+    {
+        let data = open_file(&name);
+
+        let mut decoder = PngDecoder::new(&data);
+        let expanded = decoder.decode_raw().unwrap();
+        let components = decoder.get_colorspace().unwrap().num_components();
+
+        let mut decoder = PngDecoder::new_with_options(&data, options);
+        let indices = decoder.decode_raw().unwrap();
+        let (width, height) = decoder.get_dimensions().unwrap();
+
+        assert_eq!(decoder.get_colorspace(), Some(ColorSpace::Luma), "{name}");
+        assert_eq!(indices.len(), width * height, "{name}");
+
+        let palette = decoder.get_palette().unwrap();
+
+        for (index, pixel) in indices.iter().zip(expanded.chunks_exact(components))
+        {
+            let entry = palette[usize::from(*index)];
+            let colour = [entry.red, entry.green, entry.blue, entry.alpha];
+
+            assert_eq!(pixel, &colour[..components], "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_indices_with_native_depth_stay_packed()
+{
+    let options = DecoderOptions::default()
+        .png_set_keep_palette_indices(true)
+        .png_set_expand_depth(false);
+
+    for name in ["basn3p01.png", "basn3p02.png", "basn3p04.png"]
+    {
+        let data = open_file(name);
+
+        let packed = PngDecoder::new_with_options(
+            &data,
+            DecoderOptions::default().png_set_expand_depth(false)
+        )
+        .decode_raw()
+        .unwrap();
+
+        assert_eq!(
+            PngDecoder::new_with_options(&data, options)
+                .decode_raw()
+                .unwrap(),
+            packed,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_indices_ignored_for_other_images()
+{
+    let options = DecoderOptions::default().png_set_keep_palette_indices(true);
+
+    for name in ["basn2c08.png", "tbrn2c08.png", "basn0g04.png"]
+    {
+        let data = open_file(name);
+
+        assert_eq!(
+            PngDecoder::new_with_options(&data, options)
+                .decode_raw()
+                .unwrap(),
+            PngDecoder::new(&data).decode_raw().unwrap(),
+            "{name}"
+        );
+    }
+}
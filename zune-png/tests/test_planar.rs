@@ -0,0 +1,106 @@
+//! Tests for decoding to one plane per component
+use zune_core::options::DecoderOptions;
+use zune_core::result::DecodingResult;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+fn encode(pixels: &[u8], width: u32, color: png::ColorType, depth: png::BitDepth) -> Vec<u8>
+{
+    let samples_per_pixel = color.samples() * usize::from(depth as u8).max(8) / 8;
+    let height = (pixels.len() / samples_per_pixel) as u32 / width;
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(depth);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(pixels).unwrap();
+    writer.finish().unwrap();
+
+    out
+}
+
+#[test]
+fn test_planar_rgb()
+{
+    let data = encode(
+        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+        2,
+        png::ColorType::Rgb,
+        png::BitDepth::Eight
+    );
+    let planes = PngDecoder::new(&data).decode_planar().unwrap();
+
+    assert_eq!(
+        planes,
+        vec![vec![1, 4, 7, 10], vec![2, 5, 8, 11], vec![3, 6, 9, 12]]
+    );
+}
+
+#[test]
+fn test_planar_matches_interleaved()
+{
+    for name in ["basn6a08.png", "basn3p04.png", "basi0g08.png"]
+    {
+        let data = open_file(name);
+
+        let mut decoder = PngDecoder::new(&data);
+        let interleaved = decoder.decode_raw().unwrap();
+        let components = decoder.get_colorspace().unwrap().num_components();
+
+        let planes = PngDecoder::new(&data).decode_planar().unwrap();
+
+        assert_eq!(planes.len(), components, "{name}");
+
+        for (i, sample) in interleaved.iter().enumerate()
+        {
+            assert_eq!(planes[i % components][i / components], *sample, "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_planar_u16()
+{
+    let data = open_file("basn6a16.png");
+
+    let samples = match PngDecoder::new(&data).decode().unwrap()
+    {
+        DecodingResult::U16(samples) => samples,
+        _ => unreachable!()
+    };
+    let planes = PngDecoder::new(&data).decode_planar_u16().unwrap();
+
+    assert_eq!(planes.len(), 4);
+
+    for (i, sample) in samples.iter().enumerate()
+    {
+        assert_eq!(planes[i % 4][i / 4], *sample);
+    }
+}
+
+#[test]
+fn test_planar_depth_mismatch()
+{
+    assert!(PngDecoder::new(&open_file("basn6a16.png"))
+        .decode_planar()
+        .is_err());
+    assert!(PngDecoder::new(&open_file("basn6a08.png"))
+        .decode_planar_u16()
+        .is_err());
+
+    // packed samples can't be split
+    let options = DecoderOptions::default().png_set_expand_depth(false);
+
+    assert!(
+        PngDecoder::new_with_options(&open_file("basn0g01.png"), options)
+            .decode_planar()
+            .is_err()
+    );
+}
@@ -0,0 +1,122 @@
+//! Tests for outputting premultiplied alpha
+use zune_core::options::DecoderOptions;
+use zune_core::result::DecodingResult;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Decode an image, returning its samples and the maximum sample value
+fn decode(data: &[u8], premultiply: bool) -> (Vec<u32>, u32)
+{
+    let options = DecoderOptions::default().png_set_premultiply_alpha(premultiply);
+
+    match PngDecoder::new_with_options(data, options)
+        .decode()
+        .unwrap()
+    {
+        DecodingResult::U8(data) => (data.into_iter().map(u32::from).collect(), 255),
+        DecodingResult::U16(data) => (data.into_iter().map(u32::from).collect(), 65535),
+        _ => unreachable!()
+    }
+}
+
+/// Check that `premultiplied` is `straight` with colour samples multiplied by alpha
+fn check_premultiplied(data: &[u8], components: usize)
+{
+    let (straight, max) = decode(data, false);
+    let (premultiplied, _) = decode(data, true);
+
+    for (a, b) in straight
+        .chunks_exact(components)
+        .zip(premultiplied.chunks_exact(components))
+    {
+        let alpha = a[components - 1];
+
+        for i in 0..components - 1
+        {
+            assert_eq!(b[i], (a[i] * alpha + max / 2) / max);
+        }
+        assert_eq!(a[components - 1], b[components - 1]);
+    }
+}
+
+#[test]
+fn test_premultiply_rgba()
+{
+    // 8 and 16 bit RGBA with varying transparency
+    for name in [
+        "basn6a08.png",
+        "basn6a16.png",
+        "basn4a08.png",
+        "basn4a16.png"
+    ]
+    {
+        let data = open_file(name);
+
+        let mut decoder = PngDecoder::new(&data);
+        decoder.decode_headers().unwrap();
+        let components = decoder.get_colorspace().unwrap().num_components();
+
+        check_premultiplied(&data, components);
+    }
+}
+
+#[test]
+fn test_premultiply_after_transparency_expansion()
+{
+    // palette with tRNS and grayscale with tRNS, alpha comes from the tRNS chunk
+    for (name, components) in [
+        ("tbbn3p08.png", 4),
+        ("tbwn0g16.png", 2),
+        ("tbrn2c08.png", 4)
+    ]
+    {
+        let data = open_file(name);
+
+        assert_ne!(decode(&data, false), decode(&data, true), "{name}");
+        check_premultiplied(&data, components);
+    }
+}
+
+#[test]
+fn test_premultiply_opaque_is_noop()
+{
+    // no alpha channel and a fully opaque alpha channel
+    let opaque_rgba: Vec<u8> = (0..=255).flat_map(|x| [x, 255 - x, x / 3, 255]).collect();
+
+    let mut rgba = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut rgba, 16, 16);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&opaque_rgba).unwrap();
+    }
+    for data in [open_file("basn2c08.png"), rgba]
+    {
+        assert_eq!(decode(&data, false), decode(&data, true));
+    }
+}
+
+#[test]
+fn test_premultiply_rows_match_decode_raw()
+{
+    let data = open_file("basn6a16.png");
+    let options = DecoderOptions::default().png_set_premultiply_alpha(true);
+
+    let expected = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+
+    let mut rows = Vec::new();
+    PngDecoder::new_with_options(&data, options)
+        .decode_rows(|_, row| rows.extend_from_slice(row))
+        .unwrap();
+
+    assert_eq!(rows, expected);
+}
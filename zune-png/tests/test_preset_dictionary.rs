@@ -0,0 +1,136 @@
+//! Tests for decoding image data compressed with a preset dictionary
+use zune_inflate::checksums::adler32;
+use zune_png::PngDecoder;
+
+mod common;
+
+use common::chunk;
+
+/// Write `length` bits of `value`, least significant bit first
+fn put_bits(bits: &mut Vec<bool>, value: u32, length: u32)
+{
+    for i in 0..length
+    {
+        bits.push((value >> i) & 1 == 1);
+    }
+}
+
+/// Write a huffman code, which deflate stores most significant bit first
+fn put_code(bits: &mut Vec<bool>, code: u32, length: u32)
+{
+    for i in (0..length).rev()
+    {
+        bits.push((code >> i) & 1 == 1);
+    }
+}
+
+/// The pixels of a 2x2 rgba sprite with the filter byte of each row
+fn sprite_rows() -> Vec<u8>
+{
+    vec![
+        0, 255, 0, 0, 255, 0, 255, 0, 255, //
+        0, 0, 0, 255, 255, 0, 0, 0, 0,
+    ]
+}
+
+/// Create a 2x2 rgba png whose image data is a zlib stream
+/// copying the sprite from the end of `dictionary`
+fn create_sprite_png(dictionary: &[u8]) -> Vec<u8>
+{
+    let rows = sprite_rows();
+    assert_eq!(rows.len(), 18);
+
+    // a single fixed huffman block holding one length 18, distance 18 match
+    let mut bits = Vec::new();
+    put_bits(&mut bits, 1, 1); // BFINAL
+    put_bits(&mut bits, 1, 2); // BTYPE, fixed huffman codes
+    put_code(&mut bits, 268 - 256, 7); // length code 268, lengths 17-18
+    put_bits(&mut bits, 1, 1); // extra bit, length 18
+    put_code(&mut bits, 8, 5); // distance code 8, distances 17-24
+    put_bits(&mut bits, 1, 3); // extra bits, distance 18
+    put_code(&mut bits, 0, 7); // end of block
+
+    let mut zlib = vec![0x78, 0x20]; // FDICT set
+    assert_eq!((u16::from(zlib[0]) * 256 + u16::from(zlib[1])) % 31, 0);
+    zlib.extend_from_slice(&adler32(dictionary).to_be_bytes());
+    zlib.extend(bits.chunks(8).map(|byte| {
+        byte.iter()
+            .enumerate()
+            .fold(0_u8, |acc, (i, bit)| acc | (u8::from(*bit) << i))
+    }));
+    zlib.extend_from_slice(&adler32(&rows).to_be_bytes());
+
+    let mut png = vec![137, 80, 78, 71, 13, 10, 26, 10];
+    // 2x2, 8 bit rgba
+    png.extend_from_slice(&chunk(b"IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 6, 0, 0, 0]));
+    png.extend_from_slice(&chunk(b"IDAT", &zlib));
+    png.extend_from_slice(&chunk(b"IEND", &[]));
+    png
+}
+
+fn sprite_dictionary() -> Vec<u8>
+{
+    let mut dictionary = b"shared sprite data".to_vec();
+    dictionary.extend_from_slice(&sprite_rows());
+    dictionary
+}
+
+#[test]
+fn test_preset_dictionary_round_trip()
+{
+    let dictionary = sprite_dictionary();
+    let png = create_sprite_png(&dictionary);
+
+    let mut decoder = PngDecoder::new(&png);
+    decoder.set_preset_dictionary(&dictionary);
+
+    let pixels = decoder.decode_raw().unwrap();
+
+    let expected: Vec<u8> = sprite_rows()
+        .chunks_exact(9)
+        .flat_map(|row| row[1..].to_vec())
+        .collect();
+
+    assert_eq!(pixels, expected);
+}
+
+#[test]
+fn test_preset_dictionary_missing()
+{
+    let dictionary = sprite_dictionary();
+    let png = create_sprite_png(&dictionary);
+
+    assert!(PngDecoder::new(&png).decode_raw().is_err());
+}
+
+#[test]
+fn test_preset_dictionary_mismatch()
+{
+    let dictionary = sprite_dictionary();
+    let png = create_sprite_png(&dictionary);
+
+    let mut decoder = PngDecoder::new(&png);
+    decoder.set_preset_dictionary(b"some other dictionary");
+
+    assert!(decoder.decode_raw().is_err());
+}
+
+#[test]
+fn test_preset_dictionary_ignored_for_standard_pngs()
+{
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, 2, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[7; 16]).unwrap();
+    }
+    let dictionary = sprite_dictionary();
+
+    let mut decoder = PngDecoder::new(&png);
+    decoder.set_preset_dictionary(&dictionary);
+
+    assert_eq!(decoder.decode_raw().unwrap(), vec![7; 16]);
+}
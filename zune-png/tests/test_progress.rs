@@ -0,0 +1,112 @@
+//! Tests for progress reporting while decoding
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
+
+use zune_core::options::{DecoderOptions, ScaleDown};
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+fn encode_gray(width: u32, height: u32) -> Vec<u8>
+{
+    let pixels: Vec<u8> = (0..width * height).map(|x| (x % 251) as u8).collect();
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    writer.finish().unwrap();
+
+    out
+}
+
+/// Decode `data` and return the progress reported
+fn decode_with_progress(data: &[u8], options: DecoderOptions) -> Vec<f32>
+{
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = reported.clone();
+
+    let mut decoder = PngDecoder::new_with_options(data, options);
+    decoder.set_progress_callback(move |fraction| sink.lock().unwrap().push(fraction));
+    decoder.decode_raw().unwrap();
+
+    let reported = reported.lock().unwrap().clone();
+    reported
+}
+
+fn assert_increasing_to_one(reported: &[f32])
+{
+    assert_eq!(reported.last(), Some(&1.0), "{reported:?}");
+    assert!(reported.windows(2).all(|x| x[0] < x[1]), "{reported:?}");
+    assert!(reported.iter().all(|x| (0.0..=1.0).contains(x)));
+}
+
+#[test]
+fn test_progress_standard()
+{
+    let data = encode_gray(16, 1000);
+    let reported = decode_with_progress(&data, DecoderOptions::default());
+
+    assert_increasing_to_one(&reported);
+    // reported about every hundredth of the image, not every row
+    assert_eq!(reported.len(), 100);
+    assert_eq!(reported[0], 0.01);
+}
+
+#[test]
+fn test_progress_small_image()
+{
+    let data = encode_gray(4, 3);
+    let reported = decode_with_progress(&data, DecoderOptions::default());
+
+    assert_increasing_to_one(&reported);
+    assert_eq!(reported.len(), 3);
+}
+
+#[test]
+fn test_progress_interlaced()
+{
+    let data = open_file("basi2c08.png");
+    let reported = decode_with_progress(&data, DecoderOptions::default());
+
+    // once for each of the seven passes
+    assert_increasing_to_one(&reported);
+    assert_eq!(reported.len(), 7);
+}
+
+#[test]
+fn test_progress_scaled()
+{
+    let data = encode_gray(16, 1000);
+    let options = DecoderOptions::default().png_set_scale_down(ScaleDown::Quarter);
+    let reported = decode_with_progress(&data, options);
+
+    assert_increasing_to_one(&reported);
+}
+
+#[test]
+fn test_progress_callback_does_not_need_sync()
+{
+    let data = encode_gray(4, 3);
+
+    let calls = Arc::new(Mutex::new(0));
+    let sink = calls.clone();
+    // a Cell is Send but not Sync
+    let seen = Cell::new(0);
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.set_progress_callback(move |_| {
+        seen.set(seen.get() + 1);
+        *sink.lock().unwrap() = seen.get();
+    });
+    decoder.decode_raw().unwrap();
+
+    assert_eq!(*calls.lock().unwrap(), 3);
+}
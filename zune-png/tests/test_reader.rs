@@ -0,0 +1,90 @@
+//! Tests for reading pngs from io sources
+use std::io::{Cursor, ErrorKind, Read};
+
+use zune_core::colorspace::ColorSpace;
+use zune_png::{PngDecoder, PngSource};
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// A reader that fails once it has returned `limit` bytes
+struct LimitedReader
+{
+    inner: Cursor<Vec<u8>>,
+    limit: u64
+}
+
+impl Read for LimitedReader
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize>
+    {
+        let remaining = self.limit - self.inner.position();
+
+        if remaining == 0
+        {
+            return Err(std::io::Error::other("read past limit"));
+        }
+        let len = buf.len().min(remaining as usize);
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn test_from_reader()
+{
+    let data = open_file("basn3p08.png");
+    let expected = PngDecoder::new(&data).decode_raw().unwrap();
+
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/basn3p08.png";
+    let source = PngSource::from_reader(std::fs::File::open(path).unwrap()).unwrap();
+
+    assert_eq!(source.decoder().decode_raw().unwrap(), expected);
+    assert_eq!(source.into_inner(), data);
+}
+
+#[test]
+fn test_headers_from_reader()
+{
+    let data = open_file("ccwn3p08.png");
+
+    let mut full = PngDecoder::new(&data);
+    full.decode_headers().unwrap();
+
+    // everything after the first IDAT chunk is never read
+    let idat = data.windows(4).position(|x| x == b"IDAT").unwrap();
+    let reader = LimitedReader {
+        inner: Cursor::new(data.clone()),
+        limit: idat as u64 + 4
+    };
+    let source = PngSource::headers_from_reader(reader).unwrap();
+
+    let mut decoder = source.decoder();
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_dimensions(), full.get_dimensions());
+    assert_eq!(decoder.get_colorspace(), Some(ColorSpace::RGB));
+    assert_eq!(
+        decoder.get_palette().map(<[_]>::len),
+        full.get_palette().map(<[_]>::len)
+    );
+    assert_eq!(
+        decoder.get_info().unwrap().chromaticities,
+        full.get_info().unwrap().chromaticities
+    );
+    assert!(decoder.decode_raw().is_err());
+}
+
+#[test]
+fn test_headers_from_reader_errors()
+{
+    assert!(PngSource::headers_from_reader(&b"not a png file"[..]).is_err());
+
+    // truncated before the image data
+    let data = open_file("basn3p08.png");
+    let err = PngSource::headers_from_reader(&data[..40]).err().unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,99 @@
+//! Tests for reusing a decoder across images
+use zune_core::colorspace::ColorSpace;
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+#[test]
+fn test_reset_matches_fresh_decoder()
+{
+    // palettes, transparency, gamma, interlacing and 16 bit images, in an order
+    // where state left over from one image would corrupt the next
+    let images: Vec<Vec<u8>> = [
+        "basn3p08.png",
+        "basn2c08.png",
+        "tbbn3p08.png",
+        "basn0g04.png",
+        "tbrn2c08.png",
+        "basi2c16.png",
+        "g25n2c08.png",
+        "basn6a16.png",
+        "s01n3p01.png"
+    ]
+    .iter()
+    .map(|name| open_file(name))
+    .collect();
+
+    let options = DecoderOptions::default().png_set_apply_gamma(true);
+
+    let mut decoder = PngDecoder::new_with_options(&[], options);
+
+    for _ in 0..2
+    {
+        for image in &images
+        {
+            decoder.reset(image);
+            let pixels = decoder.decode_raw().unwrap();
+            let info = decoder.get_info().unwrap().clone();
+
+            let mut fresh = PngDecoder::new_with_options(image, options);
+            let expected = fresh.decode_raw().unwrap();
+            let expected_info = fresh.get_info().unwrap();
+
+            assert_eq!(pixels, expected);
+            assert_eq!(decoder.get_colorspace(), fresh.get_colorspace());
+            assert_eq!(info.gamma, expected_info.gamma);
+            assert_eq!(info.width, expected_info.width);
+            assert_eq!(
+                decoder.get_palette().map(<[_]>::len),
+                fresh.get_palette().map(<[_]>::len)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_reset_before_headers()
+{
+    let first = open_file("basn2c08.png");
+    let second = open_file("basn0g01.png");
+
+    let mut decoder = PngDecoder::new(&first);
+    decoder.decode_headers().unwrap();
+
+    decoder.reset(&second);
+
+    assert_eq!(decoder.get_dimensions(), None);
+    assert_eq!(
+        decoder.decode_raw().unwrap(),
+        PngDecoder::new(&second).decode_raw().unwrap()
+    );
+}
+
+#[test]
+fn test_release_pixel_buffers_keeps_headers()
+{
+    let data = open_file("basi3p08.png");
+    let expected = PngDecoder::new(&data).decode_raw().unwrap();
+
+    let mut decoder = PngDecoder::new(&data);
+    decoder.decode_headers().unwrap();
+    decoder.release_pixel_buffers();
+
+    assert_eq!(decoder.get_dimensions(), Some((32, 32)));
+    assert_eq!(decoder.get_palette().map(<[_]>::len), Some(256));
+    assert_eq!(decoder.get_info().unwrap().width, 32);
+
+    // decoding pixels again requires re-reading the image
+    decoder.reset(&data);
+    assert_eq!(decoder.decode_raw().unwrap(), expected);
+
+    decoder.release_pixel_buffers();
+    assert_eq!(decoder.get_colorspace(), Some(ColorSpace::RGB));
+    assert!(decoder.get_info().is_some());
+}
@@ -0,0 +1,66 @@
+//! Check that reusing a decoder allocates less than creating a new decoder per image
+//!
+//! This is a separate test binary with a single test, since it counts allocations
+//! with a global allocator which other tests running in parallel would disturb
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zune_png::PngDecoder;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator
+{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8
+    {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout)
+    {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8
+    {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_reset_allocates_less()
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/basn3p08.png";
+    let data = std::fs::read(path).unwrap();
+
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+
+    for _ in 0..1000
+    {
+        let mut decoder = PngDecoder::new(&data);
+        std::hint::black_box(decoder.decode_raw().unwrap());
+    }
+    let fresh = ALLOCATIONS.load(Ordering::Relaxed) - start;
+
+    let mut decoder = PngDecoder::new(&data);
+    let start = ALLOCATIONS.load(Ordering::Relaxed);
+
+    for _ in 0..1000
+    {
+        decoder.reset(&data);
+        std::hint::black_box(decoder.decode_raw().unwrap());
+    }
+    let reused = ALLOCATIONS.load(Ordering::Relaxed) - start;
+
+    assert!(
+        reused < fresh,
+        "reusing the decoder made {reused} allocations, fresh decoders made {fresh}"
+    );
+}
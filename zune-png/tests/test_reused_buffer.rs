@@ -0,0 +1,63 @@
+//! Tests for decoding into buffers reused across images
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+#[test]
+fn test_reused_buffer_does_not_grow()
+{
+    // 32x32 RGB images
+    let names = [
+        "basn2c08.png",
+        "basi2c08.png",
+        "f00n2c08.png",
+        "f04n2c08.png"
+    ];
+
+    let mut buffer = Vec::new();
+    let mut capacity = None;
+
+    for name in names
+    {
+        let data = open_file(name);
+        let expected = PngDecoder::new(&data).decode_raw().unwrap();
+
+        let written = PngDecoder::new(&data)
+            .decode_into_reused(&mut buffer)
+            .unwrap();
+
+        assert_eq!(written, expected.len());
+        assert_eq!(&buffer[..written], expected, "{name}");
+
+        // only the first image allocates
+        let pointer = (buffer.as_ptr(), buffer.capacity());
+        assert_eq!(*capacity.get_or_insert(pointer), pointer, "{name}");
+    }
+}
+
+#[test]
+fn test_reused_buffer_never_shrinks()
+{
+    // 32x32 RGB followed by a smaller 32x32 grayscale image
+    let mut buffer = Vec::new();
+
+    let rgb = PngDecoder::new(&open_file("basn2c08.png"))
+        .decode_into_reused(&mut buffer)
+        .unwrap();
+
+    let data = open_file("basn0g08.png");
+    let luma = PngDecoder::new(&data)
+        .decode_into_reused(&mut buffer)
+        .unwrap();
+
+    assert!(luma < rgb);
+    assert_eq!(buffer.len(), rgb);
+    assert_eq!(
+        &buffer[..luma],
+        PngDecoder::new(&data).decode_raw().unwrap()
+    );
+}
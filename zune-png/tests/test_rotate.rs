@@ -0,0 +1,117 @@
+#![cfg(feature = "crc")]
+use zune_core::bit_depth::ByteEndian;
+use zune_core::options::DecoderOptions;
+use zune_png::{rotate_lossless, PngDecoder, RotationAngle};
+
+/// Create a png with a text, time and gamma chunk
+fn create_png(
+    width: u32, height: u32, color: png::ColorType, depth: png::BitDepth, pixels: &[u8]
+) -> Vec<u8>
+{
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(depth);
+        encoder.set_source_gamma(png::ScaledFloat::new(0.45455));
+        encoder
+            .add_text_chunk("Title".to_string(), "Rotation".to_string())
+            .unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+        writer
+            .write_chunk(png::chunk::tIME, &[0x07, 0xE7, 3, 14, 15, 9, 26])
+            .unwrap();
+        writer.write_image_data(pixels).unwrap();
+    }
+    out
+}
+
+fn decode(data: &[u8]) -> (Vec<u8>, (usize, usize))
+{
+    let options = DecoderOptions::default().set_byte_endian(ByteEndian::BE);
+    let mut decoder = PngDecoder::new_with_options(data, options);
+    let pixels = decoder.decode_raw().unwrap();
+
+    (pixels, decoder.get_dimensions().unwrap())
+}
+
+#[test]
+fn test_rotate_180_twice()
+{
+    let (width, height) = (5, 3);
+    let pixels: Vec<u8> = (0..width * height * 3).map(|x| (x * 7) as u8).collect();
+
+    let original = create_png(
+        width,
+        height,
+        png::ColorType::Rgb,
+        png::BitDepth::Eight,
+        &pixels
+    );
+
+    let once = rotate_lossless(&original, RotationAngle::Rotate180).unwrap();
+    let twice = rotate_lossless(&once, RotationAngle::Rotate180).unwrap();
+
+    // a single rotation reverses the pixel order
+    let (rotated, _) = decode(&once);
+    let reversed: Vec<u8> = pixels.chunks_exact(3).rev().flatten().copied().collect();
+    assert_eq!(rotated, reversed);
+
+    assert_eq!(decode(&twice), (pixels, (5, 3)));
+
+    // metadata survives the round trip
+    let mut decoder = PngDecoder::new(&twice);
+    decoder.decode_headers().unwrap();
+
+    let info = decoder.get_info().unwrap();
+
+    assert!((info.gamma.unwrap() - 0.45455).abs() < 1e-6);
+    assert_eq!(info.text_chunk[0].keyword, b"Title");
+    assert_eq!(info.text_chunk[0].text, b"Rotation");
+
+    let time = info.time_info.unwrap();
+    assert_eq!((time.year, time.month, time.day), (2023, 3, 14));
+
+    for chunk in decoder.chunk_report()
+    {
+        assert!(chunk.crc_valid);
+    }
+}
+
+#[test]
+fn test_rotate_90_16_bit()
+{
+    // 3x2 16 bit grayscale image, each sample is its index
+    let pixels: Vec<u8> = (0..6_u16).flat_map(|x| (x * 1000).to_be_bytes()).collect();
+
+    let original = create_png(
+        3,
+        2,
+        png::ColorType::Grayscale,
+        png::BitDepth::Sixteen,
+        &pixels
+    );
+
+    let sample = |data: &[u8], i: usize| u16::from_be_bytes([data[2 * i], data[2 * i + 1]]) / 1000;
+
+    // 0 1 2      3 0
+    // 3 4 5  ->  4 1
+    //            5 2
+    let (rotated, dims) = decode(&rotate_lossless(&original, RotationAngle::Rotate90).unwrap());
+    assert_eq!(dims, (2, 3));
+    assert_eq!(
+        (0..6).map(|i| sample(&rotated, i)).collect::<Vec<_>>(),
+        [3, 0, 4, 1, 5, 2]
+    );
+
+    // 0 1 2      2 5
+    // 3 4 5  ->  1 4
+    //            0 3
+    let (rotated, dims) = decode(&rotate_lossless(&original, RotationAngle::Rotate270).unwrap());
+    assert_eq!(dims, (2, 3));
+    assert_eq!(
+        (0..6).map(|i| sample(&rotated, i)).collect::<Vec<_>>(),
+        [2, 5, 1, 4, 0, 3]
+    );
+}
@@ -0,0 +1,155 @@
+//! Tests for scaling images down while decoding
+use zune_core::options::{DecoderOptions, ScaleDown};
+use zune_core::result::DecodingResult;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+fn samples(result: DecodingResult) -> Vec<u32>
+{
+    match result
+    {
+        DecodingResult::U8(data) => data.into_iter().map(u32::from).collect(),
+        DecodingResult::U16(data) => data.into_iter().map(u32::from).collect(),
+        _ => unreachable!()
+    }
+}
+
+/// Average each `scale x scale` block of pixels, rounding to the nearest value
+fn box_average(
+    pixels: &[u32], width: usize, height: usize, components: usize, scale: usize
+) -> Vec<u32>
+{
+    let (out_width, out_height) = (width.div_ceil(scale), height.div_ceil(scale));
+    let mut out = Vec::with_capacity(out_width * out_height * components);
+
+    for y in 0..out_height
+    {
+        for x in 0..out_width
+        {
+            let rows = (y * scale..height.min((y + 1) * scale)).collect::<Vec<_>>();
+            let columns = (x * scale..width.min((x + 1) * scale)).collect::<Vec<_>>();
+            let count = (rows.len() * columns.len()) as u32;
+
+            for c in 0..components
+            {
+                let sum: u32 = rows
+                    .iter()
+                    .flat_map(|y| {
+                        columns
+                            .iter()
+                            .map(move |x| (y * width + x) * components + c)
+                    })
+                    .map(|i| pixels[i])
+                    .sum();
+
+                out.push((sum + count / 2) / count);
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn test_scale_down_matches_box_average()
+{
+    // 8 and 16 bit, palette, transparency and interlaced images
+    for name in [
+        "basn2c08.png",
+        "basn0g16.png",
+        "basn6a16.png",
+        "basn3p08.png",
+        "basn0g04.png",
+        "tbrn2c08.png",
+        "basi2c08.png",
+        "basi4a16.png"
+    ]
+    {
+        let data = open_file(name);
+
+        let mut decoder = PngDecoder::new(&data);
+        let full = samples(decoder.decode().unwrap());
+        let (width, height) = decoder.get_dimensions().unwrap();
+        let components = decoder.get_colorspace().unwrap().num_components();
+
+        for scale_down in [ScaleDown::Half, ScaleDown::Quarter, ScaleDown::Eighth]
+        {
+            let scale = scale_down.factor();
+            let options = DecoderOptions::default().png_set_scale_down(scale_down);
+            let mut decoder = PngDecoder::new_with_options(&data, options);
+            decoder.decode_headers().unwrap();
+
+            let (out_width, out_height) = decoder.get_output_dimensions().unwrap();
+
+            // the real dimensions are unchanged
+            assert_eq!(decoder.get_dimensions(), Some((width, height)), "{name}");
+            assert_eq!(
+                (out_width, out_height),
+                (width.div_ceil(scale), height.div_ceil(scale)),
+                "{name}"
+            );
+            let bytes = if decoder.get_depth().unwrap() == zune_core::bit_depth::BitDepth::Sixteen
+            {
+                2
+            }
+            else
+            {
+                1
+            };
+            assert_eq!(
+                decoder.output_buffer_size(),
+                Some(out_width * out_height * components * bytes),
+                "{name}"
+            );
+
+            let scaled = samples(decoder.decode().unwrap());
+
+            assert_eq!(
+                scaled,
+                box_average(&full, width, height, components, scale),
+                "{name} scaled by {scale}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_scale_down_partial_blocks()
+{
+    // 3x3 gray image, scaling by 2 leaves partial blocks at the edges
+    let pixels = [0, 10, 20, 30, 40, 50, 60, 70, 255];
+
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, 3, 3);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    writer.finish().unwrap();
+
+    let options = DecoderOptions::default().png_set_scale_down(ScaleDown::Half);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    assert_eq!(samples(decoder.decode().unwrap()), [20, 35, 65, 255]);
+    assert_eq!(decoder.get_output_dimensions(), Some((2, 2)));
+}
+
+#[test]
+fn test_scale_down_ignored_for_palette_indices()
+{
+    let data = open_file("basn3p08.png");
+
+    let options = DecoderOptions::default()
+        .png_set_scale_down(ScaleDown::Quarter)
+        .png_set_keep_palette_indices(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    assert_eq!(decoder.get_output_dimensions(), decoder.get_dimensions());
+    assert_eq!(decoder.decode_raw().unwrap().len(), 32 * 32);
+}
@@ -0,0 +1,185 @@
+//! Tests for reducing 16 bit images to 8 bits while decoding
+use zune_core::bit_depth::{BitDepth, ByteEndian};
+use zune_core::options::{DecoderOptions, Rounding};
+use zune_core::result::DecodingResult;
+use zune_png::PngDecoder;
+
+fn open_file(name: &str) -> Vec<u8>
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/" + name;
+    std::fs::read(path).unwrap()
+}
+
+/// Most significant bytes of big endian 16 bit samples
+fn high_bytes(samples: &[u8]) -> Vec<u8>
+{
+    samples.chunks_exact(2).map(|x| x[0]).collect()
+}
+
+#[test]
+fn test_strip_16_matches_shifted_samples()
+{
+    // standard, interlaced, tRNS and gamma images
+    for name in [
+        "basn6a16.png",
+        "basi2c16.png",
+        "tbbn2c16.png",
+        "g25n0g16.png"
+    ]
+    {
+        let data = open_file(name);
+
+        for apply_gamma in [false, true]
+        {
+            let options = DecoderOptions::default()
+                .set_byte_endian(ByteEndian::BE)
+                .png_set_apply_gamma(apply_gamma);
+
+            let expected = high_bytes(
+                &PngDecoder::new_with_options(&data, options)
+                    .decode_raw()
+                    .unwrap()
+            );
+
+            let mut decoder = PngDecoder::new_with_options(&data, options.png_set_strip_16(true));
+            decoder.decode_headers().unwrap();
+
+            assert_eq!(decoder.get_depth(), Some(BitDepth::Eight), "{name}");
+            assert_eq!(decoder.output_buffer_size(), Some(expected.len()), "{name}");
+            assert_eq!(decoder.decode_raw().unwrap(), expected, "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_strip_16_decode_returns_u8()
+{
+    let data = open_file("basn2c16.png");
+    let options = DecoderOptions::default().png_set_strip_16(true);
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    assert!(matches!(decoder.decode().unwrap(), DecodingResult::U8(_)));
+
+    // no effect on 8 bit images
+    let data = open_file("basn2c08.png");
+    let expected = PngDecoder::new(&data).decode_raw().unwrap();
+
+    assert_eq!(
+        PngDecoder::new_with_options(&data, options)
+            .decode_raw()
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_strip_16_rows_and_passes()
+{
+    let options = DecoderOptions::default().png_set_strip_16(true);
+
+    let data = open_file("basn4a16.png");
+    let expected = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+
+    let mut rows = Vec::new();
+    PngDecoder::new_with_options(&data, options)
+        .decode_rows(|_, row| rows.extend_from_slice(row))
+        .unwrap();
+
+    assert_eq!(rows, expected);
+
+    // the last interlace pass holds every odd row
+    let data = open_file("basi0g16.png");
+    let expected = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+    decoder.decode_headers().unwrap();
+
+    let (width, height) = decoder.interlace_pass_dimensions(6).unwrap();
+    let mut pass = vec![0; width * height];
+    decoder.decode_interlace_pass(6, &mut pass).unwrap();
+
+    for (y, row) in pass.chunks_exact(width).enumerate()
+    {
+        let start = (y * 2 + 1) * width;
+        assert_eq!(row, &expected[start..start + width]);
+    }
+}
+
+#[test]
+fn test_strip_16_apng()
+{
+    let frame = |value: u16| -> Vec<u8> {
+        [value, value / 2, 1000, u16::MAX]
+            .iter()
+            .flat_map(|x| x.to_be_bytes())
+            .collect::<Vec<u8>>()
+            .repeat(4)
+    };
+    let mut data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut data, 2, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Sixteen);
+        encoder.set_animated(2, 0).unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&frame(40000)).unwrap();
+        writer.write_image_data(&frame(513)).unwrap();
+        writer.finish().unwrap();
+    }
+    let options = DecoderOptions::default().set_byte_endian(ByteEndian::BE);
+
+    let expected = PngDecoder::new_with_options(&data, options)
+        .decode_apng()
+        .unwrap();
+    let frames = PngDecoder::new_with_options(&data, options.png_set_strip_16(true))
+        .decode_apng()
+        .unwrap();
+
+    assert_eq!(frames.len(), 2);
+
+    for (frame, expected) in frames.iter().zip(&expected)
+    {
+        assert_eq!(frame.pixels, high_bytes(&expected.pixels));
+    }
+    assert_eq!(&frames[1].pixels[..4], &[2, 1, 3, 255]);
+}
+
+#[test]
+fn test_strip_16_rounding()
+{
+    let samples: [u16; 5] = [0x01FF, 0x017F, 0x0180, 0xFF80, 0xFFFF];
+    let mut data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut data, samples.len() as u32, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Sixteen);
+
+        let pixels: Vec<u8> = samples.iter().flat_map(|x| x.to_be_bytes()).collect();
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&pixels).unwrap();
+    }
+    let options = DecoderOptions::default().png_set_strip_16(true);
+
+    assert_eq!(options.png_get_downsample_rounding(), Rounding::Truncate);
+
+    let truncated = PngDecoder::new_with_options(&data, options)
+        .decode_raw()
+        .unwrap();
+    let rounded = PngDecoder::new_with_options(
+        &data,
+        options.png_set_downsample_rounding(Rounding::Round)
+    )
+    .decode_raw()
+    .unwrap();
+
+    assert_eq!(truncated, [1, 1, 1, 255, 255]);
+    // values past 0xFF7F round up to 256, which is clamped
+    assert_eq!(rounded, [2, 1, 2, 255, 255]);
+}
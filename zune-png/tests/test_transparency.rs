@@ -3,6 +3,10 @@ use std::path::Path;
 
 use png::Transformations;
 
+mod common;
+
+use common::split_chunks;
+
 fn open_and_read<P: AsRef<Path>>(path: P) -> Vec<u8>
 {
     read(path).unwrap()
@@ -53,3 +57,35 @@ fn test_palette_trns_8bit()
 
     test_decoding(path);
 }
+
+#[test]
+fn test_palette_trns_before_plte()
+{
+    let path = env!("CARGO_MANIFEST_DIR").to_string() + "/tests/png_suite/tbgn3p08.png";
+    let contents = open_and_read(path);
+
+    let mut chunks = split_chunks(&contents);
+    let plte = chunks.iter().position(|x| &x[4..8] == b"PLTE").unwrap();
+    let trns = chunks.iter().position(|x| &x[4..8] == b"tRNS").unwrap();
+    assert!(plte < trns);
+    // move tRNS before PLTE
+    let trns_chunk = chunks.remove(trns);
+    chunks.insert(plte, trns_chunk);
+
+    let mut reordered = contents[..8].to_vec();
+    chunks.iter().for_each(|x| reordered.extend_from_slice(x));
+
+    // strict mode rejects the file
+    let strict = zune_core::options::DecoderOptions::default().set_strict_mode(true);
+    assert!(zune_png::PngDecoder::new_with_options(&reordered, strict)
+        .decode_raw()
+        .is_err());
+
+    // permissive mode applies the transparency once the palette is seen
+    let permissive = zune_core::options::DecoderOptions::default().set_strict_mode(false);
+    let zune_results = zune_png::PngDecoder::new_with_options(&reordered, permissive)
+        .decode_raw()
+        .unwrap();
+
+    assert_eq!(zune_results, decode_ref(&contents));
+}
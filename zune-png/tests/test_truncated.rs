@@ -0,0 +1,143 @@
+//! Tests for decoding images whose data is cut short
+use zune_core::options::DecoderOptions;
+use zune_png::PngDecoder;
+
+const WIDTH: usize = 64;
+const HEIGHT: usize = 48;
+
+/// Pixels that don't compress well, so truncating the compressed
+/// stream loses a proportional part of the image
+fn noisy_pixels() -> Vec<u8>
+{
+    (0..WIDTH * HEIGHT * 3)
+        .map(|i| {
+            let mut x = (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            x ^= x >> 29;
+            x = x.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            (x >> 32) as u8
+        })
+        .collect()
+}
+
+fn create_png(pixels: &[u8]) -> Vec<u8>
+{
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, WIDTH as u32, HEIGHT as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut info = encoder.write_header().unwrap();
+        info.write_image_data(pixels).unwrap();
+    }
+    out
+}
+
+#[cfg(feature = "crc")]
+fn encode_interlaced(pixels: &[u8]) -> Vec<u8>
+{
+    use zune_core::colorspace::ColorSpace;
+    use zune_core::options::EncoderOptions;
+
+    let options = EncoderOptions::default()
+        .set_width(WIDTH)
+        .set_height(HEIGHT)
+        .set_colorspace(ColorSpace::RGB)
+        .png_set_encode_interlaced(true);
+
+    zune_png::PngEncoder::new(pixels, options).encode().unwrap()
+}
+
+/// Cut the file in the first IDAT chunk, keeping `kept` of every
+/// `out_of` bytes of the chunk
+fn truncate_idat(data: &[u8], kept: usize, out_of: usize) -> Vec<u8>
+{
+    let position = data.windows(4).position(|x| x == b"IDAT").unwrap();
+    let length = u32::from_be_bytes(data[position - 4..position].try_into().unwrap()) as usize;
+
+    data[..position + 4 + length * kept / out_of].to_vec()
+}
+
+#[test]
+fn test_truncated_errors_by_default()
+{
+    let data = truncate_idat(&create_png(&noisy_pixels()), 1, 2);
+
+    assert!(PngDecoder::new(&data).decode_raw().is_err());
+}
+
+#[test]
+fn test_truncated_partial_image()
+{
+    let pixels = noisy_pixels();
+    let data = truncate_idat(&create_png(&pixels), 1, 2);
+
+    let options = DecoderOptions::default().png_set_allow_truncated(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    let decoded = decoder.decode_raw().unwrap();
+    let rows = decoder.get_decoded_rows().unwrap();
+
+    assert_eq!(decoded.len(), pixels.len());
+    assert!(rows > 0 && rows < HEIGHT, "{rows}");
+
+    let split = rows * WIDTH * 3;
+
+    // complete rows are intact, the rest are zero
+    assert_eq!(decoded[..split], pixels[..split]);
+    assert!(decoded[split..].iter().all(|x| *x == 0));
+}
+
+#[test]
+fn test_truncated_missing_iend()
+{
+    let pixels = noisy_pixels();
+    let data = create_png(&pixels);
+    // drop the IEND chunk and part of the last chunk's crc
+    let data = &data[..data.len() - 14];
+
+    let options = DecoderOptions::default().png_set_allow_truncated(true);
+    let mut decoder = PngDecoder::new_with_options(data, options);
+
+    let decoded = decoder.decode_raw().unwrap();
+
+    assert_eq!(decoded, pixels);
+    assert_eq!(decoder.get_decoded_rows(), Some(HEIGHT));
+}
+
+#[test]
+fn test_complete_image_reports_all_rows()
+{
+    let pixels = noisy_pixels();
+    let data = create_png(&pixels);
+
+    let options = DecoderOptions::default().png_set_allow_truncated(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    assert_eq!(decoder.get_decoded_rows(), None);
+    assert_eq!(decoder.decode_raw().unwrap(), pixels);
+    assert_eq!(decoder.get_decoded_rows(), Some(HEIGHT));
+}
+
+#[test]
+#[cfg(feature = "crc")]
+fn test_truncated_interlaced_partial_image()
+{
+    let pixels = noisy_pixels();
+    // the last pass holds half the image data, keeping three quarters
+    // of the data cuts it short
+    let data = truncate_idat(&encode_interlaced(&pixels), 3, 4);
+
+    let options = DecoderOptions::default().png_set_allow_truncated(true);
+    let mut decoder = PngDecoder::new_with_options(&data, options);
+
+    let decoded = decoder.decode_raw().unwrap();
+    let rows = decoder.get_decoded_rows().unwrap();
+
+    assert_eq!(decoded.len(), pixels.len());
+    assert!(rows > 0 && rows < HEIGHT, "{rows}");
+
+    let split = rows * WIDTH * 3;
+
+    assert_eq!(decoded[..split], pixels[..split]);
+}
@@ -0,0 +1,84 @@
+//! Tests for reconstructing images from already decompressed data
+use zune_inflate::DeflateDecoder;
+use zune_png::PngDecoder;
+
+/// Concatenate the contents of all IDAT chunks
+fn idat_data(data: &[u8]) -> Vec<u8>
+{
+    let mut idat = Vec::new();
+    let mut position = 8;
+
+    while position + 8 <= data.len()
+    {
+        let length = u32::from_be_bytes(data[position..position + 4].try_into().unwrap()) as usize;
+
+        if &data[position + 4..position + 8] == b"IDAT"
+        {
+            idat.extend_from_slice(&data[position + 8..position + 8 + length]);
+        }
+        position += length + 12;
+    }
+    idat
+}
+
+#[test]
+fn test_unfilter_from_matches_decode()
+{
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/png_suite");
+
+    for entry in std::fs::read_dir(path).unwrap()
+    {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+
+        // files starting with x are corrupt
+        if !name.ends_with(".png") || name.starts_with('x')
+        {
+            continue;
+        }
+        let data = std::fs::read(&path).unwrap();
+
+        let expected = PngDecoder::new(&data).decode_raw().unwrap();
+
+        let inflated = DeflateDecoder::new(&idat_data(&data))
+            .decode_zlib()
+            .unwrap();
+
+        let mut decoder = PngDecoder::new(&data);
+        let mut out = vec![0; expected.len()];
+
+        decoder.unfilter_from(&inflated, &mut out).unwrap();
+
+        assert_eq!(out, expected, "{name}");
+    }
+}
+
+#[test]
+fn test_unfilter_from_known_filters()
+{
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, 4, 2);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0; 8]).unwrap();
+    }
+    let filtered = [
+        1, 10, 5, 5, 5, // sub, each pixel adds the one to its left
+        2, 1, 1, 1, 1 // up, each pixel adds the one above it
+    ];
+    let mut out = [0; 8];
+
+    PngDecoder::new(&png)
+        .unfilter_from(&filtered, &mut out)
+        .unwrap();
+
+    assert_eq!(out, [10, 15, 20, 25, 11, 16, 21, 26]);
+
+    // one byte short
+    assert!(PngDecoder::new(&png)
+        .unfilter_from(&filtered[..9], &mut out)
+        .is_err());
+}
@@ -4,7 +4,10 @@ use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 
 use zune_core::options::DecoderOptions;
-use zune_inflate::DeflateDecoder;
+use zune_inflate::errors::DecodeErrorStatus;
+use zune_inflate::{
+    DeflateDecoder, DeflateDecoderStream, DeflateEncodeOptions, DeflateEncoder, DeflateOptions
+};
 use zune_psd::PSDDecoder;
 
 use crate::{hash, sample_path, TestEntry};
@@ -71,3 +74,589 @@ fn test_inflate()
         panic!("Errors found during test decoding\n {:#?}", files);
     }
 }
+
+fn crc32(data: &[u8]) -> u32
+{
+    let mut crc = u32::MAX;
+
+    for byte in data
+    {
+        crc ^= u32::from(*byte);
+
+        for _ in 0..8
+        {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Create a gzip member holding `data` with every optional header
+/// field present
+fn create_gzip_with_all_fields(data: &[u8]) -> Vec<u8>
+{
+    use zune_inflate::DeflateEncoder;
+
+    // FHCRC | FEXTRA | FNAME | FCOMMENT
+    let mut out = vec![0x1F, 0x8B, 8, 0x02 | 0x04 | 0x08 | 0x10];
+    // mtime, xfl, os
+    out.extend_from_slice(&[0x78, 0x56, 0x34, 0x12, 2, 3]);
+    // extra field
+    out.extend_from_slice(&6_u16.to_le_bytes());
+    out.extend_from_slice(b"AB\x02\x00hi");
+    out.extend_from_slice(b"file.txt\0");
+    out.extend_from_slice(b"a comment\0");
+
+    let header_crc = (crc32(&out) & 0xFFFF) as u16;
+    out.extend_from_slice(&header_crc.to_le_bytes());
+
+    out.extend_from_slice(&DeflateEncoder::new(data).encode_deflate());
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+    out
+}
+
+#[test]
+fn test_gzip_optional_header_fields()
+{
+    let data = b"Some text that is long enough to be compressed, compressed, compressed".repeat(20);
+
+    let gzip = create_gzip_with_all_fields(&data);
+
+    let decoded = DeflateDecoder::new(&gzip).decode_gzip().unwrap();
+
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn test_gzip_header_crc_mismatch()
+{
+    use zune_inflate::DeflateOptions;
+
+    let data = b"header crc".to_vec();
+    let mut gzip = create_gzip_with_all_fields(&data);
+
+    // corrupt the comment, which the header crc covers
+    let position = gzip.windows(9).position(|x| x == b"a comment").unwrap();
+    gzip[position] = b'A';
+
+    assert!(DeflateDecoder::new(&gzip).decode_gzip().is_err());
+
+    // but it's not checked when checksums aren't confirmed
+    let options = DeflateOptions::default().set_confirm_checksum(false);
+    let decoded = DeflateDecoder::new_with_options(&gzip, options)
+        .decode_gzip()
+        .unwrap();
+
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn test_gzip_trailer_mismatch()
+{
+    let data = b"trailer".repeat(10);
+    let gzip = create_gzip_with_all_fields(&data);
+
+    // wrong crc32
+    let mut corrupt = gzip.clone();
+    let crc_position = corrupt.len() - 8;
+    corrupt[crc_position] ^= 1;
+    assert!(DeflateDecoder::new(&corrupt).decode_gzip().is_err());
+
+    // wrong ISIZE
+    let mut corrupt = gzip;
+    let size_position = corrupt.len() - 4;
+    corrupt[size_position] ^= 1;
+    assert!(DeflateDecoder::new(&corrupt).decode_gzip().is_err());
+}
+
+#[test]
+fn test_gzip_multiple_members()
+{
+    use zune_inflate::DeflateOptions;
+
+    let file = read(inflate_path().join("gzip/license.md.gz")).unwrap();
+    let license = DeflateDecoder::new(&file).decode_gzip().unwrap();
+
+    let other = b"a second member, appended to the first".repeat(3);
+
+    let mut gzip = file.clone();
+    gzip.extend_from_slice(&create_gzip_with_all_fields(&other));
+    gzip.extend_from_slice(&file);
+
+    let decoded = DeflateDecoder::new(&gzip).decode_gzip().unwrap();
+
+    assert_eq!(decoded, [&license[..], &other, &license].concat());
+
+    // only the first member
+    let options = DeflateOptions::default().set_multi_member(false);
+    let decoded = DeflateDecoder::new_with_options(&gzip, options)
+        .decode_gzip()
+        .unwrap();
+
+    assert_eq!(decoded, license);
+}
+
+#[test]
+fn test_gzip_trailing_data()
+{
+    use zune_inflate::DeflateOptions;
+
+    let data = b"trailing data".repeat(4);
+
+    let mut gzip = create_gzip_with_all_fields(&data);
+    gzip.extend_from_slice(b"\0\0garbage");
+
+    let err = DeflateDecoder::new(&gzip).decode_gzip().unwrap_err();
+    // the member is still recovered
+    assert_eq!(err.data, data);
+
+    let options = DeflateOptions::default().set_ignore_trailing_data(true);
+    let decoded = DeflateDecoder::new_with_options(&gzip, options)
+        .decode_gzip()
+        .unwrap();
+
+    assert_eq!(decoded, data);
+}
+
+fn adler32(data: &[u8]) -> u32
+{
+    let (mut a, mut b) = (1_u32, 0_u32);
+
+    for byte in data
+    {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Create a raw deflate stream holding a single length 10,
+/// distance 20 match, i.e it copies 10 bytes starting 20 bytes
+/// before the end of a preset dictionary
+fn create_dictionary_deflate() -> Vec<u8>
+{
+    // (value, length, whether it's a huffman code stored most significant bit first)
+    let fields = [
+        (1, 1, false), // BFINAL
+        (1, 2, false), // BTYPE, fixed huffman codes
+        (264 - 256, 7, true), // length code 264, length 10
+        (8, 5, true), // distance code 8, distances 17-24
+        (3, 3, false), // extra bits, distance 20
+        (0, 7, true)  // end of block
+    ];
+    let mut bits = Vec::new();
+
+    for (value, length, is_code) in fields
+    {
+        for i in 0..length
+        {
+            let shift = if is_code { length - 1 - i } else { i };
+            bits.push((value >> shift) & 1 == 1);
+        }
+    }
+    bits.chunks(8)
+        .map(|byte| {
+            byte.iter()
+                .enumerate()
+                .fold(0_u8, |acc, (i, bit)| acc | (u8::from(*bit) << i))
+        })
+        .collect()
+}
+
+const DICTIONARY: &[u8] = b"a preset dictionary: 0123456789abcdefghij";
+
+#[test]
+fn test_raw_deflate_dictionary()
+{
+    let deflate = create_dictionary_deflate();
+    let expected = &DICTIONARY[DICTIONARY.len() - 20..DICTIONARY.len() - 10];
+
+    let mut decoder = DeflateDecoder::new(&deflate);
+    decoder.set_dictionary(DICTIONARY);
+
+    assert_eq!(decoder.decode_deflate().unwrap(), expected);
+
+    // without the dictionary the match points before the start of the output
+    assert!(DeflateDecoder::new(&deflate).decode_deflate().is_err());
+}
+
+#[test]
+fn test_zlib_dictionary()
+{
+    let expected = &DICTIONARY[DICTIONARY.len() - 20..DICTIONARY.len() - 10];
+
+    // FDICT set
+    let mut zlib = vec![0x78, 0x20];
+    zlib.extend_from_slice(&adler32(DICTIONARY).to_be_bytes());
+    zlib.extend_from_slice(&create_dictionary_deflate());
+    zlib.extend_from_slice(&adler32(expected).to_be_bytes());
+
+    let mut decoder = DeflateDecoder::new(&zlib);
+    decoder.set_dictionary(DICTIONARY);
+
+    assert_eq!(decoder.decode_zlib().unwrap(), expected);
+
+    // the stream needs a dictionary
+    assert!(DeflateDecoder::new(&zlib).decode_zlib().is_err());
+
+    // which must match the one it was compressed with
+    let mut decoder = DeflateDecoder::new(&zlib);
+    decoder.set_dictionary(&DICTIONARY[1..]);
+
+    assert!(decoder.decode_zlib().is_err());
+}
+
+/// Decode `data` with a streaming decoder, pushing `chunk_size` bytes at a time
+/// and reading into a buffer of `buffer_size` bytes
+fn decode_streaming(
+    mut decoder: DeflateDecoderStream, data: &[u8], chunk_size: usize, buffer_size: usize
+) -> Result<Vec<u8>, DecodeErrorStatus>
+{
+    let mut chunks = data.chunks(chunk_size);
+    let mut buffer = vec![0; buffer_size];
+    let mut out = Vec::new();
+
+    loop
+    {
+        match decoder.read(&mut buffer)
+        {
+            Ok(0) => break,
+            Ok(n) => out.extend_from_slice(&buffer[..n]),
+            Err(err) => match (err.error, chunks.next())
+            {
+                (DecodeErrorStatus::NeedMoreInput, Some(chunk)) => decoder.push(chunk),
+                (error, _) => return Err(error)
+            }
+        }
+    }
+    assert!(decoder.is_finished());
+
+    Ok(out)
+}
+
+#[test]
+fn test_streaming_zlib_files()
+{
+    for entry in std::fs::read_dir(inflate_path().join("zlib")).unwrap()
+    {
+        let data = read(entry.unwrap().path()).unwrap();
+        let expected = DeflateDecoder::new(&data).decode_zlib().unwrap();
+
+        let decoded =
+            decode_streaming(DeflateDecoderStream::new_zlib(), &data, 100_000, 65536).unwrap();
+
+        assert!(decoded == expected);
+    }
+}
+
+#[test]
+fn test_streaming_byte_at_a_time()
+{
+    // dynamic huffman blocks, split at every possible byte
+    let data = read(inflate_path().join("zlib/enwiki_part.zlib")).unwrap();
+    let expected = DeflateDecoder::new(&data).decode_zlib().unwrap();
+
+    let mut decoder = DeflateDecoderStream::new_zlib();
+    let mut buffer = [0; 300];
+    let mut decoded = Vec::new();
+
+    for byte in data.chunks(1).take(100_000)
+    {
+        decoder.push(byte);
+
+        while let Ok(n) = decoder.read(&mut buffer)
+        {
+            decoded.extend_from_slice(&buffer[..n]);
+        }
+    }
+    assert!(decoded.len() > 200_000);
+    assert!(decoded == expected[..decoded.len()]);
+}
+
+#[test]
+fn test_streaming_raw_deflate()
+{
+    let data: Vec<u8> = (0..200_000_u32).map(|x| (x % 7 + x / 1000) as u8).collect();
+
+    for level in [0, 6]
+    {
+        let options = DeflateEncodeOptions::default().set_level(level);
+        let compressed = DeflateEncoder::new_with_options(&data, options).encode_deflate();
+
+        let decoded =
+            decode_streaming(DeflateDecoderStream::new(), &compressed, 17, 1000).unwrap();
+
+        assert!(decoded == data, "level {level}");
+    }
+}
+
+#[test]
+fn test_streaming_needs_input()
+{
+    let compressed = DeflateEncoder::new(b"streaming").encode_zlib();
+    let mut decoder = DeflateDecoderStream::new_zlib();
+    let mut buffer = [0; 32];
+
+    let err = decoder.read(&mut buffer).unwrap_err();
+    assert!(matches!(err.error, DecodeErrorStatus::NeedMoreInput));
+
+    // a partial stream produces what it can, then asks for more
+    decoder.push(&compressed[..compressed.len() - 4]);
+
+    assert_eq!(decoder.read(&mut buffer).unwrap(), 9);
+    assert_eq!(&buffer[..9], b"streaming");
+
+    let err = decoder.read(&mut buffer).unwrap_err();
+    assert!(matches!(err.error, DecodeErrorStatus::NeedMoreInput));
+    assert!(!decoder.is_finished());
+
+    decoder.push(&compressed[compressed.len() - 4..]);
+
+    assert_eq!(decoder.read(&mut buffer).unwrap(), 0);
+    assert!(decoder.is_finished());
+}
+
+#[test]
+fn test_streaming_errors()
+{
+    let data = b"the adler checksum at the end of this stream is wrong";
+
+    let mut compressed = DeflateEncoder::new(data).encode_zlib();
+    let last = compressed.len() - 1;
+    compressed[last] ^= 1;
+
+    let result = decode_streaming(DeflateDecoderStream::new_zlib(), &compressed, 5, 10);
+    assert!(matches!(result, Err(DecodeErrorStatus::MismatchedAdler(_, _))));
+
+    let options = DeflateOptions::default().set_confirm_checksum(false);
+    let result = decode_streaming(
+        DeflateDecoderStream::new_zlib_with_options(options),
+        &compressed,
+        5,
+        10
+    );
+    assert_eq!(result.unwrap(), data);
+
+    let options = DeflateOptions::default().set_limit(10);
+    let result = decode_streaming(
+        DeflateDecoderStream::new_zlib_with_options(options),
+        &compressed,
+        5,
+        10
+    );
+    assert!(matches!(result, Err(DecodeErrorStatus::OutputLimitExceeded(10, _))));
+}
+
+#[test]
+fn test_checksum_vectors()
+{
+    use zune_inflate::checksums::{adler32, adler32_update, crc32, crc32_update};
+
+    // empty input gives the initial values from RFC 1952 and RFC 1950
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(adler32(b""), 1);
+
+    // the standard check value for CRC-32/ISO-HDLC, the crc used by gzip and png
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+
+    assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    assert_eq!(adler32(b"The quick brown fox jumps over the lazy dog"), 0x5BDC_0FDA);
+    // long enough for both sums to wrap around 65521
+    assert_eq!(adler32(&[0xFF; 6000]), 0xA497_59EA);
+
+    // updates give the same result however the data is split
+    let data: Vec<u8> = (0..10_000_u32).map(|x| (x * 31 % 256) as u8).collect();
+
+    for split in [0, 1, 7, 8, 9, 5000, data.len()]
+    {
+        let (a, b) = data.split_at(split);
+
+        assert_eq!(crc32_update(crc32(a), b), crc32(&data));
+        assert_eq!(adler32_update(adler32(a), b), adler32(&data));
+    }
+}
+
+#[test]
+fn test_checksums_match_stored_values()
+{
+    use zune_inflate::checksums::{adler32, crc32};
+
+    // the gzip trailer stores the crc32 of the decompressed data
+    let gzip = read(inflate_path().join("gzip/license.md.gz")).unwrap();
+    let data = DeflateDecoder::new(&gzip).decode_gzip().unwrap();
+    let trailer = &gzip[gzip.len() - 8..gzip.len() - 4];
+
+    assert_eq!(crc32(&data), u32::from_le_bytes(trailer.try_into().unwrap()));
+
+    // and the zlib trailer the adler32
+    let zlib = DeflateEncoder::new(&data).encode_zlib();
+    let trailer = &zlib[zlib.len() - 4..];
+
+    assert_eq!(adler32(&data), u32::from_be_bytes(trailer.try_into().unwrap()));
+
+    // png chunk crcs cover the chunk type and data
+    let png = read(sample_path().join("test-images/png/f99n0g04_.png")).unwrap();
+    let length = u32::from_be_bytes(png[8..12].try_into().unwrap()) as usize;
+    let stored = u32::from_be_bytes(png[16 + length..20 + length].try_into().unwrap());
+
+    assert_eq!(crc32(&png[12..16 + length]), stored);
+}
+
+/// Inputs exercising stored, fixed and dynamic blocks
+fn encoder_inputs() -> Vec<Vec<u8>>
+{
+    let zlib = read(inflate_path().join("zlib/enwiki_part.zlib")).unwrap();
+    let text = DeflateDecoder::new(&zlib).decode_zlib().unwrap();
+
+    // a simple generator, incompressible enough to be stored
+    let mut state = 0x1234_5678_u32;
+    let noise = (0..100_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+
+    vec![
+        vec![],
+        vec![42],
+        vec![0; 300_000],
+        b"abcabcabcabd".repeat(1000),
+        noise,
+        text[..300_000].to_vec(),
+    ]
+}
+
+#[test]
+fn test_encoder_round_trip()
+{
+    for data in encoder_inputs()
+    {
+        for level in 0..=9
+        {
+            let options = DeflateEncodeOptions::default().set_level(level);
+            let mut encoder = DeflateEncoder::new_with_options(&data, options);
+
+            let deflate = encoder.encode_deflate();
+            let zlib = encoder.encode_zlib();
+            let gzip = encoder.encode_gzip();
+
+            let size = data.len();
+
+            assert!(DeflateDecoder::new(&deflate).decode_deflate().unwrap() == data, "{size} {level}");
+            assert!(DeflateDecoder::new(&zlib).decode_zlib().unwrap() == data, "{size} {level}");
+            assert!(DeflateDecoder::new(&gzip).decode_gzip().unwrap() == data, "{size} {level}");
+        }
+    }
+}
+
+#[test]
+fn test_encoder_compression_ratio()
+{
+    let inputs = encoder_inputs();
+    let (noise, text) = (&inputs[4], &inputs[5]);
+
+    // incompressible data costs little more than its size
+    let compressed = DeflateEncoder::new(noise).encode_deflate();
+    assert!(compressed.len() <= noise.len() + noise.len() / 1000);
+
+    // dynamic huffman codes do much better than fixed ones on text
+    let fast = DeflateEncodeOptions::default().set_level(1);
+
+    let fast_size = DeflateEncoder::new_with_options(text, fast).encode_deflate().len();
+    let default_size = DeflateEncoder::new(text).encode_deflate().len();
+
+    assert!(default_size <= fast_size);
+    assert!(default_size < text.len() * 2 / 5);
+}
+
+#[test]
+fn test_output_limit()
+{
+    let data: Vec<u8> = (0..2_000_000_u32).map(|x| (x / 3000) as u8).collect();
+    let limit = 100_000;
+
+    for level in [0, 6]
+    {
+        let options = DeflateEncodeOptions::default().set_level(level);
+        let mut encoder = DeflateEncoder::new_with_options(&data, options);
+
+        let deflate = encoder.encode_deflate();
+        let zlib = encoder.encode_zlib();
+        let gzip = encoder.encode_gzip();
+
+        // a huge size hint must not allocate past the limit
+        let options = DeflateOptions::default()
+            .set_limit(limit)
+            .set_size_hint(1 << 40);
+
+        let results = [
+            DeflateDecoder::new_with_options(&deflate, options).decode_deflate(),
+            DeflateDecoder::new_with_options(&zlib, options).decode_zlib(),
+            DeflateDecoder::new_with_options(&gzip, options).decode_gzip()
+        ];
+
+        for result in results
+        {
+            let err = result.unwrap_err();
+
+            match err.error
+            {
+                DecodeErrorStatus::OutputLimitExceeded(error_limit, produced) =>
+                {
+                    assert_eq!(error_limit, limit);
+                    assert!(produced > limit);
+                }
+                _ => panic!("Unexpected error {err:?}")
+            }
+            // partial output is kept, up to the limit
+            assert!(err.data.len() <= limit);
+            assert!(err.data.len() > limit / 2);
+            assert!(err.data == data[..err.data.len()]);
+        }
+
+        // output exactly as large as the limit is fine
+        let options = DeflateOptions::default().set_limit(data.len());
+        let decoded = DeflateDecoder::new_with_options(&zlib, options)
+            .decode_zlib()
+            .unwrap();
+
+        assert!(decoded == data);
+    }
+}
+
+#[test]
+fn test_output_limit_gzip_members()
+{
+    let member = DeflateEncoder::new(&[7; 60_000]).encode_gzip();
+    let gzip = [member.as_slice(), member.as_slice()].concat();
+
+    // each member fits, both together don't
+    let options = DeflateOptions::default().set_limit(100_000);
+    let err = DeflateDecoder::new_with_options(&gzip, options)
+        .decode_gzip()
+        .unwrap_err();
+
+    assert!(matches!(
+        err.error,
+        DecodeErrorStatus::OutputLimitExceeded(100_000, 120_000)
+    ));
+    assert_eq!(err.data.len(), 100_000);
+
+    // or when the first member exceeds it on its own
+    let options = DeflateOptions::default().set_limit(50_000);
+    let err = DeflateDecoder::new_with_options(&gzip, options)
+        .decode_gzip()
+        .unwrap_err();
+
+    assert!(matches!(
+        err.error,
+        DecodeErrorStatus::OutputLimitExceeded(50_000, _)
+    ));
+    assert_eq!(err.data.len(), 50_000);
+}
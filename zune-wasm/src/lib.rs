@@ -157,7 +157,7 @@ impl WasmImage
     /// Binarize an image
     pub fn threshold(&mut self, threshold: u16)
     {
-        let ops = Threshold::new(threshold, ThresholdMethod::Binary);
+        let ops = Threshold::new(threshold, ThresholdMethod::Binary).auto_grayscale(true);
         self.execute_ops(&ops);
     }
 